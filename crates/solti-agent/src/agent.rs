@@ -0,0 +1,450 @@
+use std::sync::Arc;
+
+use solti_core::SupervisorApi;
+use solti_prometheus::PrometheusMetrics;
+use solti_settings::{ConfigReloader, SupervisorSettings};
+use tracing::{info, warn};
+
+use crate::{
+    AgentBuilder, AgentError,
+    signals::{SignalAction, SignalListener, on_dump},
+};
+
+/// How the agent was asked to stop.
+enum Stop {
+    /// Stop running tasks within the grace period and deregister.
+    Graceful,
+    /// Return right away.
+    Immediate,
+}
+
+/// Assembled agent: supervisor plus the enabled logger, metrics, API and discovery.
+///
+/// Built by [`Agent::builder`]; [`Agent::run`] serves until shutdown.
+pub struct Agent {
+    pub(crate) supervisor: Arc<SupervisorApi>,
+    pub(crate) metrics: Option<PrometheusMetrics>,
+    pub(crate) settings: SupervisorSettings,
+    pub(crate) reloader: Option<Arc<ConfigReloader>>,
+    #[cfg(feature = "http")]
+    pub(crate) api: bool,
+    /// Whether the agent installed the logger and may control its level.
+    #[cfg(feature = "http")]
+    pub(crate) log_control: bool,
+    #[cfg(feature = "http")]
+    pub(crate) server: Vec<crate::builder::ServerSetup>,
+    #[cfg(feature = "discover")]
+    pub(crate) deregistration: Option<solti_discover::Deregistration>,
+}
+
+impl Agent {
+    /// Start building an agent from `settings`.
+    pub fn builder(settings: SupervisorSettings) -> AgentBuilder {
+        AgentBuilder::new(settings)
+    }
+
+    /// Supervisor handle, e.g. to submit tasks or attach subscribers.
+    pub fn supervisor(&self) -> &Arc<SupervisorApi> {
+        &self.supervisor
+    }
+
+    /// Prometheus backend; `None` when metrics are disabled.
+    pub fn metrics(&self) -> Option<&PrometheusMetrics> {
+        self.metrics.as_ref()
+    }
+
+    /// Settings the agent was built from.
+    pub fn settings(&self) -> &SupervisorSettings {
+        &self.settings
+    }
+
+    /// Settings reloader; `None` unless set with [`AgentBuilder::with_reloader`].
+    pub fn reloader(&self) -> Option<&Arc<ConfigReloader>> {
+        self.reloader.as_ref()
+    }
+
+    /// Handle announcing shutdown to the control plane; `None` without discovery.
+    #[cfg(feature = "discover")]
+    pub fn deregistration(&self) -> Option<&solti_discover::Deregistration> {
+        self.deregistration.as_ref()
+    }
+
+    /// Serve until a shutdown signal, reacting to the signals bound in `[signals]`.
+    ///
+    /// By default `SIGINT` / `SIGTERM` stop running tasks within the grace period and
+    /// deregister from the control plane, `SIGQUIT` returns immediately, `SIGHUP` reloads
+    /// the settings file (see [`AgentBuilder::with_reloader`]) and `SIGUSR2` dumps the agent
+    /// state to JSON. With feature `http` and an address in `[api]`, the API server runs
+    /// until shutdown.
+    pub async fn run(self) -> Result<(), AgentError> {
+        let mut signals = SignalListener::new(&self.settings.signals)?;
+        let supervisor = Arc::clone(&self.supervisor);
+        let reloader = self.reloader.clone();
+        let dump_dir = self
+            .settings
+            .signals
+            .dump_dir
+            .clone()
+            .unwrap_or_else(std::env::temp_dir);
+
+        self.run_with(async move {
+            loop {
+                let (signal, action) = signals.recv().await;
+                match action {
+                    SignalAction::Shutdown => {
+                        info!(%signal, "graceful shutdown requested");
+                        return Ok(Stop::Graceful);
+                    }
+                    SignalAction::Immediate => {
+                        info!(%signal, "immediate shutdown requested");
+                        return Ok(Stop::Immediate);
+                    }
+                    SignalAction::Reload => match &reloader {
+                        Some(reloader) => {
+                            info!(%signal, path = %reloader.path().display(), "reloading settings");
+                            if let Err(e) = reloader.reload() {
+                                warn!(error = %e, "settings reload failed");
+                            }
+                        }
+                        None => {
+                            warn!(%signal, "reload requested, but no settings file is attached")
+                        }
+                    },
+                    SignalAction::Dump => on_dump(&supervisor, &dump_dir),
+                }
+            }
+        })
+        .await
+    }
+
+    /// Like [`Agent::run`], but shut down gracefully when `shutdown` resolves instead of
+    /// reacting to signals.
+    ///
+    /// Used by hosts that receive stop requests in other ways, e.g. a Windows service
+    /// control handler.
+    pub async fn run_until<F>(self, shutdown: F) -> Result<(), AgentError>
+    where
+        F: Future<Output = ()>,
+    {
+        self.run_with(async {
+            shutdown.await;
+            Ok(Stop::Graceful)
+        })
+        .await
+    }
+
+    async fn run_with<F>(mut self, shutdown: F) -> Result<(), AgentError>
+    where
+        F: Future<Output = Result<Stop, AgentError>>,
+    {
+        let stop = self.serve(shutdown).await;
+        info!("agent shutting down");
+        if let Ok(Stop::Immediate) = stop {
+            return Ok(());
+        }
+        self.supervisor.shutdown().await;
+
+        #[cfg(feature = "discover")]
+        if let Some(deregistration) = &self.deregistration {
+            deregistration
+                .send_best_effort("shutdown", std::time::Duration::from_secs(2))
+                .await;
+        }
+        stop.map(|_| ())
+    }
+
+    #[cfg(feature = "http")]
+    async fn serve<F>(&mut self, shutdown: F) -> Result<Stop, AgentError>
+    where
+        F: Future<Output = Result<Stop, AgentError>>,
+    {
+        let Some(server) = self.api_server()? else {
+            return shutdown.await;
+        };
+        let server = server.with_signal_handling(false);
+        let handle = server.shutdown_handle();
+        let run = server.run();
+        tokio::pin!(run);
+        tokio::select! {
+            result = &mut run => result.map(|()| Stop::Graceful).map_err(Into::into),
+            stop = shutdown => {
+                handle.shutdown();
+                run.await?;
+                stop
+            }
+        }
+    }
+
+    #[cfg(not(feature = "http"))]
+    async fn serve<F>(&mut self, shutdown: F) -> Result<Stop, AgentError>
+    where
+        F: Future<Output = Result<Stop, AgentError>>,
+    {
+        shutdown.await
+    }
+
+    /// API server configured from `[api]` and the builder; `None` when no listener is configured.
+    #[cfg(feature = "http")]
+    fn api_server(
+        &mut self,
+    ) -> Result<Option<solti_api::ApiServer<solti_api::SupervisorApiAdapter>>, AgentError> {
+        use solti_api::{ApiServer, SupervisorApiAdapter};
+
+        let options = &self.settings.api;
+        if !self.api || (options.http_addr.is_none() && options.grpc_addr.is_none()) {
+            return Ok(None);
+        }
+
+        let mut adapter = SupervisorApiAdapter::new(Arc::clone(&self.supervisor));
+        if self.log_control {
+            adapter = adapter.with_log_level(
+                || solti_observe::current_level().map(|level| level.as_str().to_string()),
+                |filter| {
+                    solti_observe::LoggerLevel::new(filter)
+                        .and_then(|level| solti_observe::reload_level(&level))
+                        .map_err(|e| e.to_string())
+                },
+            );
+        }
+
+        if let Some(reloader) = self.reloader.clone() {
+            adapter = adapter.with_reload(move || reloader.reload().map_err(|e| e.to_string()));
+        }
+
+        let mut server = ApiServer::new(Arc::new(adapter));
+        if let Some(addr) = &options.http_addr {
+            server = server.with_http_addr(parse_addr("api.http_addr", addr)?);
+            info!(%addr, "agent HTTP API enabled");
+        }
+        if let Some(addr) = &options.grpc_addr {
+            #[cfg(feature = "grpc")]
+            {
+                server = server.with_grpc_addr(parse_addr("api.grpc_addr", addr)?);
+                info!(%addr, "agent gRPC API enabled");
+            }
+            #[cfg(not(feature = "grpc"))]
+            return Err(AgentError::Config(format!(
+                "api.grpc_addr is set ({addr}) but feature `grpc` is disabled"
+            )));
+        }
+        if let Some(metrics) = self.metrics.clone() {
+            server = server.with_metrics(move |accept| {
+                let encoded = metrics
+                    .encode(solti_prometheus::ExpositionFormat::from_accept(accept))
+                    .map_err(|e| e.to_string())?;
+                Ok((encoded.content_type.to_string(), encoded.body))
+            });
+        }
+        for setup in self.server.drain(..) {
+            server = setup(server);
+        }
+        Ok(Some(server))
+    }
+}
+
+#[cfg(feature = "http")]
+fn parse_addr(field: &str, addr: &str) -> Result<std::net::SocketAddr, AgentError> {
+    addr.parse()
+        .map_err(|e| AgentError::Config(format!("{field}: {addr}: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use solti_core::AgentControlRunner;
+    use solti_model::{AgentAction, RestartRateLimit, TaskKind};
+
+    use super::*;
+
+    fn settings() -> SupervisorSettings {
+        let mut settings = SupervisorSettings::default();
+        settings.rate_limits.default = Some(RestartRateLimit::per_minute(6));
+        settings.queue_limits.slots.insert("backup".into(), 4);
+        settings.capacity = solti_model::ResourceCapacity::unbounded().with_gpus(1);
+        settings
+    }
+
+    #[tokio::test]
+    async fn builds_supervisor_from_settings() {
+        let agent = Agent::builder(settings())
+            .without_logger()
+            .with_runners(|router| {
+                router.register(Arc::new(
+                    AgentControlRunner::new().with_action(AgentAction::FlushState, || Ok(())),
+                ));
+                Ok::<_, AgentError>(())
+            })
+            .build()
+            .await
+            .unwrap();
+
+        assert!(agent.metrics().is_some());
+        assert_eq!(
+            agent.supervisor().restart_limiter().limit_for("any"),
+            Some(RestartRateLimit::per_minute(6))
+        );
+        assert_eq!(
+            agent.supervisor().queue_limits().limit_for("backup"),
+            Some(4)
+        );
+        assert_eq!(
+            agent.supervisor().allocatable_capacity(),
+            Some(solti_model::ResourceCapacity::unbounded().with_gpus(1))
+        );
+
+        let id = agent.supervisor().submit(&control_spec()).await.unwrap();
+        let info = agent
+            .supervisor()
+            .wait(&id, std::time::Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(info.status, solti_model::TaskStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn reload_updates_supervisor_limits() {
+        let reloader = ConfigReloader::new("agent.toml", settings());
+        let agent = Agent::builder(settings())
+            .without_logger()
+            .without_metrics()
+            .with_reloader(reloader)
+            .build()
+            .await
+            .unwrap();
+
+        let mut next = settings();
+        next.rate_limits.default = Some(RestartRateLimit::per_minute(2));
+        next.queue_limits.slots.clear();
+        let report = agent.reloader().unwrap().apply(next).unwrap();
+
+        assert!(report.is_applied("rate_limits"));
+        assert!(report.is_applied("queue_limits"));
+        assert_eq!(
+            agent.supervisor().restart_limiter().limit_for("any"),
+            Some(RestartRateLimit::per_minute(2))
+        );
+        assert_eq!(agent.supervisor().queue_limits().limit_for("backup"), None);
+    }
+
+    #[cfg(feature = "discover")]
+    #[tokio::test]
+    async fn reload_applies_discovery_delay() {
+        let mut settings = settings();
+        settings.discovery = Some(solti_settings::DiscoveryOptions {
+            name: "edge-01".into(),
+            control_plane_endpoint: "http://127.0.0.1:1".into(),
+            transport: "http".into(),
+            delay_ms: 60_000,
+            ..Default::default()
+        });
+        let agent = Agent::builder(settings.clone())
+            .without_logger()
+            .without_metrics()
+            .with_reloader(ConfigReloader::new("agent.toml", settings.clone()))
+            .build()
+            .await
+            .unwrap();
+
+        settings.discovery.as_mut().unwrap().delay_ms = 5_000;
+        let report = agent.reloader().unwrap().apply(settings).unwrap();
+        assert!(report.is_applied("discovery.delay_ms"));
+        assert!(report.requires_restart.is_empty());
+        agent.supervisor().shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn parts_can_be_disabled() {
+        let agent = Agent::builder(settings())
+            .without_logger()
+            .without_metrics()
+            .without_api()
+            .without_discovery()
+            .build()
+            .await
+            .unwrap();
+        assert!(agent.metrics().is_none());
+    }
+
+    #[tokio::test]
+    async fn runner_setup_errors_are_reported() {
+        let err = Agent::builder(settings())
+            .without_logger()
+            .with_runners(|_| Err("no runtime"))
+            .build()
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(err, AgentError::Runners(_)));
+        assert_eq!(err.to_string(), "runner setup: no runtime");
+    }
+
+    #[tokio::test]
+    async fn run_until_stops_tasks() {
+        let agent = Agent::builder(settings())
+            .without_logger()
+            .without_api()
+            .build()
+            .await
+            .unwrap();
+        let supervisor = Arc::clone(agent.supervisor());
+
+        let task: taskvisor::TaskRef = taskvisor::TaskFn::arc(
+            "endless",
+            |ctx: tokio_util::sync::CancellationToken| async move {
+                ctx.cancelled().await;
+                Err::<(), _>(taskvisor::TaskError::Canceled)
+            },
+        );
+        let policy = solti_core::TaskPolicy::from_spec(&control_spec());
+        let id = supervisor.submit_with_task(task, &policy).await.unwrap();
+
+        agent
+            .run_until(tokio::time::sleep(std::time::Duration::from_millis(50)))
+            .await
+            .unwrap();
+        assert!(
+            supervisor
+                .get_task(&id)
+                .is_none_or(|info| !info.status.is_active())
+        );
+    }
+
+    #[cfg(feature = "http")]
+    #[tokio::test]
+    async fn invalid_api_address_is_rejected() {
+        let mut settings = settings();
+        settings.api.http_addr = Some("not-an-address".into());
+        let mut agent = Agent::builder(settings)
+            .without_logger()
+            .build()
+            .await
+            .unwrap();
+        assert!(matches!(agent.api_server(), Err(AgentError::Config(_))));
+    }
+
+    fn control_spec() -> solti_model::CreateSpec {
+        use solti_model::{
+            AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, RunnerLabels,
+        };
+        solti_model::CreateSpec {
+            slot: "control".into(),
+            kind: TaskKind::AgentControl {
+                action: AgentAction::FlushState,
+            },
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 100,
+                max_ms: 100,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+}
@@ -0,0 +1,293 @@
+use std::sync::{Arc, Mutex};
+
+use solti_core::{BuildContext, MetricsHandle, RunnerRouter, SupervisorApi, noop_metrics};
+use solti_model::TaskEnv;
+use solti_observe::init_logger;
+use solti_prometheus::PrometheusMetrics;
+use solti_settings::{ConfigReloader, SupervisorSettings};
+use taskvisor::Subscribe;
+use tracing::info;
+
+use crate::{Agent, AgentError};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+type RouterSetup = Box<dyn FnOnce(&mut RunnerRouter) -> Result<(), BoxError> + Send>;
+type SupervisorSetup = Box<dyn FnOnce(SupervisorApi) -> SupervisorApi + Send>;
+#[cfg(feature = "http")]
+pub(crate) type ServerSetup = Box<
+    dyn FnOnce(
+            solti_api::ApiServer<solti_api::SupervisorApiAdapter>,
+        ) -> solti_api::ApiServer<solti_api::SupervisorApiAdapter>
+        + Send,
+>;
+
+/// Builder of an [`Agent`]; see [`Agent::builder`].
+pub struct AgentBuilder {
+    settings: SupervisorSettings,
+    logger: bool,
+    metrics: bool,
+    api: bool,
+    discovery: bool,
+    env: TaskEnv,
+    subscribers: Vec<Arc<dyn Subscribe>>,
+    reloader: Option<ConfigReloader>,
+    runners: Vec<RouterSetup>,
+    supervisor: Vec<SupervisorSetup>,
+    #[cfg(feature = "http")]
+    server: Vec<ServerSetup>,
+}
+
+impl AgentBuilder {
+    pub(crate) fn new(settings: SupervisorSettings) -> Self {
+        Self {
+            settings,
+            logger: true,
+            metrics: true,
+            api: true,
+            discovery: true,
+            env: TaskEnv::default(),
+            subscribers: Vec::new(),
+            reloader: None,
+            runners: Vec::new(),
+            supervisor: Vec::new(),
+            #[cfg(feature = "http")]
+            server: Vec::new(),
+        }
+    }
+
+    /// Environment passed to every task built by the router.
+    pub fn with_env(mut self, env: TaskEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    /// Attach a lifecycle event subscriber.
+    pub fn with_subscriber(mut self, subscriber: Arc<dyn Subscribe>) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+
+    /// Reload the settings file through `reloader` on the reload signal (`SIGHUP` by default)
+    /// and, with feature `http`, on `POST /api/v1/admin/reload`.
+    ///
+    /// [`build`](Self::build) registers hooks applying `rate_limits` and `queue_limits` to the
+    /// supervisor; add hooks of your own before passing the reloader in.
+    pub fn with_reloader(mut self, reloader: ConfigReloader) -> Self {
+        self.reloader = Some(reloader);
+        self
+    }
+
+    /// Register runners on the router; called in order during [`build`](Self::build).
+    ///
+    /// ```rust,ignore
+    /// Agent::builder(settings)
+    ///     .with_runners(|router| register_subprocess_runner(router, "sandboxed"))
+    /// ```
+    pub fn with_runners<F, E>(mut self, setup: F) -> Self
+    where
+        F: FnOnce(&mut RunnerRouter) -> Result<(), E> + Send + 'static,
+        E: Into<BoxError>,
+    {
+        self.runners
+            .push(Box::new(move |router| setup(router).map_err(Into::into)));
+        self
+    }
+
+    /// Register a subprocess runner tagged `name`.
+    #[cfg(feature = "subprocess")]
+    pub fn with_subprocess_runner(self, name: &'static str) -> Self {
+        self.with_runners(move |router| {
+            solti_exec::subprocess::register_subprocess_runner(router, name)
+        })
+    }
+
+    /// Customize the supervisor after it is created from the settings
+    /// (admission policies, event capacity, ...).
+    pub fn with_supervisor<F>(mut self, setup: F) -> Self
+    where
+        F: FnOnce(SupervisorApi) -> SupervisorApi + Send + 'static,
+    {
+        self.supervisor.push(Box::new(setup));
+        self
+    }
+
+    /// Customize the API server before it starts (routes, TLS, interceptors, ...).
+    #[cfg(feature = "http")]
+    pub fn with_server<F>(mut self, setup: F) -> Self
+    where
+        F: FnOnce(
+                solti_api::ApiServer<solti_api::SupervisorApiAdapter>,
+            ) -> solti_api::ApiServer<solti_api::SupervisorApiAdapter>
+            + Send
+            + 'static,
+    {
+        self.server.push(Box::new(setup));
+        self
+    }
+
+    /// Do not install the global logger (e.g. the host already did).
+    pub fn without_logger(mut self) -> Self {
+        self.logger = false;
+        self
+    }
+
+    /// Do not create the Prometheus backend, regardless of `metrics.enabled`.
+    pub fn without_metrics(mut self) -> Self {
+        self.metrics = false;
+        self
+    }
+
+    /// Do not serve the API, regardless of `[api]`.
+    pub fn without_api(mut self) -> Self {
+        self.api = false;
+        self
+    }
+
+    /// Do not sync with the control plane, regardless of `[discovery]`.
+    pub fn without_discovery(mut self) -> Self {
+        self.discovery = false;
+        self
+    }
+
+    /// Initialize the enabled subsystems and start the supervisor.
+    pub async fn build(self) -> Result<Agent, AgentError> {
+        let settings = self.settings;
+        settings.validate()?;
+
+        if self.logger {
+            init_logger(&settings.logger)?;
+        }
+
+        let metrics = if self.metrics && settings.metrics.enabled {
+            Some(PrometheusMetrics::new()?)
+        } else {
+            None
+        };
+        let handle: MetricsHandle = match &metrics {
+            Some(metrics) => Arc::new(metrics.clone()),
+            None => noop_metrics(),
+        };
+
+        let mut router = RunnerRouter::new().with_context(BuildContext::new(self.env, handle));
+        for setup in self.runners {
+            setup(&mut router).map_err(AgentError::Runners)?;
+        }
+
+        let mut supervisor = SupervisorApi::new(
+            settings.supervisor.to_config(),
+            settings.controller.to_config(),
+            self.subscribers,
+            router,
+        )
+        .await?
+        .with_quotas(settings.quotas.clone())
+        .with_capacity(settings.capacity)
+        .with_dedup(settings.controller.dedup);
+        for setup in self.supervisor {
+            supervisor = setup(supervisor);
+        }
+
+        apply_limits(&supervisor, None, &settings);
+        let supervisor = Arc::new(supervisor);
+        info!("agent supervisor ready");
+
+        #[cfg(feature = "discover")]
+        let sync_delay = match &settings.discovery {
+            Some(options) if self.discovery => {
+                Some(solti_discover::SyncDelay::new(options.delay_ms))
+            }
+            _ => None,
+        };
+
+        let reloader = self.reloader.map(|reloader| {
+            let handle = Arc::clone(&supervisor);
+            let previous = Mutex::new(settings.clone());
+            let reloader =
+                reloader.with_hook_for(&["rate_limits", "queue_limits"], move |next, _| {
+                    let mut previous = previous.lock().expect("settings lock poisoned");
+                    apply_limits(&handle, Some(&previous), next);
+                    *previous = next.clone();
+                });
+            #[cfg(feature = "discover")]
+            let reloader = match sync_delay.clone() {
+                Some(delay) => reloader.with_hook_for(&["discovery.delay_ms"], move |next, _| {
+                    if let Some(options) = &next.discovery {
+                        delay.set(options.delay_ms);
+                    }
+                }),
+                None => reloader,
+            };
+            Arc::new(reloader)
+        });
+
+        #[cfg(feature = "discover")]
+        let deregistration = match (&settings.discovery, sync_delay) {
+            (Some(options), Some(delay)) => {
+                let (task, spec, deregistration) =
+                    solti_discover::SyncBuilder::new(options.to_config()?)
+                        .with_delay(delay)
+                        .with_maintenance(supervisor.maintenance())
+                        .with_router(supervisor.router())
+                        .with_reconciler(Arc::clone(&supervisor))
+                        .with_task_summary(Arc::clone(&supervisor))
+                        .with_capacity(Arc::clone(&supervisor))
+                        .build_with_deregistration();
+                supervisor
+                    .submit_with_task(task, &solti_core::TaskPolicy::from_spec(&spec))
+                    .await?;
+                info!(endpoint = %options.control_plane_endpoint, "discovery sync started");
+                Some(deregistration)
+            }
+            _ => None,
+        };
+
+        Ok(Agent {
+            supervisor,
+            metrics,
+            reloader,
+            #[cfg(feature = "http")]
+            api: self.api,
+            #[cfg(feature = "http")]
+            log_control: self.logger,
+            #[cfg(feature = "http")]
+            server: self.server,
+            #[cfg(feature = "discover")]
+            deregistration,
+            settings,
+        })
+    }
+}
+
+/// Apply `[rate_limits]` and `[queue_limits]` to the supervisor, clearing the slot limits
+/// that `previous` had but `settings` no longer has.
+fn apply_limits(
+    supervisor: &SupervisorApi,
+    previous: Option<&SupervisorSettings>,
+    settings: &SupervisorSettings,
+) {
+    let limiter = supervisor.restart_limiter();
+    limiter.set_default(settings.rate_limits.default);
+    for slot in previous
+        .into_iter()
+        .flat_map(|p| p.rate_limits.slots.keys())
+        .filter(|slot| !settings.rate_limits.slots.contains_key(*slot))
+    {
+        limiter.set_slot_limit(slot.clone(), None);
+    }
+    for (slot, limit) in &settings.rate_limits.slots {
+        limiter.set_slot_limit(slot.clone(), Some(*limit));
+    }
+
+    let queue_limits = supervisor.queue_limits();
+    queue_limits.set_default(settings.queue_limits.default);
+    for slot in previous
+        .into_iter()
+        .flat_map(|p| p.queue_limits.slots.keys())
+        .filter(|slot| !settings.queue_limits.slots.contains_key(*slot))
+    {
+        queue_limits.set_slot_limit(slot.clone(), None);
+    }
+    for (slot, max) in &settings.queue_limits.slots {
+        queue_limits.set_slot_limit(slot.clone(), Some(*max));
+    }
+}
@@ -0,0 +1,41 @@
+use thiserror::Error;
+
+/// Errors raised while assembling or running an [`crate::Agent`].
+#[derive(Debug, Error)]
+pub enum AgentError {
+    #[error("logger: {0}")]
+    Logger(#[from] solti_observe::LoggerError),
+
+    #[error("metrics: {0}")]
+    Metrics(#[from] prometheus::Error),
+
+    #[error("runner setup: {0}")]
+    Runners(Box<dyn std::error::Error + Send + Sync>),
+
+    #[error("invalid configuration: {0}")]
+    Config(String),
+
+    #[error(transparent)]
+    Settings(#[from] solti_settings::SettingsError),
+
+    #[error(transparent)]
+    Core(#[from] solti_core::CoreError),
+
+    #[cfg(feature = "http")]
+    #[error(transparent)]
+    Server(#[from] solti_api::ServerError),
+
+    #[error("signal handling: {0}")]
+    Signal(#[from] std::io::Error),
+
+    #[error("state dump: {0}")]
+    StateDump(#[from] serde_json::Error),
+
+    #[cfg(all(windows, feature = "windows-service"))]
+    #[error("windows service: {0}")]
+    Service(#[from] windows_service::Error),
+
+    #[cfg(all(windows, feature = "windows-service"))]
+    #[error("runtime: {0}")]
+    Runtime(std::io::Error),
+}
@@ -0,0 +1,41 @@
+//! One-stop wiring of a solti agent.
+//!
+//! [`Agent::builder`] assembles logger, Prometheus metrics, runner router, subscribers,
+//! supervisor, API server and discovery from a [`SupervisorSettings`]; each part can be
+//! customized through the builder or turned off with a `without_*` method.
+//!
+//! ```rust,ignore
+//! let settings = SupervisorSettings::load("/etc/solti/agent.toml")?;
+//! let agent = Agent::builder(settings)
+//!     .with_subprocess_runner("default")
+//!     .with_subscriber(Arc::new(Subscriber))
+//!     .build()
+//!     .await?;
+//!
+//! agent.supervisor().submit(&spec).await?;
+//! agent.run().await?; // serves the API until SIGINT / SIGTERM, then deregisters
+//! ```
+//!
+//! ## Features
+//! - `toml` (default) — load `.toml` settings files with [`SupervisorSettings::load`];
+//! - `yaml` — load `.yaml` / `.yml` settings files;
+//! - `http` — serve the HTTP API configured in `[api]` (and metrics when enabled);
+//! - `grpc` — also serve the gRPC API;
+//! - `discover` — sync with the control plane configured in `[discovery]`;
+//! - `subprocess` — [`AgentBuilder::with_subprocess_runner`];
+//! - `windows-service` — run and register the agent as a Windows service (`service` module, Windows only).
+mod error;
+pub use error::AgentError;
+
+mod agent;
+pub use agent::Agent;
+
+mod builder;
+pub use builder::AgentBuilder;
+
+mod signals;
+
+#[cfg(all(windows, feature = "windows-service"))]
+pub mod service;
+
+pub use solti_settings::SupervisorSettings;
@@ -0,0 +1,192 @@
+//! Running the agent as a Windows service.
+//!
+//! [`run_service`] hands the process over to the service control manager (SCM) and runs the
+//! agent returned by the build closure. `Stop`, `Shutdown` and `Preshutdown` controls trigger
+//! the same graceful shutdown as a signal does for [`Agent::run`]: running tasks get the
+//! supervisor grace period and the agent deregisters from the control plane.
+//!
+//! [`install_service`] / [`uninstall_service`] register the current executable with the SCM,
+//! so no NSSM-style wrapper is needed.
+//!
+//! ```rust,ignore
+//! fn main() -> Result<(), AgentError> {
+//!     if std::env::args().any(|arg| arg == "--install") {
+//!         return install_service("solti-agent", "Solti agent", vec!["--service".into()]);
+//!     }
+//!     run_service("solti-agent", || async {
+//!         let settings = SupervisorSettings::load(r"C:\ProgramData\solti\agent.toml")?;
+//!         Agent::builder(settings).build().await
+//!     })
+//! }
+//! ```
+use std::{ffi::OsString, future::Future, pin::Pin, sync::Mutex, time::Duration};
+
+use tokio::sync::watch;
+use tracing::{error, info};
+use windows_service::{
+    define_windows_service,
+    service::{
+        ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    },
+    service_control_handler::{self, ServiceControlHandlerResult, ServiceStatusHandle},
+    service_dispatcher,
+    service_manager::{ServiceManager, ServiceManagerAccess},
+};
+
+use crate::{Agent, AgentError};
+
+const SERVICE_TYPE: ServiceType = ServiceType::OWN_PROCESS;
+
+/// Time the SCM is asked to wait while the agent starts or stops.
+const PENDING_WAIT_HINT: Duration = Duration::from_secs(30);
+
+/// Service-specific exit code reported when the agent fails.
+const EXIT_FAILURE: u32 = 1;
+
+type BuildFuture = Pin<Box<dyn Future<Output = Result<Agent, AgentError>>>>;
+type Build = Box<dyn FnOnce() -> BuildFuture + Send>;
+
+/// Service passed to [`run_service`], taken by the SCM entry point.
+static SERVICE: Mutex<Option<(&'static str, Build)>> = Mutex::new(None);
+
+define_windows_service!(ffi_service_main, service_main);
+
+/// Run the process as the Windows service `name`.
+///
+/// Blocks until the service stops. `build` runs on a Tokio runtime owned by the service
+/// once the SCM has started it. Fails if the process was not started by the SCM.
+pub fn run_service<F, Fut>(name: &'static str, build: F) -> Result<(), AgentError>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<Agent, AgentError>> + 'static,
+{
+    let build: Build = Box::new(move || Box::pin(build()));
+    *SERVICE.lock().unwrap() = Some((name, build));
+    service_dispatcher::start(name, ffi_service_main)?;
+    Ok(())
+}
+
+/// Register the current executable as an auto-start service started with `arguments`.
+pub fn install_service(
+    name: &str,
+    display_name: &str,
+    arguments: Vec<OsString>,
+) -> Result<(), AgentError> {
+    let manager = ServiceManager::local_computer(
+        None::<&str>,
+        ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+    )?;
+    let info = ServiceInfo {
+        name: name.into(),
+        display_name: display_name.into(),
+        service_type: SERVICE_TYPE,
+        start_type: ServiceStartType::AutoStart,
+        error_control: ServiceErrorControl::Normal,
+        executable_path: std::env::current_exe().map_err(windows_service::Error::Winapi)?,
+        launch_arguments: arguments,
+        dependencies: Vec::new(),
+        account_name: None,
+        account_password: None,
+    };
+    manager.create_service(&info, ServiceAccess::QUERY_STATUS)?;
+    info!(service = name, "windows service installed");
+    Ok(())
+}
+
+/// Stop the service `name` if it is running and remove it from the SCM.
+pub fn uninstall_service(name: &str) -> Result<(), AgentError> {
+    let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)?;
+    let service = manager.open_service(
+        name,
+        ServiceAccess::QUERY_STATUS | ServiceAccess::STOP | ServiceAccess::DELETE,
+    )?;
+    if service.query_status()?.current_state != ServiceState::Stopped {
+        service.stop()?;
+    }
+    service.delete()?;
+    info!(service = name, "windows service removed");
+    Ok(())
+}
+
+fn service_main(_arguments: Vec<OsString>) {
+    let Some((name, build)) = SERVICE.lock().unwrap().take() else {
+        return;
+    };
+    if let Err(e) = run(name, build) {
+        error!(service = name, error = %e, "windows service failed");
+    }
+}
+
+fn run(name: &'static str, build: Build) -> Result<(), AgentError> {
+    let (stop_tx, mut stop_rx) = watch::channel(false);
+    let status = service_control_handler::register(name, move |control| match control {
+        ServiceControl::Stop | ServiceControl::Shutdown | ServiceControl::Preshutdown => {
+            stop_tx.send_replace(true);
+            ServiceControlHandlerResult::NoError
+        }
+        ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+        _ => ServiceControlHandlerResult::NotImplemented,
+    })?;
+    set_status(
+        status,
+        ServiceState::StartPending,
+        ServiceExitCode::Win32(0),
+    )?;
+
+    let result = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+        .map_err(AgentError::Runtime)
+        .and_then(|runtime| {
+            runtime.block_on(async move {
+                let agent = build().await?;
+                set_status(status, ServiceState::Running, ServiceExitCode::Win32(0))?;
+                info!(service = name, "windows service running");
+
+                agent
+                    .run_until(async move {
+                        let _ = stop_rx.wait_for(|stop| *stop).await;
+                        info!(service = name, "windows service stop requested");
+                        let _ = set_status(
+                            status,
+                            ServiceState::StopPending,
+                            ServiceExitCode::Win32(0),
+                        );
+                    })
+                    .await
+            })
+        });
+
+    let exit_code = match &result {
+        Ok(()) => ServiceExitCode::Win32(0),
+        Err(_) => ServiceExitCode::ServiceSpecific(EXIT_FAILURE),
+    };
+    set_status(status, ServiceState::Stopped, exit_code)?;
+    result
+}
+
+fn set_status(
+    handle: ServiceStatusHandle,
+    state: ServiceState,
+    exit_code: ServiceExitCode,
+) -> Result<(), AgentError> {
+    let controls_accepted = match state {
+        ServiceState::Running => ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        _ => ServiceControlAccept::empty(),
+    };
+    let wait_hint = match state {
+        ServiceState::StartPending | ServiceState::StopPending => PENDING_WAIT_HINT,
+        _ => Duration::ZERO,
+    };
+    handle.set_service_status(ServiceStatus {
+        service_type: SERVICE_TYPE,
+        current_state: state,
+        controls_accepted,
+        exit_code,
+        checkpoint: 0,
+        wait_hint,
+        process_id: None,
+    })?;
+    Ok(())
+}
@@ -0,0 +1,185 @@
+//! OS signal handling of [`crate::Agent::run`].
+//!
+//! [`SignalListener`] waits for the signals bound in [`SignalOptions`] and reports the bound
+//! [`SignalAction`]; the agent decides what to do with it.
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use solti_core::{SupervisorApi, agent_id};
+use solti_model::{ResourceCapacity, SubscriberHealth, TaskInfo};
+use solti_settings::{OsSignal, SignalOptions};
+use tracing::{info, warn};
+
+use crate::AgentError;
+
+/// Action bound to a signal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SignalAction {
+    Shutdown,
+    Immediate,
+    Reload,
+    Dump,
+}
+
+/// Receives the configured signals.
+pub(crate) struct SignalListener {
+    #[cfg(unix)]
+    streams: Vec<(OsSignal, SignalAction, tokio::signal::unix::Signal)>,
+    #[cfg(not(unix))]
+    ctrl_c: Option<SignalAction>,
+}
+
+impl SignalListener {
+    /// Install handlers for every signal bound in `options`.
+    pub(crate) fn new(options: &SignalOptions) -> Result<Self, AgentError> {
+        options.validate()?;
+        let bindings = [
+            (&options.shutdown, SignalAction::Shutdown),
+            (&options.immediate, SignalAction::Immediate),
+            (&options.reload, SignalAction::Reload),
+            (&options.dump, SignalAction::Dump),
+        ]
+        .into_iter()
+        .flat_map(|(signals, action)| signals.iter().map(move |signal| (*signal, action)));
+
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            let mut streams = Vec::new();
+            for (os_signal, action) in bindings {
+                let kind = match os_signal {
+                    OsSignal::Interrupt => SignalKind::interrupt(),
+                    OsSignal::Terminate => SignalKind::terminate(),
+                    OsSignal::Quit => SignalKind::quit(),
+                    OsSignal::Hangup => SignalKind::hangup(),
+                    OsSignal::User1 => SignalKind::user_defined1(),
+                    OsSignal::User2 => SignalKind::user_defined2(),
+                };
+                streams.push((os_signal, action, signal(kind)?));
+            }
+            Ok(Self { streams })
+        }
+        #[cfg(not(unix))]
+        {
+            let mut ctrl_c = None;
+            for (os_signal, action) in bindings {
+                match os_signal {
+                    OsSignal::Interrupt => ctrl_c = Some(action),
+                    other => warn!(signal = %other, "signal is not supported on this platform"),
+                }
+            }
+            Ok(Self { ctrl_c })
+        }
+    }
+
+    /// Wait for the next bound signal.
+    #[cfg(unix)]
+    pub(crate) async fn recv(&mut self) -> (OsSignal, SignalAction) {
+        std::future::poll_fn(|cx| {
+            for (signal, action, stream) in &mut self.streams {
+                if stream.poll_recv(cx).is_ready() {
+                    return std::task::Poll::Ready((*signal, *action));
+                }
+            }
+            std::task::Poll::Pending
+        })
+        .await
+    }
+
+    /// Wait for the next bound signal.
+    #[cfg(not(unix))]
+    pub(crate) async fn recv(&mut self) -> (OsSignal, SignalAction) {
+        match self.ctrl_c {
+            Some(action) => match tokio::signal::ctrl_c().await {
+                Ok(()) => (OsSignal::Interrupt, action),
+                Err(e) => {
+                    warn!(error = %e, "failed to listen for Ctrl+C");
+                    std::future::pending().await
+                }
+            },
+            None => std::future::pending().await,
+        }
+    }
+}
+
+/// JSON snapshot of the agent written on a dump signal.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StateDump {
+    agent_id: String,
+    ts: u64,
+    maintenance: bool,
+    tasks: Vec<TaskInfo>,
+    subscribers: Vec<SubscriberHealth>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    free_capacity: Option<ResourceCapacity>,
+}
+
+/// Write the supervisor state as `solti-state-<unix ms>.json` into `dir`.
+pub(crate) fn dump_state(supervisor: &SupervisorApi, dir: &Path) -> Result<PathBuf, AgentError> {
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let dump = StateDump {
+        agent_id: agent_id().to_string(),
+        ts,
+        maintenance: supervisor.is_maintenance(),
+        tasks: supervisor.list_all_tasks(),
+        subscribers: supervisor.subscriber_health(),
+        free_capacity: supervisor.free_capacity(),
+    };
+    let body = serde_json::to_vec_pretty(&dump)?;
+
+    let path = dir.join(format!("solti-state-{ts}.json"));
+    std::fs::write(&path, body)?;
+    Ok(path)
+}
+
+/// Handle a dump signal; failures are logged.
+pub(crate) fn on_dump(supervisor: &SupervisorApi, dir: &Path) {
+    match dump_state(supervisor, dir) {
+        Ok(path) => info!(path = %path.display(), "agent state dumped"),
+        Err(e) => warn!(error = %e, "failed to dump agent state"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn bound_signals_map_to_actions() {
+        let options = SignalOptions {
+            shutdown: Vec::new(),
+            immediate: Vec::new(),
+            reload: Vec::new(),
+            dump: vec![OsSignal::User2],
+            dump_dir: None,
+        };
+        let mut listener = SignalListener::new(&options).unwrap();
+
+        unsafe { libc::raise(libc::SIGUSR2) };
+        let received = tokio::time::timeout(std::time::Duration::from_secs(5), listener.recv())
+            .await
+            .unwrap();
+        assert_eq!(received, (OsSignal::User2, SignalAction::Dump));
+    }
+
+    #[test]
+    fn conflicting_bindings_are_rejected() {
+        let options = SignalOptions {
+            reload: vec![OsSignal::Interrupt],
+            ..Default::default()
+        };
+        assert!(matches!(
+            SignalListener::new(&options),
+            Err(AgentError::Settings(_))
+        ));
+    }
+}
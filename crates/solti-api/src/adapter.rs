@@ -1,30 +1,80 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use async_trait::async_trait;
-use solti_core::SupervisorApi;
-use solti_model::{CreateSpec, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus};
+use solti_core::{CoreError, SupervisorApi};
+use solti_model::{
+    CreateSpec, EventQuery, GroupInfo, ReloadReport, SubscriberHealth, TaskEvent, TaskId, TaskInfo,
+    TaskPage, TaskQuery, TaskStatus,
+};
 
 use crate::error::ApiError;
 use crate::handler::ApiHandler;
 
+/// Configuration reload callback used by [`SupervisorApiAdapter`].
+///
+/// Returns the reload report, or an error message if the new configuration could not be loaded.
+pub type ReloadFn = Arc<dyn Fn() -> Result<ReloadReport, String> + Send + Sync>;
+
+/// Returns the current log filter expression, if a logger is installed.
+pub type LogLevelGetFn = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+/// Applies a new log filter expression, or returns an error message if it is invalid.
+pub type LogLevelSetFn = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
 /// Adapter that bridges `SupervisorApi` to `ApiHandler`.
 ///
 /// This is a ready-to-use implementation that directly delegates to `SupervisorApi`.
 pub struct SupervisorApiAdapter {
     supervisor: Arc<SupervisorApi>,
+    reload: Option<ReloadFn>,
+    log_level: Option<(LogLevelGetFn, LogLevelSetFn)>,
 }
 
 impl SupervisorApiAdapter {
     /// Create a new adapter wrapping the given supervisor.
     pub fn new(supervisor: Arc<SupervisorApi>) -> Self {
-        Self { supervisor }
+        Self {
+            supervisor,
+            reload: None,
+            log_level: None,
+        }
+    }
+
+    /// Enable configuration reload through the API.
+    ///
+    /// Without a reload callback, [`ApiHandler::reload_config`] returns [`ApiError::Unsupported`].
+    pub fn with_reload<F>(mut self, reload: F) -> Self
+    where
+        F: Fn() -> Result<ReloadReport, String> + Send + Sync + 'static,
+    {
+        self.reload = Some(Arc::new(reload));
+        self
+    }
+
+    /// Enable runtime log level changes through the API.
+    ///
+    /// Without these callbacks, [`ApiHandler::get_log_level`] and [`ApiHandler::set_log_level`]
+    /// return [`ApiError::Unsupported`].
+    pub fn with_log_level<G, S>(mut self, get: G, set: S) -> Self
+    where
+        G: Fn() -> Option<String> + Send + Sync + 'static,
+        S: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.log_level = Some((Arc::new(get), Arc::new(set)));
+        self
+    }
+
+    fn log_level_control(&self) -> Result<&(LogLevelGetFn, LogLevelSetFn), ApiError> {
+        self.log_level
+            .as_ref()
+            .ok_or_else(|| ApiError::Unsupported("log level control".into()))
     }
 }
 
 #[async_trait]
 impl ApiHandler for SupervisorApiAdapter {
     async fn submit_task(&self, spec: CreateSpec) -> Result<TaskId, ApiError> {
-        self.supervisor.submit(&spec).await.map_err(ApiError::from)
+        self.supervisor.submit(&spec).await.map_err(core_error)
     }
 
     async fn get_task_status(&self, id: &TaskId) -> Result<Option<TaskInfo>, ApiError> {
@@ -47,10 +97,107 @@ impl ApiHandler for SupervisorApiAdapter {
         Ok(self.supervisor.query_tasks(&query))
     }
 
+    async fn tasks_revision(&self) -> Result<Option<u64>, ApiError> {
+        Ok(Some(self.supervisor.tasks_revision()))
+    }
+
     async fn cancel_task(&self, id: &TaskId) -> Result<(), ApiError> {
         self.supervisor
             .cancel_task(id)
             .await
             .map_err(ApiError::from)
     }
+
+    async fn get_group_status(&self, group: &str) -> Result<Option<GroupInfo>, ApiError> {
+        Ok(self.supervisor.get_group(group))
+    }
+
+    async fn cancel_group(&self, group: &str) -> Result<usize, ApiError> {
+        self.supervisor
+            .cancel_group(group)
+            .await
+            .map_err(core_error)
+    }
+
+    async fn wait_task(&self, id: &TaskId, timeout: Duration) -> Result<TaskInfo, ApiError> {
+        self.supervisor.wait(id, timeout).await.map_err(core_error)
+    }
+
+    async fn submit_and_wait(
+        &self,
+        spec: CreateSpec,
+        timeout: Duration,
+    ) -> Result<(TaskId, Option<TaskInfo>), ApiError> {
+        self.supervisor
+            .submit_and_wait(&spec, timeout)
+            .await
+            .map_err(core_error)
+    }
+
+    async fn wait_task_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        timeout: Duration,
+    ) -> Result<TaskInfo, ApiError> {
+        self.supervisor
+            .wait_status_change(id, from, timeout)
+            .await
+            .map_err(core_error)
+    }
+
+    async fn wait_group(&self, group: &str, timeout: Duration) -> Result<GroupInfo, ApiError> {
+        self.supervisor
+            .wait_group(group, timeout)
+            .await
+            .map_err(core_error)
+    }
+
+    async fn get_maintenance(&self) -> Result<bool, ApiError> {
+        Ok(self.supervisor.is_maintenance())
+    }
+
+    async fn set_maintenance(&self, enabled: bool) -> Result<bool, ApiError> {
+        Ok(self.supervisor.set_maintenance(enabled))
+    }
+
+    async fn reload_config(&self) -> Result<ReloadReport, ApiError> {
+        let reload = self
+            .reload
+            .as_ref()
+            .ok_or_else(|| ApiError::Unsupported("configuration reload".into()))?;
+        reload().map_err(ApiError::InvalidRequest)
+    }
+
+    async fn list_events(&self, query: EventQuery) -> Result<Vec<TaskEvent>, ApiError> {
+        Ok(self.supervisor.recent_events(&query))
+    }
+
+    async fn list_subscribers(&self) -> Result<Vec<SubscriberHealth>, ApiError> {
+        Ok(self.supervisor.subscriber_health())
+    }
+
+    async fn get_log_level(&self) -> Result<String, ApiError> {
+        let (get, _) = self.log_level_control()?;
+        get().ok_or_else(|| ApiError::Internal("logger is not initialized".into()))
+    }
+
+    async fn set_log_level(&self, level: &str) -> Result<String, ApiError> {
+        let (get, set) = self.log_level_control()?;
+        let previous = get().unwrap_or_default();
+        set(level).map_err(ApiError::InvalidRequest)?;
+        Ok(previous)
+    }
+}
+
+/// Map core errors with a dedicated API representation; everything else stays [`ApiError::Core`].
+fn core_error(e: CoreError) -> ApiError {
+    match e {
+        CoreError::QuotaExceeded(msg) => ApiError::QuotaExceeded(msg),
+        CoreError::QueueFull(msg) => ApiError::QueueFull(msg),
+        CoreError::TaskNotFound(id) => ApiError::TaskNotFound(id),
+        CoreError::GroupNotFound(group) => ApiError::GroupNotFound(group),
+        CoreError::WaitTimeout(what) => ApiError::Timeout(what),
+        other => ApiError::from(other),
+    }
 }
@@ -1,8 +1,9 @@
 use tracing::warn;
 
 use solti_model::{
-    AdmissionStrategy, BackoffStrategy, CreateSpec, Flag, JitterStrategy, RestartStrategy,
-    RunnerLabels, TaskEnv, TaskInfo, TaskKind, TaskStatus,
+    AdmissionStrategy, AgentAction, BackoffStrategy, CatchUpPolicy, ContainerMount, CreateSpec,
+    ExecutionWindow, Flag, FollowUp, GroupInfo, JitterStrategy, NetworkMode, ResourceRequests,
+    RestartStrategy, RunnerLabels, TaskEnv, TaskInfo, TaskKind, TaskStatus, WatchEvent, validate,
 };
 
 use crate::error::ApiError;
@@ -18,6 +19,7 @@ impl From<TaskStatus> for proto_api::TaskStatus {
             TaskStatus::Timeout => proto_api::TaskStatus::Timeout,
             TaskStatus::Canceled => proto_api::TaskStatus::Canceled,
             TaskStatus::Exhausted => proto_api::TaskStatus::Exhausted,
+            TaskStatus::Skipped => proto_api::TaskStatus::Skipped,
         }
     }
 }
@@ -44,6 +46,12 @@ impl From<TaskInfo> for proto_api::TaskInfo {
             })
             .as_secs() as i64;
 
+        let unix_secs = |t: std::time::SystemTime| {
+            t.duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or(0)
+        };
+
         proto_api::TaskInfo {
             id: info.id.to_string(),
             slot: info.slot,
@@ -52,6 +60,32 @@ impl From<TaskInfo> for proto_api::TaskInfo {
             created_at,
             updated_at,
             error: info.error,
+            group: info.group,
+            kind: info.kind,
+            runner: info.runner,
+            labels: info
+                .labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            started_at: info.started_at.map(unix_secs),
+            finished_at: info.finished_at.map(unix_secs),
+            duration_ms: info.duration_ms,
+        }
+    }
+}
+
+impl From<GroupInfo> for proto_api::GroupInfo {
+    fn from(info: GroupInfo) -> Self {
+        proto_api::GroupInfo {
+            group: info.group,
+            total: info.total as u32,
+            pending: info.pending as u32,
+            running: info.running as u32,
+            succeeded: info.succeeded as u32,
+            failed: info.failed as u32,
+            canceled: info.canceled as u32,
+            status: proto_api::TaskStatus::from(info.status) as i32,
         }
     }
 }
@@ -60,83 +94,187 @@ impl TryFrom<proto_api::CreateSpec> for CreateSpec {
     type Error = ApiError;
 
     fn try_from(spec: proto_api::CreateSpec) -> Result<Self, Self::Error> {
-        let kind = spec
-            .kind
-            .ok_or_else(|| ApiError::InvalidRequest("missing task kind".into()))?
-            .kind // добавить .kind для unwrap oneof
-            .ok_or_else(|| ApiError::InvalidRequest("missing task kind variant".into()))?;
-
-        let task_kind = convert_task_kind(kind)?;
-
-        let restart = convert_restart_strategy(
-            proto_api::RestartStrategy::try_from(spec.restart)
-                .map_err(|_| ApiError::InvalidRequest("invalid restart strategy".into()))?,
-            spec.restart_interval_ms,
-        )?;
-
-        let backoff = spec
-            .backoff
-            .ok_or_else(|| ApiError::InvalidRequest("missing backoff strategy".into()))?;
-
-        Ok(CreateSpec {
-            slot: validate_slot(spec.slot)?,
-            kind: task_kind,
-            timeout_ms: validate_timeout(spec.timeout_ms)?,
-            restart,
-            backoff: convert_backoff_strategy(backoff)?,
-            admission: convert_admission_strategy(
-                proto_api::AdmissionStrategy::try_from(spec.admission)
-                    .map_err(|_| ApiError::InvalidRequest("invalid admission strategy".into()))?,
-            )?,
-            labels: convert_labels(spec.labels),
-        })
+        let spec = convert_create_spec(spec)?;
+        match ApiError::from_diagnostics(&validate(&spec)) {
+            Some(err) => Err(err),
+            None => Ok(spec),
+        }
     }
 }
 
+/// Convert a spec without validating it; follow-ups are validated as part of their parent.
+fn convert_create_spec(spec: proto_api::CreateSpec) -> Result<CreateSpec, ApiError> {
+    let kind = spec
+        .kind
+        .ok_or_else(|| ApiError::invalid_field("kind", "missing task kind"))?
+        .kind // добавить .kind для unwrap oneof
+        .ok_or_else(|| ApiError::invalid_field("kind.kind", "missing task kind variant"))?;
+
+    let task_kind = convert_task_kind(kind)?;
+
+    let restart = convert_restart_strategy(
+        proto_api::RestartStrategy::try_from(spec.restart)
+            .map_err(|_| ApiError::invalid_field("restart", "invalid restart strategy"))?,
+        spec.restart_interval_ms,
+    )?;
+
+    let backoff = spec
+        .backoff
+        .ok_or_else(|| ApiError::invalid_field("backoff", "missing backoff strategy"))?;
+
+    Ok(CreateSpec {
+        slot: spec.slot,
+        kind: task_kind,
+        timeout_ms: spec.timeout_ms,
+        restart,
+        backoff: convert_backoff_strategy(backoff)?,
+        admission: convert_admission_strategy(
+            proto_api::AdmissionStrategy::try_from(spec.admission)
+                .map_err(|_| ApiError::invalid_field("admission", "invalid admission strategy"))?,
+        )?,
+        labels: convert_labels(spec.labels),
+        window: spec.window.map(convert_window).transpose()?,
+        follow_up: spec.follow_up.map(|f| convert_follow_up(*f)).transpose()?,
+        resources: spec.resources.map(|r| ResourceRequests {
+            cpu_millis: r.cpu_millis,
+            memory_bytes: r.memory_bytes,
+            gpus: r.gpus,
+        }),
+        namespace: spec.namespace,
+        catch_up: spec
+            .catch_up
+            .map(|policy| {
+                convert_catch_up_policy(
+                    proto_api::CatchUpPolicy::try_from(policy).map_err(|_| {
+                        ApiError::invalid_field("catch_up", "invalid catch-up policy")
+                    })?,
+                )
+            })
+            .transpose()?,
+    })
+}
+
+fn convert_follow_up(follow_up: proto_api::FollowUp) -> Result<FollowUp, ApiError> {
+    let convert = |spec: Option<Box<proto_api::CreateSpec>>, field: &str| {
+        spec.map(|spec| {
+            convert_create_spec(*spec)
+                .map(Box::new)
+                .map_err(|e| match e {
+                    ApiError::InvalidField {
+                        field: inner,
+                        reason,
+                    } => ApiError::invalid_field(format!("follow_up.{field}.{inner}"), reason),
+                    other => other,
+                })
+        })
+        .transpose()
+    };
+    Ok(FollowUp {
+        on_success: convert(follow_up.on_success, "on_success")?,
+        on_failure: convert(follow_up.on_failure, "on_failure")?,
+    })
+}
+
 fn convert_task_kind(kind: proto_api::task_kind::Kind) -> Result<TaskKind, ApiError> {
-    match kind {
-        proto_api::task_kind::Kind::Subprocess(sub) => {
-            if sub.command.trim().is_empty() {
-                return Err(ApiError::InvalidRequest(
-                    "subprocess command is empty".into(),
-                ));
-            }
+    Ok(match kind {
+        proto_api::task_kind::Kind::Subprocess(sub) => TaskKind::Subprocess {
+            command: sub.command,
+            args: sub.args,
+            env: convert_env(sub.env),
+            cwd: sub.cwd.map(std::path::PathBuf::from),
+            fail_on_non_zero: Flag::from(sub.fail_on_non_zero),
+        },
+        proto_api::task_kind::Kind::Wasm(wasm) => TaskKind::Wasm {
+            module: std::path::PathBuf::from(wasm.module),
+            args: wasm.args,
+            env: convert_env(wasm.env),
+        },
+        proto_api::task_kind::Kind::Container(cont) => TaskKind::Container {
+            network: convert_network_mode(proto_api::NetworkMode::try_from(cont.network).map_err(
+                |_| ApiError::invalid_field("kind.container.network", "invalid network mode"),
+            )?),
+            image: cont.image,
+            command: if cont.command.is_empty() {
+                None
+            } else {
+                Some(cont.command)
+            },
+            args: cont.args,
+            env: convert_env(cont.env),
+            mounts: cont
+                .mounts
+                .into_iter()
+                .map(|m| ContainerMount {
+                    source: m.source,
+                    target: m.target,
+                    read_only: m.read_only,
+                })
+                .collect(),
+            memory_bytes: cont.memory_bytes,
+            cpu_millis: cont.cpu_millis,
+            user: cont.user,
+        },
+        proto_api::task_kind::Kind::AgentControl(ctl) => TaskKind::AgentControl {
+            action: convert_agent_action(proto_api::AgentAction::try_from(ctl.action).map_err(
+                |_| ApiError::invalid_field("kind.agent_control.action", "invalid agent action"),
+            )?)?,
+        },
+        proto_api::task_kind::Kind::Watch(watch) => convert_watch(*watch)?,
+    })
+}
 
-            Ok(TaskKind::Subprocess {
-                command: sub.command,
-                args: sub.args,
-                env: convert_env(sub.env),
-                cwd: sub.cwd.map(std::path::PathBuf::from),
-                fail_on_non_zero: Flag::from(sub.fail_on_non_zero),
-            })
+fn convert_watch(watch: proto_api::WatchTask) -> Result<TaskKind, ApiError> {
+    let events = watch
+        .events
+        .into_iter()
+        .map(|event| match proto_api::WatchEvent::try_from(event) {
+            Ok(proto_api::WatchEvent::Create) => Ok(WatchEvent::Create),
+            Ok(proto_api::WatchEvent::Modify) => Ok(WatchEvent::Modify),
+            Ok(proto_api::WatchEvent::Remove) => Ok(WatchEvent::Remove),
+            Ok(proto_api::WatchEvent::Unspecified) | Err(_) => Err(ApiError::invalid_field(
+                "kind.watch.events",
+                "invalid watch event",
+            )),
+        })
+        .collect::<Result<_, _>>()?;
+    let action = watch
+        .action
+        .and_then(|action| action.kind)
+        .ok_or_else(|| ApiError::invalid_field("kind.watch.action", "missing watch action"))?;
+    let action = convert_task_kind(action).map_err(|e| match e {
+        ApiError::InvalidField { field, reason } => {
+            ApiError::invalid_field(field.replacen("kind", "kind.watch.action", 1), reason)
         }
-        proto_api::task_kind::Kind::Wasm(wasm) => {
-            if wasm.module.trim().is_empty() {
-                return Err(ApiError::InvalidRequest("wasm module path is empty".into()));
-            }
+        other => other,
+    })?;
+
+    Ok(TaskKind::Watch {
+        path: std::path::PathBuf::from(watch.path),
+        events,
+        debounce_ms: watch.debounce_ms,
+        action: Box::new(action),
+    })
+}
 
-            Ok(TaskKind::Wasm {
-                module: std::path::PathBuf::from(wasm.module),
-                args: wasm.args,
-                env: convert_env(wasm.env),
-            })
-        }
-        proto_api::task_kind::Kind::Container(cont) => {
-            if cont.image.trim().is_empty() {
-                return Err(ApiError::InvalidRequest("container image is empty".into()));
-            }
+fn convert_agent_action(action: proto_api::AgentAction) -> Result<AgentAction, ApiError> {
+    match action {
+        proto_api::AgentAction::Unspecified => Err(ApiError::invalid_field(
+            "kind.agent_control.action",
+            "agent action must be specified",
+        )),
+        proto_api::AgentAction::ReloadConfig => Ok(AgentAction::ReloadConfig),
+        proto_api::AgentAction::RotateLogs => Ok(AgentAction::RotateLogs),
+        proto_api::AgentAction::FlushState => Ok(AgentAction::FlushState),
+        proto_api::AgentAction::CollectGarbage => Ok(AgentAction::CollectGarbage),
+    }
+}
 
-            Ok(TaskKind::Container {
-                image: cont.image,
-                command: if cont.command.is_empty() {
-                    None
-                } else {
-                    Some(cont.command)
-                },
-                args: cont.args,
-                env: convert_env(cont.env),
-            })
-        }
+fn convert_network_mode(mode: proto_api::NetworkMode) -> Option<NetworkMode> {
+    match mode {
+        proto_api::NetworkMode::Unspecified => None,
+        proto_api::NetworkMode::Bridge => Some(NetworkMode::Bridge),
+        proto_api::NetworkMode::Host => Some(NetworkMode::Host),
+        proto_api::NetworkMode::None => Some(NetworkMode::None),
     }
 }
 
@@ -156,8 +294,9 @@ fn convert_restart_strategy(
         proto_api::RestartStrategy::Never => Ok(RestartStrategy::Never),
         proto_api::RestartStrategy::OnFailure => Ok(RestartStrategy::OnFailure),
         proto_api::RestartStrategy::Always => Ok(RestartStrategy::Always { interval_ms }),
-        proto_api::RestartStrategy::Unspecified => Err(ApiError::InvalidRequest(
-            "restart strategy not specified".into(),
+        proto_api::RestartStrategy::Unspecified => Err(ApiError::invalid_field(
+            "restart",
+            "restart strategy not specified",
         )),
     }
 }
@@ -166,7 +305,7 @@ fn convert_backoff_strategy(
     backoff: proto_api::BackoffStrategy,
 ) -> Result<BackoffStrategy, ApiError> {
     let jitter = proto_api::JitterStrategy::try_from(backoff.jitter)
-        .map_err(|_| ApiError::InvalidRequest("invalid jitter strategy".into()))?;
+        .map_err(|_| ApiError::invalid_field("backoff.jitter", "invalid jitter strategy"))?;
 
     let jitter = match jitter {
         proto_api::JitterStrategy::None => JitterStrategy::None,
@@ -174,28 +313,13 @@ fn convert_backoff_strategy(
         proto_api::JitterStrategy::Equal => JitterStrategy::Equal,
         proto_api::JitterStrategy::Decorrelated => JitterStrategy::Decorrelated,
         proto_api::JitterStrategy::Unspecified => {
-            return Err(ApiError::InvalidRequest(
-                "jitter strategy not specified".into(),
+            return Err(ApiError::invalid_field(
+                "backoff.jitter",
+                "jitter strategy not specified",
             ));
         }
     };
 
-    if backoff.first_ms == 0 {
-        return Err(ApiError::InvalidRequest(
-            "backoff first_ms cannot be zero".into(),
-        ));
-    }
-    if backoff.max_ms == 0 {
-        return Err(ApiError::InvalidRequest(
-            "backoff max_ms cannot be zero".into(),
-        ));
-    }
-    if backoff.factor <= 0.0 {
-        return Err(ApiError::InvalidRequest(
-            "backoff factor must be positive".into(),
-        ));
-    }
-
     Ok(BackoffStrategy {
         jitter,
         first_ms: backoff.first_ms,
@@ -211,8 +335,21 @@ fn convert_admission_strategy(
         proto_api::AdmissionStrategy::DropIfRunning => Ok(AdmissionStrategy::DropIfRunning),
         proto_api::AdmissionStrategy::Replace => Ok(AdmissionStrategy::Replace),
         proto_api::AdmissionStrategy::Queue => Ok(AdmissionStrategy::Queue),
-        proto_api::AdmissionStrategy::Unspecified => Err(ApiError::InvalidRequest(
-            "admission strategy not specified".into(),
+        proto_api::AdmissionStrategy::Unspecified => Err(ApiError::invalid_field(
+            "admission",
+            "admission strategy not specified",
+        )),
+    }
+}
+
+fn convert_catch_up_policy(policy: proto_api::CatchUpPolicy) -> Result<CatchUpPolicy, ApiError> {
+    match policy {
+        proto_api::CatchUpPolicy::Skip => Ok(CatchUpPolicy::Skip),
+        proto_api::CatchUpPolicy::RunOnce => Ok(CatchUpPolicy::RunOnce),
+        proto_api::CatchUpPolicy::RunAll => Ok(CatchUpPolicy::RunAll),
+        proto_api::CatchUpPolicy::Unspecified => Err(ApiError::invalid_field(
+            "catch_up",
+            "catch-up policy not specified",
         )),
     }
 }
@@ -225,18 +362,25 @@ fn convert_labels(map: std::collections::HashMap<String, String>) -> RunnerLabel
     labels
 }
 
-fn validate_slot(slot: String) -> Result<String, ApiError> {
-    if slot.trim().is_empty() {
-        return Err(ApiError::InvalidRequest("slot cannot be empty".into()));
-    }
-    Ok(slot)
-}
+fn convert_window(window: proto_api::ExecutionWindow) -> Result<ExecutionWindow, ApiError> {
+    let invalid = |field: &'static str| {
+        move |e: solti_model::ModelError| ApiError::invalid_field(field, e.to_string())
+    };
 
-fn validate_timeout(timeout_ms: u64) -> Result<u64, ApiError> {
-    if timeout_ms == 0 {
-        return Err(ApiError::InvalidRequest("timeout_ms cannot be zero".into()));
-    }
-    Ok(timeout_ms)
+    let days = window
+        .days
+        .iter()
+        .map(|d| d.parse())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(invalid("window.days"))?;
+
+    Ok(ExecutionWindow {
+        days,
+        start: window.start.parse().map_err(invalid("window.start"))?,
+        end: window.end.parse().map_err(invalid("window.end"))?,
+        utc_offset_minutes: window.utc_offset_minutes,
+        timezone: window.timezone,
+    })
 }
 
 #[cfg(test)]
@@ -281,6 +425,11 @@ mod tests {
             backoff: Some(make_backoff()),
             admission: proto_api::AdmissionStrategy::DropIfRunning as i32,
             labels: HashMap::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
         }
     }
 
@@ -294,6 +443,7 @@ mod tests {
             (TaskStatus::Timeout, proto_api::TaskStatus::Timeout),
             (TaskStatus::Canceled, proto_api::TaskStatus::Canceled),
             (TaskStatus::Exhausted, proto_api::TaskStatus::Exhausted),
+            (TaskStatus::Skipped, proto_api::TaskStatus::Skipped),
         ];
 
         for (domain, expected_proto) in cases {
@@ -307,6 +457,8 @@ mod tests {
         let now = SystemTime::now();
         let now_secs = now.duration_since(UNIX_EPOCH).unwrap().as_secs() as i64;
 
+        let mut labels = RunnerLabels::new();
+        labels.insert("team", "core");
         let info = TaskInfo {
             id: solti_model::TaskId::from("task-42"),
             slot: "my-slot".to_string(),
@@ -315,6 +467,13 @@ mod tests {
             created_at: now,
             updated_at: now,
             error: Some("boom".to_string()),
+            group: Some("batch".to_string()),
+            labels,
+            kind: Some("subprocess".to_string()),
+            runner: Some("runner-a".to_string()),
+            started_at: Some(now),
+            finished_at: None,
+            duration_ms: None,
         };
 
         let proto: proto_api::TaskInfo = info.into();
@@ -326,24 +485,44 @@ mod tests {
         assert_eq!(proto.created_at, now_secs);
         assert_eq!(proto.updated_at, now_secs);
         assert_eq!(proto.error, Some("boom".to_string()));
+        assert_eq!(proto.group, Some("batch".to_string()));
+        assert_eq!(proto.kind.as_deref(), Some("subprocess"));
+        assert_eq!(proto.runner.as_deref(), Some("runner-a"));
+        assert_eq!(proto.labels["team"], "core");
+        assert_eq!(proto.started_at, Some(now_secs));
+        assert_eq!(proto.finished_at, None);
     }
 
     #[test]
     fn task_info_no_error() {
-        let info = TaskInfo {
-            id: solti_model::TaskId::from("task-1"),
-            slot: "slot".to_string(),
-            status: TaskStatus::Succeeded,
-            attempt: 1,
-            created_at: SystemTime::now(),
-            updated_at: SystemTime::now(),
-            error: None,
-        };
+        let info = TaskInfo::pending(solti_model::TaskId::from("task-1"), "slot".to_string());
 
         let proto: proto_api::TaskInfo = info.into();
         assert_eq!(proto.error, None);
     }
 
+    #[test]
+    fn group_info_converts_correctly() {
+        let info = GroupInfo {
+            group: "batch".to_string(),
+            total: 3,
+            pending: 0,
+            running: 1,
+            succeeded: 2,
+            failed: 0,
+            canceled: 0,
+            status: TaskStatus::Running,
+        };
+
+        let proto: proto_api::GroupInfo = info.into();
+
+        assert_eq!(proto.group, "batch");
+        assert_eq!(proto.total, 3);
+        assert_eq!(proto.running, 1);
+        assert_eq!(proto.succeeded, 2);
+        assert_eq!(proto.status, proto_api::TaskStatus::Running as i32);
+    }
+
     #[test]
     fn create_spec_subprocess_valid() {
         let spec = make_valid_create_spec();
@@ -389,6 +568,7 @@ mod tests {
                         command: vec!["sh".to_string(), "-c".to_string()],
                         args: vec!["echo hello".to_string()],
                         env: vec![],
+                        ..Default::default()
                     },
                 )),
             }),
@@ -411,6 +591,7 @@ mod tests {
                         command: vec![],
                         args: vec![],
                         env: vec![],
+                        ..Default::default()
                     },
                 )),
             }),
@@ -421,6 +602,143 @@ mod tests {
         assert!(matches!(cs.kind, TaskKind::Container { command: None, .. }));
     }
 
+    #[test]
+    fn create_spec_container_mounts_and_limits() {
+        let spec = proto_api::CreateSpec {
+            kind: Some(proto_api::TaskKind {
+                kind: Some(proto_api::task_kind::Kind::Container(
+                    proto_api::ContainerTask {
+                        image: "postgres:16".to_string(),
+                        mounts: vec![proto_api::ContainerMount {
+                            source: "/srv/pg".to_string(),
+                            target: "/var/lib/postgresql/data".to_string(),
+                            read_only: false,
+                        }],
+                        memory_bytes: Some(512 << 20),
+                        cpu_millis: Some(1500),
+                        network: proto_api::NetworkMode::None as i32,
+                        user: Some("999:999".to_string()),
+                        ..Default::default()
+                    },
+                )),
+            }),
+            ..make_valid_create_spec()
+        };
+
+        let cs = CreateSpec::try_from(spec).unwrap();
+        match cs.kind {
+            TaskKind::Container {
+                mounts,
+                memory_bytes,
+                cpu_millis,
+                network,
+                user,
+                ..
+            } => {
+                assert_eq!(mounts.len(), 1);
+                assert!(mounts[0].is_bind());
+                assert_eq!(memory_bytes, Some(512 << 20));
+                assert_eq!(cpu_millis, Some(1500));
+                assert_eq!(network, Some(NetworkMode::None));
+                assert_eq!(user.as_deref(), Some("999:999"));
+            }
+            other => panic!("unexpected kind: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn create_spec_agent_control() {
+        let control = |action: proto_api::AgentAction| proto_api::CreateSpec {
+            kind: Some(proto_api::TaskKind {
+                kind: Some(proto_api::task_kind::Kind::AgentControl(
+                    proto_api::AgentControlTask {
+                        action: action as i32,
+                    },
+                )),
+            }),
+            ..make_valid_create_spec()
+        };
+
+        let cs = CreateSpec::try_from(control(proto_api::AgentAction::RotateLogs)).unwrap();
+        assert_eq!(
+            cs.kind,
+            TaskKind::AgentControl {
+                action: AgentAction::RotateLogs
+            }
+        );
+
+        let err = CreateSpec::try_from(control(proto_api::AgentAction::Unspecified)).unwrap_err();
+        assert!(err.to_string().contains("agent_control.action"), "{err}");
+    }
+
+    #[test]
+    fn create_spec_watch() {
+        let watch = |events: Vec<i32>, action: Option<proto_api::TaskKind>| proto_api::CreateSpec {
+            kind: Some(proto_api::TaskKind {
+                kind: Some(proto_api::task_kind::Kind::Watch(Box::new(
+                    proto_api::WatchTask {
+                        path: "/etc/app".into(),
+                        events,
+                        debounce_ms: 250,
+                        action: action.map(Box::new),
+                    },
+                ))),
+            }),
+            ..make_valid_create_spec()
+        };
+        let action = make_valid_create_spec().kind;
+
+        let cs = CreateSpec::try_from(watch(
+            vec![proto_api::WatchEvent::Modify as i32],
+            action.clone(),
+        ))
+        .unwrap();
+        let TaskKind::Watch {
+            path,
+            events,
+            debounce_ms,
+            action: inner,
+        } = cs.kind
+        else {
+            panic!("expected watch kind, got {:?}", cs.kind);
+        };
+        assert_eq!(path, std::path::PathBuf::from("/etc/app"));
+        assert_eq!(events, [WatchEvent::Modify]);
+        assert_eq!(debounce_ms, 250);
+        assert_eq!(inner.kind(), "subprocess");
+
+        let err = CreateSpec::try_from(watch(vec![], None)).unwrap_err();
+        assert!(err.to_string().contains("kind.watch.action"), "{err}");
+        let err = CreateSpec::try_from(watch(vec![0], action)).unwrap_err();
+        assert!(err.to_string().contains("kind.watch.events"), "{err}");
+    }
+
+    #[test]
+    fn reject_relative_container_mount_target() {
+        let spec = proto_api::CreateSpec {
+            kind: Some(proto_api::TaskKind {
+                kind: Some(proto_api::task_kind::Kind::Container(
+                    proto_api::ContainerTask {
+                        image: "alpine".to_string(),
+                        mounts: vec![proto_api::ContainerMount {
+                            source: "data".to_string(),
+                            target: "data".to_string(),
+                            read_only: true,
+                        }],
+                        ..Default::default()
+                    },
+                )),
+            }),
+            ..make_valid_create_spec()
+        };
+
+        let err = CreateSpec::try_from(spec).unwrap_err();
+        assert!(
+            matches!(err, ApiError::InvalidField { ref field, .. } if field == "kind.container.mounts[0].target"),
+            "{err:?}"
+        );
+    }
+
     #[test]
     fn create_spec_always_with_interval() {
         let spec = proto_api::CreateSpec {
@@ -488,7 +806,9 @@ mod tests {
             ..make_valid_create_spec()
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
-        assert!(matches!(err, ApiError::InvalidRequest(msg) if msg.contains("missing task kind")));
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("missing task kind"))
+        );
     }
 
     #[test]
@@ -499,7 +819,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("missing task kind variant"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("missing task kind variant"))
         );
     }
 
@@ -511,7 +831,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("subprocess command is empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("subprocess command is empty"))
         );
     }
 
@@ -523,7 +843,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("subprocess command is empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("subprocess command is empty"))
         );
     }
 
@@ -541,7 +861,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("wasm module path is empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("wasm module path is empty"))
         );
     }
 
@@ -555,6 +875,7 @@ mod tests {
                         command: vec![],
                         args: vec![],
                         env: vec![],
+                        ..Default::default()
                     },
                 )),
             }),
@@ -562,7 +883,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("container image is empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("container image is empty"))
         );
     }
 
@@ -574,7 +895,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("slot cannot be empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("slot cannot be empty"))
         );
     }
 
@@ -586,7 +907,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("slot cannot be empty"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("slot cannot be empty"))
         );
     }
 
@@ -598,7 +919,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("timeout_ms cannot be zero"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("timeout_ms cannot be zero"))
         );
     }
 
@@ -609,7 +930,9 @@ mod tests {
             ..make_valid_create_spec()
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
-        assert!(matches!(err, ApiError::InvalidRequest(msg) if msg.contains("missing backoff")));
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("missing backoff"))
+        );
     }
 
     #[test]
@@ -623,7 +946,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("first_ms cannot be zero"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("first_ms cannot be zero"))
         );
     }
 
@@ -638,7 +961,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("max_ms cannot be zero"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("max_ms cannot be zero"))
         );
     }
 
@@ -653,7 +976,7 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("factor must be positive"))
+            matches!(err, ApiError::InvalidField { field, reason } if field == "backoff.factor" && reason.contains("factor must be positive"))
         );
     }
 
@@ -668,7 +991,131 @@ mod tests {
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
         assert!(
-            matches!(err, ApiError::InvalidRequest(msg) if msg.contains("factor must be positive"))
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("factor must be positive"))
+        );
+    }
+
+    #[test]
+    fn window_converts() {
+        let spec = proto_api::CreateSpec {
+            window: Some(proto_api::ExecutionWindow {
+                days: vec!["mon".into(), "fri".into()],
+                start: "09:00".into(),
+                end: "17:30".into(),
+                utc_offset_minutes: 0,
+                timezone: Some("Europe/Berlin".into()),
+            }),
+            ..make_valid_create_spec()
+        };
+        let window = CreateSpec::try_from(spec).unwrap().window.unwrap();
+        assert_eq!(
+            window.days,
+            vec![solti_model::Weekday::Mon, solti_model::Weekday::Fri]
+        );
+        assert_eq!(window.start.to_string(), "09:00");
+        assert_eq!(window.end.to_string(), "17:30");
+        assert_eq!(window.timezone.as_deref(), Some("Europe/Berlin"));
+    }
+
+    #[test]
+    fn reject_invalid_window() {
+        let spec = proto_api::CreateSpec {
+            window: Some(proto_api::ExecutionWindow {
+                days: vec![],
+                start: "25:00".into(),
+                end: "06:00".into(),
+                utc_offset_minutes: 0,
+                timezone: None,
+            }),
+            ..make_valid_create_spec()
+        };
+        let err = CreateSpec::try_from(spec).unwrap_err();
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("time of day"))
+        );
+    }
+
+    #[test]
+    fn follow_up_converts() {
+        let notify = proto_api::CreateSpec {
+            slot: "notify".into(),
+            ..make_valid_create_spec()
+        };
+        let spec = proto_api::CreateSpec {
+            follow_up: Some(Box::new(proto_api::FollowUp {
+                on_success: None,
+                on_failure: Some(Box::new(notify)),
+            })),
+            ..make_valid_create_spec()
+        };
+        let follow_up = CreateSpec::try_from(spec).unwrap().follow_up.unwrap();
+        assert!(follow_up.on_success.is_none());
+        assert_eq!(follow_up.on_failure.unwrap().slot, "notify");
+    }
+
+    #[test]
+    fn resources_convert() {
+        let spec = proto_api::CreateSpec {
+            resources: Some(proto_api::ResourceRequests {
+                cpu_millis: 500,
+                memory_bytes: 0,
+                gpus: 1,
+            }),
+            ..make_valid_create_spec()
+        };
+        let resources = CreateSpec::try_from(spec).unwrap().resources.unwrap();
+        assert_eq!(resources.cpu_millis, 500);
+        assert_eq!(resources.gpus, 1);
+
+        let spec = proto_api::CreateSpec {
+            resources: Some(proto_api::ResourceRequests::default()),
+            ..make_valid_create_spec()
+        };
+        let err = CreateSpec::try_from(spec).unwrap_err();
+        assert!(matches!(err, ApiError::InvalidField { field, .. } if field == "resources"));
+    }
+
+    #[test]
+    fn namespace_converts() {
+        let spec = proto_api::CreateSpec {
+            namespace: Some("team-a".into()),
+            ..make_valid_create_spec()
+        };
+        let spec = CreateSpec::try_from(spec).unwrap();
+        assert_eq!(spec.namespace.as_deref(), Some("team-a"));
+        assert!(spec.labels.is_empty());
+    }
+
+    #[test]
+    fn reject_invalid_follow_up() {
+        let spec = proto_api::CreateSpec {
+            follow_up: Some(Box::new(proto_api::FollowUp {
+                on_success: Some(Box::new(proto_api::CreateSpec {
+                    kind: None,
+                    ..make_valid_create_spec()
+                })),
+                on_failure: None,
+            })),
+            ..make_valid_create_spec()
+        };
+        let err = CreateSpec::try_from(spec).unwrap_err();
+        assert!(
+            matches!(err, ApiError::InvalidField { field, .. } if field == "follow_up.on_success.kind")
+        );
+
+        let spec = proto_api::CreateSpec {
+            follow_up: Some(Box::new(proto_api::FollowUp {
+                on_success: None,
+                on_failure: Some(Box::new(proto_api::CreateSpec {
+                    timeout_ms: 0,
+                    ..make_valid_create_spec()
+                })),
+            })),
+            ..make_valid_create_spec()
+        };
+        let err = CreateSpec::try_from(spec).unwrap_err();
+        assert!(
+            matches!(err, ApiError::InvalidField { field, .. } if field == "follow_up.on_failure.timeout_ms")
         );
     }
 
@@ -682,7 +1129,9 @@ mod tests {
             ..make_valid_create_spec()
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
-        assert!(matches!(err, ApiError::InvalidRequest(msg) if msg.contains("jitter")));
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("jitter"))
+        );
     }
 
     #[test]
@@ -717,7 +1166,9 @@ mod tests {
             ..make_valid_create_spec()
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
-        assert!(matches!(err, ApiError::InvalidRequest(msg) if msg.contains("restart")));
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("restart"))
+        );
     }
 
     #[test]
@@ -730,6 +1181,25 @@ mod tests {
         assert!(matches!(cs.restart, RestartStrategy::Never));
     }
 
+    #[test]
+    fn catch_up_policy_converts() {
+        let spec = proto_api::CreateSpec {
+            catch_up: Some(proto_api::CatchUpPolicy::RunAll as i32),
+            ..make_valid_create_spec()
+        };
+        let spec = CreateSpec::try_from(spec).unwrap();
+        assert_eq!(spec.catch_up, Some(CatchUpPolicy::RunAll));
+
+        for invalid in [proto_api::CatchUpPolicy::Unspecified as i32, 99] {
+            let spec = proto_api::CreateSpec {
+                catch_up: Some(invalid),
+                ..make_valid_create_spec()
+            };
+            let err = CreateSpec::try_from(spec).unwrap_err();
+            assert!(matches!(err, ApiError::InvalidField { field, .. } if field == "catch_up"));
+        }
+    }
+
     #[test]
     fn reject_unspecified_admission() {
         let spec = proto_api::CreateSpec {
@@ -737,7 +1207,9 @@ mod tests {
             ..make_valid_create_spec()
         };
         let err = CreateSpec::try_from(spec).unwrap_err();
-        assert!(matches!(err, ApiError::InvalidRequest(msg) if msg.contains("admission")));
+        assert!(
+            matches!(err, ApiError::InvalidField { reason: msg, .. } if msg.contains("admission"))
+        );
     }
 
     #[test]
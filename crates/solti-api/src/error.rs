@@ -1,48 +1,411 @@
+use serde::{Deserialize, Serialize};
+use solti_core::{CoreError, RunnerError};
 use thiserror::Error;
 
+/// Content type of HTTP error responses ([RFC 9457](https://www.rfc-editor.org/rfc/rfc9457)).
+pub const PROBLEM_CONTENT_TYPE: &str = "application/problem+json";
+
+/// Prefix of the problem `type` URI; followed by the error code.
+const PROBLEM_TYPE_PREFIX: &str = "urn:solti:problem:";
+
+/// API error.
+///
+/// HTTP responses render it as an RFC 9457 problem document (see [`Problem`]);
+/// gRPC maps it onto a status code.
+///
+/// ## Error codes
+/// Stable, machine-readable codes returned in [`Problem::code`]:
+///
+/// | code                    | status | meaning                                          |
+/// | ----------------------- | ------ | ------------------------------------------------ |
+/// | `invalid_request`       | 400    | malformed or invalid request                     |
+/// | `command_denied`        | 403    | command rejected by the agent's policy           |
+/// | `admission_denied`      | 403    | spec rejected by the admission policy            |
+/// | `task_not_found`        | 404    | unknown task id                                  |
+/// | `group_not_found`       | 404    | unknown task group                               |
+/// | `timeout`               | 408    | waiting or the request itself timed out          |
+/// | `payload_too_large`     | 413    | request body exceeds the configured limit        |
+/// | `quota_exceeded`        | 429    | submission exceeds a task quota                  |
+/// | `queue_full`            | 429    | slot queue reached its length cap                |
+/// | `insufficient_capacity` | 503    | task does not fit into the agent's free capacity |
+/// | `unsupported`           | 501    | operation not available on this agent            |
+/// | `internal`              | 500    | unexpected handler failure                       |
+/// | `no_runner`             | 500    | no runner accepts the task kind                  |
+/// | `supervisor_error`      | 500    | supervisor rejected the task                     |
+/// | `store_error`           | 500    | task state store failure                         |
+/// | `mapping_error`         | 500    | spec could not be mapped to a task               |
+/// | `runner_error`          | 500    | runner failed to build the task                  |
 #[derive(Debug, Error)]
 pub enum ApiError {
     #[error("invalid request: {0}")]
     InvalidRequest(String),
 
+    #[error("invalid request: {field}: {reason}")]
+    InvalidField { field: String, reason: String },
+
     #[error("task not found: {0}")]
     TaskNotFound(String),
 
+    #[error("group not found: {0}")]
+    GroupNotFound(String),
+
+    #[error("timed out: {0}")]
+    Timeout(String),
+
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("queue full: {0}")]
+    QueueFull(String),
+
+    #[error("payload too large: {0}")]
+    PayloadTooLarge(String),
+
     #[error("internal error: {0}")]
     Internal(String),
 
+    #[error("unsupported operation: {0}")]
+    Unsupported(String),
+
     #[error("core error: {0}")]
     Core(#[from] solti_core::CoreError),
 }
 
+/// `google.rpc.ErrorInfo` domain of gRPC errors.
+#[cfg(feature = "grpc")]
+pub const ERROR_DOMAIN: &str = "solti.dev";
+
+/// Maps the error onto a gRPC status carrying `google.rpc` error details.
+///
+/// Every status has an `ErrorInfo` whose reason is the upper-cased error code;
+/// invalid fields add a `BadRequest` field violation and missing tasks or groups
+/// a `ResourceInfo`.
 #[cfg(feature = "grpc")]
 impl From<ApiError> for tonic::Status {
     fn from(err: ApiError) -> Self {
-        match err {
-            ApiError::InvalidRequest(msg) => tonic::Status::invalid_argument(msg),
-            ApiError::TaskNotFound(msg) => tonic::Status::not_found(msg),
-            ApiError::Internal(msg) => tonic::Status::internal(format!("internal error: {}", msg)),
-            ApiError::Core(e) => tonic::Status::internal(format!("core error: {}", e)),
+        use tonic::Code;
+        use tonic_types::{ErrorDetails, StatusExt};
+
+        let mut details = ErrorDetails::with_error_info(
+            err.code().to_ascii_uppercase(),
+            ERROR_DOMAIN,
+            std::collections::HashMap::new(),
+        );
+        let (code, message) = match err {
+            ApiError::InvalidRequest(msg) => (Code::InvalidArgument, msg),
+            ApiError::InvalidField { field, reason } => {
+                details.add_bad_request_violation(field, reason.clone());
+                (Code::InvalidArgument, reason)
+            }
+            ApiError::TaskNotFound(id) | ApiError::Core(CoreError::TaskNotFound(id)) => {
+                details.set_resource_info("task", id.clone(), "", "task not found");
+                (Code::NotFound, id)
+            }
+            ApiError::GroupNotFound(group) | ApiError::Core(CoreError::GroupNotFound(group)) => {
+                details.set_resource_info("group", group.clone(), "", "group not found");
+                (Code::NotFound, group)
+            }
+            ApiError::Timeout(msg) => (Code::DeadlineExceeded, msg),
+            ApiError::QuotaExceeded(msg)
+            | ApiError::QueueFull(msg)
+            | ApiError::Core(CoreError::QueueFull(msg))
+            | ApiError::PayloadTooLarge(msg) => (Code::ResourceExhausted, msg),
+            ApiError::Core(
+                e @ (CoreError::QuotaExceeded(_) | CoreError::InsufficientCapacity(_)),
+            ) => (Code::ResourceExhausted, e.to_string()),
+            ApiError::Internal(msg) => (Code::Internal, format!("internal error: {}", msg)),
+            ApiError::Unsupported(msg) => (Code::Unimplemented, msg),
+            ApiError::Core(
+                e @ (CoreError::Runner(RunnerError::CommandDenied { .. })
+                | CoreError::AdmissionDenied(_)),
+            ) => (Code::PermissionDenied, e.to_string()),
+            ApiError::Core(e) => (Code::Internal, format!("core error: {}", e)),
+        };
+        tonic::Status::with_error_details(code, message, details)
+    }
+}
+
+impl ApiError {
+    /// Invalid value of a request field; `field` is a dotted path such as `backoff.factor`.
+    pub fn invalid_field(field: impl Into<String>, reason: impl Into<String>) -> Self {
+        ApiError::InvalidField {
+            field: field.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// First error among spec validation `diagnostics`, if any; warnings are ignored.
+    pub fn from_diagnostics(diagnostics: &[solti_model::Diagnostic]) -> Option<Self> {
+        diagnostics
+            .iter()
+            .find(|d| d.is_error())
+            .map(|d| ApiError::invalid_field(d.field.clone(), d.message.clone()))
+    }
+
+    /// Stable machine-readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::InvalidRequest(_) | ApiError::InvalidField { .. } => "invalid_request",
+            ApiError::TaskNotFound(_) => "task_not_found",
+            ApiError::GroupNotFound(_) => "group_not_found",
+            ApiError::Timeout(_) => "timeout",
+            ApiError::QuotaExceeded(_) => "quota_exceeded",
+            ApiError::QueueFull(_) => "queue_full",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::Internal(_) => "internal",
+            ApiError::Unsupported(_) => "unsupported",
+            ApiError::Core(e) => match e {
+                CoreError::NoRunner(_) => "no_runner",
+                CoreError::Supervisor(_) => "supervisor_error",
+                CoreError::QuotaExceeded(_) => "quota_exceeded",
+                CoreError::QueueFull(_) => "queue_full",
+                CoreError::InsufficientCapacity(_) => "insufficient_capacity",
+                CoreError::AdmissionDenied(_) => "admission_denied",
+                CoreError::TaskNotFound(_) => "task_not_found",
+                CoreError::GroupNotFound(_) => "group_not_found",
+                CoreError::WaitTimeout(_) => "timeout",
+                CoreError::Store(_) => "store_error",
+                CoreError::Mapping(_) => "mapping_error",
+                CoreError::Runner(RunnerError::CommandDenied { .. }) => "command_denied",
+                CoreError::Runner(_) => "runner_error",
+            },
         }
     }
+
+    /// HTTP status code of the error.
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::InvalidRequest(_) | ApiError::InvalidField { .. } => 400,
+            ApiError::Core(
+                CoreError::Runner(RunnerError::CommandDenied { .. })
+                | CoreError::AdmissionDenied(_),
+            ) => 403,
+            ApiError::TaskNotFound(_) | ApiError::GroupNotFound(_) => 404,
+            ApiError::Timeout(_) => 408,
+            ApiError::PayloadTooLarge(_) => 413,
+            ApiError::QuotaExceeded(_)
+            | ApiError::QueueFull(_)
+            | ApiError::Core(CoreError::QuotaExceeded(_) | CoreError::QueueFull(_)) => 429,
+            ApiError::Unsupported(_) => 501,
+            ApiError::Core(CoreError::InsufficientCapacity(_)) => 503,
+            ApiError::Internal(_) | ApiError::Core(_) => 500,
+        }
+    }
+
+    /// Short human-readable summary of the error class.
+    fn title(&self) -> &'static str {
+        match self {
+            ApiError::InvalidRequest(_) | ApiError::InvalidField { .. } => "Invalid request",
+            ApiError::TaskNotFound(_) => "Task not found",
+            ApiError::GroupNotFound(_) => "Group not found",
+            ApiError::Timeout(_) => "Timed out",
+            ApiError::QuotaExceeded(_) | ApiError::Core(CoreError::QuotaExceeded(_)) => {
+                "Quota exceeded"
+            }
+            ApiError::QueueFull(_) | ApiError::Core(CoreError::QueueFull(_)) => "Queue full",
+            ApiError::PayloadTooLarge(_) => "Payload too large",
+            ApiError::Internal(_) => "Internal error",
+            ApiError::Unsupported(_) => "Unsupported operation",
+            ApiError::Core(CoreError::Runner(RunnerError::CommandDenied { .. })) => {
+                "Command denied"
+            }
+            ApiError::Core(CoreError::AdmissionDenied(_)) => "Admission denied",
+            ApiError::Core(CoreError::InsufficientCapacity(_)) => "Insufficient capacity",
+            ApiError::Core(_) => "Core error",
+        }
+    }
+
+    /// Problem document describing the error.
+    pub fn to_problem(&self) -> Problem {
+        let code = self.code();
+        let detail = match self {
+            ApiError::InvalidRequest(msg)
+            | ApiError::TaskNotFound(msg)
+            | ApiError::GroupNotFound(msg)
+            | ApiError::Timeout(msg)
+            | ApiError::QuotaExceeded(msg)
+            | ApiError::QueueFull(msg)
+            | ApiError::PayloadTooLarge(msg)
+            | ApiError::Internal(msg)
+            | ApiError::Unsupported(msg) => msg.clone(),
+            ApiError::InvalidField { field, reason } => format!("{field}: {reason}"),
+            ApiError::Core(e) => e.to_string(),
+        };
+        Problem {
+            kind: format!("{PROBLEM_TYPE_PREFIX}{code}"),
+            title: self.title().to_string(),
+            status: self.status(),
+            detail,
+            instance: None,
+            code: code.to_string(),
+        }
+    }
+}
+
+/// RFC 9457 problem details of an HTTP error response.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Problem {
+    /// Problem type URI (`urn:solti:problem:{code}`).
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Short summary of the problem type.
+    pub title: String,
+    /// HTTP status code.
+    pub status: u16,
+    /// Explanation specific to this occurrence.
+    pub detail: String,
+    /// Path of the request that failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Stable machine-readable error code (see [`ApiError`]).
+    pub code: String,
+}
+
+#[cfg(feature = "http")]
+impl axum::response::IntoResponse for Problem {
+    fn into_response(self) -> axum::response::Response {
+        use axum::http::{StatusCode, header};
+
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::to_vec(&self).unwrap_or_default();
+        let mut response =
+            (status, [(header::CONTENT_TYPE, PROBLEM_CONTENT_TYPE)], body).into_response();
+        // Lets the router fill in `instance` from the request path.
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<axum::extract::rejection::JsonRejection> for ApiError {
+    fn from(e: axum::extract::rejection::JsonRejection) -> Self {
+        if e.status() == axum::http::StatusCode::PAYLOAD_TOO_LARGE {
+            return ApiError::PayloadTooLarge(e.body_text());
+        }
+        ApiError::InvalidRequest(e.body_text())
+    }
+}
+
+#[cfg(feature = "http")]
+impl From<axum::extract::rejection::QueryRejection> for ApiError {
+    fn from(e: axum::extract::rejection::QueryRejection) -> Self {
+        ApiError::InvalidRequest(e.body_text())
+    }
 }
 
 #[cfg(feature = "http")]
 impl axum::response::IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        use axum::http::StatusCode;
+        self.to_problem().into_response()
+    }
+}
 
-        let (status, message) = match self {
-            ApiError::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg),
-            ApiError::TaskNotFound(msg) => (StatusCode::NOT_FOUND, msg),
-            ApiError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg),
-            ApiError::Core(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()),
-        };
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problem_carries_code_status_and_detail() {
+        let problem = ApiError::TaskNotFound("r-backup-1".into()).to_problem();
+        assert_eq!(problem.kind, "urn:solti:problem:task_not_found");
+        assert_eq!(problem.title, "Task not found");
+        assert_eq!(problem.status, 404);
+        assert_eq!(problem.detail, "r-backup-1");
+        assert_eq!(problem.code, "task_not_found");
+
+        let json = serde_json::to_value(&problem).unwrap();
+        assert_eq!(json["type"], "urn:solti:problem:task_not_found");
+        assert!(json.get("instance").is_none());
+    }
+
+    #[cfg(feature = "grpc")]
+    #[test]
+    fn grpc_status_carries_error_details() {
+        use tonic_types::StatusExt;
+
+        let status = tonic::Status::from(ApiError::invalid_field(
+            "backoff.factor",
+            "backoff factor must be positive",
+        ));
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        let details = status.get_error_details();
+        let info = details.error_info().unwrap();
+        assert_eq!(info.reason, "INVALID_REQUEST");
+        assert_eq!(info.domain, ERROR_DOMAIN);
+        let violation = &details.bad_request().unwrap().field_violations[0];
+        assert_eq!(violation.field, "backoff.factor");
+        assert_eq!(violation.description, "backoff factor must be positive");
+
+        let status = tonic::Status::from(ApiError::from(CoreError::TaskNotFound("t-1".into())));
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        let details = status.get_error_details();
+        assert_eq!(details.error_info().unwrap().reason, "TASK_NOT_FOUND");
+        let resource = details.resource_info().unwrap();
+        assert_eq!(resource.resource_type, "task");
+        assert_eq!(resource.resource_name, "t-1");
+    }
+
+    #[test]
+    fn core_errors_keep_their_own_codes() {
+        let err = ApiError::from(CoreError::NoRunner("TaskKind::None".into()));
+        assert_eq!(err.code(), "no_runner");
+        assert_eq!(err.status(), 500);
+        assert_eq!(
+            err.to_problem().detail,
+            "no suitable runner for task kind: TaskKind::None"
+        );
+    }
+
+    #[test]
+    fn denied_commands_are_forbidden() {
+        let err = ApiError::from(CoreError::Runner(RunnerError::CommandDenied {
+            command: "/bin/sh".into(),
+            reason: "shells are not allowed".into(),
+        }));
+        assert_eq!(err.code(), "command_denied");
+        assert_eq!(err.status(), 403);
+        assert_eq!(err.to_problem().title, "Command denied");
+    }
+
+    #[test]
+    fn insufficient_capacity_is_unavailable() {
+        let err = ApiError::from(CoreError::InsufficientCapacity(
+            "gpus: requested 1, available 0".into(),
+        ));
+        assert_eq!(err.code(), "insufficient_capacity");
+        assert_eq!(err.status(), 503);
+        assert_eq!(err.to_problem().title, "Insufficient capacity");
+    }
+
+    #[test]
+    fn full_queues_are_too_many_requests() {
+        let err = ApiError::from(CoreError::QueueFull(
+            "slot backup: 4 queued tasks, limit 4".into(),
+        ));
+        assert_eq!(err.code(), "queue_full");
+        assert_eq!(err.status(), 429);
+        assert_eq!(err.to_problem().title, "Queue full");
+
+        #[cfg(feature = "grpc")]
+        assert_eq!(
+            tonic::Status::from(err).code(),
+            tonic::Code::ResourceExhausted
+        );
+    }
 
-        let body = serde_json::json!({
-            "error": message
-        });
+    #[test]
+    fn exceeded_quotas_are_too_many_requests() {
+        let err = ApiError::from(CoreError::QuotaExceeded(
+            "namespace=a: tasks 2 exceeds limit 2".into(),
+        ));
+        assert_eq!(err.code(), "quota_exceeded");
+        assert_eq!(err.status(), 429);
+        assert_eq!(err.to_problem().title, "Quota exceeded");
 
-        (status, axum::Json(body)).into_response()
+        #[cfg(feature = "grpc")]
+        assert_eq!(
+            tonic::Status::from(err).code(),
+            tonic::Code::ResourceExhausted
+        );
     }
 }
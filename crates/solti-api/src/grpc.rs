@@ -1,19 +1,35 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use tonic::{Request, Response, Status};
+use tonic::{Request, Response, Status, metadata::MetadataMap};
 use tracing::debug;
 
-use solti_model::TaskQuery;
+use solti_model::{RestartStrategy, TaskId, TaskQuery};
 
 use crate::error::ApiError;
 use crate::handler::ApiHandler;
-use crate::proto_api::{self, solti_api_server::SoltiApi};
+use crate::proto_api::{
+    self,
+    solti_api_server::{SoltiApi, SoltiApiServer},
+};
+
+/// Request hook run by [`SoltiApiService`] before every call.
+///
+/// Inspects the request metadata and returns an error status (e.g. `unauthenticated`)
+/// to reject the call before it reaches the handler.
+pub type GrpcInterceptFn = Arc<dyn Fn(&MetadataMap) -> Result<(), Status> + Send + Sync>;
+
+/// Default maximum size of a decoded request message.
+pub const DEFAULT_MAX_DECODING_MESSAGE_SIZE: usize = 1024 * 1024;
 
 /// gRPC service implementation.
 ///
 /// This struct wraps an `ApiHandler` and implements the generated `SoltiApi` trait.
+/// Mount it with [`into_server`](Self::into_server) so the message size limits apply.
 pub struct SoltiApiService<H> {
     handler: Arc<H>,
+    interceptor: Option<GrpcInterceptFn>,
+    max_decoding_message_size: usize,
+    max_encoding_message_size: Option<usize>,
 }
 
 impl<H> SoltiApiService<H>
@@ -22,7 +38,79 @@ where
 {
     /// Create a new gRPC service with the given handler.
     pub fn new(handler: Arc<H>) -> Self {
-        Self { handler }
+        Self {
+            handler,
+            interceptor: None,
+            max_decoding_message_size: DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            max_encoding_message_size: None,
+        }
+    }
+
+    /// Reject request messages larger than `bytes` with `RESOURCE_EXHAUSTED`
+    /// (default [`DEFAULT_MAX_DECODING_MESSAGE_SIZE`]).
+    pub fn with_max_decoding_message_size(mut self, bytes: usize) -> Self {
+        self.max_decoding_message_size = bytes;
+        self
+    }
+
+    /// Fail calls whose response message exceeds `bytes` (unlimited by default).
+    pub fn with_max_encoding_message_size(mut self, bytes: usize) -> Self {
+        self.max_encoding_message_size = Some(bytes);
+        self
+    }
+
+    /// Wrap the service into the generated tonic server with the configured limits applied.
+    pub fn into_server(self) -> SoltiApiServer<Self> {
+        let decoding = self.max_decoding_message_size;
+        let encoding = self.max_encoding_message_size;
+        let server = SoltiApiServer::new(self).max_decoding_message_size(decoding);
+        match encoding {
+            Some(limit) => server.max_encoding_message_size(limit),
+            None => server,
+        }
+    }
+
+    /// Run `interceptor` on every request before it reaches the handler.
+    ///
+    /// Meant for authentication: reject with `Status::unauthenticated` or
+    /// `Status::permission_denied` when the metadata does not carry valid credentials.
+    pub fn with_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&MetadataMap) -> Result<(), Status> + Send + Sync + 'static,
+    {
+        self.interceptor = Some(Arc::new(interceptor));
+        self
+    }
+
+    #[cfg(feature = "http")]
+    pub(crate) fn with_intercept_fn(mut self, interceptor: GrpcInterceptFn) -> Self {
+        self.interceptor = Some(interceptor);
+        self
+    }
+
+    /// Serve the service on a unix domain socket at `path` with permission bits `mode`.
+    ///
+    /// See [`bind_unix`](crate::bind_unix) for how the socket is created.
+    #[cfg(unix)]
+    pub async fn serve_unix(
+        self,
+        path: impl AsRef<std::path::Path>,
+        mode: u32,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let listener = crate::unix::bind_unix(path, mode)?;
+        tonic::transport::Server::builder()
+            .add_service(self.into_server())
+            .serve_with_incoming(tokio_stream::wrappers::UnixListenerStream::new(listener))
+            .await?;
+        Ok(())
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn intercept<T>(&self, request: &Request<T>) -> Result<(), Status> {
+        match &self.interceptor {
+            Some(interceptor) => interceptor(request.metadata()),
+            None => Ok(()),
+        }
     }
 }
 
@@ -35,6 +123,7 @@ where
         &self,
         request: Request<proto_api::SubmitTaskRequest>,
     ) -> Result<Response<proto_api::SubmitTaskResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         let spec = req
@@ -52,10 +141,54 @@ where
         }))
     }
 
+    async fn submit_and_wait(
+        &self,
+        request: Request<proto_api::SubmitAndWaitRequest>,
+    ) -> Result<Response<proto_api::SubmitAndWaitResponse>, Status> {
+        self.intercept(&request)?;
+        let deadline = grpc_timeout(request.metadata());
+        let req = request.into_inner();
+
+        let spec = req
+            .spec
+            .ok_or_else(|| Status::invalid_argument("missing spec"))?;
+        let spec =
+            solti_model::CreateSpec::try_from(spec).map_err(|e: ApiError| Status::from(e))?;
+        if spec.restart != RestartStrategy::Never {
+            return Err(Status::invalid_argument(
+                "SubmitAndWait is only supported for tasks with restart: never",
+            ));
+        }
+
+        let mut timeout = Duration::from_millis(match req.timeout_ms {
+            0 => DEFAULT_WAIT_TIMEOUT_MS,
+            ms => ms.min(MAX_WAIT_TIMEOUT_MS),
+        });
+        if let Some(deadline) = deadline {
+            timeout = timeout.min(deadline.saturating_sub(deadline_margin(deadline)));
+        }
+
+        debug!(slot = %spec.slot, kind = ?spec.kind, ?timeout, "grpc: submitting task and waiting");
+        let (task_id, info) = self
+            .handler
+            .submit_and_wait(spec, timeout)
+            .await
+            .map_err(Status::from)?;
+
+        match info {
+            Some(info) => Ok(Response::new(proto_api::SubmitAndWaitResponse {
+                task_id: task_id.to_string(),
+                info: Some(proto_api::TaskInfo::from(info)),
+            })),
+            None => Err(still_active(&task_id)),
+        }
+    }
+
     async fn get_task_status(
         &self,
         request: Request<proto_api::GetTaskStatusRequest>,
     ) -> Result<Response<proto_api::GetTaskStatusResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         let task_id = solti_model::TaskId::from(req.task_id);
@@ -76,6 +209,7 @@ where
         &self,
         request: Request<proto_api::ListTasksRequest>,
     ) -> Result<Response<proto_api::ListTasksResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         let mut query = TaskQuery::new();
@@ -126,8 +260,9 @@ where
 
     async fn list_all_tasks(
         &self,
-        _request: Request<proto_api::ListAllTasksRequest>,
+        request: Request<proto_api::ListAllTasksRequest>,
     ) -> Result<Response<proto_api::ListAllTasksResponse>, Status> {
+        self.intercept(&request)?;
         let tasks = self.handler.list_all_tasks().await.map_err(Status::from)?;
         debug!(count = tasks.len(), "grpc: tasks listed");
 
@@ -140,6 +275,7 @@ where
         &self,
         request: Request<proto_api::ListTasksBySlotRequest>,
     ) -> Result<Response<proto_api::ListTasksBySlotResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         if req.slot.trim().is_empty() {
@@ -162,6 +298,7 @@ where
         &self,
         request: Request<proto_api::ListTasksByStatusRequest>,
     ) -> Result<Response<proto_api::ListTasksByStatusResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         let domain_status = proto_to_domain_status(req.status)?;
@@ -183,6 +320,7 @@ where
         &self,
         request: Request<proto_api::CancelTaskRequest>,
     ) -> Result<Response<proto_api::CancelTaskResponse>, Status> {
+        self.intercept(&request)?;
         let req = request.into_inner();
 
         if req.task_id.trim().is_empty() {
@@ -199,6 +337,151 @@ where
         debug!(%task_id, "grpc: task canceled");
         Ok(Response::new(proto_api::CancelTaskResponse {}))
     }
+
+    async fn get_group_status(
+        &self,
+        request: Request<proto_api::GetGroupStatusRequest>,
+    ) -> Result<Response<proto_api::GetGroupStatusResponse>, Status> {
+        self.intercept(&request)?;
+        let req = request.into_inner();
+
+        debug!(group = %req.group, "grpc: getting group status");
+        let info = self
+            .handler
+            .get_group_status(&req.group)
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(proto_api::GetGroupStatusResponse {
+            info: info.map(proto_api::GroupInfo::from),
+        }))
+    }
+
+    async fn cancel_group(
+        &self,
+        request: Request<proto_api::CancelGroupRequest>,
+    ) -> Result<Response<proto_api::CancelGroupResponse>, Status> {
+        self.intercept(&request)?;
+        let req = request.into_inner();
+
+        if req.group.trim().is_empty() {
+            return Err(Status::invalid_argument("group cannot be empty"));
+        }
+
+        let canceled = self
+            .handler
+            .cancel_group(&req.group)
+            .await
+            .map_err(Status::from)?;
+
+        debug!(group = %req.group, canceled, "grpc: group canceled");
+        Ok(Response::new(proto_api::CancelGroupResponse {
+            canceled: canceled as u32,
+        }))
+    }
+
+    async fn wait_group(
+        &self,
+        request: Request<proto_api::WaitGroupRequest>,
+    ) -> Result<Response<proto_api::WaitGroupResponse>, Status> {
+        self.intercept(&request)?;
+        let req = request.into_inner();
+
+        let timeout_ms = match req.timeout_ms {
+            0 => DEFAULT_WAIT_TIMEOUT_MS,
+            ms => ms.min(MAX_WAIT_TIMEOUT_MS),
+        };
+
+        debug!(group = %req.group, timeout_ms, "grpc: waiting for group completion");
+        let info = self
+            .handler
+            .wait_group(&req.group, Duration::from_millis(timeout_ms))
+            .await
+            .map_err(Status::from)?;
+
+        Ok(Response::new(proto_api::WaitGroupResponse {
+            info: Some(proto_api::GroupInfo::from(info)),
+        }))
+    }
+
+    async fn get_maintenance(
+        &self,
+        request: Request<proto_api::GetMaintenanceRequest>,
+    ) -> Result<Response<proto_api::MaintenanceResponse>, Status> {
+        self.intercept(&request)?;
+        let enabled = self.handler.get_maintenance().await.map_err(Status::from)?;
+
+        Ok(Response::new(proto_api::MaintenanceResponse {
+            enabled,
+            previous: enabled,
+        }))
+    }
+
+    async fn set_maintenance(
+        &self,
+        request: Request<proto_api::SetMaintenanceRequest>,
+    ) -> Result<Response<proto_api::MaintenanceResponse>, Status> {
+        self.intercept(&request)?;
+        let req = request.into_inner();
+
+        let previous = self
+            .handler
+            .set_maintenance(req.enabled)
+            .await
+            .map_err(Status::from)?;
+
+        debug!(
+            enabled = req.enabled,
+            previous, "grpc: maintenance mode updated"
+        );
+        Ok(Response::new(proto_api::MaintenanceResponse {
+            enabled: req.enabled,
+            previous,
+        }))
+    }
+}
+
+/// Default wait timeout for task and group completion.
+const DEFAULT_WAIT_TIMEOUT_MS: u64 = 30_000;
+/// Upper bound for wait timeout to avoid holding calls indefinitely.
+const MAX_WAIT_TIMEOUT_MS: u64 = 300_000;
+
+/// Upper bound for the time reserved to deliver a reply before the call deadline.
+const MAX_DEADLINE_MARGIN: Duration = Duration::from_millis(500);
+
+/// Time reserved before the client deadline so the reply (or the task id) still arrives.
+fn deadline_margin(deadline: Duration) -> Duration {
+    (deadline / 10).min(MAX_DEADLINE_MARGIN)
+}
+
+/// Parse the `grpc-timeout` header set by clients with a call deadline.
+///
+/// The value is up to 8 digits followed by a unit: `H`, `M`, `S`, `m` (ms), `u` (µs) or `n` (ns).
+fn grpc_timeout(metadata: &MetadataMap) -> Option<Duration> {
+    let raw = metadata.get("grpc-timeout")?.to_str().ok()?;
+    if raw.len() < 2 {
+        return None;
+    }
+    let (value, unit) = raw.split_at(raw.len() - 1);
+    let value: u64 = value.parse().ok()?;
+    Some(match unit {
+        "H" => Duration::from_secs(value.saturating_mul(3600)),
+        "M" => Duration::from_secs(value.saturating_mul(60)),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    })
+}
+
+/// DEADLINE_EXCEEDED carrying the id of the task that is still running.
+fn still_active(task_id: &TaskId) -> Status {
+    let mut status = Status::deadline_exceeded(format!("task {task_id} is still active"));
+    if let Ok(value) = task_id.as_str().parse() {
+        status.metadata_mut().insert("task-id", value);
+    }
+    status
 }
 
 /// Convert proto TaskStatus i32 to domain TaskStatus.
@@ -215,8 +498,139 @@ fn proto_to_domain_status(raw: i32) -> Result<solti_model::TaskStatus, Status> {
         proto_api::TaskStatus::Timeout => Ok(solti_model::TaskStatus::Timeout),
         proto_api::TaskStatus::Canceled => Ok(solti_model::TaskStatus::Canceled),
         proto_api::TaskStatus::Exhausted => Ok(solti_model::TaskStatus::Exhausted),
+        proto_api::TaskStatus::Skipped => Ok(solti_model::TaskStatus::Skipped),
         proto_api::TaskStatus::Unspecified => {
             Err(Status::invalid_argument("status cannot be unspecified"))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metadata(timeout: &str) -> MetadataMap {
+        let mut md = MetadataMap::new();
+        md.insert("grpc-timeout", timeout.parse().unwrap());
+        md
+    }
+
+    #[allow(clippy::result_large_err)]
+    #[tokio::test]
+    async fn interceptor_rejects_before_the_handler() {
+        let service =
+            SoltiApiService::new(Arc::new(crate::testing::Maintenance)).with_interceptor(|md| {
+                match md.get("authorization").and_then(|v| v.to_str().ok()) {
+                    Some("Bearer secret") => Ok(()),
+                    _ => Err(Status::unauthenticated("missing or invalid token")),
+                }
+            });
+
+        let err = service
+            .get_maintenance(Request::new(proto_api::GetMaintenanceRequest {}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        let mut request = Request::new(proto_api::GetMaintenanceRequest {});
+        request
+            .metadata_mut()
+            .insert("authorization", "Bearer secret".parse().unwrap());
+        let response = service.get_maintenance(request).await.unwrap();
+        assert!(response.into_inner().enabled);
+    }
+
+    #[tokio::test]
+    async fn submit_and_wait_reports_instantly_completing_task() {
+        use solti_core::{AgentControlRunner, RunnerRouter, SupervisorApi};
+
+        let mut runners = RunnerRouter::new();
+        runners.register(Arc::new(
+            AgentControlRunner::new()
+                .with_action(solti_model::AgentAction::CollectGarbage, || Ok(())),
+        ));
+        let supervisor = SupervisorApi::new(
+            taskvisor::SupervisorConfig::default(),
+            taskvisor::ControllerConfig::default(),
+            Vec::new(),
+            runners,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+        let service = SoltiApiService::new(Arc::new(crate::SupervisorApiAdapter::new(Arc::new(
+            supervisor,
+        ))));
+
+        let spec = proto_api::CreateSpec {
+            slot: "instant".to_string(),
+            kind: Some(proto_api::TaskKind {
+                kind: Some(proto_api::task_kind::Kind::AgentControl(
+                    proto_api::AgentControlTask {
+                        action: proto_api::AgentAction::CollectGarbage as i32,
+                    },
+                )),
+            }),
+            timeout_ms: 1_000,
+            restart: proto_api::RestartStrategy::Never as i32,
+            restart_interval_ms: None,
+            backoff: Some(proto_api::BackoffStrategy {
+                jitter: proto_api::JitterStrategy::None as i32,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            }),
+            admission: proto_api::AdmissionStrategy::Queue as i32,
+            labels: Default::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+        for _ in 0..20 {
+            let request = Request::new(proto_api::SubmitAndWaitRequest {
+                spec: Some(spec.clone()),
+                timeout_ms: 5_000,
+            });
+            let response = service.submit_and_wait(request).await.unwrap().into_inner();
+            assert_eq!(
+                response.info.unwrap().status,
+                proto_api::TaskStatus::Succeeded as i32
+            );
+        }
+    }
+
+    #[test]
+    fn parses_grpc_timeout_header() {
+        assert_eq!(grpc_timeout(&metadata("5S")), Some(Duration::from_secs(5)));
+        assert_eq!(
+            grpc_timeout(&metadata("250m")),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            grpc_timeout(&metadata("2M")),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(grpc_timeout(&metadata("10x")), None);
+        assert_eq!(grpc_timeout(&MetadataMap::new()), None);
+    }
+
+    #[test]
+    fn deadline_margin_is_bounded() {
+        assert_eq!(
+            deadline_margin(Duration::from_secs(1)),
+            Duration::from_millis(100)
+        );
+        assert_eq!(
+            deadline_margin(Duration::from_secs(60)),
+            MAX_DEADLINE_MARGIN
+        );
+    }
+
+    #[test]
+    fn still_active_carries_task_id() {
+        let status = still_active(&TaskId::from("runner-a-slot-1"));
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+        assert_eq!(status.metadata().get("task-id").unwrap(), "runner-a-slot-1");
+    }
+}
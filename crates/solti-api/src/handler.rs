@@ -1,5 +1,10 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
-use solti_model::{CreateSpec, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus};
+use solti_model::{
+    CreateSpec, EventQuery, GroupInfo, ReloadReport, SubscriberHealth, TaskEvent, TaskId, TaskInfo,
+    TaskPage, TaskQuery, TaskStatus,
+};
 
 use crate::error::ApiError;
 
@@ -31,9 +36,116 @@ pub trait ApiHandler: Send + Sync + 'static {
     /// with offset/limit pagination. Returns a page with total count.
     async fn query_tasks(&self, query: TaskQuery) -> Result<TaskPage<TaskInfo>, ApiError>;
 
+    /// Revision of the task listing, changing whenever any task changes.
+    ///
+    /// Enables conditional `GET /api/v1/tasks` requests (`ETag` / `If-None-Match`).
+    /// Handlers returning `None` always send full listings.
+    async fn tasks_revision(&self) -> Result<Option<u64>, ApiError> {
+        Ok(None)
+    }
+
     /// Cancel a running task.
     ///
     /// Sends cancellation signal to the task. The task must cooperate
     /// by checking its `CancellationToken`.
     async fn cancel_task(&self, id: &TaskId) -> Result<(), ApiError>;
+
+    /// Wait until a task reaches a terminal state.
+    ///
+    /// Fails with [`ApiError::Timeout`] if the task is still active after `timeout`.
+    async fn wait_task(&self, id: &TaskId, timeout: Duration) -> Result<TaskInfo, ApiError> {
+        let _ = (id, timeout);
+        Err(ApiError::Unsupported("waiting for tasks".into()))
+    }
+
+    /// Submit a new task and wait until it reaches a terminal state.
+    ///
+    /// Returns the task id with its terminal info, or with `None` if the task is still
+    /// active after `timeout`. The default submits and then calls
+    /// [`ApiHandler::wait_task`]; backends should override it to start watching the task
+    /// before submitting, so tasks finishing right away are not missed.
+    async fn submit_and_wait(
+        &self,
+        spec: CreateSpec,
+        timeout: Duration,
+    ) -> Result<(TaskId, Option<TaskInfo>), ApiError> {
+        let id = self.submit_task(spec).await?;
+        match self.wait_task(&id, timeout).await {
+            Ok(info) => Ok((id, Some(info))),
+            Err(ApiError::Timeout(_)) => Ok((id, None)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait until the status of a task differs from `from`.
+    ///
+    /// Resolves immediately if the task is no longer in `from`.
+    /// Fails with [`ApiError::Timeout`] if the task is still in `from` after `timeout`.
+    async fn wait_task_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        timeout: Duration,
+    ) -> Result<TaskInfo, ApiError> {
+        let _ = (id, from, timeout);
+        Err(ApiError::Unsupported(
+            "waiting for task status changes".into(),
+        ))
+    }
+
+    /// Get aggregated status of a task group.
+    async fn get_group_status(&self, group: &str) -> Result<Option<GroupInfo>, ApiError>;
+
+    /// Cancel all active members of a task group.
+    ///
+    /// Returns the number of canceled members.
+    async fn cancel_group(&self, group: &str) -> Result<usize, ApiError>;
+
+    /// Wait until all members of a task group reach a terminal state.
+    ///
+    /// Fails with [`ApiError::Timeout`] if the group is still active after `timeout`.
+    async fn wait_group(&self, group: &str, timeout: Duration) -> Result<GroupInfo, ApiError>;
+
+    /// Returns `true` if the agent is in maintenance mode.
+    async fn get_maintenance(&self) -> Result<bool, ApiError>;
+
+    /// Enable or disable maintenance mode.
+    ///
+    /// While enabled, periodic tasks keep their state but skip their runs.
+    /// Returns the previous value.
+    async fn set_maintenance(&self, enabled: bool) -> Result<bool, ApiError>;
+
+    /// Reload agent configuration.
+    ///
+    /// Applies settings that are safe to change at runtime and reports which
+    /// changes require restart. Handlers without a configuration source return
+    /// [`ApiError::Unsupported`].
+    async fn reload_config(&self) -> Result<ReloadReport, ApiError> {
+        Err(ApiError::Unsupported("configuration reload".into()))
+    }
+
+    /// List recent lifecycle events retained by the agent, oldest first.
+    async fn list_events(&self, query: EventQuery) -> Result<Vec<TaskEvent>, ApiError> {
+        let _ = query;
+        Err(ApiError::Unsupported("event history".into()))
+    }
+
+    /// Health of the lifecycle event subscribers attached to the agent.
+    async fn list_subscribers(&self) -> Result<Vec<SubscriberHealth>, ApiError> {
+        Err(ApiError::Unsupported("subscriber health".into()))
+    }
+
+    /// Returns the log filter expression of the running logger.
+    async fn get_log_level(&self) -> Result<String, ApiError> {
+        Err(ApiError::Unsupported("log level control".into()))
+    }
+
+    /// Replace the log filter expression of the running logger.
+    ///
+    /// Accepts the same syntax as the logger configuration
+    /// (e.g. `"info"` or `"solti_exec=trace,info"`). Returns the previous value.
+    async fn set_log_level(&self, level: &str) -> Result<String, ApiError> {
+        let _ = level;
+        Err(ApiError::Unsupported("log level control".into()))
+    }
 }
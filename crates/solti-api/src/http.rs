@@ -1,20 +1,48 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use axum::{
     Json, Router,
-    extract::{Path, Query, State},
-    response::IntoResponse,
-    routing::{get, post},
+    extract::{
+        DefaultBodyLimit, Path, Query, Request, State,
+        rejection::{JsonRejection, QueryRejection},
+    },
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post, put},
 };
 use serde::{Deserialize, Serialize};
-use solti_model::{CreateSpec, TaskId, TaskInfo, TaskQuery, TaskStatus};
+use solti_model::{
+    CreateSpec, Diagnostic, EventQuery, GroupInfo, RestartStrategy, SubscriberHealth, TaskEvent,
+    TaskId, TaskInfo, TaskQuery, TaskStatus, validate,
+};
+use tower_http::{
+    compression::CompressionLayer,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing::debug;
 
-use crate::{error::ApiError, handler::ApiHandler};
+use crate::{
+    error::{ApiError, Problem},
+    handler::ApiHandler,
+};
+
+/// Default maximum size of a request body.
+pub const DEFAULT_BODY_LIMIT: usize = 1024 * 1024;
+
+/// Default upper bound for handling a request.
+///
+/// Leaves headroom above the longest wait a client can request (`wait_timeout` is capped at 300s).
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(330);
 
 /// HTTP API service builder.
 pub struct HttpApi<H> {
     handler: Arc<H>,
+    compression: bool,
+    tracing: bool,
+    body_limit: usize,
+    request_timeout: Option<Duration>,
 }
 
 impl<H> HttpApi<H>
@@ -23,22 +51,142 @@ where
 {
     /// Create new HTTP API with the given handler.
     pub fn new(handler: Arc<H>) -> Self {
-        Self { handler }
+        Self {
+            handler,
+            compression: true,
+            tracing: true,
+            body_limit: DEFAULT_BODY_LIMIT,
+            request_timeout: Some(DEFAULT_REQUEST_TIMEOUT),
+        }
+    }
+
+    /// Compress responses with gzip or brotli when the client's `Accept-Encoding` allows it
+    /// (enabled by default).
+    ///
+    /// Disable when a reverse proxy in front of the agent already compresses responses.
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Trace requests and tag them with an `x-request-id` (enabled by default).
+    ///
+    /// A request id sent by the client is kept, otherwise a UUID is generated; either
+    /// way it is echoed in the response. Each request runs in a `debug` span carrying
+    /// method, path, request id and, for task routes, the task id; completion is logged
+    /// with status and latency.
+    pub fn with_tracing(mut self, enabled: bool) -> Self {
+        self.tracing = enabled;
+        self
+    }
+
+    /// Reject request bodies larger than `bytes` with `413 Payload Too Large`
+    /// (default [`DEFAULT_BODY_LIMIT`]).
+    pub fn with_body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    /// Abort requests not answered within `timeout` with `408 Request Timeout`
+    /// (default [`DEFAULT_REQUEST_TIMEOUT`]; `None` disables the limit).
+    ///
+    /// The timeout covers reading the body, so it also bounds slow uploads. Keep it above
+    /// the wait timeouts clients use with `?wait=true`, `?wait_for=` and the group wait endpoint.
+    pub fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
     }
 
     /// Build axum router with mounted endpoints.
     ///
     /// Routes:
-    /// - POST /api/v1/tasks - Submit task
-    /// - GET /api/v1/tasks/:id - Get task status
+    /// - POST /api/v1/tasks - Submit task (`?wait=true` to await a one-shot task)
+    /// - GET /api/v1/tasks/:id - Get task status (`?wait_for=terminal|change` to long-poll)
     /// - GET /api/v1/tasks - List all tasks (or filter by query params)
+    /// - POST /api/v1/tasks/validate - Validate a spec without submitting it (dry run)
+    /// - POST /api/v1/tasks/:id/cancel - Cancel task
+    /// - GET /api/v1/groups/:group - Get group status
+    /// - POST /api/v1/groups/:group/cancel - Cancel all active group members
+    /// - GET /api/v1/groups/:group/wait - Wait for group completion
+    /// - POST /api/v1/admin/reload - Reload configuration
+    /// - GET /api/v1/admin/maintenance - Get maintenance mode
+    /// - PUT /api/v1/admin/maintenance - Enable or disable maintenance mode
+    /// - GET /api/v1/events - Recent lifecycle events (filter by since/slot/task)
+    /// - GET /api/v1/admin/subscribers - Health of lifecycle event subscribers
+    /// - GET /api/v1/admin/loglevel - Get log filter
+    /// - PUT /api/v1/admin/loglevel - Replace log filter
+    /// - GET /api/v1/info - Agent identity, version and build info
+    ///
+    /// Compression applies to the routes above only; wrap the merged router in
+    /// [`CompressionLayer`] to also compress routes added by the host (e.g. `/metrics`).
     pub fn router(self) -> Router {
-        Router::new()
+        let router = Router::new()
             .route("/api/v1/tasks", post(submit_task::<H>))
             .route("/api/v1/tasks", get(list_tasks::<H>))
+            .route("/api/v1/tasks/validate", post(validate_task))
             .route("/api/v1/tasks/{id}", get(get_task_status::<H>))
-            .route("/api/v1/tasks/{id}/cancel", post(cancel_task::<H>)) // НОВОЕ
+            .route("/api/v1/tasks/{id}/cancel", post(cancel_task::<H>))
+            .route("/api/v1/groups/{group}", get(get_group_status::<H>))
+            .route("/api/v1/groups/{group}/cancel", post(cancel_group::<H>))
+            .route("/api/v1/groups/{group}/wait", get(wait_group::<H>))
+            .route("/api/v1/admin/reload", post(reload_config::<H>))
+            .route("/api/v1/admin/maintenance", get(get_maintenance::<H>))
+            .route("/api/v1/admin/maintenance", put(set_maintenance::<H>))
+            .route("/api/v1/events", get(list_events::<H>))
+            .route("/api/v1/admin/subscribers", get(list_subscribers::<H>))
+            .route("/api/v1/admin/loglevel", get(get_log_level::<H>))
+            .route("/api/v1/admin/loglevel", put(set_log_level::<H>))
+            .route("/api/v1/info", get(get_info))
             .with_state(self.handler)
+            .layer(DefaultBodyLimit::max(self.body_limit));
+
+        let router = match self.request_timeout {
+            Some(timeout) => router.layer(middleware::from_fn(move |req: Request, next: Next| {
+                request_timeout(timeout, req, next)
+            })),
+            None => router,
+        };
+        let router = router.layer(middleware::from_fn(problem_instance));
+
+        let router = if self.compression {
+            router.layer(CompressionLayer::new())
+        } else {
+            router
+        };
+
+        if self.tracing {
+            router
+                .layer(PropagateRequestIdLayer::x_request_id())
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(request_span)
+                        .on_request(())
+                        .on_response(|res: &Response, latency: Duration, _: &tracing::Span| {
+                            debug!(
+                                status = res.status().as_u16(),
+                                latency_ms = latency.as_millis() as u64,
+                                "http: request finished"
+                            );
+                        }),
+                )
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+        } else {
+            router
+        }
+    }
+
+    /// Serve the API on a unix domain socket at `path` with permission bits `mode`.
+    ///
+    /// Meant for sidecars: local callers are authorized by file permissions instead of
+    /// TCP, TLS and tokens. See [`bind_unix`](crate::bind_unix) for how the socket is created.
+    #[cfg(unix)]
+    pub async fn serve_unix(
+        self,
+        path: impl AsRef<std::path::Path>,
+        mode: u32,
+    ) -> std::io::Result<()> {
+        let listener = crate::unix::bind_unix(path, mode)?;
+        axum::serve(listener, self.router()).await
     }
 }
 
@@ -46,14 +194,94 @@ where
 // Request/Response types
 // ============================================================================
 
+#[derive(Debug, Serialize, Deserialize)]
+struct MaintenanceRequest {
+    enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MaintenanceResponse {
+    enabled: bool,
+    previous: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListEventsParams {
+    /// Only events after this sequence number
+    since: Option<u64>,
+    /// Filter by slot name
+    slot: Option<String>,
+    /// Filter by task id
+    task: Option<String>,
+    /// Max items (default 100, max 1000)
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ListEventsResponse {
+    events: Vec<TaskEvent>,
+    /// Cursor for the next `since` query.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ListSubscribersResponse {
+    subscribers: Vec<SubscriberHealth>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LogLevelRequest {
+    level: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LogLevelResponse {
+    level: String,
+    previous: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct InfoResponse {
+    id: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    git_sha: Option<String>,
+    /// Build time in unix seconds.
+    build_timestamp: u64,
+    platform: String,
+    arch: String,
+    os: String,
+    uptime_seconds: u64,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct SubmitTaskRequest {
     spec: CreateSpec,
 }
 
+#[derive(Debug, Deserialize)]
+struct SubmitTaskParams {
+    /// Block until the task reaches a terminal state
+    #[serde(default)]
+    wait: bool,
+    /// Max time to wait, e.g. `30s`, `1500ms`, `2m` (default 30s, max 300s)
+    wait_timeout: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct SubmitTaskResponse {
     task_id: String,
+    /// Task info when the submission waited for completion.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<TaskInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ValidateTaskResponse {
+    /// Whether the spec would be accepted (no error diagnostics).
+    valid: bool,
+    diagnostics: Vec<Diagnostic>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,6 +290,27 @@ struct GetTaskStatusResponse {
     info: Option<TaskInfo>,
 }
 
+/// Condition a long-polling task status request waits for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum WaitFor {
+    /// The task reaches a terminal state.
+    Terminal,
+    /// The task status changes.
+    Change,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetTaskStatusParams {
+    /// Hold the request until the condition is met
+    wait_for: Option<WaitFor>,
+    /// Status last seen by the client; `wait_for=change` waits until the task leaves it
+    /// (default: the current status)
+    since: Option<String>,
+    /// Max time to wait, e.g. `30s`, `1500ms`, `2m` (default 30s, max 300s)
+    timeout: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct ListTasksParams {
     /// Filter by slot name
@@ -80,43 +329,224 @@ struct ListTasksResponse {
     total: usize,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+struct GetGroupStatusResponse {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<GroupInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CancelGroupResponse {
+    canceled: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct WaitGroupParams {
+    /// Max time to wait in milliseconds (default 30000, max 300000)
+    timeout_ms: Option<u64>,
+}
+
+/// Default wait timeout for task and group completion.
+const DEFAULT_WAIT_TIMEOUT_MS: u64 = 30_000;
+/// Upper bound for wait timeout to avoid holding connections indefinitely.
+const MAX_WAIT_TIMEOUT_MS: u64 = 300_000;
+
 // ============================================================================
 // Handlers
 // ============================================================================
 
 /// POST /api/v1/tasks
+///
+/// Query params:
+/// - ?wait=true          - wait for a one-shot (`restart: never`) task to finish
+/// - ?wait_timeout=30s   - max time to wait (default 30s, max 300s)
+///
+/// Responds with:
+/// - 201 and the task id without `wait`
+/// - 200 and the terminal task info (status, error, timings) once the task finished
+/// - 202 and the current task info if it is still active after the timeout
 async fn submit_task<H>(
     State(handler): State<Arc<H>>,
-    Json(req): Json<SubmitTaskRequest>,
+    params: Result<Query<SubmitTaskParams>, QueryRejection>,
+    req: Result<Json<SubmitTaskRequest>, JsonRejection>,
 ) -> Result<impl IntoResponse, ApiError>
 where
     H: ApiHandler,
 {
-    debug!(slot = %req.spec.slot, kind = ?req.spec.kind, "submitting task");
-    let task_id = handler.submit_task(req.spec).await?;
+    let Query(params) = params?;
+    let Json(req) = req?;
+    check_spec(&req.spec)?;
+    let timeout = if params.wait {
+        if req.spec.restart != RestartStrategy::Never {
+            return Err(ApiError::InvalidRequest(
+                "wait is only supported for tasks with restart: never".into(),
+            ));
+        }
+        let timeout_ms = match params.wait_timeout.as_deref() {
+            Some(raw) => parse_timeout_ms("wait_timeout", raw)?,
+            None => DEFAULT_WAIT_TIMEOUT_MS,
+        };
+        Some(Duration::from_millis(timeout_ms.min(MAX_WAIT_TIMEOUT_MS)))
+    } else {
+        None
+    };
+
+    let Some(timeout) = timeout else {
+        debug!(slot = %req.spec.slot, kind = ?req.spec.kind, "submitting task");
+        let task_id = handler.submit_task(req.spec).await?;
+        record_task_id(&task_id);
+        let response = SubmitTaskResponse {
+            task_id: task_id.to_string(),
+            info: None,
+        };
+        return Ok((StatusCode::CREATED, Json(response)));
+    };
+
+    debug!(slot = %req.spec.slot, kind = ?req.spec.kind, ?timeout, "submitting task and waiting for completion");
+    let (task_id, info) = handler.submit_and_wait(req.spec, timeout).await?;
+    record_task_id(&task_id);
+    let (status, info) = match info {
+        Some(info) => (StatusCode::OK, Some(info)),
+        None => (
+            StatusCode::ACCEPTED,
+            handler.get_task_status(&task_id).await?,
+        ),
+    };
 
     let response = SubmitTaskResponse {
         task_id: task_id.to_string(),
+        info,
+    };
+    Ok((status, Json(response)))
+}
+
+/// POST /api/v1/tasks/validate
+///
+/// Dry run: responds with 200 and all validation diagnostics (errors and warnings)
+/// without submitting the task.
+async fn validate_task(
+    req: Result<Json<SubmitTaskRequest>, JsonRejection>,
+) -> Result<Json<ValidateTaskResponse>, ApiError> {
+    let Json(req) = req?;
+    let diagnostics = validate(&req.spec);
+    Ok(Json(ValidateTaskResponse {
+        valid: !diagnostics.iter().any(Diagnostic::is_error),
+        diagnostics,
+    }))
+}
+
+/// Reject specs with validation errors; warnings are only logged.
+fn check_spec(spec: &CreateSpec) -> Result<(), ApiError> {
+    let diagnostics = validate(spec);
+    if let Some(err) = ApiError::from_diagnostics(&diagnostics) {
+        return Err(err);
+    }
+    for d in &diagnostics {
+        debug!(slot = %spec.slot, code = %d.code, field = %d.field, "spec warning: {}", d.message);
+    }
+    Ok(())
+}
+
+/// Parse a timeout such as `30s`, `1500ms` or `2m` into milliseconds; bare numbers are seconds.
+///
+/// `param` names the query parameter in error messages.
+fn parse_timeout_ms(param: &str, raw: &str) -> Result<u64, ApiError> {
+    let raw = raw.trim();
+    let (value, unit_ms) = if let Some(v) = raw.strip_suffix("ms") {
+        (v, 1)
+    } else if let Some(v) = raw.strip_suffix('s') {
+        (v, 1_000)
+    } else if let Some(v) = raw.strip_suffix('m') {
+        (v, 60_000)
+    } else {
+        (raw, 1_000)
     };
 
-    Ok((axum::http::StatusCode::CREATED, Json(response)))
+    value
+        .trim()
+        .parse::<u64>()
+        .map(|v| v.saturating_mul(unit_ms))
+        .map_err(|_| {
+            ApiError::InvalidRequest(format!(
+                "invalid {param}: '{raw}' (expected e.g. 30s, 1500ms, 2m)"
+            ))
+        })
 }
 
 /// GET /api/v1/tasks/:id
+///
+/// Query params (long polling, for clients without SSE/WebSocket):
+/// - ?wait_for=terminal - hold the request until the task reaches a terminal state
+/// - ?wait_for=change   - hold the request until the task status changes
+/// - ?since=running     - status last seen by the client (`wait_for=change` only)
+/// - ?timeout=30s       - max time to wait (default 30s, max 300s)
+///
+/// Responds with:
+/// - 200 and the task info (`info` is absent for unknown tasks without `wait_for`)
+/// - 200 and the new task info once the awaited condition is met
+/// - 202 and the current task info if the condition is not met within the timeout
 async fn get_task_status<H>(
     State(handler): State<Arc<H>>,
     Path(id): Path<String>,
+    params: Result<Query<GetTaskStatusParams>, QueryRejection>,
 ) -> Result<impl IntoResponse, ApiError>
 where
     H: ApiHandler,
 {
+    let Query(params) = params?;
     let task_id = TaskId::from(id);
-    debug!(%task_id, "getting task status");
-    let info = handler.get_task_status(&task_id).await?;
+    record_task_id(&task_id);
+
+    let Some(wait_for) = params.wait_for else {
+        if params.since.is_some() || params.timeout.is_some() {
+            return Err(ApiError::InvalidRequest(
+                "since and timeout require wait_for".into(),
+            ));
+        }
+        debug!(%task_id, "getting task status");
+        let info = handler.get_task_status(&task_id).await?;
+        return Ok((StatusCode::OK, Json(GetTaskStatusResponse { info })));
+    };
+    let timeout_ms = match params.timeout.as_deref() {
+        Some(raw) => parse_timeout_ms("timeout", raw)?,
+        None => DEFAULT_WAIT_TIMEOUT_MS,
+    };
+    let timeout = Duration::from_millis(timeout_ms.min(MAX_WAIT_TIMEOUT_MS));
 
-    let response = GetTaskStatusResponse { info };
+    debug!(%task_id, ?wait_for, ?timeout, "waiting for task status");
+    let waited = match wait_for {
+        WaitFor::Terminal => {
+            if params.since.is_some() {
+                return Err(ApiError::InvalidRequest(
+                    "since is only supported with wait_for=change".into(),
+                ));
+            }
+            handler.wait_task(&task_id, timeout).await
+        }
+        WaitFor::Change => {
+            let from = match params.since.as_deref() {
+                Some(raw) => parse_status(raw)?,
+                None => {
+                    handler
+                        .get_task_status(&task_id)
+                        .await?
+                        .ok_or_else(|| ApiError::TaskNotFound(task_id.to_string()))?
+                        .status
+                }
+            };
+            handler.wait_task_change(&task_id, from, timeout).await
+        }
+    };
 
-    Ok(Json(response))
+    let (status, info) = match waited {
+        Ok(info) => (StatusCode::OK, Some(info)),
+        Err(ApiError::Timeout(_)) => (
+            StatusCode::ACCEPTED,
+            handler.get_task_status(&task_id).await?,
+        ),
+        Err(e) => return Err(e),
+    };
+    Ok((status, Json(GetTaskStatusResponse { info })))
 }
 
 /// GET /api/v1/tasks
@@ -126,13 +556,29 @@ where
 /// - ?status=running - filter by status
 /// - ?limit=50     - max items per page (default 100, max 1000)
 /// - ?offset=0     - pagination offset (default 0)
+///
+/// Responses carry an `ETag` derived from the task state revision; a request whose
+/// `If-None-Match` matches it gets `304 Not Modified` without a body.
 async fn list_tasks<H>(
     State(handler): State<Arc<H>>,
-    Query(params): Query<ListTasksParams>,
-) -> Result<impl IntoResponse, ApiError>
+    params: Result<Query<ListTasksParams>, QueryRejection>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError>
 where
     H: ApiHandler,
 {
+    let Query(params) = params?;
+    // Read the revision before the listing: a change in between only makes the tag stale.
+    let etag = handler
+        .tasks_revision()
+        .await?
+        .map(|revision| format!("\"tasks-{revision:x}\""));
+    if let Some(etag) = &etag
+        && if_none_match(&headers, etag)
+    {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag.clone())]).into_response());
+    }
+
     let mut query = TaskQuery::new();
 
     if let Some(slot) = params.slot {
@@ -162,7 +608,19 @@ where
         tasks: page.items,
         total: page.total,
     };
-    Ok(Json(response))
+    let etag = etag.map(|etag| [(header::ETAG, etag)]);
+    Ok((etag, Json(response)).into_response())
+}
+
+/// Whether an `If-None-Match` header matches `etag` (weak comparison).
+fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|tag| tag.trim())
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
 }
 
 /// Parse TaskStatus from string.
@@ -175,8 +633,9 @@ fn parse_status(s: &str) -> Result<TaskStatus, ApiError> {
         "timeout" => Ok(TaskStatus::Timeout),
         "canceled" => Ok(TaskStatus::Canceled),
         "exhausted" => Ok(TaskStatus::Exhausted),
+        "skipped" => Ok(TaskStatus::Skipped),
         _ => Err(ApiError::InvalidRequest(format!(
-            "invalid status: '{}' (valid: pending, running, succeeded, failed, timeout, canceled, exhausted)",
+            "invalid status: '{}' (valid: pending, running, succeeded, failed, timeout, canceled, exhausted, skipped)",
             s
         ))),
     }
@@ -195,8 +654,551 @@ where
     }
 
     let task_id = TaskId::from(id);
+    record_task_id(&task_id);
     handler.cancel_task(&task_id).await?;
     debug!(%task_id, "task canceled");
 
-    Ok(axum::http::StatusCode::NO_CONTENT)
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/admin/reload
+async fn reload_config<H>(State(handler): State<Arc<H>>) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let report = handler.reload_config().await?;
+    debug!(
+        applied = ?report.applied,
+        requires_restart = ?report.requires_restart,
+        "configuration reloaded"
+    );
+
+    Ok(Json(report))
+}
+
+/// GET /api/v1/admin/maintenance
+async fn get_maintenance<H>(State(handler): State<Arc<H>>) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let enabled = handler.get_maintenance().await?;
+    Ok(Json(MaintenanceResponse {
+        enabled,
+        previous: enabled,
+    }))
+}
+
+/// PUT /api/v1/admin/maintenance
+async fn set_maintenance<H>(
+    State(handler): State<Arc<H>>,
+    req: Result<Json<MaintenanceRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let Json(req) = req?;
+    let previous = handler.set_maintenance(req.enabled).await?;
+    debug!(enabled = req.enabled, previous, "maintenance mode updated");
+
+    Ok(Json(MaintenanceResponse {
+        enabled: req.enabled,
+        previous,
+    }))
+}
+
+/// GET /api/v1/events
+///
+/// Query params (all optional, combinable):
+/// - ?since=42     - only events with a greater sequence number
+/// - ?slot=name    - filter by slot
+/// - ?task=id      - filter by task id
+/// - ?limit=50     - max items (default 100, max 1000)
+async fn list_events<H>(
+    State(handler): State<Arc<H>>,
+    params: Result<Query<ListEventsParams>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let Query(params) = params?;
+    let mut query = EventQuery::new();
+    if let Some(since) = params.since {
+        query = query.with_since(since);
+    }
+    if let Some(slot) = params.slot {
+        if slot.trim().is_empty() {
+            return Err(ApiError::InvalidRequest("slot cannot be empty".into()));
+        }
+        query = query.with_slot(slot);
+    }
+    if let Some(task) = params.task {
+        query = query.with_task(task);
+    }
+    if let Some(limit) = params.limit {
+        query = query.with_limit(limit);
+    }
+
+    let events = handler.list_events(query).await?;
+    debug!(count = events.len(), "events listed");
+
+    Ok(Json(ListEventsResponse {
+        next: events.last().map(|e| e.seq).or(params.since),
+        events,
+    }))
+}
+
+/// GET /api/v1/admin/subscribers
+async fn list_subscribers<H>(State(handler): State<Arc<H>>) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let subscribers = handler.list_subscribers().await?;
+    Ok(Json(ListSubscribersResponse { subscribers }))
+}
+
+/// GET /api/v1/admin/loglevel
+async fn get_log_level<H>(State(handler): State<Arc<H>>) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let level = handler.get_log_level().await?;
+    Ok(Json(LogLevelResponse {
+        previous: level.clone(),
+        level,
+    }))
+}
+
+/// PUT /api/v1/admin/loglevel
+async fn set_log_level<H>(
+    State(handler): State<Arc<H>>,
+    req: Result<Json<LogLevelRequest>, JsonRejection>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let Json(req) = req?;
+    let previous = handler.set_log_level(&req.level).await?;
+    debug!(level = %req.level, %previous, "log level updated");
+
+    Ok(Json(LogLevelResponse {
+        level: req.level,
+        previous,
+    }))
+}
+
+/// GET /api/v1/info
+async fn get_info() -> Json<InfoResponse> {
+    let build = solti_core::build_info();
+    Json(InfoResponse {
+        id: solti_core::agent_id().to_string(),
+        version: build.version.to_string(),
+        git_sha: build.git_sha.map(str::to_string),
+        build_timestamp: build.build_timestamp,
+        platform: solti_core::platform().to_string(),
+        arch: solti_core::arch().to_string(),
+        os: solti_core::os_info(),
+        uptime_seconds: solti_core::uptime_seconds(),
+    })
+}
+
+/// GET /api/v1/groups/:group
+async fn get_group_status<H>(
+    State(handler): State<Arc<H>>,
+    Path(group): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    debug!(%group, "getting group status");
+    let info = handler.get_group_status(&group).await?;
+
+    Ok(Json(GetGroupStatusResponse { info }))
+}
+
+/// POST /api/v1/groups/:group/cancel
+async fn cancel_group<H>(
+    State(handler): State<Arc<H>>,
+    Path(group): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    if group.trim().is_empty() {
+        return Err(ApiError::InvalidRequest("group cannot be empty".into()));
+    }
+
+    let canceled = handler.cancel_group(&group).await?;
+    debug!(%group, canceled, "group canceled");
+
+    Ok(Json(CancelGroupResponse { canceled }))
+}
+
+/// GET /api/v1/groups/:group/wait
+///
+/// Query params:
+/// - ?timeout_ms=30000 - max time to wait (default 30000, max 300000)
+async fn wait_group<H>(
+    State(handler): State<Arc<H>>,
+    Path(group): Path<String>,
+    params: Result<Query<WaitGroupParams>, QueryRejection>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    H: ApiHandler,
+{
+    let Query(params) = params?;
+    let timeout_ms = params
+        .timeout_ms
+        .unwrap_or(DEFAULT_WAIT_TIMEOUT_MS)
+        .min(MAX_WAIT_TIMEOUT_MS);
+
+    debug!(%group, timeout_ms, "waiting for group completion");
+    let info = handler
+        .wait_group(&group, Duration::from_millis(timeout_ms))
+        .await?;
+
+    Ok(Json(info))
+}
+
+/// Span of a traced request; `task_id` is filled in by task handlers.
+fn request_span<B>(request: &axum::http::Request<B>) -> tracing::Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    tracing::debug_span!(
+        "http",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id,
+        task_id = tracing::field::Empty,
+    )
+}
+
+/// Attach `task_id` to the request span.
+fn record_task_id(task_id: &TaskId) {
+    tracing::Span::current().record("task_id", tracing::field::display(task_id));
+}
+
+/// Answer with a `timeout` problem when the request is not done within `timeout`.
+async fn request_timeout(timeout: Duration, request: Request, next: Next) -> Response {
+    match tokio::time::timeout(timeout, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => ApiError::Timeout(format!(
+            "request not completed within {}ms",
+            timeout.as_millis()
+        ))
+        .into_response(),
+    }
+}
+
+/// Fill the `instance` member of problem responses with the request path.
+async fn problem_instance(request: Request, next: Next) -> Response {
+    let path = request.uri().path().to_owned();
+    let response = next.run(request).await;
+    match response.extensions().get::<Problem>() {
+        Some(problem) if problem.instance.is_none() => {
+            let mut problem = problem.clone();
+            problem.instance = Some(path);
+            problem.into_response()
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn problem_responses_carry_request_path() {
+        let router =
+            Router::new()
+                .route(
+                    "/api/v1/tasks/{id}",
+                    get(|Path(id): Path<String>| async move {
+                        ApiError::TaskNotFound(id).into_response()
+                    }),
+                )
+                .layer(middleware::from_fn(problem_instance));
+
+        let request = Request::get("/api/v1/tasks/missing")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(router, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            crate::error::PROBLEM_CONTENT_TYPE
+        );
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, "task_not_found");
+        assert_eq!(problem.detail, "missing");
+        assert_eq!(problem.instance.as_deref(), Some("/api/v1/tasks/missing"));
+    }
+
+    #[tokio::test]
+    async fn request_ids_are_propagated_or_generated() {
+        let router = HttpApi::new(Arc::new(crate::testing::Maintenance)).router();
+        let request = |id: Option<&str>| {
+            let mut builder = Request::get("/api/v1/admin/maintenance");
+            if let Some(id) = id {
+                builder = builder.header("x-request-id", id);
+            }
+            builder.body(axum::body::Body::empty()).unwrap()
+        };
+
+        let response = tower::ServiceExt::oneshot(router.clone(), request(Some("req-42")))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-request-id"], "req-42");
+
+        let response = tower::ServiceExt::oneshot(router, request(None))
+            .await
+            .unwrap();
+        let generated = response.headers()["x-request-id"].to_str().unwrap();
+        assert_eq!(generated.len(), 36, "{generated}");
+    }
+
+    #[tokio::test]
+    async fn info_reports_build_metadata() {
+        let router = HttpApi::new(Arc::new(crate::testing::Maintenance)).router();
+        let request = Request::get("/api/v1/info")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(router, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let info: InfoResponse = serde_json::from_slice(&body).unwrap();
+        assert_eq!(info.version, solti_core::build_info().version);
+        assert_eq!(info.id, solti_core::agent_id());
+        assert!(info.build_timestamp > 0);
+    }
+
+    #[tokio::test]
+    async fn validate_reports_diagnostics_without_submitting() {
+        // `Maintenance` panics on submit, so reaching the handler would fail the test.
+        let router = HttpApi::new(Arc::new(crate::testing::Maintenance)).router();
+        let spec = serde_json::json!({
+            "slot": "",
+            "kind": { "subprocess": { "command": "ls" } },
+            "timeoutMs": 100,
+            "restart": { "type": "onFailure" },
+            "backoff": { "jitter": "none", "firstMs": 1000, "maxMs": 5000, "factor": 2.0 },
+            "admission": "dropIfRunning"
+        });
+        let request = |path: &str| {
+            Request::post(path)
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(
+                    serde_json::json!({ "spec": spec }).to_string(),
+                ))
+                .unwrap()
+        };
+
+        let response =
+            tower::ServiceExt::oneshot(router.clone(), request("/api/v1/tasks/validate"))
+                .await
+                .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let report: ValidateTaskResponse = serde_json::from_slice(&body).unwrap();
+        assert!(!report.valid);
+        let codes: Vec<_> = report.diagnostics.iter().map(|d| d.code.as_str()).collect();
+        assert_eq!(codes, ["empty_slot", "timeout_below_backoff"]);
+
+        let response = tower::ServiceExt::oneshot(router, request("/api/v1/tasks"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.detail, "slot: slot cannot be empty");
+    }
+
+    #[tokio::test]
+    async fn oversized_bodies_are_rejected() {
+        let router = HttpApi::new(Arc::new(crate::testing::Maintenance))
+            .with_body_limit(64)
+            .router();
+        let body = format!(r#"{{"spec":{{"slot":"{}"}}}}"#, "x".repeat(128));
+        let request = Request::post("/api/v1/tasks")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body))
+            .unwrap();
+
+        let response = tower::ServiceExt::oneshot(router, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let problem: Problem = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem.code, "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn slow_requests_time_out() {
+        let timeout = Duration::from_millis(20);
+        let router = Router::new()
+            .route(
+                "/slow",
+                get(|| async {
+                    tokio::time::sleep(Duration::from_secs(5)).await;
+                    StatusCode::OK
+                }),
+            )
+            .layer(middleware::from_fn(move |req: Request, next: Next| {
+                request_timeout(timeout, req, next)
+            }));
+
+        let request = Request::get("/slow")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let response = tower::ServiceExt::oneshot(router, request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+    }
+
+    #[test]
+    fn if_none_match_compares_weakly() {
+        let etag = "\"tasks-2a\"";
+        let mut headers = HeaderMap::new();
+        assert!(!if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, "\"tasks-29\"".parse().unwrap());
+        assert!(!if_none_match(&headers, etag));
+
+        headers.insert(
+            header::IF_NONE_MATCH,
+            "\"tasks-29\", W/\"tasks-2a\"".parse().unwrap(),
+        );
+        assert!(if_none_match(&headers, etag));
+
+        headers.insert(header::IF_NONE_MATCH, "*".parse().unwrap());
+        assert!(if_none_match(&headers, etag));
+    }
+
+    #[tokio::test]
+    async fn task_status_long_polls() {
+        let router = HttpApi::new(Arc::new(crate::testing::RunningTask)).router();
+        let get = |uri: &str| {
+            let request = Request::get(uri).body(axum::body::Body::empty()).unwrap();
+            tower::ServiceExt::oneshot(router.clone(), request)
+        };
+        let info = |response: Response| async {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: GetTaskStatusResponse = serde_json::from_slice(&body).unwrap();
+            body.info.unwrap()
+        };
+
+        let response = get("/api/v1/tasks/t-1?wait_for=change&since=pending")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(info(response).await.status, TaskStatus::Running);
+
+        // Still running when the wait times out: the current info is returned.
+        let response = get("/api/v1/tasks/t-1?wait_for=change&timeout=10ms")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        assert_eq!(info(response).await.status, TaskStatus::Running);
+        let response = get("/api/v1/tasks/t-1?wait_for=terminal").await.unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let response = get("/api/v1/tasks/t-2?wait_for=change").await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = get("/api/v1/tasks/t-1?wait_for=terminal&since=running")
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = get("/api/v1/tasks/t-1?timeout=1s").await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn submit_waits_for_instantly_completing_task() {
+        use solti_core::{AgentControlRunner, RunnerRouter, SupervisorApi};
+        use solti_model::{
+            AdmissionStrategy, AgentAction, BackoffStrategy, JitterStrategy, RunnerLabels, TaskKind,
+        };
+
+        let mut runners = RunnerRouter::new();
+        runners.register(Arc::new(
+            AgentControlRunner::new().with_action(AgentAction::CollectGarbage, || Ok(())),
+        ));
+        let supervisor = SupervisorApi::new(
+            taskvisor::SupervisorConfig::default(),
+            taskvisor::ControllerConfig::default(),
+            Vec::new(),
+            runners,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+        let router = HttpApi::new(Arc::new(crate::SupervisorApiAdapter::new(Arc::new(
+            supervisor,
+        ))))
+        .router();
+
+        let spec = CreateSpec {
+            slot: "instant".into(),
+            kind: TaskKind::AgentControl {
+                action: AgentAction::CollectGarbage,
+            },
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+        let body = serde_json::to_vec(&SubmitTaskRequest { spec }).unwrap();
+        for _ in 0..20 {
+            let request = Request::post("/api/v1/tasks?wait=true&wait_timeout=5s")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(body.clone()))
+                .unwrap();
+            let response = tower::ServiceExt::oneshot(router.clone(), request)
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            let body: SubmitTaskResponse = serde_json::from_slice(&body).unwrap();
+            assert_eq!(body.info.unwrap().status, TaskStatus::Succeeded);
+        }
+    }
+
+    #[test]
+    fn parses_wait_timeouts() {
+        assert_eq!(parse_timeout_ms("wait_timeout", "30s").unwrap(), 30_000);
+        assert_eq!(parse_timeout_ms("wait_timeout", "1500ms").unwrap(), 1_500);
+        assert_eq!(parse_timeout_ms("wait_timeout", "2m").unwrap(), 120_000);
+        assert_eq!(parse_timeout_ms("wait_timeout", "10").unwrap(), 10_000);
+        assert!(parse_timeout_ms("wait_timeout", "soon").is_err());
+        assert!(parse_timeout_ms("wait_timeout", "-1s").is_err());
+    }
 }
@@ -1,11 +1,11 @@
 mod error;
-pub use error::ApiError;
+pub use error::{ApiError, PROBLEM_CONTENT_TYPE, Problem};
 
 mod handler;
 pub use handler::ApiHandler;
 
 mod adapter;
-pub use adapter::SupervisorApiAdapter;
+pub use adapter::{LogLevelGetFn, LogLevelSetFn, ReloadFn, SupervisorApiAdapter};
 
 #[cfg(feature = "grpc")]
 mod proto_api {
@@ -18,8 +18,11 @@ mod convert;
 #[cfg(feature = "grpc")]
 mod grpc;
 
+#[cfg(all(test, any(feature = "http", feature = "grpc")))]
+mod testing;
+
 #[cfg(feature = "grpc")]
-pub use grpc::SoltiApiService;
+pub use grpc::{DEFAULT_MAX_DECODING_MESSAGE_SIZE, GrpcInterceptFn, SoltiApiService};
 
 #[cfg(feature = "grpc")]
 pub use proto_api::solti_api_server::SoltiApiServer;
@@ -31,7 +34,31 @@ pub use tonic;
 mod http;
 
 #[cfg(feature = "http")]
-pub use http::HttpApi;
+pub use http::{DEFAULT_BODY_LIMIT, DEFAULT_REQUEST_TIMEOUT, HttpApi};
 
 #[cfg(feature = "http")]
 pub use axum;
+
+#[cfg(feature = "http")]
+mod server;
+
+#[cfg(feature = "http")]
+pub use server::{ApiServer, DEFAULT_GRACE_PERIOD, MetricsFn, ServerError, ShutdownHandle};
+
+#[cfg(feature = "tls")]
+mod tls;
+
+#[cfg(feature = "tls")]
+pub use tls::ServerTlsConfig;
+
+#[cfg(all(feature = "http", feature = "grpc"))]
+mod mux;
+
+#[cfg(all(feature = "http", feature = "grpc"))]
+pub use mux::{Multiplexed, is_grpc, multiplex};
+
+#[cfg(all(unix, any(feature = "http", feature = "grpc")))]
+mod unix;
+
+#[cfg(all(unix, any(feature = "http", feature = "grpc")))]
+pub use unix::{DEFAULT_SOCKET_MODE, bind_unix};
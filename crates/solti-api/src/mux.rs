@@ -0,0 +1,160 @@
+//! Serving HTTP and gRPC on a single listener.
+
+use std::{
+    convert::Infallible,
+    future::{Future, poll_fn},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use axum::{
+    BoxError, Router,
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use tower_service::Service;
+
+/// Whether `request` is a gRPC call (`content-type: application/grpc[+proto|+json|...]`).
+pub fn is_grpc<B>(request: &axum::http::Request<B>) -> bool {
+    request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|ct| ct.starts_with("application/grpc"))
+}
+
+/// Router serving the HTTP API and a gRPC service on the same listener.
+///
+/// Requests are routed by content type: gRPC calls go to `grpc`, everything else to
+/// `http`. `grpc` is typically `service.into_server()`; use tonic's `Routes`
+/// to expose more services (health, reflection) next to it.
+///
+/// gRPC needs HTTP/2; `axum::serve` accepts both HTTP/1.1 and cleartext HTTP/2
+/// connections, so the result can be served directly:
+///
+/// ```rust,ignore
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+/// let http = HttpApi::new(handler.clone()).router();
+/// let app = solti_api::multiplex(http, SoltiApiService::new(handler).into_server());
+/// axum::serve(listener, app).await?;
+/// ```
+pub fn multiplex<G, B>(http: Router, grpc: G) -> Router
+where
+    G: Service<Request, Response = axum::http::Response<B>, Error = Infallible>
+        + Clone
+        + Send
+        + Sync
+        + 'static,
+    G::Future: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    Router::new().fallback_service(Multiplexed { http, grpc })
+}
+
+/// Service dispatching between the HTTP router and a gRPC service; see [`multiplex`].
+#[derive(Clone)]
+pub struct Multiplexed<G> {
+    http: Router,
+    grpc: G,
+}
+
+impl<G, B> Service<Request> for Multiplexed<G>
+where
+    G: Service<Request, Response = axum::http::Response<B>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    G::Future: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Both inner services are cloned per call and polled there.
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        if is_grpc(&request) {
+            let mut grpc = self.grpc.clone();
+            Box::pin(async move {
+                poll_fn(|cx| grpc.poll_ready(cx)).await?;
+                let response = grpc.call(request).await?;
+                Ok(response.map(Body::new).into_response())
+            })
+        } else {
+            let mut http = self.http.clone();
+            Box::pin(async move { http.call(request).await })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+
+    use super::*;
+    use crate::{
+        HttpApi, SoltiApiService,
+        proto_api::{GetMaintenanceRequest, solti_api_client::SoltiApiClient},
+        testing::Maintenance,
+    };
+
+    #[test]
+    fn detects_grpc_content_types() {
+        let request = |ct: &str| {
+            axum::http::Request::builder()
+                .header(header::CONTENT_TYPE, ct)
+                .body(())
+                .unwrap()
+        };
+        assert!(is_grpc(&request("application/grpc")));
+        assert!(is_grpc(&request("application/grpc+proto")));
+        assert!(!is_grpc(&request("application/json")));
+        assert!(!is_grpc(&axum::http::Request::new(())));
+    }
+
+    #[tokio::test]
+    async fn serves_both_transports_on_one_port() {
+        let handler = Arc::new(Maintenance);
+        let app = multiplex(
+            HttpApi::new(handler.clone()).router(),
+            SoltiApiService::new(handler).into_server(),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let mut client = SoltiApiClient::connect(format!("http://{addr}"))
+            .await
+            .unwrap();
+        let reply = client
+            .get_maintenance(GetMaintenanceRequest {})
+            .await
+            .unwrap();
+        assert!(reply.into_inner().enabled);
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                b"GET /api/v1/admin/maintenance HTTP/1.1\r\nHost: agent\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""enabled":true"#), "{response}");
+    }
+}
@@ -0,0 +1,465 @@
+//! Ready-to-run API server.
+//!
+//! [`ApiServer`] binds the configured listeners, mounts the HTTP routes (plus `/metrics`
+//! and host routes) and the gRPC service, optionally terminates TLS, and shuts everything
+//! down gracefully on SIGINT/SIGTERM or a [`ShutdownHandle`] call.
+
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    Router,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use thiserror::Error;
+use tokio::{net::TcpListener, task::JoinSet};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::{handler::ApiHandler, http::HttpApi};
+
+/// Default time in-flight requests get to finish after shutdown was requested.
+pub const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(30);
+
+/// Renders metrics for a scrape.
+///
+/// Receives the request's `Accept` header (empty when absent) and returns the
+/// content type and body, or an error message.
+pub type MetricsFn = Arc<dyn Fn(&str) -> Result<(String, Vec<u8>), String> + Send + Sync>;
+
+/// Errors returned by [`ApiServer::run`].
+#[derive(Debug, Error)]
+pub enum ServerError {
+    #[error("no listener configured")]
+    NoListener,
+
+    #[error("failed to bind {addr}: {source}")]
+    Bind {
+        addr: SocketAddr,
+        source: std::io::Error,
+    },
+
+    #[error("tls: {0}")]
+    Tls(String),
+
+    #[error("server error: {0}")]
+    Serve(#[from] std::io::Error),
+}
+
+/// Requests shutdown of a running [`ApiServer`].
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownHandle(CancellationToken);
+
+impl ShutdownHandle {
+    /// Stop accepting connections and let in-flight requests finish.
+    pub fn shutdown(&self) {
+        self.0.cancel();
+    }
+
+    /// Whether shutdown was requested.
+    pub fn is_shutdown(&self) -> bool {
+        self.0.is_cancelled()
+    }
+
+    /// Wait until shutdown is requested.
+    pub async fn wait(&self) {
+        self.0.cancelled().await
+    }
+}
+
+/// API server builder.
+///
+/// Serves the HTTP API on the HTTP address and the gRPC service on the gRPC address;
+/// when both addresses are equal, a single listener serves both (see [`multiplex`](crate::multiplex)).
+///
+/// ```rust,ignore
+/// ApiServer::new(handler)
+///     .with_http_addr("0.0.0.0:8080".parse()?)
+///     .with_metrics(move |accept| render(&metrics, accept))
+///     .run()
+///     .await?;
+/// ```
+pub struct ApiServer<H> {
+    handler: Arc<H>,
+    http_addr: Option<SocketAddr>,
+    #[cfg(feature = "grpc")]
+    grpc_addr: Option<SocketAddr>,
+    #[cfg(feature = "grpc")]
+    interceptor: Option<crate::grpc::GrpcInterceptFn>,
+    #[cfg(feature = "grpc")]
+    grpc_max_message_size: usize,
+    #[cfg(feature = "tls")]
+    tls: Option<crate::tls::ServerTlsConfig>,
+    routes: Router,
+    metrics: Option<MetricsFn>,
+    compression: bool,
+    tracing: bool,
+    body_limit: usize,
+    request_timeout: Option<Duration>,
+    signals: bool,
+    grace_period: Duration,
+    shutdown: ShutdownHandle,
+}
+
+impl<H> ApiServer<H>
+where
+    H: ApiHandler,
+{
+    /// Create a server for `handler` without listeners.
+    pub fn new(handler: Arc<H>) -> Self {
+        Self {
+            handler,
+            http_addr: None,
+            #[cfg(feature = "grpc")]
+            grpc_addr: None,
+            #[cfg(feature = "grpc")]
+            interceptor: None,
+            #[cfg(feature = "grpc")]
+            grpc_max_message_size: crate::grpc::DEFAULT_MAX_DECODING_MESSAGE_SIZE,
+            #[cfg(feature = "tls")]
+            tls: None,
+            routes: Router::new(),
+            metrics: None,
+            compression: true,
+            tracing: true,
+            body_limit: crate::http::DEFAULT_BODY_LIMIT,
+            request_timeout: Some(crate::http::DEFAULT_REQUEST_TIMEOUT),
+            signals: true,
+            grace_period: DEFAULT_GRACE_PERIOD,
+            shutdown: ShutdownHandle::default(),
+        }
+    }
+
+    /// Serve the HTTP API on `addr`.
+    pub fn with_http_addr(mut self, addr: SocketAddr) -> Self {
+        self.http_addr = Some(addr);
+        self
+    }
+
+    /// Serve the gRPC service on `addr`.
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_addr(mut self, addr: SocketAddr) -> Self {
+        self.grpc_addr = Some(addr);
+        self
+    }
+
+    /// Run `interceptor` on every gRPC request (see [`SoltiApiService::with_interceptor`](crate::SoltiApiService::with_interceptor)).
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&tonic::metadata::MetadataMap) -> Result<(), tonic::Status> + Send + Sync + 'static,
+    {
+        self.interceptor = Some(Arc::new(interceptor));
+        self
+    }
+
+    /// Maximum size of a gRPC request message
+    /// (see [`SoltiApiService::with_max_decoding_message_size`](crate::SoltiApiService::with_max_decoding_message_size)).
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_max_message_size(mut self, bytes: usize) -> Self {
+        self.grpc_max_message_size = bytes;
+        self
+    }
+
+    /// Terminate TLS on every listener.
+    #[cfg(feature = "tls")]
+    pub fn with_tls(mut self, tls: crate::tls::ServerTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Merge host routes into the HTTP API.
+    pub fn with_routes(mut self, routes: Router) -> Self {
+        self.routes = self.routes.merge(routes);
+        self
+    }
+
+    /// Expose metrics at `GET /metrics` on the HTTP listener.
+    pub fn with_metrics<F>(mut self, render: F) -> Self
+    where
+        F: Fn(&str) -> Result<(String, Vec<u8>), String> + Send + Sync + 'static,
+    {
+        self.metrics = Some(Arc::new(render));
+        self
+    }
+
+    /// Compress HTTP API responses (enabled by default, see [`HttpApi::with_compression`]).
+    pub fn with_compression(mut self, enabled: bool) -> Self {
+        self.compression = enabled;
+        self
+    }
+
+    /// Trace HTTP requests with request ids (enabled by default, see [`HttpApi::with_tracing`]).
+    pub fn with_tracing(mut self, enabled: bool) -> Self {
+        self.tracing = enabled;
+        self
+    }
+
+    /// Maximum size of an HTTP request body (see [`HttpApi::with_body_limit`]).
+    pub fn with_body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = bytes;
+        self
+    }
+
+    /// Upper bound for handling an HTTP request (see [`HttpApi::with_request_timeout`]).
+    pub fn with_request_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+
+    /// Shut down on SIGINT / SIGTERM (enabled by default).
+    ///
+    /// Disable when the host handles signals itself and calls [`ShutdownHandle::shutdown`].
+    pub fn with_signal_handling(mut self, enabled: bool) -> Self {
+        self.signals = enabled;
+        self
+    }
+
+    /// Time in-flight requests get to finish after shutdown was requested
+    /// (default [`DEFAULT_GRACE_PERIOD`]); remaining connections are then dropped.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Handle stopping the server once [`run`](Self::run) is called.
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Bind all listeners and serve until shutdown.
+    pub async fn run(self) -> Result<(), ServerError> {
+        let shutdown = self.shutdown.clone();
+        let grace_period = self.grace_period;
+        let signals = self.signals;
+
+        #[cfg(feature = "tls")]
+        let tls = self.tls.as_ref().map(|tls| tls.load()).transpose()?;
+
+        let apps = self.apps();
+        if apps.is_empty() {
+            return Err(ServerError::NoListener);
+        }
+
+        let mut servers = JoinSet::new();
+        for (addr, app) in apps {
+            let listener = TcpListener::bind(addr)
+                .await
+                .map_err(|source| ServerError::Bind { addr, source })?;
+            let local = listener.local_addr()?;
+            let token = shutdown.0.clone();
+
+            #[cfg(feature = "tls")]
+            if let Some(config) = &tls {
+                let listener = crate::tls::TlsListener::new(listener, config.clone())?;
+                info!(addr = %local, "api server listening (tls)");
+                servers.spawn(async move {
+                    axum::serve(listener, app)
+                        .with_graceful_shutdown(token.cancelled_owned())
+                        .await
+                });
+                continue;
+            }
+
+            info!(addr = %local, "api server listening");
+            servers.spawn(async move {
+                axum::serve(listener, app)
+                    .with_graceful_shutdown(token.cancelled_owned())
+                    .await
+            });
+        }
+
+        if signals {
+            let token = shutdown.0.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = shutdown_signal() => {
+                        info!("api server: shutdown signal received");
+                        token.cancel();
+                    }
+                    _ = token.cancelled() => {}
+                }
+            });
+        }
+
+        let grace = async {
+            shutdown.wait().await;
+            tokio::time::sleep(grace_period).await;
+        };
+        tokio::pin!(grace);
+
+        loop {
+            tokio::select! {
+                joined = servers.join_next() => match joined {
+                    None => break,
+                    Some(Ok(Ok(()))) => {}
+                    Some(Ok(Err(e))) => {
+                        shutdown.shutdown();
+                        return Err(ServerError::Serve(e));
+                    }
+                    Some(Err(e)) => {
+                        shutdown.shutdown();
+                        return Err(ServerError::Serve(std::io::Error::other(e)));
+                    }
+                },
+                _ = &mut grace => {
+                    warn!(
+                        remaining = servers.len(),
+                        "api server: grace period elapsed, dropping open connections"
+                    );
+                    break;
+                }
+            }
+        }
+
+        info!("api server stopped");
+        Ok(())
+    }
+
+    /// Routers per listen address.
+    fn apps(&self) -> Vec<(SocketAddr, Router)> {
+        let mut apps = Vec::new();
+
+        #[cfg(feature = "grpc")]
+        let grpc = {
+            let mut service = crate::grpc::SoltiApiService::new(self.handler.clone())
+                .with_max_decoding_message_size(self.grpc_max_message_size);
+            if let Some(interceptor) = self.interceptor.clone() {
+                service = service.with_intercept_fn(interceptor);
+            }
+            service.into_server()
+        };
+
+        #[cfg(feature = "grpc")]
+        if let Some(addr) = self.grpc_addr {
+            if self.http_addr == Some(addr) {
+                apps.push((addr, crate::mux::multiplex(self.http_router(), grpc)));
+                return apps;
+            }
+            apps.push((addr, Router::new().fallback_service(grpc)));
+        }
+
+        if let Some(addr) = self.http_addr {
+            apps.push((addr, self.http_router()));
+        }
+        apps
+    }
+
+    fn http_router(&self) -> Router {
+        let mut router = HttpApi::new(self.handler.clone())
+            .with_compression(self.compression)
+            .with_tracing(self.tracing)
+            .with_body_limit(self.body_limit)
+            .with_request_timeout(self.request_timeout)
+            .router()
+            .merge(self.routes.clone());
+
+        if let Some(render) = self.metrics.clone() {
+            router = router.route(
+                "/metrics",
+                get(move |headers: HeaderMap| async move { render_metrics(&render, &headers) }),
+            );
+        }
+        router
+    }
+}
+
+fn render_metrics(render: &MetricsFn, headers: &HeaderMap) -> Response {
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    match render(accept) {
+        Ok((content_type, body)) => ([(header::CONTENT_TYPE, content_type)], body).into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Resolves on SIGINT, or SIGTERM on unix.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if tokio::signal::ctrl_c().await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(_) => std::future::pending::<()>().await,
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    use super::*;
+    use crate::testing::Maintenance;
+
+    fn free_addr() -> SocketAddr {
+        std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    async fn get(addr: SocketAddr, path: &str) -> String {
+        let mut stream = loop {
+            match TcpStream::connect(addr).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+        let request = format!("GET {path} HTTP/1.1\r\nHost: agent\r\nConnection: close\r\n\r\n");
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn serves_api_and_metrics_until_shutdown() {
+        let addr = free_addr();
+        let server = ApiServer::new(Arc::new(Maintenance))
+            .with_http_addr(addr)
+            .with_metrics(|accept| Ok(("text/plain".into(), format!("accept={accept}").into())))
+            .with_signal_handling(false);
+        let shutdown = server.shutdown_handle();
+        let running = tokio::spawn(server.run());
+
+        let response = get(addr, "/api/v1/admin/maintenance").await;
+        assert!(response.contains(r#""enabled":true"#), "{response}");
+        let response = get(addr, "/metrics").await;
+        assert!(response.ends_with("accept="), "{response}");
+
+        shutdown.shutdown();
+        tokio::time::timeout(Duration::from_secs(5), running)
+            .await
+            .expect("server did not stop")
+            .unwrap()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn requires_a_listener() {
+        let server = ApiServer::new(Arc::new(Maintenance)).with_signal_handling(false);
+        assert!(matches!(server.run().await, Err(ServerError::NoListener)));
+    }
+}
@@ -0,0 +1,119 @@
+//! Test doubles shared by the transport tests.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use solti_model::{CreateSpec, GroupInfo, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus};
+
+use crate::{error::ApiError, handler::ApiHandler};
+
+/// Handler answering only maintenance queries.
+pub(crate) struct Maintenance;
+
+#[async_trait]
+impl ApiHandler for Maintenance {
+    async fn submit_task(&self, _: CreateSpec) -> Result<TaskId, ApiError> {
+        unimplemented!()
+    }
+    async fn get_task_status(&self, _: &TaskId) -> Result<Option<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn list_all_tasks(&self) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn list_tasks_by_slot(&self, _: &str) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn list_tasks_by_status(&self, _: TaskStatus) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn query_tasks(&self, _: TaskQuery) -> Result<TaskPage<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn cancel_task(&self, _: &TaskId) -> Result<(), ApiError> {
+        unimplemented!()
+    }
+    async fn get_group_status(&self, _: &str) -> Result<Option<GroupInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn cancel_group(&self, _: &str) -> Result<usize, ApiError> {
+        unimplemented!()
+    }
+    async fn wait_group(&self, _: &str, _: Duration) -> Result<GroupInfo, ApiError> {
+        unimplemented!()
+    }
+    async fn get_maintenance(&self) -> Result<bool, ApiError> {
+        Ok(true)
+    }
+    async fn set_maintenance(&self, _: bool) -> Result<bool, ApiError> {
+        unimplemented!()
+    }
+}
+
+/// Handler tracking a single task `t-1` that stays running.
+#[cfg(feature = "http")]
+pub(crate) struct RunningTask;
+
+#[cfg(feature = "http")]
+impl RunningTask {
+    fn info() -> TaskInfo {
+        let mut info = TaskInfo::pending(TaskId::from("t-1"), "slot".into());
+        info.status = TaskStatus::Running;
+        info
+    }
+}
+
+#[cfg(feature = "http")]
+#[async_trait]
+impl ApiHandler for RunningTask {
+    async fn submit_task(&self, _: CreateSpec) -> Result<TaskId, ApiError> {
+        unimplemented!()
+    }
+    async fn get_task_status(&self, id: &TaskId) -> Result<Option<TaskInfo>, ApiError> {
+        Ok((id.as_str() == "t-1").then(Self::info))
+    }
+    async fn list_all_tasks(&self) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn list_tasks_by_slot(&self, _: &str) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn list_tasks_by_status(&self, _: TaskStatus) -> Result<Vec<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn query_tasks(&self, _: TaskQuery) -> Result<TaskPage<TaskInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn cancel_task(&self, _: &TaskId) -> Result<(), ApiError> {
+        unimplemented!()
+    }
+    async fn wait_task(&self, id: &TaskId, _: Duration) -> Result<TaskInfo, ApiError> {
+        Err(ApiError::Timeout(format!("task {id}")))
+    }
+    async fn wait_task_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        _: Duration,
+    ) -> Result<TaskInfo, ApiError> {
+        match from {
+            TaskStatus::Running => Err(ApiError::Timeout(format!("task {id}"))),
+            _ => Ok(Self::info()),
+        }
+    }
+    async fn get_group_status(&self, _: &str) -> Result<Option<GroupInfo>, ApiError> {
+        unimplemented!()
+    }
+    async fn cancel_group(&self, _: &str) -> Result<usize, ApiError> {
+        unimplemented!()
+    }
+    async fn wait_group(&self, _: &str, _: Duration) -> Result<GroupInfo, ApiError> {
+        unimplemented!()
+    }
+    async fn get_maintenance(&self) -> Result<bool, ApiError> {
+        unimplemented!()
+    }
+    async fn set_maintenance(&self, _: bool) -> Result<bool, ApiError> {
+        unimplemented!()
+    }
+}
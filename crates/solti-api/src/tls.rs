@@ -0,0 +1,209 @@
+//! TLS termination for [`ApiServer`](crate::ApiServer) listeners.
+
+use std::{
+    io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use rustls::{
+    RootCertStore, ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+    server::WebPkiClientVerifier,
+};
+use tokio::{net::TcpListener, sync::mpsc};
+use tokio_rustls::{TlsAcceptor, server::TlsStream};
+use tracing::debug;
+
+use crate::server::ServerError;
+
+const ALPN_H2: &[u8] = b"h2";
+const ALPN_HTTP11: &[u8] = b"http/1.1";
+
+/// Upper bound for a client to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Connections that finished the handshake but were not accepted yet.
+const ACCEPT_BACKLOG: usize = 64;
+
+/// Server TLS / mTLS settings.
+///
+/// - `cert` / `key` — PEM certificate chain and private key of the agent.
+/// - `client_ca` — PEM bundle verifying client certificates; when set, clients
+///   without a certificate signed by it are rejected (mTLS).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerTlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    pub client_ca: Option<PathBuf>,
+}
+
+impl ServerTlsConfig {
+    /// TLS with the given certificate chain and key, without client authentication.
+    pub fn new(cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        Self {
+            cert: cert.into(),
+            key: key.into(),
+            client_ca: None,
+        }
+    }
+
+    /// Require client certificates signed by the CA bundle at `path`.
+    pub fn with_client_ca(mut self, path: impl Into<PathBuf>) -> Self {
+        self.client_ca = Some(path.into());
+        self
+    }
+
+    /// Load the referenced files into a rustls config advertising `h2` and `http/1.1`.
+    pub fn load(&self) -> Result<Arc<ServerConfig>, ServerError> {
+        let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| ServerError::Tls(e.to_string()))?;
+
+        let builder = match &self.client_ca {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in read_certs(path)? {
+                    roots.add(cert).map_err(|e| {
+                        ServerError::Tls(format!(
+                            "invalid CA certificate in {}: {e}",
+                            path.display()
+                        ))
+                    })?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .map_err(|e| ServerError::Tls(e.to_string()))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let chain = read_certs(&self.cert)?;
+        let bytes = read(&self.key)?;
+        let key = PrivateKeyDer::from_pem_slice(&bytes).map_err(|e| {
+            ServerError::Tls(format!("invalid private key {}: {e}", self.key.display()))
+        })?;
+        let mut config = builder
+            .with_single_cert(chain, key)
+            .map_err(|e| ServerError::Tls(format!("invalid server certificate: {e}")))?;
+        config.alpn_protocols = vec![ALPN_H2.to_vec(), ALPN_HTTP11.to_vec()];
+        Ok(Arc::new(config))
+    }
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, ServerError> {
+    std::fs::read(path).map_err(|e| ServerError::Tls(format!("{}: {e}", path.display())))
+}
+
+fn read_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, ServerError> {
+    let bytes = read(path)?;
+    let certs = CertificateDer::pem_slice_iter(&bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| ServerError::Tls(format!("invalid PEM in {}: {e}", path.display())))?;
+    if certs.is_empty() {
+        return Err(ServerError::Tls(format!(
+            "no certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+/// Listener yielding TLS streams.
+///
+/// Handshakes run in their own tasks so a slow client does not hold up
+/// other connections; failed handshakes are logged and dropped.
+pub(crate) struct TlsListener {
+    ready: mpsc::Receiver<(TlsStream<tokio::net::TcpStream>, SocketAddr)>,
+    local_addr: SocketAddr,
+}
+
+impl TlsListener {
+    pub(crate) fn new(listener: TcpListener, config: Arc<ServerConfig>) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let acceptor = TlsAcceptor::from(config);
+        let (tx, ready) = mpsc::channel(ACCEPT_BACKLOG);
+
+        tokio::spawn(async move {
+            loop {
+                let (stream, addr) = tokio::select! {
+                    _ = tx.closed() => return,
+                    accepted = listener.accept() => match accepted {
+                        Ok(accepted) => accepted,
+                        Err(e) => {
+                            debug!(error = %e, "tls: accept failed");
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            continue;
+                        }
+                    },
+                };
+
+                let acceptor = acceptor.clone();
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls)) => {
+                            let _ = tx.send((tls, addr)).await;
+                        }
+                        Ok(Err(e)) => debug!(%addr, error = %e, "tls: handshake failed"),
+                        Err(_) => debug!(%addr, "tls: handshake timed out"),
+                    }
+                });
+            }
+        });
+
+        Ok(Self { ready, local_addr })
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<tokio::net::TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.ready.recv().await {
+            Some(conn) => conn,
+            // The acceptor task only exits once this receiver is gone.
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_reports_missing_files() {
+        let cfg = ServerTlsConfig::new("/nonexistent/cert.pem", "/nonexistent/key.pem");
+        match cfg.load() {
+            Err(ServerError::Tls(msg)) => assert!(msg.contains("/nonexistent/cert.pem")),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn load_rejects_empty_client_ca() {
+        let dir = std::env::temp_dir().join(format!("solti-api-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ca = dir.join("empty.pem");
+        std::fs::write(&ca, b"").unwrap();
+
+        let cfg =
+            ServerTlsConfig::new(dir.join("cert.pem"), dir.join("key.pem")).with_client_ca(&ca);
+        match cfg.load() {
+            Err(ServerError::Tls(msg)) => assert!(msg.contains("no certificates found")),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
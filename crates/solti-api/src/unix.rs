@@ -0,0 +1,71 @@
+use std::{
+    fs, io,
+    os::unix::fs::{FileTypeExt, PermissionsExt},
+    path::Path,
+};
+
+use tokio::net::UnixListener;
+
+/// Default permissions of API sockets: read/write for owner and group.
+pub const DEFAULT_SOCKET_MODE: u32 = 0o660;
+
+/// Bind a unix domain socket at `path` and set its permission bits to `mode`.
+///
+/// A socket file left behind by a previous run is removed first; any other
+/// file at `path` is an error. Connecting to a unix socket requires write
+/// permission, so `mode` decides which local users may call the API.
+///
+/// Must be called from within a tokio runtime.
+pub fn bind_unix(path: impl AsRef<Path>, mode: u32) -> io::Result<UnixListener> {
+    let path = path.as_ref();
+    match fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => fs::remove_file(path)?,
+        Ok(_) => {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let listener = UnixListener::bind(path)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode))?;
+    Ok(listener)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("solti-api-{}-{name}.sock", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn binds_with_mode_and_replaces_stale_socket() {
+        let path = socket_path("bind");
+        let first = bind_unix(&path, 0o600).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        // The previous listener's file is stale once it is dropped.
+        drop(first);
+        let _second = bind_unix(&path, DEFAULT_SOCKET_MODE).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, DEFAULT_SOCKET_MODE);
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_to_replace_regular_files() {
+        let path = socket_path("file");
+        fs::write(&path, b"data").unwrap();
+        let err = bind_unix(&path, DEFAULT_SOCKET_MODE).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(&path).unwrap(), b"data");
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+//! Capture build metadata for [`solti_core::build_info`].
+//!
+//! - `SOLTI_GIT_SHA`: taken from the environment if set, otherwise from `git rev-parse`;
+//!   omitted when building outside a git checkout.
+//! - `SOLTI_BUILD_TIMESTAMP`: unix seconds, from `SOURCE_DATE_EPOCH` for reproducible
+//!   builds, otherwise the time the build script ran.
+use std::{
+    env,
+    path::Path,
+    process::Command,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=SOLTI_GIT_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+
+    let sha = env::var("SOLTI_GIT_SHA")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(git_sha);
+    if let Some(sha) = sha {
+        println!("cargo:rustc-env=SOLTI_GIT_SHA={sha}");
+    }
+
+    let timestamp = env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+    println!("cargo:rustc-env=SOLTI_BUILD_TIMESTAMP={timestamp}");
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let out = String::from_utf8(output.stdout).ok()?;
+    let out = out.trim();
+    (!out.is_empty()).then(|| out.to_string())
+}
+
+fn git_sha() -> Option<String> {
+    let sha = git(&["rev-parse", "--short=12", "HEAD"])?;
+
+    // Rebuild when HEAD moves: HEAD itself changes on checkout, the branch ref on commit.
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        let git_dir = Path::new(&git_dir);
+        println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+        if let Some(head_ref) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!(
+                "cargo:rerun-if-changed={}",
+                git_dir.join(head_ref).display()
+            );
+        }
+    }
+    Some(sha)
+}
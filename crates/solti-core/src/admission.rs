@@ -0,0 +1,259 @@
+//! Pluggable admission control for submitted specs.
+//!
+//! An [`AdmissionPolicy`] runs in [`crate::SupervisorApi::submit_as`] before the spec is
+//! routed to a runner. It sees the spec, the submitting [`Caller`] and the current task
+//! state, and may rewrite the spec (inject labels, clamp timeouts) or reject it.
+//! Policies compose with [`AdmissionChain`]; the default is [`AllowAll`].
+use std::{fmt, sync::Arc};
+
+use solti_model::{CreateSpec, TaskInfo, TaskStatus};
+
+use crate::state::TaskState;
+
+/// Identity of whoever submitted a spec.
+///
+/// Filled in by the transport (e.g. from a client certificate or token);
+/// [`Caller::anonymous`] when unknown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Caller {
+    pub identity: Option<String>,
+}
+
+impl Caller {
+    /// Caller with a known identity.
+    pub fn new(identity: impl Into<String>) -> Self {
+        Self {
+            identity: Some(identity.into()),
+        }
+    }
+
+    /// Caller without identity.
+    pub fn anonymous() -> Self {
+        Self::default()
+    }
+}
+
+/// Inputs available to an [`AdmissionPolicy`] besides the spec itself.
+pub struct AdmissionContext<'a> {
+    caller: &'a Caller,
+    namespace: Option<String>,
+    state: &'a TaskState,
+    follow_up_of: Option<&'a TaskInfo>,
+}
+
+impl<'a> AdmissionContext<'a> {
+    pub(crate) fn new(caller: &'a Caller, spec: &CreateSpec, state: &'a TaskState) -> Self {
+        Self {
+            caller,
+            namespace: spec.namespace.clone(),
+            state,
+            follow_up_of: None,
+        }
+    }
+
+    /// Mark the spec as a follow-up submitted when `parent` terminated.
+    pub(crate) fn with_follow_up_of(mut self, parent: &'a TaskInfo) -> Self {
+        self.follow_up_of = Some(parent);
+        self
+    }
+
+    /// Who submitted the spec.
+    pub fn caller(&self) -> &Caller {
+        self.caller
+    }
+
+    /// Namespace of the spec as submitted, before any policy rewrote it.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Task whose termination triggered this submission, for follow-ups.
+    ///
+    /// Set only when the supervisor itself submits a [`solti_model::FollowUp`]: the follow-up
+    /// spec was part of the parent spec, which went through admission when it was submitted.
+    pub fn follow_up_of(&self) -> Option<&TaskInfo> {
+        self.follow_up_of
+    }
+
+    /// Tasks currently known in `slot`.
+    pub fn tasks_in_slot(&self, slot: &str) -> Vec<TaskInfo> {
+        self.state.list_by_slot(slot)
+    }
+
+    /// Pending and running tasks.
+    pub fn active_tasks(&self) -> Vec<TaskInfo> {
+        self.state
+            .list_all()
+            .into_iter()
+            .filter(|t| matches!(t.status, TaskStatus::Pending | TaskStatus::Running))
+            .collect()
+    }
+}
+
+/// Decides whether a spec may be submitted.
+///
+/// Returning `Err(reason)` rejects the submission with [`crate::CoreError::AdmissionDenied`].
+/// Policies run synchronously on the submit path and should not block.
+///
+/// Closures with the matching signature implement the trait:
+///
+/// ```rust
+/// # use solti_core::{AdmissionChain, AdmissionContext};
+/// # use solti_model::CreateSpec;
+/// let chain = AdmissionChain::new()
+///     .with(|spec: &mut CreateSpec, ctx: &AdmissionContext<'_>| {
+///         if ctx.caller().identity.is_none() {
+///             return Err("anonymous submissions are not allowed".to_string());
+///         }
+///         spec.timeout_ms = spec.timeout_ms.min(60_000);
+///         Ok(())
+///     });
+/// ```
+pub trait AdmissionPolicy: Send + Sync {
+    /// Inspect and optionally rewrite `spec`.
+    fn admit(&self, spec: &mut CreateSpec, ctx: &AdmissionContext<'_>) -> Result<(), String>;
+}
+
+impl<F> AdmissionPolicy for F
+where
+    F: Fn(&mut CreateSpec, &AdmissionContext<'_>) -> Result<(), String> + Send + Sync,
+{
+    fn admit(&self, spec: &mut CreateSpec, ctx: &AdmissionContext<'_>) -> Result<(), String> {
+        self(spec, ctx)
+    }
+}
+
+/// Policy admitting every spec unchanged.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+impl AdmissionPolicy for AllowAll {
+    fn admit(&self, _spec: &mut CreateSpec, _ctx: &AdmissionContext<'_>) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Runs policies in order; each sees the spec as rewritten by the previous ones
+/// and the first rejection stops the chain.
+#[derive(Clone, Default)]
+pub struct AdmissionChain {
+    policies: Vec<Arc<dyn AdmissionPolicy>>,
+}
+
+impl AdmissionChain {
+    /// Empty chain (admits everything).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a policy.
+    pub fn with(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.policies.push(Arc::new(policy));
+        self
+    }
+
+    /// Number of policies in the chain.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Whether the chain has no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+}
+
+impl AdmissionPolicy for AdmissionChain {
+    fn admit(&self, spec: &mut CreateSpec, ctx: &AdmissionContext<'_>) -> Result<(), String> {
+        self.policies.iter().try_for_each(|p| p.admit(spec, ctx))
+    }
+}
+
+impl fmt::Debug for AdmissionChain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionChain")
+            .field("policies", &self.policies.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, RunnerLabels, TaskKind,
+    };
+
+    use super::*;
+
+    fn spec() -> CreateSpec {
+        CreateSpec {
+            slot: "demo".into(),
+            kind: TaskKind::None,
+            timeout_ms: 600_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+        .with_namespace("team-a")
+    }
+
+    fn clamp_timeout(spec: &mut CreateSpec, _: &AdmissionContext<'_>) -> Result<(), String> {
+        spec.timeout_ms = spec.timeout_ms.min(60_000);
+        Ok(())
+    }
+
+    fn require_identity(_: &mut CreateSpec, ctx: &AdmissionContext<'_>) -> Result<(), String> {
+        match &ctx.caller().identity {
+            Some(_) => Ok(()),
+            None => Err(format!(
+                "anonymous submission to namespace {}",
+                ctx.namespace().unwrap_or("-")
+            )),
+        }
+    }
+
+    #[test]
+    fn chain_applies_policies_in_order() {
+        let state = TaskState::new();
+        let chain = AdmissionChain::new()
+            .with(clamp_timeout)
+            .with(require_identity);
+        assert_eq!(chain.len(), 2);
+
+        let caller = Caller::new("ci");
+        let mut s = spec();
+        let ctx = AdmissionContext::new(&caller, &s, &state);
+        chain.admit(&mut s, &ctx).unwrap();
+        assert_eq!(s.timeout_ms, 60_000);
+
+        let anonymous = Caller::anonymous();
+        let mut s = spec();
+        let ctx = AdmissionContext::new(&anonymous, &s, &state);
+        let err = chain.admit(&mut s, &ctx).unwrap_err();
+        assert_eq!(err, "anonymous submission to namespace team-a");
+    }
+
+    #[test]
+    fn empty_chain_and_allow_all_admit_unchanged() {
+        let state = TaskState::new();
+        let caller = Caller::anonymous();
+        let mut s = spec();
+        let ctx = AdmissionContext::new(&caller, &s, &state);
+
+        AdmissionChain::new().admit(&mut s, &ctx).unwrap();
+        AllowAll.admit(&mut s, &ctx).unwrap();
+        assert_eq!(s.timeout_ms, 600_000);
+        assert!(ctx.active_tasks().is_empty());
+    }
+}
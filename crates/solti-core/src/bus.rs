@@ -0,0 +1,786 @@
+//! Pluggable transport for lifecycle events.
+//!
+//! Every runtime event is converted into a [`TaskEvent`] and published to the
+//! configured [`EventBus`]. Sinks (journal, webhook, Kafka, ...) implement
+//! [`EventSubscriber`] and attach via [`crate::SupervisorApi::attach_subscriber`];
+//! hosts with their own transport swap the bus with [`crate::SupervisorApi::with_event_bus`].
+
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use solti_model::{SubscriberHealth, SubscriberState, TaskEvent};
+use tokio::{
+    sync::{
+        Notify,
+        broadcast::{self, error::RecvError},
+    },
+    time::Instant,
+};
+use tracing::warn;
+
+use crate::metrics::{MetricsHandle, NoOpMetrics};
+
+/// Number of events buffered per subscriber of the default bus.
+pub const DEFAULT_EVENT_BUS_CAPACITY: usize = 1024;
+
+/// Default length of a subscriber queue (see [`SubscriberOptions`]).
+pub const DEFAULT_SUBSCRIBER_QUEUE_CAPACITY: usize = 1024;
+
+/// Default delay before a failed subscriber receives the next event.
+pub const DEFAULT_SUBSCRIBER_RESTART_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Default upper bound of the subscriber restart backoff.
+pub const DEFAULT_SUBSCRIBER_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Consumer of lifecycle events.
+#[async_trait]
+pub trait EventSubscriber: Send + Sync + 'static {
+    /// Handle a single event.
+    async fn on_event(&self, event: &TaskEvent);
+
+    /// Subscriber name used in logs.
+    fn name(&self) -> &'static str;
+}
+
+/// What happens to new events while a subscriber queue is full.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Discard the oldest queued event to make room (default).
+    DropOldest,
+    /// Wait up to the given duration for the subscriber to catch up, then drop the event.
+    ///
+    /// While waiting, further events accumulate in the bus buffer.
+    Block(Duration),
+    /// Append overflowing events as JSON lines to the given file and replay them,
+    /// in order, once the queue drains.
+    ///
+    /// The file is truncated when the subscriber is attached.
+    SpillToDisk(PathBuf),
+}
+
+impl OverflowPolicy {
+    /// Return label value for metrics.
+    pub fn as_label(&self) -> &'static str {
+        match self {
+            OverflowPolicy::DropOldest => "drop_oldest",
+            OverflowPolicy::Block(_) => "block",
+            OverflowPolicy::SpillToDisk(_) => "spill_to_disk",
+        }
+    }
+}
+
+/// Queue settings of a single subscriber.
+#[derive(Debug, Clone)]
+pub struct SubscriberOptions {
+    capacity: usize,
+    overflow: OverflowPolicy,
+    backoff: Duration,
+    max_backoff: Duration,
+    max_failures: Option<u32>,
+    event_timeout: Option<Duration>,
+}
+
+impl SubscriberOptions {
+    /// Queue up to `capacity` events before applying the overflow policy.
+    pub fn with_capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Handle a full queue according to `policy`.
+    pub fn with_overflow(mut self, policy: OverflowPolicy) -> Self {
+        self.overflow = policy;
+        self
+    }
+
+    /// Wait `initial` after a failed delivery, doubling up to `max` while failures continue.
+    pub fn with_restart_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff = initial;
+        self.max_backoff = max.max(initial);
+        self
+    }
+
+    /// Disable the subscriber after `failures` consecutive failed deliveries.
+    ///
+    /// By default a failing subscriber is restarted indefinitely.
+    pub fn with_max_failures(mut self, failures: u32) -> Self {
+        self.max_failures = Some(failures.max(1));
+        self
+    }
+
+    /// Abort deliveries running longer than `timeout` and count them as failures.
+    pub fn with_event_timeout(mut self, timeout: Duration) -> Self {
+        self.event_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum number of queued events.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Policy applied while the queue is full.
+    pub fn overflow(&self) -> &OverflowPolicy {
+        &self.overflow
+    }
+}
+
+impl Default for SubscriberOptions {
+    fn default() -> Self {
+        Self {
+            capacity: DEFAULT_SUBSCRIBER_QUEUE_CAPACITY,
+            overflow: OverflowPolicy::DropOldest,
+            backoff: DEFAULT_SUBSCRIBER_RESTART_BACKOFF,
+            max_backoff: DEFAULT_SUBSCRIBER_MAX_BACKOFF,
+            max_failures: None,
+            event_timeout: None,
+        }
+    }
+}
+
+/// Transport delivering lifecycle events to subscribers.
+pub trait EventBus: Send + Sync {
+    /// Publish an event to all attached subscribers.
+    ///
+    /// Called from the runtime's event pipeline; must not block.
+    fn publish(&self, event: TaskEvent);
+
+    /// Attach a subscriber receiving events published after this call.
+    fn attach(&self, subscriber: Arc<dyn EventSubscriber>);
+
+    /// Attach a subscriber with its own queue settings.
+    ///
+    /// Buses without per-subscriber queues ignore `options`.
+    fn attach_with(&self, subscriber: Arc<dyn EventSubscriber>, options: SubscriberOptions) {
+        let _ = options;
+        self.attach(subscriber);
+    }
+
+    /// Health of the attached subscribers.
+    ///
+    /// Buses without subscriber supervision report nothing.
+    fn subscribers(&self) -> Vec<SubscriberHealth> {
+        Vec::new()
+    }
+}
+
+/// Shared handle to an event bus.
+pub type EventBusHandle = Arc<dyn EventBus>;
+
+/// In-process bus backed by a tokio broadcast channel (default).
+///
+/// Each subscriber gets a bounded queue drained by its own task; a full queue is
+/// handled by the subscriber's [`OverflowPolicy`]. Events lost to overflow are
+/// logged and reported via [`crate::MetricsBackend::record_subscriber_overflow`].
+///
+/// Every delivery runs in a separate task, so a panicking subscriber does not take
+/// the bus down: the failure is recorded, the subscriber is restarted after a backoff
+/// and, if configured, disabled after too many consecutive failures.
+#[derive(Clone)]
+pub struct BroadcastEventBus {
+    tx: broadcast::Sender<TaskEvent>,
+    metrics: MetricsHandle,
+    queues: Arc<Mutex<Vec<Arc<SubscriberQueue>>>>,
+}
+
+impl BroadcastEventBus {
+    /// Create a bus buffering up to `capacity` events ahead of the subscriber queues.
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self {
+            tx,
+            metrics: Arc::new(NoOpMetrics),
+            queues: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Report subscriber overflow to `metrics`.
+    pub fn with_metrics(mut self, metrics: MetricsHandle) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Receive events directly instead of attaching an [`EventSubscriber`].
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskEvent> {
+        self.tx.subscribe()
+    }
+}
+
+impl Default for BroadcastEventBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_BUS_CAPACITY)
+    }
+}
+
+impl EventBus for BroadcastEventBus {
+    fn publish(&self, event: TaskEvent) {
+        let _ = self.tx.send(event);
+    }
+
+    fn attach(&self, subscriber: Arc<dyn EventSubscriber>) {
+        self.attach_with(subscriber, SubscriberOptions::default());
+    }
+
+    fn attach_with(&self, subscriber: Arc<dyn EventSubscriber>, options: SubscriberOptions) {
+        let name = subscriber.name();
+        let queue = Arc::new(SubscriberQueue::new(name, &options, self.metrics.clone()));
+        self.queues.lock().unwrap().push(Arc::clone(&queue));
+
+        let mut rx = self.tx.subscribe();
+        let producer = Arc::clone(&queue);
+        tokio::spawn(async move {
+            while !producer.is_closed() {
+                match rx.recv().await {
+                    Ok(event) => producer.push(event).await,
+                    Err(RecvError::Lagged(missed)) => producer.overflowed(missed),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            producer.close();
+        });
+
+        tokio::spawn(async move {
+            let mut backoff = options.backoff;
+            while let Some(event) = queue.pop().await {
+                let error = match deliver(&subscriber, event, options.event_timeout).await {
+                    Ok(()) => {
+                        queue.delivered();
+                        backoff = options.backoff;
+                        continue;
+                    }
+                    Err(error) => error,
+                };
+
+                warn!(subscriber = name, error = %error, "event subscriber failed");
+                let failures = queue.failed(error);
+                if options.max_failures.is_some_and(|max| failures >= max) {
+                    warn!(
+                        subscriber = name,
+                        failures, "event subscriber disabled after consecutive failures"
+                    );
+                    queue.disable();
+                    break;
+                }
+                queue.set_state(SubscriberState::Restarting);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(options.max_backoff);
+                queue.set_state(SubscriberState::Running);
+            }
+        });
+    }
+
+    fn subscribers(&self) -> Vec<SubscriberHealth> {
+        self.queues
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|queue| queue.health())
+            .collect()
+    }
+}
+
+/// Run a single delivery in its own task, turning panics and timeouts into errors.
+async fn deliver(
+    subscriber: &Arc<dyn EventSubscriber>,
+    event: TaskEvent,
+    timeout: Option<Duration>,
+) -> Result<(), String> {
+    let subscriber = Arc::clone(subscriber);
+    let mut handle = tokio::spawn(async move { subscriber.on_event(&event).await });
+
+    let joined = match timeout {
+        Some(timeout) => match tokio::time::timeout(timeout, &mut handle).await {
+            Ok(joined) => joined,
+            Err(_) => {
+                handle.abort();
+                return Err(format!("timed out after {}ms", timeout.as_millis()));
+            }
+        },
+        None => handle.await,
+    };
+    joined.map_err(|e| match e.try_into_panic() {
+        Ok(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            format!("panicked: {message}")
+        }
+        Err(e) => e.to_string(),
+    })
+}
+
+/// Bounded queue between the bus and a single subscriber.
+///
+/// Fed by one producer task and drained by one consumer task,
+/// so single-permit notifications cannot be lost.
+struct SubscriberQueue {
+    name: &'static str,
+    capacity: usize,
+    overflow: OverflowPolicy,
+    metrics: MetricsHandle,
+    inner: Mutex<QueueInner>,
+    health: Mutex<SubscriberHealth>,
+    ready: Notify,
+    space: Notify,
+}
+
+#[derive(Default)]
+struct QueueInner {
+    events: VecDeque<TaskEvent>,
+    spill: Option<(PathBuf, File)>,
+    spilled: usize,
+    closed: bool,
+}
+
+impl SubscriberQueue {
+    fn new(name: &'static str, options: &SubscriberOptions, metrics: MetricsHandle) -> Self {
+        let mut inner = QueueInner::default();
+        if let OverflowPolicy::SpillToDisk(path) = &options.overflow {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|file| file.set_len(0).map(|()| file));
+            match file {
+                Ok(file) => inner.spill = Some((path.clone(), file)),
+                Err(e) => warn!(
+                    subscriber = name,
+                    path = %path.display(),
+                    error = %e,
+                    "cannot create event spill file; overflowing events will be dropped"
+                ),
+            }
+        }
+        Self {
+            name,
+            capacity: options.capacity,
+            overflow: options.overflow.clone(),
+            metrics,
+            inner: Mutex::new(inner),
+            health: Mutex::new(SubscriberHealth::new(name)),
+            ready: Notify::new(),
+            space: Notify::new(),
+        }
+    }
+
+    async fn push(&self, event: TaskEvent) {
+        if self.is_closed() {
+            return;
+        }
+        match &self.overflow {
+            OverflowPolicy::DropOldest => {
+                let dropped = {
+                    let mut inner = self.inner.lock().unwrap();
+                    let dropped = inner.events.len() >= self.capacity;
+                    if dropped {
+                        inner.events.pop_front();
+                    }
+                    inner.events.push_back(event);
+                    dropped
+                };
+                if dropped {
+                    self.overflowed(1);
+                }
+            }
+            OverflowPolicy::Block(timeout) => {
+                let deadline = Instant::now() + *timeout;
+                loop {
+                    {
+                        let mut inner = self.inner.lock().unwrap();
+                        if inner.events.len() < self.capacity {
+                            inner.events.push_back(event);
+                            break;
+                        }
+                    }
+                    if tokio::time::timeout_at(deadline, self.space.notified())
+                        .await
+                        .is_err()
+                    {
+                        self.overflowed(1);
+                        return;
+                    }
+                }
+            }
+            OverflowPolicy::SpillToDisk(_) => {
+                let spilled = {
+                    let mut inner = self.inner.lock().unwrap();
+                    if inner.spilled == 0 && inner.events.len() < self.capacity {
+                        inner.events.push_back(event);
+                        None
+                    } else {
+                        Some(inner.spill(&event))
+                    }
+                };
+                match spilled {
+                    None => {}
+                    Some(Ok(())) => self.count_overflow(1),
+                    Some(Err(e)) => {
+                        warn!(subscriber = self.name, error = %e, "cannot spill event to disk");
+                        self.overflowed(1);
+                    }
+                }
+            }
+        }
+        self.ready.notify_one();
+    }
+
+    /// Next event, or `None` once the bus is closed and the queue is drained.
+    async fn pop(&self) -> Option<TaskEvent> {
+        loop {
+            {
+                let mut inner = self.inner.lock().unwrap();
+                if inner.events.is_empty()
+                    && inner.spilled > 0
+                    && let Err(e) = inner.unspill()
+                {
+                    warn!(subscriber = self.name, error = %e, "cannot replay spilled events");
+                }
+                if let Some(event) = inner.events.pop_front() {
+                    drop(inner);
+                    self.space.notify_one();
+                    return Some(event);
+                }
+                if inner.closed {
+                    return None;
+                }
+            }
+            self.ready.notified().await;
+        }
+    }
+
+    fn close(&self) {
+        self.inner.lock().unwrap().closed = true;
+        self.ready.notify_one();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.inner.lock().unwrap().closed
+    }
+
+    /// Stop accepting events and discard the queued ones.
+    fn disable(&self) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.closed = true;
+            inner.events.clear();
+        }
+        self.space.notify_one();
+        self.set_state(SubscriberState::Disabled);
+    }
+
+    fn health(&self) -> SubscriberHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    fn set_state(&self, state: SubscriberState) {
+        self.health.lock().unwrap().state = state;
+    }
+
+    fn delivered(&self) {
+        let mut health = self.health.lock().unwrap();
+        health.delivered += 1;
+        health.consecutive_failures = 0;
+    }
+
+    /// Record a failed delivery; returns the number of consecutive failures.
+    fn failed(&self, error: String) -> u32 {
+        let mut health = self.health.lock().unwrap();
+        health.failures += 1;
+        health.consecutive_failures += 1;
+        health.last_error = Some(error);
+        health.consecutive_failures
+    }
+
+    fn count_overflow(&self, count: u64) {
+        self.health.lock().unwrap().overflowed += count;
+        self.metrics
+            .record_subscriber_overflow(self.name, self.overflow.as_label(), count);
+    }
+
+    /// Record `count` events lost for this subscriber.
+    fn overflowed(&self, count: u64) {
+        warn!(
+            subscriber = self.name,
+            policy = self.overflow.as_label(),
+            dropped = count,
+            "event subscriber queue overflowed"
+        );
+        self.count_overflow(count);
+    }
+}
+
+impl QueueInner {
+    fn spill(&mut self, event: &TaskEvent) -> std::io::Result<()> {
+        let (_, file) = self
+            .spill
+            .as_mut()
+            .ok_or_else(|| std::io::Error::other("spill file unavailable"))?;
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+        self.spilled += 1;
+        Ok(())
+    }
+
+    /// Move all spilled events back into memory and truncate the spill file.
+    fn unspill(&mut self) -> std::io::Result<()> {
+        self.spilled = 0;
+        let Some((path, file)) = self.spill.as_mut() else {
+            return Ok(());
+        };
+        for line in BufReader::new(File::open(path)?).lines() {
+            match serde_json::from_str(&line?) {
+                Ok(event) => self.events.push_back(event),
+                Err(e) => warn!(error = %e, "skipping malformed spilled event"),
+            }
+        }
+        file.set_len(0)?;
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use solti_model::TaskEventKind;
+
+    use crate::{AdmissionDecision, MetricsBackend, TaskOutcome};
+
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<TaskEventKind>>);
+
+    #[async_trait]
+    impl EventSubscriber for Collect {
+        async fn on_event(&self, event: &TaskEvent) {
+            self.0.lock().unwrap().push(event.kind);
+        }
+
+        fn name(&self) -> &'static str {
+            "collect"
+        }
+    }
+
+    fn event(kind: TaskEventKind) -> TaskEvent {
+        TaskEvent {
+            seq: 0,
+            timestamp_ms: 0,
+            kind,
+            task: None,
+            slot: None,
+            attempt: None,
+            reason: None,
+            timeout_ms: None,
+            delay_ms: None,
+        }
+    }
+
+    /// Records event sequence numbers; blocks on the first event until released.
+    #[derive(Default)]
+    struct Gated {
+        seen: Mutex<Vec<u64>>,
+        started: Notify,
+        release: Notify,
+    }
+
+    #[async_trait]
+    impl EventSubscriber for Gated {
+        async fn on_event(&self, event: &TaskEvent) {
+            let first = {
+                let mut seen = self.seen.lock().unwrap();
+                seen.push(event.seq);
+                seen.len() == 1
+            };
+            if first {
+                self.started.notify_one();
+                self.release.notified().await;
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "gated"
+        }
+    }
+
+    #[derive(Default)]
+    struct Overflows(AtomicU64);
+
+    impl MetricsBackend for Overflows {
+        fn record_task_started(&self, _: &str) {}
+        fn record_task_completed(&self, _: &str, _: TaskOutcome, _: u64) {}
+        fn record_task_queue_wait(&self, _: &str, _: u64) {}
+        fn record_runner_error(&self, _: &str, _: &str) {}
+        fn record_runner_health(&self, _: &str, _: bool) {}
+        fn record_tasks_by_status(&self, _: &str, _: usize) {}
+        fn record_active_slots(&self, _: usize) {}
+        fn record_admission(&self, _: &str, _: AdmissionDecision) {}
+
+        fn record_subscriber_overflow(&self, subscriber: &str, _: &str, count: u64) {
+            assert_eq!(subscriber, "gated");
+            self.0.fetch_add(count, Ordering::SeqCst);
+        }
+    }
+
+    fn numbered(seq: u64) -> TaskEvent {
+        TaskEvent {
+            seq,
+            ..event(TaskEventKind::TaskStarting)
+        }
+    }
+
+    async fn eventually(mut done: impl FnMut() -> bool) {
+        for _ in 0..200 {
+            if done() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert!(done(), "condition not reached");
+    }
+
+    /// Deliver event 1, publish 2..=5 while the subscriber is stuck on it,
+    /// wait until `overflowed` events were reported, then let the subscriber proceed
+    /// and collect `delivered` events.
+    async fn run_gated(options: SubscriberOptions, overflowed: u64, delivered: usize) -> Vec<u64> {
+        let metrics = Arc::new(Overflows::default());
+        let bus = BroadcastEventBus::default().with_metrics(metrics.clone());
+        let sub = Arc::new(Gated::default());
+        bus.attach_with(sub.clone(), options.with_capacity(2));
+
+        bus.publish(numbered(1));
+        sub.started.notified().await;
+        for seq in 2..=5 {
+            bus.publish(numbered(seq));
+        }
+        eventually(|| metrics.0.load(Ordering::SeqCst) == overflowed).await;
+        sub.release.notify_one();
+
+        eventually(|| sub.seen.lock().unwrap().len() == delivered).await;
+        sub.seen.lock().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn drop_oldest_keeps_latest_events() {
+        let seen = run_gated(SubscriberOptions::default(), 2, 3).await;
+        assert_eq!(seen, vec![1, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn block_drops_events_after_timeout() {
+        let options = SubscriberOptions::default()
+            .with_overflow(OverflowPolicy::Block(Duration::from_millis(10)));
+        let seen = run_gated(options, 2, 3).await;
+        assert_eq!(seen, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn spill_to_disk_replays_events_in_order() {
+        let path = std::env::temp_dir().join(format!("solti-spill-{}.jsonl", std::process::id()));
+        let options =
+            SubscriberOptions::default().with_overflow(OverflowPolicy::SpillToDisk(path.clone()));
+        let seen = run_gated(options, 2, 5).await;
+        assert_eq!(seen, vec![1, 2, 3, 4, 5]);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 0);
+        let _ = std::fs::remove_file(path);
+    }
+
+    /// Panics on events with an odd sequence number, hangs on zero.
+    #[derive(Default)]
+    struct Flaky(Mutex<Vec<u64>>);
+
+    #[async_trait]
+    impl EventSubscriber for Flaky {
+        async fn on_event(&self, event: &TaskEvent) {
+            match event.seq {
+                0 => std::future::pending().await,
+                seq if seq % 2 == 1 => panic!("odd event {seq}"),
+                seq => self.0.lock().unwrap().push(seq),
+            }
+        }
+
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+    }
+
+    fn supervised() -> SubscriberOptions {
+        SubscriberOptions::default()
+            .with_restart_backoff(Duration::from_millis(1), Duration::from_millis(2))
+    }
+
+    #[tokio::test]
+    async fn panicking_subscriber_is_restarted() {
+        let bus = BroadcastEventBus::default();
+        let sub = Arc::new(Flaky::default());
+        bus.attach_with(sub.clone(), supervised());
+
+        for seq in 1..=4 {
+            bus.publish(numbered(seq));
+        }
+        eventually(|| bus.subscribers()[0].delivered == 2).await;
+        assert_eq!(*sub.0.lock().unwrap(), vec![2, 4]);
+
+        let health = &bus.subscribers()[0];
+        assert_eq!(health.name, "flaky");
+        assert_eq!(health.state, SubscriberState::Running);
+        assert_eq!(health.delivered, 2);
+        assert_eq!(health.failures, 2);
+        assert_eq!(health.consecutive_failures, 0);
+        assert_eq!(health.last_error.as_deref(), Some("panicked: odd event 3"));
+    }
+
+    #[tokio::test]
+    async fn subscriber_is_disabled_after_consecutive_failures() {
+        let bus = BroadcastEventBus::default();
+        let sub = Arc::new(Flaky::default());
+        bus.attach_with(
+            sub.clone(),
+            supervised()
+                .with_max_failures(2)
+                .with_event_timeout(Duration::from_millis(10)),
+        );
+
+        for seq in [1, 0, 2] {
+            bus.publish(numbered(seq));
+        }
+        eventually(|| bus.subscribers()[0].state == SubscriberState::Disabled).await;
+
+        let health = &bus.subscribers()[0];
+        assert_eq!(health.failures, 2);
+        assert_eq!(health.last_error.as_deref(), Some("timed out after 10ms"));
+        bus.publish(numbered(4));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(sub.0.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn attached_subscribers_receive_published_events() {
+        let bus = BroadcastEventBus::default();
+        let first = Arc::new(Collect::default());
+        let second = Arc::new(Collect::default());
+        bus.attach(first.clone());
+        bus.attach(second.clone());
+
+        bus.publish(event(TaskEventKind::TaskStarting));
+        bus.publish(event(TaskEventKind::TaskStopped));
+
+        for _ in 0..100 {
+            if first.0.lock().unwrap().len() == 2 && second.0.lock().unwrap().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        let expected = vec![TaskEventKind::TaskStarting, TaskEventKind::TaskStopped];
+        assert_eq!(*first.0.lock().unwrap(), expected);
+        assert_eq!(*second.0.lock().unwrap(), expected);
+    }
+}
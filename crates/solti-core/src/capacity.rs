@@ -0,0 +1,238 @@
+//! Agent-wide resource capacity.
+//!
+//! [`CapacityTracker`] remembers the resource requests of admitted tasks and refuses tasks that
+//! do not fit into what is left of the allocatable capacity. Like quotas, usage is recomputed
+//! from [`TaskState`]: only active (pending or running) tasks hold resources, plus restartable
+//! tasks between runs. A reservation holds until [`CapacityTracker::confirm`] reports the task
+//! registered in state, and is dropped once a confirmed task leaves the state.
+use std::{collections::HashMap, sync::Mutex};
+
+use solti_model::{CreateSpec, ResourceCapacity, ResourceRequests, RestartStrategy, TaskId};
+
+use crate::{error::CoreError, state::TaskState};
+
+/// Resources reserved by an admitted task.
+struct Reservation {
+    request: ResourceRequests,
+    /// The task runs again after a terminal status.
+    restartable: bool,
+    /// The task is registered in state.
+    registered: bool,
+}
+
+/// Admission-time capacity checks.
+pub(crate) struct CapacityTracker {
+    allocatable: ResourceCapacity,
+    admitted: Mutex<HashMap<TaskId, Reservation>>,
+}
+
+impl CapacityTracker {
+    pub(crate) fn new(allocatable: ResourceCapacity) -> Self {
+        Self {
+            allocatable,
+            admitted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Allocatable capacity of the agent.
+    pub(crate) fn allocatable(&self) -> ResourceCapacity {
+        self.allocatable
+    }
+
+    /// Reserve the requests of the spec, or fail with [`CoreError::InsufficientCapacity`].
+    pub(crate) fn admit(
+        &self,
+        id: &TaskId,
+        spec: &CreateSpec,
+        state: &TaskState,
+    ) -> Result<(), CoreError> {
+        let mut admitted = self.admitted.lock().unwrap();
+        admitted.retain(|id, r| !r.registered || state.get(id).is_some());
+
+        let request = spec.resources.unwrap_or_default();
+        let free = self.allocatable.remaining(&used(&admitted, state));
+        if let Some((what, requested, available)) = free.shortfall(&request) {
+            return Err(CoreError::InsufficientCapacity(format!(
+                "{what}: requested {requested}, available {available}"
+            )));
+        }
+        admitted.insert(
+            id.clone(),
+            Reservation {
+                request,
+                restartable: spec.restart != RestartStrategy::Never,
+                registered: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Mark the reservation of a task as registered in state.
+    ///
+    /// From then on the reservation is dropped as soon as the task leaves the state.
+    pub(crate) fn confirm(&self, id: &TaskId) {
+        if let Some(r) = self.admitted.lock().unwrap().get_mut(id) {
+            r.registered = true;
+        }
+    }
+
+    /// Drop the reservation of a task that was not submitted after all.
+    pub(crate) fn release(&self, id: &TaskId) {
+        self.admitted.lock().unwrap().remove(id);
+    }
+
+    /// Capacity not reserved by active tasks.
+    pub(crate) fn free(&self, state: &TaskState) -> ResourceCapacity {
+        let admitted = self.admitted.lock().unwrap();
+        self.allocatable.remaining(&used(&admitted, state))
+    }
+}
+
+fn used(admitted: &HashMap<TaskId, Reservation>, state: &TaskState) -> ResourceRequests {
+    admitted
+        .iter()
+        .filter(|(id, r)| match state.get(id) {
+            Some(info) => info.status.is_active() || r.restartable,
+            // Newly admitted tasks may not be registered in state yet.
+            None => !r.registered,
+        })
+        .fold(ResourceRequests::default(), |sum, (_, r)| {
+            sum.saturating_add(r.request)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, RunnerLabels, TaskKind, TaskStatus,
+    };
+    use std::sync::{Arc, Barrier};
+
+    fn spec() -> CreateSpec {
+        CreateSpec {
+            slot: "slot".into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    fn admit(
+        c: &CapacityTracker,
+        state: &TaskState,
+        id: &str,
+        spec: &CreateSpec,
+    ) -> Result<(), CoreError> {
+        let id = TaskId::from(id);
+        c.admit(&id, spec, state)?;
+        state.add_task(id.clone(), spec.slot.clone());
+        c.confirm(&id);
+        Ok(())
+    }
+
+    #[test]
+    fn refuses_tasks_that_do_not_fit() {
+        let state = TaskState::new();
+        let c = CapacityTracker::new(
+            ResourceCapacity::unbounded()
+                .with_cpu_millis(1_000)
+                .with_gpus(1),
+        );
+
+        admit(
+            &c,
+            &state,
+            "t1",
+            &spec().with_cpu_request(600).with_gpu_request(1),
+        )
+        .unwrap();
+        let err = admit(&c, &state, "t2", &spec().with_gpu_request(1)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "insufficient capacity: gpus: requested 1, available 0"
+        );
+        assert!(admit(&c, &state, "t2", &spec().with_cpu_request(500)).is_err());
+        // Memory is not limited.
+        admit(&c, &state, "t2", &spec().with_memory_request(1 << 40)).unwrap();
+
+        let free = c.free(&state);
+        assert_eq!(free.cpu_millis, Some(400));
+        assert_eq!(free.gpus, Some(0));
+        assert_eq!(free.memory_bytes, None);
+    }
+
+    #[test]
+    fn finished_and_released_tasks_free_capacity() {
+        let state = TaskState::new();
+        let c = CapacityTracker::new(ResourceCapacity::unbounded().with_gpus(1));
+
+        admit(&c, &state, "t1", &spec().with_gpu_request(1)).unwrap();
+        state.update_status(&TaskId::from("t1"), TaskStatus::Failed, None);
+        admit(&c, &state, "t2", &spec().with_gpu_request(1)).unwrap();
+
+        c.release(&TaskId::from("t2"));
+        state.remove_task(&TaskId::from("t2"));
+        assert_eq!(c.free(&state).gpus, Some(1));
+    }
+
+    #[test]
+    fn concurrent_submissions_do_not_overcommit() {
+        let state = TaskState::new();
+        let c = Arc::new(CapacityTracker::new(
+            ResourceCapacity::unbounded().with_gpus(2),
+        ));
+        let barrier = Arc::new(Barrier::new(8));
+
+        // Admit concurrently without registering, as racing submissions do before their
+        // tasks reach the state.
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let (c, state, barrier) = (Arc::clone(&c), state.clone(), Arc::clone(&barrier));
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    let id = TaskId::from(format!("t{i}"));
+                    c.admit(&id, &spec().with_gpu_request(1), &state).is_ok()
+                })
+            })
+            .collect();
+        let admitted = handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .filter(|ok| *ok)
+            .count();
+
+        assert_eq!(admitted, 2);
+        assert_eq!(c.free(&state).gpus, Some(0));
+    }
+
+    #[test]
+    fn restartable_tasks_hold_capacity_between_runs() {
+        let state = TaskState::new();
+        let c = CapacityTracker::new(ResourceCapacity::unbounded().with_gpus(1));
+        let mut periodic = spec().with_gpu_request(1);
+        periodic.restart = RestartStrategy::Always {
+            interval_ms: Some(60_000),
+        };
+
+        admit(&c, &state, "t1", &periodic).unwrap();
+        state.update_status(&TaskId::from("t1"), TaskStatus::Succeeded, None);
+        assert!(admit(&c, &state, "t2", &spec().with_gpu_request(1)).is_err());
+
+        state.remove_task(&TaskId::from("t1"));
+        admit(&c, &state, "t2", &spec().with_gpu_request(1)).unwrap();
+    }
+}
@@ -0,0 +1,275 @@
+//! Missed-run catch-up for periodic tasks.
+//!
+//! [`FireHistory`] records when each periodic slot last executed. When a periodic task is
+//! submitted again (typically after an agent restart), the gap since the last fire is
+//! compared with the restart interval and the spec's [`CatchUpPolicy`] decides what to do
+//! with the runs that were missed.
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use solti_model::{CatchUpPolicy, Slot, TaskId};
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+
+use crate::{error::CoreError, state::TaskState};
+
+/// Upper bound on back-to-back runs performed by [`CatchUpPolicy::RunAll`].
+pub const MAX_CATCH_UP_RUNS: u64 = 100;
+
+/// Last fire time (Unix milliseconds) of every periodic slot.
+///
+/// The history is either kept in memory or persisted as a JSON object in a file,
+/// rewritten after every recorded fire.
+pub struct FireHistory {
+    path: Option<PathBuf>,
+    fires: Mutex<Fires>,
+    /// Serializes file writes; holds the version of the last persisted snapshot.
+    persisted: Mutex<u64>,
+}
+
+/// Fire times plus a version bumped on every change.
+#[derive(Default)]
+struct Fires {
+    by_slot: HashMap<Slot, u64>,
+    version: u64,
+}
+
+impl FireHistory {
+    /// Create a non-persistent history.
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            fires: Mutex::new(Fires::default()),
+            persisted: Mutex::new(0),
+        }
+    }
+
+    /// Open a history persisted at `path`, loading previous fire times if the file exists.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, CoreError> {
+        let path = path.as_ref().to_path_buf();
+        let by_slot = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| CoreError::Store(format!("{}: {e}", path.display())))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(CoreError::Store(format!("{}: {e}", path.display()))),
+        };
+        Ok(Self {
+            path: Some(path),
+            fires: Mutex::new(Fires {
+                by_slot,
+                version: 0,
+            }),
+            persisted: Mutex::new(0),
+        })
+    }
+
+    /// Last fire time of a slot in Unix milliseconds.
+    pub fn last_fire(&self, slot: &str) -> Option<u64> {
+        self.fires.lock().unwrap().by_slot.get(slot).copied()
+    }
+
+    /// Record a fire of `slot` at `unix_ms`.
+    ///
+    /// Writes the file of a persistent history on the calling thread; async callers should
+    /// go through `spawn_blocking`. Persistence is best-effort: write failures are logged and
+    /// the in-memory value is kept.
+    pub fn record(&self, slot: &str, unix_ms: u64) {
+        if let Some(snapshot) = self.remember(slot, unix_ms) {
+            self.persist(snapshot);
+        }
+    }
+
+    /// Update the in-memory fire time, returning the snapshot to persist (if any).
+    fn remember(&self, slot: &str, unix_ms: u64) -> Option<(u64, Vec<u8>)> {
+        let mut fires = self.fires.lock().unwrap();
+        fires.by_slot.insert(slot.to_string(), unix_ms);
+        fires.version += 1;
+
+        self.path.as_ref()?;
+        match serde_json::to_vec(&fires.by_slot) {
+            Ok(bytes) => Some((fires.version, bytes)),
+            Err(e) => {
+                warn!(error = %e, "failed to serialize fire history");
+                None
+            }
+        }
+    }
+
+    /// Write a snapshot unless a newer one was already written.
+    fn persist(&self, (version, bytes): (u64, Vec<u8>)) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let mut persisted = self.persisted.lock().unwrap();
+        if *persisted >= version {
+            return;
+        }
+        let tmp = path.with_extension("tmp");
+        match std::fs::write(&tmp, bytes).and_then(|_| std::fs::rename(&tmp, path)) {
+            Ok(()) => *persisted = version,
+            Err(e) => warn!(path = %path.display(), error = %e, "failed to persist fire history"),
+        }
+    }
+
+    /// Wrap a periodic task: record its fires and apply `policy` to runs missed since the last one.
+    pub(crate) fn wrap(
+        self: &Arc<Self>,
+        slot: Slot,
+        interval_ms: u64,
+        policy: CatchUpPolicy,
+        state: TaskState,
+        task: TaskRef,
+    ) -> TaskRef {
+        let missed = self
+            .last_fire(&slot)
+            .map(|last| missed_runs(last, now_ms(), interval_ms))
+            .unwrap_or(0);
+        if missed > 0 {
+            info!(%slot, missed, policy = %policy, "periodic task missed runs");
+        }
+
+        Arc::new(CatchUpTask {
+            history: Arc::clone(self),
+            slot,
+            state,
+            startup: Mutex::new(Some(startup_runs(policy, missed))),
+            inner: task,
+        })
+    }
+}
+
+/// Number of scheduled fires in `(last, now]` for the given interval.
+fn missed_runs(last_ms: u64, now_ms: u64, interval_ms: u64) -> u64 {
+    if interval_ms == 0 {
+        return 0;
+    }
+    now_ms.saturating_sub(last_ms) / interval_ms
+}
+
+/// Number of runs to perform on the first execution after submission.
+fn startup_runs(policy: CatchUpPolicy, missed: u64) -> u64 {
+    match policy {
+        _ if missed == 0 => 1,
+        CatchUpPolicy::Skip => 0,
+        CatchUpPolicy::RunOnce => 1,
+        CatchUpPolicy::RunAll => missed.min(MAX_CATCH_UP_RUNS),
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Task wrapper applying the catch-up plan on its first execution.
+///
+/// Back-to-back catch-up runs share one execution, so the task timeout covers all of them.
+struct CatchUpTask {
+    history: Arc<FireHistory>,
+    slot: Slot,
+    state: TaskState,
+    startup: Mutex<Option<u64>>,
+    inner: TaskRef,
+}
+
+impl Task for CatchUpTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        let runs = self.startup.lock().unwrap().take().unwrap_or(1);
+        if runs == 0 {
+            let id = TaskId::from(self.inner.name());
+            debug!(task = %id, "skipping missed runs, waiting for the next tick");
+            self.state.mark_skipped(&id);
+            return Box::pin(async { Ok(()) });
+        }
+
+        let history = Arc::clone(&self.history);
+        let inner = Arc::clone(&self.inner);
+        let slot = self.slot.clone();
+        Box::pin(async move {
+            for _ in 0..runs {
+                if let Some(snapshot) = history.remember(&slot, now_ms()) {
+                    let history = Arc::clone(&history);
+                    tokio::task::spawn_blocking(move || history.persist(snapshot));
+                }
+                inner.spawn(ctx.clone()).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missed_runs_counts_elapsed_ticks() {
+        assert_eq!(missed_runs(1_000, 1_500, 1_000), 0);
+        assert_eq!(missed_runs(1_000, 2_000, 1_000), 1);
+        assert_eq!(missed_runs(1_000, 10_500, 1_000), 9);
+        assert_eq!(missed_runs(5_000, 1_000, 1_000), 0);
+        assert_eq!(missed_runs(0, 10_000, 0), 0);
+    }
+
+    #[test]
+    fn startup_runs_follow_policy() {
+        assert_eq!(startup_runs(CatchUpPolicy::Skip, 0), 1);
+        assert_eq!(startup_runs(CatchUpPolicy::Skip, 3), 0);
+        assert_eq!(startup_runs(CatchUpPolicy::RunOnce, 3), 1);
+        assert_eq!(startup_runs(CatchUpPolicy::RunAll, 3), 3);
+        assert_eq!(
+            startup_runs(CatchUpPolicy::RunAll, 1_000),
+            MAX_CATCH_UP_RUNS
+        );
+    }
+
+    #[test]
+    fn history_persists_to_file() {
+        let path =
+            std::env::temp_dir().join(format!("solti-fire-history-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let history = FireHistory::open(&path).unwrap();
+        assert_eq!(history.last_fire("nightly"), None);
+        history.record("nightly", 42);
+
+        let reopened = FireHistory::open(&path).unwrap();
+        assert_eq!(reopened.last_fire("nightly"), Some(42));
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn stale_snapshots_are_not_written() {
+        let path = std::env::temp_dir().join(format!(
+            "solti-fire-history-stale-{}.json",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+
+        let history = FireHistory::open(&path).unwrap();
+        let older = history.remember("nightly", 1).unwrap();
+        let newer = history.remember("nightly", 2).unwrap();
+        history.persist(newer);
+        history.persist(older);
+
+        let reopened = FireHistory::open(&path).unwrap();
+        assert_eq!(reopened.last_fire("nightly"), Some(2));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,102 @@
+//! Content hashing of submitted specs.
+//!
+//! With deduplication enabled (see [`crate::SupervisorApi::with_dedup`]), every spec admitted
+//! through [`crate::SupervisorApi::submit`] is tagged with [`LABEL_SPEC_HASH`]; resubmitting an
+//! identical spec while a task with the same hash is still active in the slot returns that
+//! task instead of creating a new one.
+use serde_json::Value;
+use solti_model::{CreateSpec, LABEL_SPEC_HASH, TaskId, TaskInfo};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Canonical content hash of a spec as 16 lowercase hex digits.
+///
+/// Covers the spec serialized as compact JSON with object keys sorted, so label order and
+/// map ordering do not matter. [`LABEL_SPEC_HASH`] itself is excluded. The hash is stable
+/// across processes and agent restarts (64-bit FNV-1a).
+pub fn spec_hash(spec: &CreateSpec) -> String {
+    let mut spec = spec.clone();
+    spec.labels.remove(LABEL_SPEC_HASH);
+    let value = serde_json::to_value(&spec).expect("CreateSpec serializes to JSON");
+    let bytes = serde_json::to_vec(&sorted(value)).expect("JSON value serializes");
+
+    let hash = bytes.iter().fold(FNV_OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    });
+    format!("{hash:016x}")
+}
+
+/// Active task among `tasks` carrying the spec hash `hash`.
+pub(crate) fn find_active(tasks: &[TaskInfo], hash: &str) -> Option<TaskId> {
+    tasks
+        .iter()
+        .find(|info| info.status.is_active() && info.labels.get(LABEL_SPEC_HASH) == Some(hash))
+        .map(|info| info.id.clone())
+}
+
+/// Rebuild objects with sorted keys, independent of serde_json's map ordering.
+pub(crate) fn sorted(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(k, v)| (k, sorted(v))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sorted).collect()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, RunnerLabels, TaskKind,
+    };
+
+    fn spec(labels: RunnerLabels) -> CreateSpec {
+        CreateSpec {
+            slot: "backup".into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels,
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    #[test]
+    fn hash_ignores_label_order_and_its_own_label() {
+        let mut a = RunnerLabels::new();
+        a.insert("team", "ops").insert("env", "prod");
+        let mut b = RunnerLabels::new();
+        b.insert("env", "prod").insert("team", "ops");
+
+        let hash = spec_hash(&spec(a));
+        assert_eq!(hash.len(), 16);
+        assert_eq!(hash, spec_hash(&spec(b.clone())));
+
+        b.insert(LABEL_SPEC_HASH, "0000000000000000");
+        assert_eq!(hash, spec_hash(&spec(b)));
+    }
+
+    #[test]
+    fn hash_changes_with_content() {
+        let base = spec(RunnerLabels::new());
+        let mut other = base.clone();
+        other.timeout_ms = 2_000;
+        assert_ne!(spec_hash(&base), spec_hash(&other));
+    }
+}
@@ -10,6 +10,30 @@ pub enum CoreError {
     #[error("supervisor error: {0}")]
     Supervisor(String),
 
+    #[error("quota exceeded: {0}")]
+    QuotaExceeded(String),
+
+    #[error("queue full: {0}")]
+    QueueFull(String),
+
+    #[error("insufficient capacity: {0}")]
+    InsufficientCapacity(String),
+
+    #[error("admission denied: {0}")]
+    AdmissionDenied(String),
+
+    #[error("task not found: {0}")]
+    TaskNotFound(String),
+
+    #[error("group not found: {0}")]
+    GroupNotFound(String),
+
+    #[error("timed out waiting for {0}")]
+    WaitTimeout(String),
+
+    #[error("state store error: {0}")]
+    Store(String),
+
     #[error("mapping error: {0}")]
     Mapping(String),
 
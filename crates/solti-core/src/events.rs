@@ -0,0 +1,209 @@
+//! Bounded in-memory history of lifecycle events.
+//!
+//! Keeps the last N taskvisor events so operators can inspect recent history
+//! without an external event sink.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, RwLock},
+};
+
+use async_trait::async_trait;
+use solti_model::{EventQuery, Slot, TaskEvent, TaskId};
+use taskvisor::{Event, EventKind, Subscribe};
+
+use crate::{bus::EventBusHandle, map::to_task_event, state::TaskState};
+
+/// Number of events retained by default.
+pub const DEFAULT_EVENT_LOG_CAPACITY: usize = 1024;
+
+/// Ring buffer of recent lifecycle events.
+#[derive(Clone)]
+pub struct EventLog {
+    inner: Arc<Mutex<EventLogInner>>,
+}
+
+struct EventLogInner {
+    capacity: usize,
+    events: VecDeque<TaskEvent>,
+    /// Slots of live tasks, so events emitted after state cleanup keep their slot.
+    slots: HashMap<TaskId, Slot>,
+}
+
+impl EventLog {
+    /// Create an empty log retaining at most `capacity` events.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(EventLogInner {
+                capacity,
+                events: VecDeque::with_capacity(capacity.min(DEFAULT_EVENT_LOG_CAPACITY)),
+                slots: HashMap::new(),
+            })),
+        }
+    }
+
+    /// Change the number of retained events, dropping the oldest if needed.
+    pub fn set_capacity(&self, capacity: usize) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.capacity = capacity;
+        while inner.events.len() > capacity {
+            inner.events.pop_front();
+        }
+    }
+
+    /// Append a record, evicting the oldest one when full.
+    pub fn push(&self, record: TaskEvent) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.capacity == 0 {
+            return;
+        }
+        if inner.events.len() >= inner.capacity {
+            inner.events.pop_front();
+        }
+        inner.events.push_back(record);
+    }
+
+    /// Return matching events, oldest first, up to `query.limit`.
+    pub fn query(&self, query: &EventQuery) -> Vec<TaskEvent> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .events
+            .iter()
+            .filter(|r| query.matches(r))
+            .take(query.limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Number of retained events.
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().events.len()
+    }
+
+    /// Returns `true` if no events are retained.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Convert and append a runtime event, returning the stored record.
+    fn record(&self, event: &Event, state: &TaskState) -> TaskEvent {
+        let task = event.task.as_deref().map(TaskId::from);
+        let slot = task.as_ref().and_then(|id| {
+            let mut inner = self.inner.lock().unwrap();
+            if let Some(slot) = inner.slots.get(id) {
+                return Some(slot.clone());
+            }
+            let slot = state.get(id)?.slot;
+            inner.slots.insert(id.clone(), slot.clone());
+            Some(slot)
+        });
+
+        let record = to_task_event(event, slot);
+        self.push(record.clone());
+
+        if event.kind == EventKind::TaskRemoved
+            && let Some(id) = task
+        {
+            self.inner.lock().unwrap().slots.remove(&id);
+        }
+        record
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVENT_LOG_CAPACITY)
+    }
+}
+
+/// Subscriber feeding taskvisor events into an [`EventLog`] and the event bus.
+pub(crate) struct EventLogSubscriber {
+    log: EventLog,
+    state: TaskState,
+    bus: Arc<RwLock<EventBusHandle>>,
+}
+
+impl EventLogSubscriber {
+    pub(crate) fn new(log: EventLog, state: TaskState, bus: Arc<RwLock<EventBusHandle>>) -> Self {
+        Self { log, state, bus }
+    }
+}
+
+#[async_trait]
+impl Subscribe for EventLogSubscriber {
+    async fn on_event(&self, event: &Event) {
+        if matches!(
+            event.kind,
+            EventKind::SubscriberOverflow | EventKind::SubscriberPanicked
+        ) {
+            return;
+        }
+        let record = self.log.record(event, &self.state);
+        self.bus.read().unwrap().publish(record);
+    }
+
+    fn name(&self) -> &'static str {
+        "event-log-subscriber"
+    }
+
+    fn queue_capacity(&self) -> usize {
+        2048
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_model::TaskEventKind;
+
+    #[test]
+    fn keeps_last_events_only() {
+        let log = EventLog::new(3);
+        let state = TaskState::new();
+        for _ in 0..5 {
+            log.record(
+                &Event::new(EventKind::TaskStarting).with_task("r-a-1"),
+                &state,
+            );
+        }
+        assert_eq!(log.len(), 3);
+
+        let all = log.query(&EventQuery::new());
+        assert!(all.windows(2).all(|w| w[0].seq < w[1].seq));
+
+        log.set_capacity(1);
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.query(&EventQuery::new())[0].seq, all[2].seq);
+    }
+
+    #[test]
+    fn resolves_slot_and_keeps_it_until_removed() {
+        let log = EventLog::default();
+        let state = TaskState::new();
+        let id = TaskId::from("r-backup-1");
+        state.add_task(id.clone(), "backup".into());
+
+        log.record(
+            &Event::new(EventKind::TaskStarting)
+                .with_task("r-backup-1")
+                .with_attempt(1),
+            &state,
+        );
+        state.remove_task(&id);
+        log.record(
+            &Event::new(EventKind::TaskRemoved).with_task("r-backup-1"),
+            &state,
+        );
+        log.record(&Event::new(EventKind::ShutdownRequested), &state);
+
+        let by_slot = log.query(&EventQuery::new().with_slot("backup"));
+        assert_eq!(by_slot.len(), 2);
+        assert_eq!(by_slot[0].kind, TaskEventKind::TaskStarting);
+        assert_eq!(by_slot[0].attempt, Some(1));
+        assert_eq!(by_slot[1].kind, TaskEventKind::TaskRemoved);
+
+        let since = log.query(&EventQuery::new().with_since(by_slot[1].seq));
+        assert_eq!(since.len(), 1);
+        assert!(since[0].task.is_none());
+    }
+}
@@ -0,0 +1,36 @@
+//! Follow-up tasks submitted when a task terminates.
+//!
+//! [`FollowUps`] remembers the [`FollowUp`] of every active task submitted through
+//! [`crate::SupervisorApi::submit`]. When the task leaves the supervisor, the entry is taken and
+//! the follow-up matching its final status is submitted; canceling the task forgets the entry,
+//! so canceled tasks never trigger follow-ups.
+use std::{collections::HashMap, sync::Mutex};
+
+use solti_model::{FollowUp, TaskId};
+
+/// Pending follow-ups keyed by the id of the task that triggers them.
+#[derive(Default)]
+pub(crate) struct FollowUps {
+    pending: Mutex<HashMap<TaskId, FollowUp>>,
+}
+
+impl FollowUps {
+    pub(crate) fn register(&self, id: TaskId, follow_up: FollowUp) {
+        self.pending.lock().unwrap().insert(id, follow_up);
+    }
+
+    /// Take the follow-up of a terminated task.
+    pub(crate) fn take(&self, id: &TaskId) -> Option<FollowUp> {
+        self.pending.lock().unwrap().remove(id)
+    }
+
+    /// Drop the follow-up of a task being canceled.
+    pub(crate) fn forget(&self, id: &TaskId) {
+        self.pending.lock().unwrap().remove(id);
+    }
+
+    /// Drop every pending follow-up, e.g. on shutdown.
+    pub(crate) fn clear(&self) {
+        self.pending.lock().unwrap().clear();
+    }
+}
@@ -4,15 +4,73 @@ pub use error::CoreError;
 mod map;
 pub use map::{
     to_admission_policy, to_backoff_policy, to_controller_spec, to_jitter_policy,
-    to_restart_policy, to_task_spec,
+    to_restart_policy, to_task_event, to_task_event_kind, to_task_spec,
 };
 
 mod router;
-pub use router::RunnerRouter;
+pub use router::{RunnerRouter, runner_health_check};
 
 mod runner;
 pub use runner::make_run_id;
-pub use runner::{BuildContext, Runner, RunnerError};
+pub use runner::{AgentControlRunner, BuildContext, CommandPolicy, Runner, RunnerError};
+#[cfg(feature = "chaos")]
+pub use runner::{ChaosConfig, ChaosRunner};
+pub use runner::{
+    PrefixSequenceGenerator, RunIdGenerator, TaskIdGenerator, TaskIdGeneratorHandle,
+    UuidV4Generator, UuidV7Generator,
+};
+
+mod quota;
+
+mod capacity;
+
+mod queue;
+pub use queue::QueueLimits;
+
+mod dedup;
+pub use dedup::spec_hash;
+
+mod follow_up;
+
+mod window;
+
+#[cfg(feature = "watch")]
+mod watch;
+
+mod catch_up;
+pub use catch_up::{FireHistory, MAX_CATCH_UP_RUNS};
+
+mod maintenance;
+pub use maintenance::MaintenanceMode;
+
+mod events;
+pub use events::{DEFAULT_EVENT_LOG_CAPACITY, EventLog};
+
+mod bus;
+pub use bus::{
+    BroadcastEventBus, DEFAULT_EVENT_BUS_CAPACITY, DEFAULT_SUBSCRIBER_MAX_BACKOFF,
+    DEFAULT_SUBSCRIBER_QUEUE_CAPACITY, DEFAULT_SUBSCRIBER_RESTART_BACKOFF, EventBus,
+    EventBusHandle, EventSubscriber, OverflowPolicy, SubscriberOptions,
+};
+
+mod output;
+pub use output::{
+    DEFAULT_OUTPUT_CAPACITY, DEFAULT_OUTPUT_RATE_LIMIT, OutputPublisher, TaskOutputBus,
+};
+
+mod admission;
+pub use admission::{AdmissionChain, AdmissionContext, AdmissionPolicy, AllowAll, Caller};
+
+#[cfg(feature = "signing")]
+mod signing;
+#[cfg(feature = "signing")]
+pub use signing::{SignatureError, SpecVerifier, sign_spec, signing_payload};
+
+#[cfg(feature = "loadgen")]
+pub mod loadgen;
+
+mod limiter;
+pub use limiter::RestartLimiter;
 
 mod policy;
 pub use policy::TaskPolicy;
@@ -21,9 +79,16 @@ pub mod supervisor;
 pub use supervisor::SupervisorApi;
 
 mod metrics;
-pub use metrics::{MetricsBackend, MetricsHandle, NoOpMetrics, TaskOutcome, noop_metrics};
+pub use metrics::{
+    AdmissionDecision, LogMetrics, MetricsBackend, MetricsHandle, MultiMetrics, NoOpMetrics,
+    TaskOutcome, noop_metrics,
+};
 
 mod system;
-pub use system::{agent_id, arch, os_info, platform, uptime_seconds};
+pub use system::{
+    BuildInfo, LoadSnapshot, agent_id, arch, build_info, load_snapshot, os_info, platform,
+    uptime_seconds,
+};
 
 mod state;
+pub use state::{STATE_WATCH_CAPACITY, StateChange};
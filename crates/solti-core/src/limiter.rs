@@ -0,0 +1,192 @@
+//! Per-slot restart rate limiting.
+//!
+//! [`RestartLimiter`] caps how often tasks of one slot may start, independent of restart
+//! and backoff policies. Every task submitted through [`crate::SupervisorApi`] is wrapped so
+//! that each execution first acquires a start permit for its slot.
+use std::{
+    collections::{HashMap, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
+
+use solti_model::{RestartRateLimit, Slot};
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+/// Runtime-adjustable restart rate limits.
+///
+/// A slot-specific limit takes precedence over the default one.
+/// Limits can be changed at any time (e.g. on configuration reload);
+/// the new limit applies to the next execution.
+#[derive(Default)]
+pub struct RestartLimiter {
+    default: RwLock<Option<RestartRateLimit>>,
+    slots: RwLock<HashMap<Slot, RestartRateLimit>>,
+    starts: Mutex<HashMap<Slot, VecDeque<Instant>>>,
+}
+
+impl RestartLimiter {
+    /// Create a limiter without any limits.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set (or clear) the limit applied to slots without their own limit.
+    pub fn set_default(&self, limit: Option<RestartRateLimit>) {
+        *self.default.write().unwrap() = limit;
+    }
+
+    /// Set (or clear) the limit of a specific slot.
+    pub fn set_slot_limit(&self, slot: impl Into<Slot>, limit: Option<RestartRateLimit>) {
+        let slot = slot.into();
+        let mut slots = self.slots.write().unwrap();
+        match limit {
+            Some(limit) => {
+                slots.insert(slot, limit);
+            }
+            None => {
+                slots.remove(&slot);
+            }
+        }
+    }
+
+    /// Effective limit for a slot.
+    pub fn limit_for(&self, slot: &str) -> Option<RestartRateLimit> {
+        if let Some(limit) = self.slots.read().unwrap().get(slot) {
+            return Some(*limit);
+        }
+        *self.default.read().unwrap()
+    }
+
+    /// Try to record a start for the slot.
+    ///
+    /// Returns `Ok(())` if the start is allowed, or `Err(wait)` with the time
+    /// until the oldest start leaves the window.
+    pub(crate) fn try_acquire(&self, slot: &str) -> Result<(), Duration> {
+        let Some(limit) = self.limit_for(slot) else {
+            return Ok(());
+        };
+        let window = Duration::from_millis(limit.window_ms);
+        let now = Instant::now();
+
+        let mut starts = self.starts.lock().unwrap();
+        let history = starts.entry(slot.to_string()).or_default();
+        while history
+            .front()
+            .is_some_and(|t| now.duration_since(*t) >= window)
+        {
+            history.pop_front();
+        }
+
+        if history.len() < limit.max_runs as usize {
+            history.push_back(now);
+            return Ok(());
+        }
+        let wait = history
+            .front()
+            .map(|oldest| window.saturating_sub(now.duration_since(*oldest)))
+            .unwrap_or(window);
+        Err(wait)
+    }
+
+    /// Wrap a task so that every execution waits for a start permit of `slot`.
+    pub(crate) fn wrap(self: &Arc<Self>, slot: Slot, task: TaskRef) -> TaskRef {
+        Arc::new(RateLimitedTask {
+            limiter: Arc::clone(self),
+            slot,
+            inner: task,
+        })
+    }
+}
+
+/// Task wrapper enforcing [`RestartLimiter`] before each execution.
+///
+/// Waiting for a permit happens inside the execution, so it counts towards the task timeout.
+struct RateLimitedTask {
+    limiter: Arc<RestartLimiter>,
+    slot: Slot,
+    inner: TaskRef,
+}
+
+impl Task for RateLimitedTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        let limiter = Arc::clone(&self.limiter);
+        let inner = Arc::clone(&self.inner);
+        let slot = self.slot.clone();
+
+        Box::pin(async move {
+            while let Err(wait) = limiter.try_acquire(&slot) {
+                debug!(%slot, wait_ms = wait.as_millis() as u64, "restart rate limit hit, delaying execution");
+                tokio::select! {
+                    _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                    _ = tokio::time::sleep(wait) => {}
+                }
+            }
+            inner.spawn(ctx).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_limit_always_allows() {
+        let limiter = RestartLimiter::new();
+        for _ in 0..100 {
+            assert!(limiter.try_acquire("slot").is_ok());
+        }
+    }
+
+    #[test]
+    fn limit_blocks_after_max_runs() {
+        let limiter = RestartLimiter::new();
+        limiter.set_default(Some(RestartRateLimit::per_minute(2)));
+
+        assert!(limiter.try_acquire("slot").is_ok());
+        assert!(limiter.try_acquire("slot").is_ok());
+        let wait = limiter.try_acquire("slot").unwrap_err();
+        assert!(wait > Duration::from_secs(59));
+
+        // Separate slots have separate windows.
+        assert!(limiter.try_acquire("other").is_ok());
+    }
+
+    #[test]
+    fn slot_limit_overrides_default() {
+        let limiter = RestartLimiter::new();
+        limiter.set_default(Some(RestartRateLimit::per_minute(1)));
+        limiter.set_slot_limit("busy", Some(RestartRateLimit::per_minute(3)));
+
+        assert_eq!(limiter.limit_for("busy").unwrap().max_runs, 3);
+        assert_eq!(limiter.limit_for("idle").unwrap().max_runs, 1);
+
+        limiter.set_slot_limit("busy", None);
+        assert_eq!(limiter.limit_for("busy").unwrap().max_runs, 1);
+    }
+
+    #[test]
+    fn window_expiry_frees_permits() {
+        let limiter = RestartLimiter::new();
+        limiter.set_default(Some(RestartRateLimit {
+            max_runs: 1,
+            window_ms: 20,
+        }));
+
+        assert!(limiter.try_acquire("slot").is_ok());
+        assert!(limiter.try_acquire("slot").is_err());
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(limiter.try_acquire("slot").is_ok());
+    }
+}
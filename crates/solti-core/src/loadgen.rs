@@ -0,0 +1,328 @@
+//! Load generator for the submit → route → state pipeline.
+//!
+//! [`run_load`] submits a weighted mix of specs at a fixed rate through a live
+//! [`SupervisorApi`] and reports achieved throughput, submit latency percentiles and
+//! the latency of state reads taken while the load runs (a proxy for contention on
+//! the task state). Meant for benchmarks and regression checks, not production agents.
+//!
+//! ```rust,ignore
+//! let profile = LoadProfile::new(500, Duration::from_secs(10))
+//!     .with_spec(3, echo_spec)
+//!     .with_spec(1, sleep_spec)
+//!     .with_slots(64);
+//! let report = run_load(&api, &profile).await;
+//! println!("{report}");
+//! ```
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+use solti_model::CreateSpec;
+use tokio::time::{MissedTickBehavior, interval};
+
+use crate::SupervisorApi;
+
+/// What to submit and how fast.
+#[derive(Debug, Clone)]
+pub struct LoadProfile {
+    rate_per_sec: u32,
+    duration: Duration,
+    mix: Vec<(u32, CreateSpec)>,
+    slots: usize,
+    drain: Duration,
+}
+
+impl LoadProfile {
+    /// Submit `rate_per_sec` specs per second for `duration`.
+    pub fn new(rate_per_sec: u32, duration: Duration) -> Self {
+        Self {
+            rate_per_sec: rate_per_sec.max(1),
+            duration,
+            mix: Vec::new(),
+            slots: 1,
+            drain: Duration::ZERO,
+        }
+    }
+
+    /// Add `spec` to the mix with relative `weight`.
+    ///
+    /// Specs are picked by smooth weighted round-robin, so runs are reproducible.
+    pub fn with_spec(mut self, weight: u32, spec: CreateSpec) -> Self {
+        if weight > 0 {
+            self.mix.push((weight, spec));
+        }
+        self
+    }
+
+    /// Spread each spec over `slots` slots (`<slot>-0` .. `<slot>-{n-1}`).
+    ///
+    /// With the default of one slot, admission strategies of the specs decide how
+    /// overlapping submissions are handled.
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.slots = slots.max(1);
+        self
+    }
+
+    /// After submitting, wait up to `drain` for submitted tasks to finish.
+    pub fn with_drain_timeout(mut self, drain: Duration) -> Self {
+        self.drain = drain;
+        self
+    }
+}
+
+/// Latency percentiles of one measured operation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LatencySummary {
+    pub samples: usize,
+    pub p50: Duration,
+    pub p90: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+impl LatencySummary {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_unstable();
+        let pick = |q: f64| samples[((samples.len() - 1) as f64 * q).round() as usize];
+        Self {
+            samples: samples.len(),
+            p50: pick(0.50),
+            p90: pick(0.90),
+            p99: pick(0.99),
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Result of a [`run_load`] run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport {
+    /// Accepted submissions.
+    pub submitted: u64,
+    /// Submissions rejected by admission, quotas, routing or the controller.
+    pub rejected: u64,
+    /// Submitted tasks that reached a terminal state by the end of the run.
+    pub completed: u64,
+    /// Time spent submitting (excluding the drain).
+    pub elapsed: Duration,
+    /// Time spent waiting for tasks to finish.
+    pub drained: Duration,
+    /// Accepted submissions per second.
+    pub throughput: f64,
+    pub submit_latency: LatencySummary,
+    /// Latency of listing all tasks while the load runs.
+    pub state_read_latency: LatencySummary,
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let row = |f: &mut fmt::Formatter<'_>, name: &str, l: &LatencySummary| {
+            writeln!(
+                f,
+                "{name:<11} n={} p50={:?} p90={:?} p99={:?} max={:?}",
+                l.samples, l.p50, l.p90, l.p99, l.max
+            )
+        };
+        writeln!(
+            f,
+            "submitted={} rejected={} completed={} elapsed={:?} drained={:?} throughput={:.1}/s",
+            self.submitted,
+            self.rejected,
+            self.completed,
+            self.elapsed,
+            self.drained,
+            self.throughput
+        )?;
+        row(f, "submit", &self.submit_latency)?;
+        row(f, "state read", &self.state_read_latency)
+    }
+}
+
+/// Run `profile` against `api` and report what was measured.
+///
+/// Submissions are paced by a fixed-rate interval; when a submission takes longer
+/// than the interval, missed ticks are skipped, so the achieved throughput shows
+/// where the pipeline saturates.
+pub async fn run_load(api: &SupervisorApi, profile: &LoadProfile) -> LoadReport {
+    let mut report = LoadReport::default();
+    if profile.mix.is_empty() {
+        return report;
+    }
+
+    let mut picker = WeightedRoundRobin::new(profile.mix.iter().map(|(w, _)| *w).collect());
+    let mut submit_samples = Vec::new();
+    let mut read_samples = Vec::new();
+    let mut ids = Vec::new();
+
+    let mut ticker = interval(Duration::from_secs(1) / profile.rate_per_sec);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    let start = Instant::now();
+    let mut n = 0usize;
+
+    while start.elapsed() < profile.duration {
+        ticker.tick().await;
+
+        let (_, base) = &profile.mix[picker.next()];
+        let mut spec = base.clone();
+        spec.slot = format!("{}-{}", base.slot, n % profile.slots);
+        n += 1;
+
+        let t = Instant::now();
+        let result = api.submit(&spec).await;
+        submit_samples.push(t.elapsed());
+        match result {
+            Ok(id) => {
+                report.submitted += 1;
+                ids.push(id);
+            }
+            Err(_) => report.rejected += 1,
+        }
+
+        let t = Instant::now();
+        let _ = api.list_all_tasks();
+        read_samples.push(t.elapsed());
+    }
+    report.elapsed = start.elapsed();
+
+    let drain_start = Instant::now();
+    loop {
+        // Tasks already dropped from the state count as finished.
+        let done = ids
+            .iter()
+            .filter(|id| api.get_task(id).is_none_or(|t| t.status.is_terminal()))
+            .count() as u64;
+        report.completed = done;
+        if done == report.submitted || drain_start.elapsed() >= profile.drain {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    report.drained = drain_start.elapsed();
+
+    report.throughput = report.submitted as f64 / report.elapsed.as_secs_f64().max(f64::EPSILON);
+    report.submit_latency = LatencySummary::from_samples(submit_samples);
+    report.state_read_latency = LatencySummary::from_samples(read_samples);
+    report
+}
+
+/// Smooth weighted round-robin (as used by nginx): deterministic and evenly interleaved.
+struct WeightedRoundRobin {
+    weights: Vec<i64>,
+    current: Vec<i64>,
+    total: i64,
+}
+
+impl WeightedRoundRobin {
+    fn new(weights: Vec<u32>) -> Self {
+        let weights: Vec<i64> = weights.into_iter().map(i64::from).collect();
+        Self {
+            current: vec![0; weights.len()],
+            total: weights.iter().sum(),
+            weights,
+        }
+    }
+
+    fn next(&mut self) -> usize {
+        for (c, w) in self.current.iter_mut().zip(&self.weights) {
+            *c += w;
+        }
+        let (best, _) = self
+            .current
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(&a.0)))
+            .expect("mix is not empty");
+        self.current[best] -= self.total;
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solti_model::{
+        AdmissionStrategy, AgentAction, BackoffStrategy, JitterStrategy, RestartStrategy,
+        RunnerLabels, TaskKind,
+    };
+    use taskvisor::{ControllerConfig, SupervisorConfig};
+
+    use super::*;
+    use crate::{AgentControlRunner, RunnerRouter};
+
+    #[test]
+    fn weighted_round_robin_interleaves() {
+        let mut rr = WeightedRoundRobin::new(vec![3, 1]);
+        let picks: Vec<_> = (0..8).map(|_| rr.next()).collect();
+        assert_eq!(picks, [0, 0, 1, 0, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn percentiles_of_samples() {
+        let samples = (1..=100).map(Duration::from_millis).collect();
+        let l = LatencySummary::from_samples(samples);
+        assert_eq!(l.samples, 100);
+        assert_eq!(l.p50, Duration::from_millis(51));
+        assert_eq!(l.p99, Duration::from_millis(99));
+        assert_eq!(l.max, Duration::from_millis(100));
+        assert_eq!(
+            LatencySummary::from_samples(Vec::new()),
+            LatencySummary::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn submits_mix_and_reports() {
+        let mut router = RunnerRouter::new();
+        router.register(std::sync::Arc::new(
+            AgentControlRunner::new().with_action(AgentAction::CollectGarbage, || Ok(())),
+        ));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let spec = |slot: &str, action| CreateSpec {
+            slot: slot.into(),
+            kind: TaskKind::AgentControl { action },
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+        let profile = LoadProfile::new(200, Duration::from_millis(200))
+            .with_spec(1, spec("gc", AgentAction::CollectGarbage))
+            // No handler for this action: every submission is rejected at build time.
+            .with_spec(1, spec("flush", AgentAction::FlushState))
+            .with_slots(4)
+            .with_drain_timeout(Duration::from_secs(5));
+
+        let report = run_load(&api, &profile).await;
+        assert!(report.submitted > 0, "{report}");
+        assert!(report.rejected > 0, "{report}");
+        assert!(report.submitted.abs_diff(report.rejected) <= 1, "{report}");
+        assert_eq!(report.completed, report.submitted, "{report}");
+        assert_eq!(
+            report.submit_latency.samples as u64,
+            report.submitted + report.rejected
+        );
+        assert!(report.to_string().contains("state read"));
+    }
+}
@@ -0,0 +1,122 @@
+//! Agent-wide maintenance mode.
+//!
+//! While maintenance is enabled, periodic tasks submitted through
+//! [`crate::SupervisorApi::submit`] keep their schedule and state but do not execute:
+//! each tick completes immediately and is recorded as [`solti_model::TaskStatus::Skipped`].
+//! Normal execution resumes on the first tick after maintenance is disabled.
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use solti_model::TaskId;
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+use crate::state::TaskState;
+
+/// Shared maintenance flag.
+#[derive(Debug, Default)]
+pub struct MaintenanceMode {
+    enabled: AtomicBool,
+}
+
+impl MaintenanceMode {
+    /// Create a flag with maintenance disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `true` if maintenance is currently enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// Enable or disable maintenance, returning the previous value.
+    pub fn set(&self, enabled: bool) -> bool {
+        let previous = self.enabled.swap(enabled, Ordering::AcqRel);
+        if previous != enabled {
+            info!(enabled, "maintenance mode changed");
+        }
+        previous
+    }
+
+    /// Wrap a task so that its executions are skipped while maintenance is enabled.
+    pub(crate) fn wrap(self: &Arc<Self>, state: TaskState, task: TaskRef) -> TaskRef {
+        Arc::new(MaintenanceTask {
+            mode: Arc::clone(self),
+            state,
+            inner: task,
+        })
+    }
+}
+
+/// Task wrapper skipping executions during maintenance.
+struct MaintenanceTask {
+    mode: Arc<MaintenanceMode>,
+    state: TaskState,
+    inner: TaskRef,
+}
+
+impl Task for MaintenanceTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        if self.mode.is_enabled() {
+            let id = TaskId::from(self.inner.name());
+            debug!(task = %id, "maintenance mode enabled, skipping run");
+            self.state.mark_skipped(&id);
+            return Box::pin(async { Ok(()) });
+        }
+        self.inner.spawn(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use taskvisor::TaskFn;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn skips_while_enabled() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let state = TaskState::new();
+        let id = TaskId::from("periodic");
+        state.add_task(id.clone(), "slot".into());
+
+        let counter = Arc::clone(&runs);
+        let task: TaskRef = TaskFn::arc("periodic", move |_ctx: CancellationToken| {
+            let counter = Arc::clone(&counter);
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        });
+
+        let mode = Arc::new(MaintenanceMode::new());
+        let task = mode.wrap(state.clone(), task);
+
+        assert!(!mode.set(true));
+        task.spawn(CancellationToken::new()).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert!(state.take_skipped(&id));
+
+        assert!(mode.set(false));
+        task.spawn(CancellationToken::new()).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(!state.take_skipped(&id));
+    }
+}
@@ -1,13 +1,15 @@
 //! Adapter layer between `solti-model` (public specs) and the taskvisor runtime.
 //!
 //! This crate maps high-level API types into taskvisor’s internal execution structures.
-use std::time::Duration;
+use std::time::{Duration, UNIX_EPOCH};
 
 use solti_model::{
-    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy,
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, Slot,
+    TaskEvent, TaskEventKind, TaskId,
 };
 use taskvisor::{
-    AdmissionPolicy, BackoffPolicy, ControllerSpec, JitterPolicy, RestartPolicy, TaskRef, TaskSpec,
+    AdmissionPolicy, BackoffPolicy, ControllerSpec, Event, EventKind, JitterPolicy, RestartPolicy,
+    TaskRef, TaskSpec,
 };
 
 /// Convert a high-level admission strategy from the public model into the controller admission policy used by taskvisor.
@@ -67,3 +69,49 @@ pub fn to_controller_spec(task: TaskRef, s: &CreateSpec) -> ControllerSpec {
         task_spec: to_task_spec(task, s),
     }
 }
+
+/// Convert a taskvisor event kind into the public event kind.
+pub fn to_task_event_kind(k: EventKind) -> TaskEventKind {
+    match k {
+        EventKind::ControllerSubmitted => TaskEventKind::ControllerSubmitted,
+        EventKind::ControllerRejected => TaskEventKind::ControllerRejected,
+        EventKind::ControllerSlotTransition => TaskEventKind::ControllerSlotTransition,
+        EventKind::TaskAddRequested => TaskEventKind::TaskAddRequested,
+        EventKind::TaskAdded => TaskEventKind::TaskAdded,
+        EventKind::TaskStarting => TaskEventKind::TaskStarting,
+        EventKind::TaskStopped => TaskEventKind::TaskStopped,
+        EventKind::TaskFailed => TaskEventKind::TaskFailed,
+        EventKind::TimeoutHit => TaskEventKind::TimeoutHit,
+        EventKind::BackoffScheduled => TaskEventKind::BackoffScheduled,
+        EventKind::ActorExhausted => TaskEventKind::ActorExhausted,
+        EventKind::ActorDead => TaskEventKind::ActorDead,
+        EventKind::TaskRemoveRequested => TaskEventKind::TaskRemoveRequested,
+        EventKind::TaskRemoved => TaskEventKind::TaskRemoved,
+        EventKind::ShutdownRequested => TaskEventKind::ShutdownRequested,
+        EventKind::AllStoppedWithinGrace => TaskEventKind::AllStoppedWithinGrace,
+        EventKind::GraceExceeded => TaskEventKind::GraceExceeded,
+        EventKind::SubscriberPanicked => TaskEventKind::SubscriberPanicked,
+        EventKind::SubscriberOverflow => TaskEventKind::SubscriberOverflow,
+    }
+}
+
+/// Build a public `TaskEvent` from a taskvisor event.
+///
+/// taskvisor events carry no slot; pass it when known.
+pub fn to_task_event(e: &Event, slot: Option<Slot>) -> TaskEvent {
+    TaskEvent {
+        seq: e.seq,
+        timestamp_ms: e
+            .at
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        kind: to_task_event_kind(e.kind),
+        task: e.task.as_deref().map(TaskId::from),
+        slot,
+        attempt: e.attempt,
+        reason: e.reason.as_deref().map(str::to_string),
+        timeout_ms: e.timeout_ms.map(u64::from),
+        delay_ms: e.delay_ms.map(u64::from),
+    }
+}
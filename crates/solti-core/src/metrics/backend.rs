@@ -26,6 +26,38 @@ impl TaskOutcome {
     }
 }
 
+/// Outcome of admitting a submitted task into its slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdmissionDecision {
+    /// Task was handed to the controller and runs once the slot is free.
+    Queued,
+    /// Task was handed to the controller and cancels the running task of the slot.
+    Replaced,
+    /// Task was dropped because the slot already had a running task.
+    DroppedRunning,
+    /// Task was refused (quota exceeded or controller rejected it).
+    Rejected,
+    /// Task was refused because the slot queue reached its length cap.
+    QueueFull,
+    /// An identical active task already existed; its id was returned instead.
+    Deduplicated,
+}
+
+impl AdmissionDecision {
+    /// Return label value for metrics.
+    #[inline]
+    pub fn as_label(&self) -> &'static str {
+        match self {
+            AdmissionDecision::Queued => "queued",
+            AdmissionDecision::Replaced => "replaced",
+            AdmissionDecision::DroppedRunning => "dropped_running",
+            AdmissionDecision::Rejected => "rejected",
+            AdmissionDecision::QueueFull => "queue_full",
+            AdmissionDecision::Deduplicated => "deduplicated",
+        }
+    }
+}
+
 /// Backend metrics collection interface.
 ///
 /// This trait abstracts metrics collection across different backends.
@@ -47,6 +79,15 @@ pub trait MetricsBackend: Send + Sync + 'static {
     /// - `outcome`: How the task terminated
     /// - `duration_ms`: Execution time in milliseconds
     fn record_task_completed(&self, runner_type: &str, outcome: TaskOutcome, duration_ms: u64);
+    /// Record how long a task waited between submission and its first start.
+    ///
+    /// Called by runners when the first attempt begins executing. Covers admission
+    /// backlog (busy slot, queued behind other tasks) but not restart backoff.
+    ///
+    /// # Arguments
+    /// - `runner_type`: Runner implementation
+    /// - `wait_ms`: Time since submission in milliseconds
+    fn record_task_queue_wait(&self, runner_type: &str, wait_ms: u64);
     /// Record runner-specific error during task setup/teardown.
     ///
     /// Called when runner fails to spawn/cleanup a task.
@@ -56,6 +97,43 @@ pub trait MetricsBackend: Send + Sync + 'static {
     /// - `runner_type`: Runner implementation
     /// - `error_kind`: Error category
     fn record_runner_error(&self, runner_type: &str, error_kind: &str);
+    /// Record the result of a runner health check.
+    ///
+    /// Called by the router after each health probe, whether or not the state changed.
+    ///
+    /// # Arguments
+    /// - `runner_type`: Runner implementation
+    /// - `healthy`: Whether the runner passed its health check
+    fn record_runner_health(&self, runner_type: &str, healthy: bool);
+    /// Record the number of tracked tasks in a status.
+    ///
+    /// Called for every status whenever the task state changes.
+    ///
+    /// # Arguments
+    /// - `status`: Task status label (e.g. "running")
+    /// - `count`: Tasks currently in that status
+    fn record_tasks_by_status(&self, status: &str, count: usize);
+    /// Record the number of slots with at least one pending or running task.
+    ///
+    /// Called whenever the task state changes.
+    fn record_active_slots(&self, count: usize);
+    /// Record lifecycle events that did not fit into a subscriber queue.
+    ///
+    /// Called by [`crate::BroadcastEventBus`] when events are dropped or spilled to disk.
+    ///
+    /// # Arguments
+    /// - `subscriber`: Subscriber name
+    /// - `policy`: Overflow policy label (e.g. "drop_oldest")
+    /// - `count`: Number of affected events
+    fn record_subscriber_overflow(&self, subscriber: &str, policy: &str, count: u64);
+    /// Record the admission decision for a submitted task.
+    ///
+    /// Called by [`crate::SupervisorApi::submit`] for every submission that reaches admission.
+    ///
+    /// # Arguments
+    /// - `strategy`: Admission strategy label (e.g. "drop-if-running")
+    /// - `decision`: What happened to the task
+    fn record_admission(&self, strategy: &str, decision: AdmissionDecision);
 }
 
 /// Shared handle to metrics backend.
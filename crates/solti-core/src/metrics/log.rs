@@ -0,0 +1,76 @@
+use tracing::debug;
+
+use crate::metrics::backend::{AdmissionDecision, MetricsBackend, TaskOutcome};
+
+/// Metrics backend writing every recording as a `debug` log event.
+///
+/// Meant for examples, tests and minimal builds without a metrics exporter.
+/// Enable with a `solti_core::metrics=debug` log filter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogMetrics;
+
+impl MetricsBackend for LogMetrics {
+    fn record_task_started(&self, runner_type: &str) {
+        debug!(runner_type, "metric: task started");
+    }
+
+    fn record_task_completed(&self, runner_type: &str, outcome: TaskOutcome, duration_ms: u64) {
+        debug!(
+            runner_type,
+            outcome = outcome.as_label(),
+            duration_ms,
+            "metric: task completed"
+        );
+    }
+
+    fn record_task_queue_wait(&self, runner_type: &str, wait_ms: u64) {
+        debug!(runner_type, wait_ms, "metric: task queue wait");
+    }
+
+    fn record_runner_error(&self, runner_type: &str, error_kind: &str) {
+        debug!(runner_type, error_kind, "metric: runner error");
+    }
+
+    fn record_runner_health(&self, runner_type: &str, healthy: bool) {
+        debug!(runner_type, healthy, "metric: runner health");
+    }
+
+    fn record_tasks_by_status(&self, status: &str, count: usize) {
+        debug!(status, count, "metric: tasks by status");
+    }
+
+    fn record_active_slots(&self, count: usize) {
+        debug!(count, "metric: active slots");
+    }
+
+    fn record_subscriber_overflow(&self, subscriber: &str, policy: &str, count: u64) {
+        debug!(subscriber, policy, count, "metric: subscriber overflow");
+    }
+
+    fn record_admission(&self, strategy: &str, decision: AdmissionDecision) {
+        debug!(
+            strategy,
+            decision = decision.as_label(),
+            "metric: admission"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_metrics_accepts_every_call() {
+        let metrics = LogMetrics;
+        metrics.record_task_started("test");
+        metrics.record_task_completed("test", TaskOutcome::Failure, 100);
+        metrics.record_task_queue_wait("test", 5);
+        metrics.record_runner_error("test", "error");
+        metrics.record_runner_health("test", true);
+        metrics.record_tasks_by_status("running", 1);
+        metrics.record_active_slots(1);
+        metrics.record_subscriber_overflow("journal", "block", 1);
+        metrics.record_admission("replace", AdmissionDecision::Replaced);
+    }
+}
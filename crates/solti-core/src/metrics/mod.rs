@@ -3,11 +3,20 @@
 //! This module provides a backend interface for collecting runtime metrics from task execution.
 //! Metrics backends (prometheus, statsd, etc) implement [`MetricsBackend`] and are injected via [`crate::BuildContext`].
 mod backend;
-pub use backend::{MetricsBackend, MetricsHandle, TaskOutcome};
+pub use backend::{AdmissionDecision, MetricsBackend, MetricsHandle, TaskOutcome};
 
 mod noop;
 pub use noop::NoOpMetrics;
 
+mod log;
+pub use log::LogMetrics;
+
+mod multi;
+pub use multi::MultiMetrics;
+
+mod state;
+pub(crate) use state::spawn_state_gauges;
+
 use std::sync::Arc;
 
 /// Create a no-op metrics handle.
@@ -0,0 +1,173 @@
+use crate::metrics::backend::{AdmissionDecision, MetricsBackend, MetricsHandle, TaskOutcome};
+
+/// Metrics backend forwarding every call to a list of backends.
+///
+/// Useful for dual-write setups (e.g. Prometheus scraping plus an exporter)
+/// without touching runners. Backends are called in the order they were added.
+#[derive(Clone, Default)]
+pub struct MultiMetrics {
+    backends: Vec<MetricsHandle>,
+}
+
+impl MultiMetrics {
+    /// Create a fan-out over `backends`.
+    pub fn new(backends: Vec<MetricsHandle>) -> Self {
+        Self { backends }
+    }
+
+    /// Add another backend.
+    pub fn with_backend(mut self, backend: MetricsHandle) -> Self {
+        self.backends.push(backend);
+        self
+    }
+
+    /// Number of backends receiving calls.
+    pub fn len(&self) -> usize {
+        self.backends.len()
+    }
+
+    /// Whether no backends are configured.
+    pub fn is_empty(&self) -> bool {
+        self.backends.is_empty()
+    }
+
+    #[inline]
+    fn each(&self, f: impl Fn(&dyn MetricsBackend)) {
+        for backend in &self.backends {
+            f(backend.as_ref());
+        }
+    }
+}
+
+impl MetricsBackend for MultiMetrics {
+    fn record_task_started(&self, runner_type: &str) {
+        self.each(|b| b.record_task_started(runner_type));
+    }
+
+    fn record_task_completed(&self, runner_type: &str, outcome: TaskOutcome, duration_ms: u64) {
+        self.each(|b| b.record_task_completed(runner_type, outcome, duration_ms));
+    }
+
+    fn record_task_queue_wait(&self, runner_type: &str, wait_ms: u64) {
+        self.each(|b| b.record_task_queue_wait(runner_type, wait_ms));
+    }
+
+    fn record_runner_error(&self, runner_type: &str, error_kind: &str) {
+        self.each(|b| b.record_runner_error(runner_type, error_kind));
+    }
+
+    fn record_runner_health(&self, runner_type: &str, healthy: bool) {
+        self.each(|b| b.record_runner_health(runner_type, healthy));
+    }
+
+    fn record_tasks_by_status(&self, status: &str, count: usize) {
+        self.each(|b| b.record_tasks_by_status(status, count));
+    }
+
+    fn record_active_slots(&self, count: usize) {
+        self.each(|b| b.record_active_slots(count));
+    }
+
+    fn record_subscriber_overflow(&self, subscriber: &str, policy: &str, count: u64) {
+        self.each(|b| b.record_subscriber_overflow(subscriber, policy, count));
+    }
+
+    fn record_admission(&self, strategy: &str, decision: AdmissionDecision) {
+        self.each(|b| b.record_admission(strategy, decision));
+    }
+}
+
+impl std::fmt::Debug for MultiMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiMetrics")
+            .field("backends", &self.backends.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct Calls(Mutex<Vec<String>>);
+
+    impl Calls {
+        fn push(&self, call: String) {
+            self.0.lock().unwrap().push(call);
+        }
+    }
+
+    impl MetricsBackend for Calls {
+        fn record_task_started(&self, runner_type: &str) {
+            self.push(format!("started {runner_type}"));
+        }
+        fn record_task_completed(&self, runner_type: &str, outcome: TaskOutcome, ms: u64) {
+            self.push(format!(
+                "completed {runner_type} {} {ms}",
+                outcome.as_label()
+            ));
+        }
+        fn record_task_queue_wait(&self, runner_type: &str, wait_ms: u64) {
+            self.push(format!("wait {runner_type} {wait_ms}"));
+        }
+        fn record_runner_error(&self, runner_type: &str, error_kind: &str) {
+            self.push(format!("error {runner_type} {error_kind}"));
+        }
+        fn record_runner_health(&self, runner_type: &str, healthy: bool) {
+            self.push(format!("health {runner_type} {healthy}"));
+        }
+        fn record_tasks_by_status(&self, status: &str, count: usize) {
+            self.push(format!("status {status} {count}"));
+        }
+        fn record_active_slots(&self, count: usize) {
+            self.push(format!("slots {count}"));
+        }
+        fn record_subscriber_overflow(&self, subscriber: &str, policy: &str, count: u64) {
+            self.push(format!("overflow {subscriber} {policy} {count}"));
+        }
+        fn record_admission(&self, strategy: &str, decision: AdmissionDecision) {
+            self.push(format!("admission {strategy} {}", decision.as_label()));
+        }
+    }
+
+    #[test]
+    fn forwards_every_call_to_all_backends() {
+        let first = Arc::new(Calls::default());
+        let second = Arc::new(Calls::default());
+        let multi = MultiMetrics::new(vec![first.clone()]).with_backend(second.clone());
+        assert_eq!(multi.len(), 2);
+
+        multi.record_task_started("subprocess");
+        multi.record_task_completed("subprocess", TaskOutcome::Timeout, 10);
+        multi.record_task_queue_wait("subprocess", 5);
+        multi.record_runner_error("subprocess", "spawn_failed");
+        multi.record_runner_health("subprocess", false);
+        multi.record_tasks_by_status("running", 2);
+        multi.record_active_slots(1);
+        multi.record_subscriber_overflow("journal", "drop_oldest", 3);
+        multi.record_admission("queue", AdmissionDecision::Queued);
+
+        let expected = vec![
+            "started subprocess",
+            "completed subprocess timeout 10",
+            "wait subprocess 5",
+            "error subprocess spawn_failed",
+            "health subprocess false",
+            "status running 2",
+            "slots 1",
+            "overflow journal drop_oldest 3",
+            "admission queue queued",
+        ];
+        assert_eq!(*first.0.lock().unwrap(), expected);
+        assert_eq!(*second.0.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn empty_fan_out_is_a_no_op() {
+        let multi = MultiMetrics::default();
+        assert!(multi.is_empty());
+        multi.record_task_started("subprocess");
+    }
+}
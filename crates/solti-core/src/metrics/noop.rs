@@ -1,4 +1,4 @@
-use crate::metrics::backend::{MetricsBackend, TaskOutcome};
+use crate::metrics::backend::{AdmissionDecision, MetricsBackend, TaskOutcome};
 
 /// No-op metrics backend that compiles to nothing.
 #[derive(Debug, Clone, Copy, Default)]
@@ -11,8 +11,26 @@ impl MetricsBackend for NoOpMetrics {
     #[inline(always)]
     fn record_task_completed(&self, _: &str, _: TaskOutcome, _: u64) {}
 
+    #[inline(always)]
+    fn record_task_queue_wait(&self, _: &str, _: u64) {}
+
     #[inline(always)]
     fn record_runner_error(&self, _: &str, _: &str) {}
+
+    #[inline(always)]
+    fn record_runner_health(&self, _: &str, _: bool) {}
+
+    #[inline(always)]
+    fn record_tasks_by_status(&self, _: &str, _: usize) {}
+
+    #[inline(always)]
+    fn record_active_slots(&self, _: usize) {}
+
+    #[inline(always)]
+    fn record_subscriber_overflow(&self, _: &str, _: &str, _: u64) {}
+
+    #[inline(always)]
+    fn record_admission(&self, _: &str, _: AdmissionDecision) {}
 }
 
 #[cfg(test)]
@@ -30,7 +48,13 @@ mod tests {
         for _ in 0..1000 {
             metrics.record_task_started("test");
             metrics.record_task_completed("test", TaskOutcome::Success, 100);
+            metrics.record_task_queue_wait("test", 5);
             metrics.record_runner_error("test", "error");
+            metrics.record_runner_health("test", true);
+            metrics.record_tasks_by_status("running", 1);
+            metrics.record_active_slots(1);
+            metrics.record_subscriber_overflow("journal", "drop_oldest", 1);
+            metrics.record_admission("queue", AdmissionDecision::Queued);
         }
     }
 }
@@ -0,0 +1,95 @@
+use tokio::sync::broadcast::error::{RecvError, TryRecvError};
+
+use crate::{metrics::MetricsHandle, state::TaskState};
+
+/// Keep task-state gauges in sync with `state`.
+///
+/// Publishes once on start and again after every batch of state changes,
+/// so dashboards can chart backlog without polling the task list.
+pub(crate) fn spawn_state_gauges(state: TaskState, metrics: MetricsHandle) {
+    let mut changes = state.watch();
+    publish(&state, &metrics);
+
+    tokio::spawn(async move {
+        loop {
+            match changes.recv().await {
+                Ok(_) | Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            }
+            // Coalesce bursts of changes into a single update.
+            while matches!(changes.try_recv(), Ok(_) | Err(TryRecvError::Lagged(_))) {}
+            publish(&state, &metrics);
+        }
+    });
+}
+
+fn publish(state: &TaskState, metrics: &MetricsHandle) {
+    for (status, count) in state.status_counts() {
+        metrics.record_tasks_by_status(status.as_str(), count);
+    }
+    metrics.record_active_slots(state.active_slots());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use solti_model::{TaskId, TaskStatus};
+
+    use crate::{AdmissionDecision, MetricsBackend, TaskOutcome};
+
+    #[derive(Default)]
+    struct Recorded {
+        by_status: Mutex<HashMap<String, usize>>,
+        active_slots: Mutex<usize>,
+    }
+
+    impl MetricsBackend for Recorded {
+        fn record_task_started(&self, _: &str) {}
+        fn record_task_completed(&self, _: &str, _: TaskOutcome, _: u64) {}
+        fn record_task_queue_wait(&self, _: &str, _: u64) {}
+        fn record_runner_error(&self, _: &str, _: &str) {}
+        fn record_runner_health(&self, _: &str, _: bool) {}
+
+        fn record_tasks_by_status(&self, status: &str, count: usize) {
+            self.by_status
+                .lock()
+                .unwrap()
+                .insert(status.to_string(), count);
+        }
+
+        fn record_active_slots(&self, count: usize) {
+            *self.active_slots.lock().unwrap() = count;
+        }
+
+        fn record_subscriber_overflow(&self, _: &str, _: &str, _: u64) {}
+        fn record_admission(&self, _: &str, _: AdmissionDecision) {}
+    }
+
+    #[tokio::test]
+    async fn gauges_follow_state_changes() {
+        let state = TaskState::new();
+        let recorded = Arc::new(Recorded::default());
+        spawn_state_gauges(state.clone(), recorded.clone());
+        assert_eq!(recorded.by_status.lock().unwrap()["pending"], 0);
+
+        let id = TaskId::from("task-1");
+        state.add_task(id.clone(), "slot".to_string());
+        state.update_status(&id, TaskStatus::Running, None);
+
+        for _ in 0..100 {
+            if recorded.by_status.lock().unwrap()["running"] == 1 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        assert_eq!(recorded.by_status.lock().unwrap()["running"], 1);
+        assert_eq!(recorded.by_status.lock().unwrap()["pending"], 0);
+        assert_eq!(*recorded.active_slots.lock().unwrap(), 1);
+    }
+}
@@ -0,0 +1,190 @@
+//! Captured task output forwarded to subscribers.
+//!
+//! Runners publish output lines through a [`TaskOutputBus`] carried by the
+//! [`crate::BuildContext`]; journal, Kafka or audit sinks subscribe to it via
+//! [`crate::SupervisorApi::watch_output`].
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use solti_model::{OutputStream, Slot, TaskId, TaskOutput};
+use tokio::sync::broadcast;
+
+/// Number of lines buffered per subscriber before it starts lagging.
+pub const DEFAULT_OUTPUT_CAPACITY: usize = 1024;
+
+/// Lines per second forwarded for a single task attempt by default.
+pub const DEFAULT_OUTPUT_RATE_LIMIT: u32 = 100;
+
+/// Broadcast channel of captured task output.
+///
+/// Lines are only built when at least one subscriber is attached.
+#[derive(Clone)]
+pub struct TaskOutputBus {
+    tx: broadcast::Sender<TaskOutput>,
+    rate_limit: u32,
+}
+
+impl TaskOutputBus {
+    /// Create a bus buffering up to `capacity` lines per subscriber.
+    pub fn new(capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        Self {
+            tx,
+            rate_limit: DEFAULT_OUTPUT_RATE_LIMIT,
+        }
+    }
+
+    /// Forward at most `lines_per_sec` lines per task attempt; `0` disables the limit.
+    pub fn with_rate_limit(mut self, lines_per_sec: u32) -> Self {
+        self.rate_limit = lines_per_sec;
+        self
+    }
+
+    /// Subscribe to output lines published after this call.
+    pub fn subscribe(&self) -> broadcast::Receiver<TaskOutput> {
+        self.tx.subscribe()
+    }
+
+    /// Create a rate-limited publisher for one attempt of a task.
+    pub fn publisher(&self, task: TaskId, slot: Slot, attempt: u32) -> OutputPublisher {
+        OutputPublisher {
+            tx: self.tx.clone(),
+            task,
+            slot,
+            attempt,
+            rate_limit: self.rate_limit,
+            window: Mutex::new(RateWindow::default()),
+        }
+    }
+}
+
+impl Default for TaskOutputBus {
+    fn default() -> Self {
+        Self::new(DEFAULT_OUTPUT_CAPACITY)
+    }
+}
+
+/// Publishes output of a single task attempt, enforcing the bus rate limit.
+///
+/// Shared by the stdout and stderr readers of the attempt.
+pub struct OutputPublisher {
+    tx: broadcast::Sender<TaskOutput>,
+    task: TaskId,
+    slot: Slot,
+    attempt: u32,
+    rate_limit: u32,
+    window: Mutex<RateWindow>,
+}
+
+impl OutputPublisher {
+    /// Publish a line.
+    ///
+    /// Returns `false` if the line was dropped by the rate limit;
+    /// lines published without subscribers count as delivered.
+    pub fn publish(&self, stream: OutputStream, line: &str) -> bool {
+        if !self.admit() {
+            return false;
+        }
+        if self.tx.receiver_count() == 0 {
+            return true;
+        }
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let _ = self.tx.send(TaskOutput {
+            task: self.task.clone(),
+            slot: self.slot.clone(),
+            attempt: self.attempt,
+            stream,
+            line: line.to_string(),
+            timestamp_ms,
+        });
+        true
+    }
+
+    /// Number of lines dropped by the rate limit so far.
+    pub fn dropped(&self) -> u64 {
+        self.window.lock().unwrap().dropped
+    }
+
+    fn admit(&self) -> bool {
+        if self.rate_limit == 0 {
+            return true;
+        }
+        let mut window = self.window.lock().unwrap();
+        let now = Instant::now();
+        if window
+            .started
+            .is_none_or(|started| now.duration_since(started) >= Duration::from_secs(1))
+        {
+            window.started = Some(now);
+            window.sent = 0;
+        }
+        if window.sent < self.rate_limit {
+            window.sent += 1;
+            true
+        } else {
+            window.dropped += 1;
+            false
+        }
+    }
+}
+
+/// Fixed one-second window of forwarded lines.
+#[derive(Default)]
+struct RateWindow {
+    started: Option<Instant>,
+    sent: u32,
+    dropped: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscribers_receive_structured_lines() {
+        let bus = TaskOutputBus::default();
+        let mut rx = bus.subscribe();
+        let publisher = bus.publisher(TaskId::from("r-backup-1"), "backup".into(), 2);
+
+        assert!(publisher.publish(OutputStream::Stdout, "hello"));
+        let out = rx.try_recv().unwrap();
+        assert_eq!(out.task.as_str(), "r-backup-1");
+        assert_eq!(out.slot, "backup");
+        assert_eq!(out.attempt, 2);
+        assert_eq!(out.stream, OutputStream::Stdout);
+        assert_eq!(out.line, "hello");
+    }
+
+    #[test]
+    fn rate_limit_applies_per_attempt() {
+        let bus = TaskOutputBus::default().with_rate_limit(3);
+        let mut rx = bus.subscribe();
+        let first = bus.publisher(TaskId::from("a"), "slot".into(), 1);
+        let second = bus.publisher(TaskId::from("b"), "slot".into(), 1);
+
+        let sent = (0..5)
+            .filter(|i| first.publish(OutputStream::Stdout, &i.to_string()))
+            .count();
+        assert_eq!(sent, 3);
+        assert_eq!(first.dropped(), 2);
+        assert!(second.publish(OutputStream::Stderr, "other task"));
+
+        let received: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(received.len(), 4);
+    }
+
+    #[test]
+    fn zero_rate_limit_is_unlimited() {
+        let bus = TaskOutputBus::default().with_rate_limit(0);
+        let publisher = bus.publisher(TaskId::from("a"), "slot".into(), 1);
+        assert!((0..1000).all(|_| publisher.publish(OutputStream::Stdout, "x")));
+        assert_eq!(publisher.dropped(), 0);
+    }
+}
@@ -1,5 +1,6 @@
 use solti_model::{
-    AdmissionStrategy, BackoffStrategy, CreateSpec, RestartStrategy, Slot, TimeoutMs,
+    AdmissionStrategy, BackoffStrategy, CreateSpec, ExecutionWindow, RestartStrategy, Slot,
+    TimeoutMs,
 };
 
 /// Runtime policy for a pre-built task.
@@ -13,6 +14,7 @@ pub struct TaskPolicy {
     pub restart: RestartStrategy,
     pub backoff: BackoffStrategy,
     pub admission: AdmissionStrategy,
+    pub window: Option<ExecutionWindow>,
 }
 
 impl TaskPolicy {
@@ -24,6 +26,7 @@ impl TaskPolicy {
             restart: spec.restart,
             backoff: spec.backoff.clone(),
             admission: spec.admission,
+            window: spec.window.clone(),
         }
     }
 
@@ -41,6 +44,15 @@ impl TaskPolicy {
             restart,
             backoff,
             admission,
+            window: None,
         }
     }
+
+    /// Restrict executions to an execution window.
+    ///
+    /// Runs attempted outside the window are skipped.
+    pub fn with_window(mut self, window: ExecutionWindow) -> Self {
+        self.window = Some(window);
+        self
+    }
 }
@@ -0,0 +1,153 @@
+//! Per-slot queue length caps.
+//!
+//! [`QueueLimits`] bounds the backlog that [`AdmissionStrategy::Queue`] can build up in a slot.
+//! The queue length of a slot is the number of its pending tasks; it is recomputed from
+//! [`TaskState`] on every submission.
+use std::{collections::HashMap, sync::RwLock};
+
+use solti_model::{AdmissionStrategy, CreateSpec, Slot, TaskStatus};
+
+use crate::{error::CoreError, state::TaskState};
+
+/// Runtime-adjustable queue length caps.
+///
+/// A slot-specific cap takes precedence over the default one.
+/// Caps can be changed at any time (e.g. on configuration reload);
+/// the new cap applies to the next submission.
+#[derive(Default)]
+pub struct QueueLimits {
+    default: RwLock<Option<usize>>,
+    slots: RwLock<HashMap<Slot, usize>>,
+}
+
+impl QueueLimits {
+    /// Create queue limits without any caps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set (or clear) the cap applied to slots without their own cap.
+    pub fn set_default(&self, max: Option<usize>) {
+        *self.default.write().unwrap() = max;
+    }
+
+    /// Set (or clear) the cap of a specific slot.
+    pub fn set_slot_limit(&self, slot: impl Into<Slot>, max: Option<usize>) {
+        let slot = slot.into();
+        let mut slots = self.slots.write().unwrap();
+        match max {
+            Some(max) => {
+                slots.insert(slot, max);
+            }
+            None => {
+                slots.remove(&slot);
+            }
+        }
+    }
+
+    /// Effective cap for a slot.
+    pub fn limit_for(&self, slot: &str) -> Option<usize> {
+        if let Some(max) = self.slots.read().unwrap().get(slot) {
+            return Some(*max);
+        }
+        *self.default.read().unwrap()
+    }
+
+    /// Refuse a queued submission that would grow the slot backlog beyond its cap.
+    ///
+    /// Only [`AdmissionStrategy::Queue`] is checked; a task that starts right away
+    /// (the slot has no active task) never counts as queued.
+    pub(crate) fn check(&self, spec: &CreateSpec, state: &TaskState) -> Result<(), CoreError> {
+        if spec.admission != AdmissionStrategy::Queue {
+            return Ok(());
+        }
+        let Some(max) = self.limit_for(&spec.slot) else {
+            return Ok(());
+        };
+
+        let tasks = state.list_by_slot(&spec.slot);
+        if !tasks.iter().any(|info| info.status.is_active()) {
+            return Ok(());
+        }
+        let queued = tasks
+            .iter()
+            .filter(|info| info.status == TaskStatus::Pending)
+            .count();
+        if queued >= max {
+            return Err(CoreError::QueueFull(format!(
+                "slot {}: {queued} queued tasks, limit {max}",
+                spec.slot
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_model::{
+        BackoffStrategy, JitterStrategy, RestartStrategy, RunnerLabels, TaskId, TaskKind,
+    };
+
+    fn spec(admission: AdmissionStrategy) -> CreateSpec {
+        CreateSpec {
+            slot: "backup".into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    #[test]
+    fn slot_limit_overrides_default() {
+        let limits = QueueLimits::new();
+        limits.set_default(Some(10));
+        limits.set_slot_limit("backup", Some(2));
+
+        assert_eq!(limits.limit_for("backup"), Some(2));
+        assert_eq!(limits.limit_for("other"), Some(10));
+
+        limits.set_slot_limit("backup", None);
+        assert_eq!(limits.limit_for("backup"), Some(10));
+    }
+
+    #[test]
+    fn rejects_queued_tasks_beyond_the_cap() {
+        let state = TaskState::new();
+        let limits = QueueLimits::new();
+        limits.set_slot_limit("backup", Some(1));
+        let queue = spec(AdmissionStrategy::Queue);
+
+        // Idle slot: the task starts right away.
+        limits.check(&queue, &state).unwrap();
+        state.add_task(TaskId::from("t1"), "backup".into());
+        state.update_status(&TaskId::from("t1"), TaskStatus::Running, None);
+
+        limits.check(&queue, &state).unwrap();
+        state.add_task(TaskId::from("t2"), "backup".into());
+
+        let err = limits.check(&queue, &state).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "queue full: slot backup: 1 queued tasks, limit 1"
+        );
+        // Other strategies never queue behind the running task.
+        limits
+            .check(&spec(AdmissionStrategy::Replace), &state)
+            .unwrap();
+    }
+}
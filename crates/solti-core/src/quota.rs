@@ -0,0 +1,315 @@
+//! Quota enforcement for submitted tasks.
+//!
+//! [`QuotaTracker`] remembers which tasks were admitted under quota and what they requested.
+//! Usage is recomputed on every admission from [`TaskState`]: tasks that left the state no
+//! longer count, and only active (pending or running) tasks consume quota. Restartable tasks
+//! keep their quota between runs, since the supervisor will run them again.
+//!
+//! A reservation made by [`QuotaTracker::admit`] holds until the supervisor confirms the task
+//! is registered in state ([`QuotaTracker::confirm`]); only confirmed reservations are dropped
+//! once their task leaves the state, so concurrent submissions cannot overrun a quota.
+use std::{collections::HashMap, sync::Mutex};
+
+use solti_model::{CreateSpec, RestartStrategy, RunnerLabels, TaskId, TaskQuota, TaskStatus};
+
+use crate::{error::CoreError, state::TaskState};
+
+/// Requests of an admitted task.
+struct Admitted {
+    namespace: Option<String>,
+    labels: RunnerLabels,
+    cpu_millis: u64,
+    memory_bytes: u64,
+    /// The task runs again after a terminal status.
+    restartable: bool,
+    /// The task is registered in state.
+    registered: bool,
+}
+
+/// Current consumption within a quota scope.
+#[derive(Default)]
+struct Usage {
+    tasks: usize,
+    /// Pending or running tasks.
+    running: usize,
+    cpu_millis: u64,
+    memory_bytes: u64,
+}
+
+/// Admission-time quota checks.
+pub(crate) struct QuotaTracker {
+    quotas: Vec<TaskQuota>,
+    admitted: Mutex<HashMap<TaskId, Admitted>>,
+}
+
+impl QuotaTracker {
+    pub(crate) fn new(quotas: Vec<TaskQuota>) -> Self {
+        Self {
+            quotas,
+            admitted: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Check the spec against all matching quotas and reserve its usage.
+    ///
+    /// Returns [`CoreError::QuotaExceeded`] naming the first violated limit.
+    pub(crate) fn admit(
+        &self,
+        id: &TaskId,
+        spec: &CreateSpec,
+        state: &TaskState,
+    ) -> Result<(), CoreError> {
+        let mut admitted = self.admitted.lock().unwrap();
+        admitted.retain(|id, task| !task.registered || state.get(id).is_some());
+
+        let request = spec.resources.unwrap_or_default();
+        let (cpu, mem) = (request.cpu_millis, request.memory_bytes);
+
+        for quota in self
+            .quotas
+            .iter()
+            .filter(|q| q.scope.matches(spec.namespace.as_deref(), &spec.labels))
+        {
+            let usage = usage(quota, &admitted, state);
+            let scope = quota.scope.to_string();
+
+            if let Some(max) = quota.max_tasks
+                && usage.tasks + 1 > max
+            {
+                return Err(exceeded(&scope, "tasks", usage.tasks, max));
+            }
+            if let Some(max) = quota.max_running
+                && usage.running >= max
+            {
+                return Err(exceeded(&scope, "running tasks", usage.running, max));
+            }
+            if let Some(max) = quota.max_cpu_millis
+                && usage.cpu_millis + cpu > max
+            {
+                return Err(exceeded(&scope, "cpu millis", usage.cpu_millis + cpu, max));
+            }
+            if let Some(max) = quota.max_memory_bytes
+                && usage.memory_bytes + mem > max
+            {
+                return Err(exceeded(
+                    &scope,
+                    "memory bytes",
+                    usage.memory_bytes + mem,
+                    max,
+                ));
+            }
+        }
+
+        admitted.insert(
+            id.clone(),
+            Admitted {
+                namespace: spec.namespace.clone(),
+                labels: spec.labels.clone(),
+                cpu_millis: cpu,
+                memory_bytes: mem,
+                restartable: spec.restart != RestartStrategy::Never,
+                registered: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Mark the reservation of a task as registered in state.
+    ///
+    /// From then on the reservation is dropped as soon as the task leaves the state.
+    pub(crate) fn confirm(&self, id: &TaskId) {
+        if let Some(task) = self.admitted.lock().unwrap().get_mut(id) {
+            task.registered = true;
+        }
+    }
+
+    /// Drop the reservation of a task that was not submitted after all.
+    pub(crate) fn release(&self, id: &TaskId) {
+        self.admitted.lock().unwrap().remove(id);
+    }
+}
+
+fn usage(quota: &TaskQuota, admitted: &HashMap<TaskId, Admitted>, state: &TaskState) -> Usage {
+    let mut usage = Usage::default();
+    for (id, task) in admitted {
+        if !quota.scope.matches(task.namespace.as_deref(), &task.labels) {
+            continue;
+        }
+        let status = match state.get(id) {
+            Some(info) => info.status,
+            // Newly admitted tasks may not be registered in state yet.
+            None if !task.registered => TaskStatus::Pending,
+            None => continue,
+        };
+        if !status.is_active() && !task.restartable {
+            continue;
+        }
+
+        usage.tasks += 1;
+        // Pending tasks are about to run: counting only running ones would let a burst of
+        // submissions through before any of them starts.
+        if status.is_active() {
+            usage.running += 1;
+        }
+        usage.cpu_millis += task.cpu_millis;
+        usage.memory_bytes += task.memory_bytes;
+    }
+    usage
+}
+
+fn exceeded(
+    scope: &str,
+    what: &str,
+    used: impl std::fmt::Display,
+    max: impl std::fmt::Display,
+) -> CoreError {
+    CoreError::QuotaExceeded(format!("{scope}: {what} {used} exceeds limit {max}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, QuotaScope, RestartStrategy, TaskKind,
+    };
+
+    fn spec(ns: &str) -> CreateSpec {
+        CreateSpec {
+            slot: "slot".into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+        .with_namespace(ns)
+    }
+
+    fn admit(
+        q: &QuotaTracker,
+        state: &TaskState,
+        id: &str,
+        spec: &CreateSpec,
+    ) -> Result<(), CoreError> {
+        let id = TaskId::from(id);
+        q.admit(&id, spec, state)?;
+        state.add_task(id.clone(), spec.slot.clone());
+        q.confirm(&id);
+        Ok(())
+    }
+
+    #[test]
+    fn max_tasks_rejects_and_frees_on_completion() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a")).with_max_tasks(2),
+        ]);
+
+        admit(&q, &state, "t1", &spec("a")).unwrap();
+        admit(&q, &state, "t2", &spec("a")).unwrap();
+        assert!(matches!(
+            admit(&q, &state, "t3", &spec("a")),
+            Err(CoreError::QuotaExceeded(_))
+        ));
+        // Other namespaces are not affected.
+        admit(&q, &state, "t4", &spec("b")).unwrap();
+
+        state.update_status(&TaskId::from("t1"), TaskStatus::Succeeded, None);
+        admit(&q, &state, "t3", &spec("a")).unwrap();
+    }
+
+    #[test]
+    fn max_running_counts_pending_tasks() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a")).with_max_running(2),
+        ]);
+
+        // None of the tasks has started yet.
+        admit(&q, &state, "t1", &spec("a")).unwrap();
+        admit(&q, &state, "t2", &spec("a")).unwrap();
+        assert!(matches!(
+            admit(&q, &state, "t3", &spec("a")),
+            Err(CoreError::QuotaExceeded(_))
+        ));
+
+        state.update_status(&TaskId::from("t1"), TaskStatus::Running, None);
+        state.update_status(&TaskId::from("t2"), TaskStatus::Succeeded, None);
+        admit(&q, &state, "t3", &spec("a")).unwrap();
+    }
+
+    #[test]
+    fn resource_requests_are_summed() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a"))
+                .with_max_cpu_millis(1_000)
+                .with_max_memory_bytes(1 << 20),
+        ]);
+
+        admit(&q, &state, "t1", &spec("a").with_cpu_request(600)).unwrap();
+        assert!(admit(&q, &state, "t2", &spec("a").with_cpu_request(500)).is_err());
+        admit(&q, &state, "t2", &spec("a").with_cpu_request(400)).unwrap();
+        assert!(admit(&q, &state, "t3", &spec("a").with_memory_request(2 << 20)).is_err());
+    }
+
+    #[test]
+    fn removed_tasks_release_quota() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a")).with_max_tasks(1),
+        ]);
+
+        admit(&q, &state, "t1", &spec("a")).unwrap();
+        state.remove_task(&TaskId::from("t1"));
+        admit(&q, &state, "t2", &spec("a")).unwrap();
+    }
+
+    #[test]
+    fn unregistered_reservations_count() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a")).with_max_tasks(1),
+        ]);
+
+        // A concurrent submission that has been admitted but not registered yet.
+        q.admit(&TaskId::from("t1"), &spec("a"), &state).unwrap();
+        assert!(matches!(
+            admit(&q, &state, "t2", &spec("a")),
+            Err(CoreError::QuotaExceeded(_))
+        ));
+
+        q.release(&TaskId::from("t1"));
+        admit(&q, &state, "t2", &spec("a")).unwrap();
+    }
+
+    #[test]
+    fn restartable_tasks_hold_quota_between_runs() {
+        let state = TaskState::new();
+        let q = QuotaTracker::new(vec![
+            TaskQuota::new(QuotaScope::namespace("a")).with_max_tasks(1),
+        ]);
+        let mut periodic = spec("a");
+        periodic.restart = RestartStrategy::Always {
+            interval_ms: Some(60_000),
+        };
+
+        admit(&q, &state, "t1", &periodic).unwrap();
+        state.update_status(&TaskId::from("t1"), TaskStatus::Succeeded, None);
+        assert!(admit(&q, &state, "t2", &spec("a")).is_err());
+
+        state.remove_task(&TaskId::from("t1"));
+        admit(&q, &state, "t2", &spec("a")).unwrap();
+    }
+}
@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use solti_model::{
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, RunnerLabels,
+    TaskKind,
+};
+use taskvisor::{TaskError, TaskFn, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::trace;
+
+use crate::router::RunnerRouter;
+
+/// Logical slot name used for the runner health check task.
+///
+/// Ensures that only one health sweep runs at any time.
+pub const RUNNER_HEALTH_SLOT: &str = "solti-runner-health";
+
+/// Build the periodic runner health check task and its model-level specification.
+///
+/// Every `interval_ms` the task calls [`RunnerRouter::check_health`], disabling
+/// runners that fail their probe and re-enabling those that recovered.
+///
+/// Returns:
+/// - [`TaskRef`]    — executable task body.
+/// - [`CreateSpec`] — restart/backoff/admission policy and slot binding.
+pub fn runner_health_check(router: Arc<RunnerRouter>, interval_ms: u64) -> (TaskRef, CreateSpec) {
+    let task: TaskRef = TaskFn::arc(RUNNER_HEALTH_SLOT, move |ctx: CancellationToken| {
+        let router = Arc::clone(&router);
+
+        async move {
+            if ctx.is_cancelled() {
+                return Err(TaskError::Canceled);
+            }
+            trace!("running runner health checks");
+            router.check_health().await;
+            Ok(())
+        }
+    });
+
+    let backoff = BackoffStrategy {
+        jitter: JitterStrategy::Equal,
+        first_ms: interval_ms,
+        max_ms: interval_ms,
+        factor: 1.0,
+    };
+    let spec = CreateSpec {
+        slot: RUNNER_HEALTH_SLOT.to_string(),
+        timeout_ms: interval_ms,
+        restart: RestartStrategy::periodic(interval_ms),
+        backoff,
+        admission: AdmissionStrategy::Replace,
+        kind: TaskKind::None,
+        labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
+    };
+    (task, spec)
+}
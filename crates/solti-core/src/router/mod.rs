@@ -2,14 +2,23 @@
 //!
 //! The router checks registered runners in order and delegates task construction
 //! to the first one that reports `supports(spec) == true` and matches label constraints (if any).
-use std::sync::Arc;
+//! Runners that failed their last health check are skipped until they recover.
+mod health;
+pub use health::runner_health_check;
 
-use solti_model::{CreateSpec, LABEL_RUNNER_TAG, RunnerLabels, TaskKind};
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use solti_model::{CreateSpec, LABEL_RUNNER_TAG, RunnerInfo, RunnerLabels, TaskKind};
 use taskvisor::TaskRef;
-use tracing::{debug, instrument, trace};
+use tracing::{debug, info, instrument, trace, warn};
 
 use crate::{
     error::CoreError,
+    limiter::RestartLimiter,
+    metrics::MetricsHandle,
     runner::{BuildContext, Runner},
 };
 
@@ -19,6 +28,22 @@ pub struct RunnerEntry {
     pub runner: Arc<dyn Runner>,
     /// Static labels attached to this runner (e.g. capacity class, backend tag).
     pub labels: RunnerLabels,
+    /// Result of the last health check (runners start healthy).
+    healthy: AtomicBool,
+}
+
+impl RunnerEntry {
+    fn new(runner: Arc<dyn Runner>, labels: RunnerLabels) -> Self {
+        Self {
+            runner,
+            labels,
+            healthy: AtomicBool::new(true),
+        }
+    }
+
+    fn is_healthy(&self) -> bool {
+        self.healthy.load(Ordering::Relaxed)
+    }
 }
 
 /// Router that selects an appropriate [`Runner`] for a given [`CreateSpec`].
@@ -29,6 +54,9 @@ pub struct RunnerEntry {
 pub struct RunnerRouter {
     runners: Vec<RunnerEntry>,
     ctx: BuildContext,
+    /// Restart limiter applied to each action run of watch tasks.
+    #[cfg_attr(not(feature = "watch"), allow(dead_code))]
+    limiter: Option<Arc<RestartLimiter>>,
 }
 
 impl RunnerRouter {
@@ -38,6 +66,7 @@ impl RunnerRouter {
         Self {
             runners: Vec::new(),
             ctx: BuildContext::default(),
+            limiter: None,
         }
     }
 
@@ -50,15 +79,21 @@ impl RunnerRouter {
         self
     }
 
+    /// Apply the restart limiter of the owning supervisor to watch actions.
+    ///
+    /// Watch tasks never end on their own, so the limiter is applied to every action run instead
+    /// of the watch itself.
+    pub(crate) fn set_restart_limiter(&mut self, limiter: Arc<RestartLimiter>) {
+        self.limiter = Some(limiter);
+    }
+
     /// Register a new runner without labels.
     ///
     /// Runners are queried in the order they are registered; the first one that reports `supports(spec) == true` (and matches labels, if any) is used.
     #[inline]
     pub fn register(&mut self, runner: Arc<dyn Runner>) {
-        self.runners.push(RunnerEntry {
-            runner,
-            labels: RunnerLabels::default(),
-        });
+        self.runners
+            .push(RunnerEntry::new(runner, RunnerLabels::default()));
     }
 
     /// Register a new runner with static labels.
@@ -66,12 +101,13 @@ impl RunnerRouter {
     /// These labels are used by the router to further narrow down candidates when [`CreateSpec::runner_tag`] is set.
     #[inline]
     pub fn register_with_labels(&mut self, runner: Arc<dyn Runner>, labels: RunnerLabels) {
-        self.runners.push(RunnerEntry { runner, labels });
+        self.runners.push(RunnerEntry::new(runner, labels));
     }
 
     /// Pick the first runner that claims to support the given spec and matches label selector.
     ///
     /// Routing rules:
+    /// - skip runners that failed their last health check;
     /// - filter runners by `Runner::supports(spec)`;
     /// - if `spec.runner_tag()` is set, keep only runners whose `labels` contain this tag;
     /// - pick the first matching entry.
@@ -80,6 +116,7 @@ impl RunnerRouter {
 
         self.runners
             .iter()
+            .filter(|entry| entry.is_healthy())
             .filter(|entry| entry.runner.supports(spec))
             .filter(move |entry| {
                 if let Some(wanted) = wanted {
@@ -98,8 +135,18 @@ impl RunnerRouter {
     /// Build a [`TaskRef`] for the given spec using the selected runner.
     ///
     /// `TaskKind::None` is not routable and must be used with [`SupervisorApi::submit_with_task`](crate::supervisor::SupervisorApi::submit_with_task).
+    /// `TaskKind::Watch` is built by the router itself (with the `watch` feature): its action is
+    /// routed like any other kind and the built task is wrapped in a filesystem watcher.
     #[instrument(level = "debug", skip(self, spec), fields(kind = ?spec.kind, slot = %spec.slot))]
     pub fn build(&self, spec: &CreateSpec) -> Result<TaskRef, CoreError> {
+        self.build_with_runner(spec).map(|(task, _)| task)
+    }
+
+    /// Same as [`RunnerRouter::build`], also returning the name of the runner that built the task.
+    pub(crate) fn build_with_runner(
+        &self,
+        spec: &CreateSpec,
+    ) -> Result<(TaskRef, &'static str), CoreError> {
         trace!(spec = ?spec, "router received spec");
 
         if matches!(spec.kind, TaskKind::None) {
@@ -107,13 +154,66 @@ impl RunnerRouter {
                 "TaskKind::None requires submit_with_task()".to_string(),
             ));
         }
+        if let TaskKind::Watch {
+            path,
+            events,
+            debounce_ms,
+            action,
+        } = &spec.kind
+        {
+            return self.build_watch(spec, path, events, *debounce_ms, action);
+        }
         let r = self
             .pick(spec)
             .ok_or_else(|| CoreError::NoRunner(spec.kind.kind().to_string()))?;
 
         let task = r.build_task(spec, &self.ctx).map_err(CoreError::from)?;
         debug!(runner = r.name(), "runner built task successfully");
-        Ok(task)
+        Ok((task, r.name()))
+    }
+
+    /// Build the action of a watch spec with the runner picked for it and wrap it in a watcher.
+    #[cfg(feature = "watch")]
+    fn build_watch(
+        &self,
+        spec: &CreateSpec,
+        path: &std::path::Path,
+        events: &[solti_model::WatchEvent],
+        debounce_ms: u64,
+        action: &TaskKind,
+    ) -> Result<(TaskRef, &'static str), CoreError> {
+        let action_spec = CreateSpec {
+            kind: action.clone(),
+            ..spec.clone()
+        };
+        let (task, runner) = self.build_with_runner(&action_spec)?;
+        let task = match &self.limiter {
+            Some(limiter) => limiter.wrap(spec.slot.clone(), task),
+            None => task,
+        };
+        let task = crate::watch::wrap_watch(
+            path.to_path_buf(),
+            events.to_vec(),
+            std::time::Duration::from_millis(debounce_ms),
+            Some(std::time::Duration::from_millis(spec.timeout_ms)),
+            task,
+        );
+        Ok((task, runner))
+    }
+
+    /// Watch specs need the `watch` feature.
+    #[cfg(not(feature = "watch"))]
+    fn build_watch(
+        &self,
+        _spec: &CreateSpec,
+        _path: &std::path::Path,
+        _events: &[solti_model::WatchEvent],
+        _debounce_ms: u64,
+        _action: &TaskKind,
+    ) -> Result<(TaskRef, &'static str), CoreError> {
+        Err(CoreError::NoRunner(
+            "watch (solti-core built without the `watch` feature)".to_string(),
+        ))
     }
 
     /// Returns `true` if at least one registered runner advertises the given runner-tag.
@@ -122,6 +222,64 @@ impl RunnerRouter {
             .iter()
             .any(|e| e.labels.get(LABEL_RUNNER_TAG) == Some(tag))
     }
+
+    /// Metrics handle of the build context.
+    pub(crate) fn metrics(&self) -> &MetricsHandle {
+        self.ctx.metrics()
+    }
+
+    /// Build context shared by all runners.
+    pub(crate) fn context(&self) -> &BuildContext {
+        &self.ctx
+    }
+
+    /// Run [`Runner::health_check`] on every registered runner and update its availability.
+    ///
+    /// Runners that fail are skipped by [`RunnerRouter::pick`] and re-enabled once a later check passes.
+    /// Every result is reported via [`crate::MetricsBackend::record_runner_health`].
+    pub async fn check_health(&self) {
+        for entry in &self.runners {
+            let name = entry.runner.name();
+            let healthy = match entry.runner.health_check().await {
+                Ok(()) => true,
+                Err(e) => {
+                    debug!(runner = name, error = %e, "runner health check failed");
+                    false
+                }
+            };
+
+            let was_healthy = entry.healthy.swap(healthy, Ordering::Relaxed);
+            match (was_healthy, healthy) {
+                (true, false) => warn!(runner = name, "runner is unhealthy; disabled for routing"),
+                (false, true) => info!(runner = name, "runner recovered; re-enabled for routing"),
+                _ => {}
+            }
+            self.ctx.metrics().record_runner_health(name, healthy);
+        }
+    }
+
+    /// Snapshot of all registered runners in registration order.
+    pub fn runners(&self) -> Vec<RunnerInfo> {
+        self.runners
+            .iter()
+            .map(|e| RunnerInfo {
+                name: e.runner.name().to_string(),
+                kinds: e.runner.kinds().iter().map(|k| k.to_string()).collect(),
+                labels: e.labels.clone(),
+                healthy: e.is_healthy(),
+            })
+            .collect()
+    }
+
+    /// Returns the result of the last health check for the runner with the given name.
+    ///
+    /// Returns `None` if no runner with this name is registered.
+    pub fn is_runner_healthy(&self, name: &str) -> Option<bool> {
+        self.runners
+            .iter()
+            .find(|e| e.runner.name() == name)
+            .map(RunnerEntry::is_healthy)
+    }
 }
 
 #[cfg(test)]
@@ -148,6 +306,10 @@ mod tests {
             matches!(spec.kind, TaskKind::Subprocess { .. })
         }
 
+        fn kinds(&self) -> &'static [&'static str] {
+            &["subprocess"]
+        }
+
         fn build_task(
             &self,
             _spec: &CreateSpec,
@@ -179,9 +341,42 @@ mod tests {
             backoff: mk_backoff(),
             admission: AdmissionStrategy::DropIfRunning,
             labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
         }
     }
 
+    #[test]
+    fn watch_routes_its_action() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(SubprocessRunnerDummy));
+        let spec = mk_spec(TaskKind::Watch {
+            path: PathBuf::from("/etc/app"),
+            events: Vec::new(),
+            debounce_ms: 100,
+            action: Box::new(TaskKind::Subprocess {
+                command: "reload".into(),
+                args: Vec::new(),
+                env: TaskEnv::default(),
+                cwd: None,
+                fail_on_non_zero: Flag::enabled(),
+            }),
+        });
+
+        let built = router.build_with_runner(&spec);
+        #[cfg(feature = "watch")]
+        {
+            let (task, runner) = built.expect("watch action should be routed");
+            assert_eq!(runner, "subprocess-only");
+            assert_eq!(task.name(), "test-subprocess-runner");
+        }
+        #[cfg(not(feature = "watch"))]
+        assert!(matches!(built, Err(CoreError::NoRunner(msg)) if msg.contains("watch")));
+    }
+
     #[test]
     fn build_fails_for_taskkind_none() {
         let router = RunnerRouter::new();
@@ -314,4 +509,85 @@ mod tests {
         let picked = router.pick(&spec).expect("runner should be picked");
         assert_eq!(picked.name(), "r2");
     }
+
+    #[tokio::test]
+    async fn pick_skips_unhealthy_runner_until_recovered() {
+        use std::sync::atomic::AtomicBool;
+
+        struct Flaky {
+            healthy: Arc<AtomicBool>,
+        }
+
+        #[async_trait::async_trait]
+        impl Runner for Flaky {
+            fn name(&self) -> &'static str {
+                "flaky"
+            }
+
+            fn supports(&self, spec: &CreateSpec) -> bool {
+                matches!(spec.kind, TaskKind::Subprocess { .. })
+            }
+
+            fn build_task(
+                &self,
+                _spec: &CreateSpec,
+                _ctx: &BuildContext,
+            ) -> Result<TaskRef, RunnerError> {
+                Ok(TaskFn::arc(
+                    "flaky-task",
+                    |_ctx: CancellationToken| async move { Ok::<(), TaskError>(()) },
+                ))
+            }
+
+            async fn health_check(&self) -> Result<(), RunnerError> {
+                if self.healthy.load(Ordering::Relaxed) {
+                    Ok(())
+                } else {
+                    Err(RunnerError::Unhealthy("backend down".into()))
+                }
+            }
+        }
+
+        let healthy = Arc::new(AtomicBool::new(false));
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(Flaky {
+            healthy: Arc::clone(&healthy),
+        }));
+        router.register(Arc::new(SubprocessRunnerDummy));
+
+        let spec = mk_spec(TaskKind::Subprocess {
+            command: "echo".into(),
+            args: Vec::new(),
+            env: TaskEnv::default(),
+            cwd: None,
+            fail_on_non_zero: Flag::enabled(),
+        });
+
+        assert_eq!(router.pick(&spec).unwrap().name(), "flaky");
+
+        router.check_health().await;
+        assert_eq!(router.is_runner_healthy("flaky"), Some(false));
+        assert_eq!(router.pick(&spec).unwrap().name(), "subprocess-only");
+
+        healthy.store(true, Ordering::Relaxed);
+        router.check_health().await;
+        assert_eq!(router.is_runner_healthy("flaky"), Some(true));
+        assert_eq!(router.pick(&spec).unwrap().name(), "flaky");
+    }
+
+    #[test]
+    fn runners_snapshot_reports_kinds_labels_and_health() {
+        let mut labels = RunnerLabels::new();
+        labels.insert(LABEL_RUNNER_TAG, "runner-a");
+
+        let mut router = RunnerRouter::new();
+        router.register_with_labels(Arc::new(SubprocessRunnerDummy), labels.clone());
+
+        let runners = router.runners();
+        assert_eq!(runners.len(), 1);
+        assert_eq!(runners[0].name, "subprocess-only");
+        assert_eq!(runners[0].kinds, vec!["subprocess".to_string()]);
+        assert_eq!(runners[0].labels, labels);
+        assert!(runners[0].healthy);
+    }
 }
@@ -0,0 +1,292 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use solti_model::CreateSpec;
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::{BuildContext, Runner, RunnerError};
+
+/// Fault probabilities for [`ChaosRunner`].
+///
+/// Each attempt draws one fault, checked in order: hang, fail, delay. Probabilities
+/// are clamped to `0.0..=1.0`; the same seed yields the same fault sequence.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    seed: u64,
+    hang: f64,
+    fail: f64,
+    delay: f64,
+    max_delay: Duration,
+}
+
+impl ChaosConfig {
+    /// No faults; add them with the `with_*` methods.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            hang: 0.0,
+            fail: 0.0,
+            delay: 0.0,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    /// Block attempts until they are canceled (by timeout or shutdown) with `probability`.
+    pub fn with_hang(mut self, probability: f64) -> Self {
+        self.hang = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Fail attempts with [`TaskError::Fail`] before running them with `probability`.
+    pub fn with_failure(mut self, probability: f64) -> Self {
+        self.fail = probability.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay attempts by up to `max_delay` before running them with `probability`.
+    pub fn with_delay(mut self, probability: f64, max_delay: Duration) -> Self {
+        self.delay = probability.clamp(0.0, 1.0);
+        self.max_delay = max_delay;
+        self
+    }
+
+    fn draw(&self, rng: &SplitMix64) -> Fault {
+        let roll = rng.next_f64();
+        if roll < self.hang {
+            Fault::Hang
+        } else if roll < self.hang + self.fail {
+            Fault::Fail
+        } else if roll < self.hang + self.fail + self.delay {
+            Fault::Delay(self.max_delay.mul_f64(rng.next_f64()))
+        } else {
+            Fault::None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fault {
+    None,
+    Delay(Duration),
+    Fail,
+    Hang,
+}
+
+/// Runner wrapper injecting faults into the tasks built by another runner.
+///
+/// Meant for validating restart, backoff and alerting configuration outside production:
+/// routing, names and health checks are those of the wrapped runner, but attempts
+/// randomly hang, fail or start late according to [`ChaosConfig`].
+///
+/// ```rust,ignore
+/// let chaos = ChaosConfig::new(42)
+///     .with_failure(0.2)
+///     .with_delay(0.3, Duration::from_secs(2));
+/// router.register(Arc::new(ChaosRunner::new(Arc::new(SubprocessRunner::new("default")), chaos)));
+/// ```
+pub struct ChaosRunner {
+    inner: Arc<dyn Runner>,
+    config: ChaosConfig,
+    rng: Arc<SplitMix64>,
+}
+
+impl ChaosRunner {
+    /// Wrap `inner` with the faults in `config`.
+    pub fn new(inner: Arc<dyn Runner>, config: ChaosConfig) -> Self {
+        Self {
+            inner,
+            rng: Arc::new(SplitMix64::new(config.seed)),
+            config,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Runner for ChaosRunner {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn supports(&self, spec: &CreateSpec) -> bool {
+        self.inner.supports(spec)
+    }
+
+    fn kinds(&self) -> &'static [&'static str] {
+        self.inner.kinds()
+    }
+
+    fn build_task(&self, spec: &CreateSpec, ctx: &BuildContext) -> Result<TaskRef, RunnerError> {
+        Ok(Arc::new(ChaosTask {
+            inner: self.inner.build_task(spec, ctx)?,
+            config: self.config,
+            rng: Arc::clone(&self.rng),
+        }))
+    }
+
+    fn build_run_id(&self, slot: &str, ctx: &BuildContext) -> String {
+        self.inner.build_run_id(slot, ctx)
+    }
+
+    async fn health_check(&self) -> Result<(), RunnerError> {
+        self.inner.health_check().await
+    }
+}
+
+struct ChaosTask {
+    inner: TaskRef,
+    config: ChaosConfig,
+    rng: Arc<SplitMix64>,
+}
+
+impl Task for ChaosTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        let fault = self.config.draw(&self.rng);
+        let task = self.inner.name().to_string();
+        match fault {
+            Fault::None => self.inner.spawn(ctx),
+            Fault::Fail => {
+                warn!(%task, "chaos: injecting failure");
+                Box::pin(async {
+                    Err(TaskError::Fail {
+                        reason: "chaos: injected failure".into(),
+                    })
+                })
+            }
+            Fault::Hang => {
+                warn!(%task, "chaos: hanging attempt until canceled");
+                Box::pin(async move {
+                    ctx.cancelled().await;
+                    Err(TaskError::Canceled)
+                })
+            }
+            Fault::Delay(delay) => {
+                warn!(%task, delay_ms = delay.as_millis() as u64, "chaos: delaying attempt");
+                let inner = Arc::clone(&self.inner);
+                Box::pin(async move {
+                    tokio::select! {
+                        _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                        _ = tokio::time::sleep(delay) => {}
+                    }
+                    inner.spawn(ctx).await
+                })
+            }
+        }
+    }
+}
+
+/// Small seeded PRNG shared by the tasks of one runner.
+struct SplitMix64(AtomicU64);
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self(AtomicU64::new(seed))
+    }
+
+    fn next_u64(&self) -> u64 {
+        let mut z = self
+            .0
+            .fetch_add(0x9E37_79B9_7F4A_7C15, Ordering::Relaxed)
+            .wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0.0..1.0`.
+    fn next_f64(&self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use taskvisor::TaskFn;
+
+    use super::*;
+
+    fn counting_task(runs: Arc<AtomicUsize>) -> TaskRef {
+        TaskFn::arc("chaos-target", move |_ctx: CancellationToken| {
+            let runs = Arc::clone(&runs);
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+    }
+
+    fn chaos_task(config: ChaosConfig, runs: Arc<AtomicUsize>) -> ChaosTask {
+        ChaosTask {
+            inner: counting_task(runs),
+            rng: Arc::new(SplitMix64::new(config.seed)),
+            config,
+        }
+    }
+
+    #[test]
+    fn faults_follow_probabilities_and_seed() {
+        let config = ChaosConfig::new(7)
+            .with_failure(0.25)
+            .with_delay(0.25, Duration::from_millis(100));
+        let draws = |config: ChaosConfig| {
+            let rng = SplitMix64::new(config.seed);
+            (0..4000).map(|_| config.draw(&rng)).collect::<Vec<_>>()
+        };
+
+        let faults = draws(config);
+        assert_eq!(faults, draws(config));
+        let fails = faults.iter().filter(|f| **f == Fault::Fail).count();
+        let delays = faults
+            .iter()
+            .filter(|f| matches!(f, Fault::Delay(d) if *d <= Duration::from_millis(100)))
+            .count();
+        assert!((800..1200).contains(&fails), "{fails}");
+        assert!((800..1200).contains(&delays), "{delays}");
+        assert!(!faults.contains(&Fault::Hang));
+
+        assert!(draws(ChaosConfig::new(7)).iter().all(|f| *f == Fault::None));
+    }
+
+    #[tokio::test]
+    async fn injected_faults_replace_or_defer_the_attempt() {
+        let runs = Arc::new(AtomicUsize::new(0));
+
+        let task = chaos_task(ChaosConfig::new(1).with_failure(1.0), Arc::clone(&runs));
+        assert!(matches!(
+            task.spawn(CancellationToken::new()).await,
+            Err(TaskError::Fail { .. })
+        ));
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        let task = chaos_task(
+            ChaosConfig::new(1).with_delay(1.0, Duration::from_millis(5)),
+            Arc::clone(&runs),
+        );
+        task.spawn(CancellationToken::new()).await.unwrap();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        let task = chaos_task(ChaosConfig::new(1).with_hang(1.0), Arc::clone(&runs));
+        let cancel = CancellationToken::new();
+        let attempt = task.spawn(cancel.clone());
+        cancel.cancel();
+        assert!(matches!(attempt.await, Err(TaskError::Canceled)));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+}
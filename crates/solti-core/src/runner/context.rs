@@ -1,20 +1,36 @@
-use std::fmt;
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
 
 use solti_model::TaskEnv;
 
-use crate::metrics::MetricsHandle;
+use super::{
+    CommandPolicy,
+    id::{RunIdGenerator, TaskIdGenerator, TaskIdGeneratorHandle},
+};
+use crate::{metrics::MetricsHandle, output::TaskOutputBus};
 
 /// Shared build context passed to all runners.
 #[derive(Clone)]
 pub struct BuildContext {
     env: TaskEnv,
     metrics: MetricsHandle,
+    task_ids: Arc<RwLock<TaskIdGeneratorHandle>>,
+    output: TaskOutputBus,
+    commands: CommandPolicy,
 }
 
 impl BuildContext {
     /// Create a new build context with the given params.
     pub fn new(env: TaskEnv, metrics: MetricsHandle) -> Self {
-        Self { env, metrics }
+        Self {
+            env,
+            metrics,
+            task_ids: default_task_ids(),
+            output: TaskOutputBus::default(),
+            commands: CommandPolicy::default(),
+        }
     }
 
     /// Get a reference to the shared environment.
@@ -38,6 +54,44 @@ impl BuildContext {
         self.metrics = metrics;
         self
     }
+
+    /// Bus receiving output captured by runners.
+    pub fn output(&self) -> &TaskOutputBus {
+        &self.output
+    }
+
+    /// Replace the output bus and return updated context.
+    pub fn with_output(mut self, output: TaskOutputBus) -> Self {
+        self.output = output;
+        self
+    }
+
+    /// Policy restricting which programs subprocess tasks may run.
+    pub fn command_policy(&self) -> &CommandPolicy {
+        &self.commands
+    }
+
+    /// Replace the command policy and return updated context.
+    pub fn with_command_policy(mut self, policy: CommandPolicy) -> Self {
+        self.commands = policy;
+        self
+    }
+
+    /// Replace the task id generator and return updated context.
+    pub fn with_task_ids(mut self, generator: impl TaskIdGenerator + 'static) -> Self {
+        self.task_ids = Arc::new(RwLock::new(Arc::new(generator)));
+        self
+    }
+
+    /// Generate an id for a new task of `slot` built by `runner`.
+    pub fn task_id(&self, runner: &str, slot: &str) -> String {
+        self.task_ids.read().unwrap().generate(runner, slot)
+    }
+
+    /// Swap the task id generator in place; shared by all clones of this context.
+    pub(crate) fn set_task_ids(&self, generator: TaskIdGeneratorHandle) {
+        *self.task_ids.write().unwrap() = generator;
+    }
 }
 
 impl Default for BuildContext {
@@ -45,15 +99,23 @@ impl Default for BuildContext {
         Self {
             env: TaskEnv::default(),
             metrics: crate::metrics::noop_metrics(),
+            task_ids: default_task_ids(),
+            output: TaskOutputBus::default(),
+            commands: CommandPolicy::default(),
         }
     }
 }
 
+fn default_task_ids() -> Arc<RwLock<TaskIdGeneratorHandle>> {
+    Arc::new(RwLock::new(Arc::new(RunIdGenerator)))
+}
+
 impl fmt::Debug for BuildContext {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("BuildContext")
             .field("env_len", &self.env.len())
             .field("metrics", &"<handle>")
+            .field("commands", &self.commands)
             .finish()
     }
 }
@@ -117,6 +179,22 @@ mod tests {
         ctx.metrics().record_task_started("test");
     }
 
+    #[test]
+    fn with_task_ids_replaces_generator() {
+        let ctx = BuildContext::default();
+        assert!(
+            ctx.task_id("runner-a", "slot")
+                .starts_with("runner-a-slot-")
+        );
+
+        let ctx = ctx.with_task_ids(crate::PrefixSequenceGenerator::new("job"));
+        assert_eq!(ctx.task_id("runner-a", "slot"), "job-0000000000000001");
+
+        let shared = ctx.clone();
+        ctx.set_task_ids(std::sync::Arc::new(crate::UuidV4Generator));
+        assert_eq!(shared.task_id("runner-a", "slot").len(), 36);
+    }
+
     #[test]
     fn display_includes_env_length() {
         let mut env = TaskEnv::new();
@@ -0,0 +1,198 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use solti_model::{AgentAction, CreateSpec, TaskKind};
+use taskvisor::{TaskError, TaskFn, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info};
+
+use super::{BuildContext, Runner, RunnerError};
+
+type ActionHandler = Arc<dyn Fn() -> Result<(), String> + Send + Sync>;
+
+/// Built-in runner executing [`TaskKind::AgentControl`] tasks.
+///
+/// Only actions with a registered handler can run; the host wires them to its own
+/// config reloader, log setup or state store. Specs naming any other action fail
+/// to build with [`RunnerError::InvalidSpec`]. Handlers run on the async runtime
+/// and should return quickly; a returned error fails the task.
+///
+/// ```rust
+/// # use std::sync::Arc;
+/// # use solti_core::{AgentControlRunner, RunnerRouter};
+/// # use solti_model::AgentAction;
+/// let runner = AgentControlRunner::new()
+///     .with_action(AgentAction::RotateLogs, || Ok(()));
+///
+/// let mut router = RunnerRouter::new();
+/// router.register(Arc::new(runner));
+/// ```
+#[derive(Clone, Default)]
+pub struct AgentControlRunner {
+    handlers: HashMap<AgentAction, ActionHandler>,
+}
+
+impl AgentControlRunner {
+    /// Runner without enabled actions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enable `action`, running `handler` when a control task requests it.
+    pub fn with_action<F>(mut self, action: AgentAction, handler: F) -> Self
+    where
+        F: Fn() -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.handlers.insert(action, Arc::new(handler));
+        self
+    }
+
+    /// Returns `true` if `action` has a handler.
+    pub fn is_enabled(&self, action: AgentAction) -> bool {
+        self.handlers.contains_key(&action)
+    }
+}
+
+impl Runner for AgentControlRunner {
+    fn name(&self) -> &'static str {
+        "agent-control"
+    }
+
+    fn supports(&self, spec: &CreateSpec) -> bool {
+        matches!(spec.kind, TaskKind::AgentControl { .. })
+    }
+
+    fn kinds(&self) -> &'static [&'static str] {
+        &["agent-control"]
+    }
+
+    fn build_task(&self, spec: &CreateSpec, ctx: &BuildContext) -> Result<TaskRef, RunnerError> {
+        let action = match &spec.kind {
+            TaskKind::AgentControl { action } => *action,
+            other => {
+                return Err(RunnerError::UnsupportedKind {
+                    runner: self.name(),
+                    kind: other.kind().to_string(),
+                });
+            }
+        };
+        let handler = self.handlers.get(&action).cloned().ok_or_else(|| {
+            RunnerError::InvalidSpec(format!("agent action '{}' is not enabled", action.as_str()))
+        })?;
+
+        let run_id = self.build_run_id(&spec.slot, ctx);
+        let slot = spec.slot.clone();
+        Ok(TaskFn::arc(
+            run_id.clone(),
+            move |cancel: CancellationToken| {
+                let handler = Arc::clone(&handler);
+                let run_id = run_id.clone();
+                let slot = slot.clone();
+                async move {
+                    if cancel.is_cancelled() {
+                        return Err(TaskError::Canceled);
+                    }
+                    debug!(task = %run_id, %slot, action = action.as_str(), "running agent action");
+                    match handler() {
+                        Ok(()) => {
+                            info!(task = %run_id, %slot, action = action.as_str(), "agent action completed");
+                            Ok(())
+                        }
+                        Err(reason) => Err(TaskError::Fail {
+                            reason: format!("agent action '{}' failed: {reason}", action.as_str()),
+                        }),
+                    }
+                }
+            },
+        ))
+    }
+}
+
+impl fmt::Debug for AgentControlRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let actions: Vec<_> = AgentAction::ALL
+            .iter()
+            .filter(|a| self.is_enabled(**a))
+            .map(AgentAction::as_str)
+            .collect();
+        f.debug_struct("AgentControlRunner")
+            .field("actions", &actions)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, RunnerLabels,
+    };
+
+    use super::*;
+    use crate::metrics::noop_metrics;
+
+    fn spec(action: AgentAction) -> CreateSpec {
+        CreateSpec {
+            slot: "agent-maintenance".into(),
+            kind: TaskKind::AgentControl { action },
+            timeout_ms: 5_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    fn ctx() -> BuildContext {
+        BuildContext::new(Default::default(), noop_metrics())
+    }
+
+    #[tokio::test]
+    async fn runs_enabled_actions() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let runner = AgentControlRunner::new()
+            .with_action(AgentAction::ReloadConfig, move || {
+                counter.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            })
+            .with_action(AgentAction::FlushState, || Err("store offline".into()));
+
+        let task = runner
+            .build_task(&spec(AgentAction::ReloadConfig), &ctx())
+            .unwrap();
+        task.spawn(CancellationToken::new()).await.unwrap();
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let task = runner
+            .build_task(&spec(AgentAction::FlushState), &ctx())
+            .unwrap();
+        match task.spawn(CancellationToken::new()).await {
+            Err(TaskError::Fail { reason }) => {
+                assert!(reason.contains("store offline"), "{reason}")
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_actions_without_handler() {
+        let runner = AgentControlRunner::new().with_action(AgentAction::RotateLogs, || Ok(()));
+        assert!(runner.supports(&spec(AgentAction::CollectGarbage)));
+        match runner.build_task(&spec(AgentAction::CollectGarbage), &ctx()) {
+            Err(RunnerError::InvalidSpec(msg)) => assert!(msg.contains("collect-garbage"), "{msg}"),
+            Err(other) => panic!("unexpected error: {other}"),
+            Ok(_) => panic!("disabled action was built"),
+        }
+    }
+}
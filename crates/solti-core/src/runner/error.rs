@@ -16,6 +16,12 @@ pub enum RunnerError {
 
     #[error("io error: {0}")]
     Io(String),
+
+    #[error("runner unhealthy: {0}")]
+    Unhealthy(String),
+
+    #[error("command '{command}' denied: {reason}")]
+    CommandDenied { command: String, reason: String },
 }
 
 impl From<std::io::Error> for RunnerError {
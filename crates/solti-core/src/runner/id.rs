@@ -1,4 +1,7 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
 
 /// Global monotonically increasing sequence for run identifiers.
 ///
@@ -19,3 +22,104 @@ fn next_seq() -> u64 {
 pub fn make_run_id(runner_name: &str, slot: &str) -> String {
     format!("{runner_name}-{slot}-{seq:x}", seq = next_seq())
 }
+
+/// Strategy producing task ids for newly built tasks.
+///
+/// The id becomes the taskvisor task name and the [`solti_model::TaskId`] reported by the API,
+/// so it must be unique within the agent.
+pub trait TaskIdGenerator: Send + Sync {
+    /// Produce a new id for a task of `slot` built by `runner`.
+    fn generate(&self, runner: &str, slot: &str) -> String;
+}
+
+/// Shared handle to a task id generator.
+pub type TaskIdGeneratorHandle = Arc<dyn TaskIdGenerator>;
+
+/// Default generator: `{runner}-{slot}-{seq:x}` (see [`make_run_id`]).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunIdGenerator;
+
+impl TaskIdGenerator for RunIdGenerator {
+    fn generate(&self, runner: &str, slot: &str) -> String {
+        make_run_id(runner, slot)
+    }
+}
+
+/// Random UUIDv4 ids.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV4Generator;
+
+impl TaskIdGenerator for UuidV4Generator {
+    fn generate(&self, _runner: &str, _slot: &str) -> String {
+        uuid::Uuid::new_v4().to_string()
+    }
+}
+
+/// Time-ordered UUIDv7 ids; ids sort by creation time.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UuidV7Generator;
+
+impl TaskIdGenerator for UuidV7Generator {
+    fn generate(&self, _runner: &str, _slot: &str) -> String {
+        uuid::Uuid::now_v7().to_string()
+    }
+}
+
+/// `{prefix}-{seq:016x}` ids with a zero-padded hex sequence.
+///
+/// The padding keeps lexical order equal to creation order.
+#[derive(Debug)]
+pub struct PrefixSequenceGenerator {
+    prefix: String,
+    seq: AtomicU64,
+}
+
+impl PrefixSequenceGenerator {
+    /// Create a generator starting at sequence 1.
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+            seq: AtomicU64::new(1),
+        }
+    }
+}
+
+impl TaskIdGenerator for PrefixSequenceGenerator {
+    fn generate(&self, _runner: &str, _slot: &str) -> String {
+        let seq = self.seq.fetch_add(1, Ordering::Relaxed);
+        format!("{}-{seq:016x}", self.prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_ids_keep_runner_and_slot() {
+        let id = RunIdGenerator.generate("runner-a", "backup");
+        assert!(id.starts_with("runner-a-backup-"));
+    }
+
+    #[test]
+    fn uuid_generators_produce_versioned_uuids() {
+        let v4 = uuid::Uuid::parse_str(&UuidV4Generator.generate("r", "s")).unwrap();
+        assert_eq!(v4.get_version_num(), 4);
+
+        let ids: Vec<String> = (0..50)
+            .map(|_| UuidV7Generator.generate("r", "s"))
+            .collect();
+        let v7 = uuid::Uuid::parse_str(&ids[0]).unwrap();
+        assert_eq!(v7.get_version_num(), 7);
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn prefix_sequence_sorts_lexically() {
+        let g = PrefixSequenceGenerator::new("agent-1");
+        assert_eq!(g.generate("r", "s"), "agent-1-0000000000000001");
+
+        let ids: Vec<String> = (0..20).map(|_| g.generate("r", "s")).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+}
@@ -7,9 +7,24 @@ pub use error::RunnerError;
 mod context;
 pub use context::BuildContext;
 
+mod policy;
+pub use policy::CommandPolicy;
+
+mod control;
+pub use control::AgentControlRunner;
+
+#[cfg(feature = "chaos")]
+mod chaos;
+#[cfg(feature = "chaos")]
+pub use chaos::{ChaosConfig, ChaosRunner};
+
 mod id;
-pub use id::make_run_id;
+pub use id::{
+    PrefixSequenceGenerator, RunIdGenerator, TaskIdGenerator, TaskIdGeneratorHandle,
+    UuidV4Generator, UuidV7Generator, make_run_id,
+};
 
+use async_trait::async_trait;
 use solti_model::CreateSpec;
 use taskvisor::TaskRef;
 
@@ -18,6 +33,8 @@ use taskvisor::TaskRef;
 /// A runner is responsible for:
 /// - deciding whether it can handle a given [`CreateSpec`] (`supports`)
 /// - building a concrete [`TaskRef`] that the supervisor can execute (`build_task`)
+/// - optionally reporting backend availability (`health_check`)
+#[async_trait]
 pub trait Runner: Send + Sync {
     /// Runner name used in logs and diagnostics.
     fn name(&self) -> &'static str;
@@ -25,16 +42,32 @@ pub trait Runner: Send + Sync {
     /// Returns `true` if this runner can handle the given spec.
     fn supports(&self, spec: &CreateSpec) -> bool;
 
+    /// Task kinds this runner can execute (see [`solti_model::TaskKind::kind`]).
+    ///
+    /// Only used to advertise capabilities; routing always goes through [`Runner::supports`].
+    fn kinds(&self) -> &'static [&'static str] {
+        &[]
+    }
+
     /// Build a concrete [`TaskRef`] for the given spec.
     ///
     /// The provided [`BuildContext`] carries shared dependencies injected at router setup time.
     fn build_task(&self, spec: &CreateSpec, ctx: &BuildContext) -> Result<TaskRef, RunnerError>;
 
-    /// Builds a default run id for a given slot.
+    /// Builds a run id for a given slot.
     ///
     /// Runners may override this if they need custom id format,
-    /// otherwise the core helper is used.
-    fn build_run_id(&self, slot: &str) -> String {
-        make_run_id(self.name(), slot)
+    /// otherwise the generator configured on the [`BuildContext`] is used.
+    fn build_run_id(&self, slot: &str, ctx: &BuildContext) -> String {
+        ctx.task_id(self.name(), slot)
+    }
+
+    /// Check whether the runner backend is currently usable.
+    ///
+    /// Runners with external dependencies (docker socket, wasm engine, etc.) may override this.
+    /// Unhealthy runners are skipped by the router until a later check succeeds.
+    /// Probes run on the async runtime and must not block it (use async I/O or `spawn_blocking`).
+    async fn health_check(&self) -> Result<(), RunnerError> {
+        Ok(())
     }
 }
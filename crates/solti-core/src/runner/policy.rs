@@ -0,0 +1,216 @@
+use super::RunnerError;
+
+/// Program names treated as shells by [`CommandPolicy::with_deny_shells`].
+const SHELLS: &[&str] = &[
+    "sh",
+    "bash",
+    "dash",
+    "zsh",
+    "ksh",
+    "mksh",
+    "ash",
+    "csh",
+    "tcsh",
+    "fish",
+    "busybox",
+    "pwsh",
+    "powershell",
+    "powershell.exe",
+    "cmd",
+    "cmd.exe",
+];
+
+/// Restricts which programs subprocess tasks may run.
+///
+/// Checked by runners while building a task, so a rejected command never starts;
+/// the build fails with [`RunnerError::CommandDenied`].
+///
+/// Patterns are matched against the command as submitted (no `PATH` lookup):
+/// - a pattern containing `/` matches the whole command, e.g. `/usr/bin/rsync`;
+/// - a pattern without `/` matches the program name (last path component), e.g. `rsync`;
+/// - `*` matches any run of characters except `/`, `?` a single one: `/opt/jobs/*`.
+///
+/// Rules are applied in order: shells (if denied), the denylist, then the allowlist.
+/// An empty allowlist allows everything not denied. Prefer absolute paths in
+/// allowlists: a bare name such as `rsync` also matches `/tmp/rsync`.
+///
+/// ```rust
+/// # use solti_core::CommandPolicy;
+/// let policy = CommandPolicy::default()
+///     .with_allowed("/opt/jobs/*")
+///     .with_allowed("/usr/bin/rsync")
+///     .with_deny_shells(true);
+///
+/// assert!(policy.check("/opt/jobs/backup").is_ok());
+/// assert!(policy.check("/bin/sh").is_err());
+/// assert!(policy.check("curl").is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandPolicy {
+    allow: Vec<String>,
+    deny: Vec<String>,
+    deny_shells: bool,
+}
+
+impl CommandPolicy {
+    /// Policy allowing every command (same as `Default`).
+    pub fn allow_all() -> Self {
+        Self::default()
+    }
+
+    /// Add a pattern to the allowlist; once non-empty, only matching commands may run.
+    pub fn with_allowed(mut self, pattern: impl Into<String>) -> Self {
+        self.allow.push(pattern.into());
+        self
+    }
+
+    /// Add a pattern to the denylist.
+    pub fn with_denied(mut self, pattern: impl Into<String>) -> Self {
+        self.deny.push(pattern.into());
+        self
+    }
+
+    /// Reject well-known shells and `busybox` regardless of their path.
+    pub fn with_deny_shells(mut self, deny: bool) -> Self {
+        self.deny_shells = deny;
+        self
+    }
+
+    /// Returns `true` if the policy does not restrict anything.
+    pub fn is_unrestricted(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty() && !self.deny_shells
+    }
+
+    /// Check whether `command` may run.
+    pub fn check(&self, command: &str) -> Result<(), RunnerError> {
+        let denied = |reason: String| RunnerError::CommandDenied {
+            command: command.to_string(),
+            reason,
+        };
+
+        if self.deny_shells && SHELLS.contains(&program_name(command)) {
+            return Err(denied("shells are not allowed".into()));
+        }
+        if let Some(pattern) = self.deny.iter().find(|p| matches(p, command)) {
+            return Err(denied(format!("matches denied pattern '{pattern}'")));
+        }
+        if self.allow.is_empty() {
+            return Ok(());
+        }
+        if command.split('/').any(|c| c == "..") {
+            return Err(denied("relative path components are not allowed".into()));
+        }
+        if self.allow.iter().any(|p| matches(p, command)) {
+            Ok(())
+        } else {
+            Err(denied("not in the allowlist".into()))
+        }
+    }
+}
+
+fn program_name(command: &str) -> &str {
+    command.rsplit('/').next().unwrap_or(command)
+}
+
+fn matches(pattern: &str, command: &str) -> bool {
+    if pattern.contains('/') {
+        glob(pattern.as_bytes(), command.as_bytes())
+    } else {
+        glob(pattern.as_bytes(), program_name(command).as_bytes())
+    }
+}
+
+/// Match `text` against a pattern with `*` and `?` wildcards that never cross `/`.
+fn glob(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // Position after the last `*` and the text index it is currently matched up to.
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some(b'*') => {
+                star = Some((p + 1, t));
+                p += 1;
+            }
+            Some(b'?') if text[t] != b'/' => {
+                p += 1;
+                t += 1;
+            }
+            Some(&c) if c != b'?' && c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((sp, st)) if text[st] != b'/' => {
+                    star = Some((sp, st + 1));
+                    p = sp;
+                    t = st + 1;
+                }
+                _ => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == b'*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unrestricted_by_default() {
+        let policy = CommandPolicy::default();
+        assert!(policy.is_unrestricted());
+        assert!(policy.check("/bin/sh").is_ok());
+        assert!(policy.check("anything").is_ok());
+    }
+
+    #[test]
+    fn glob_does_not_cross_directories() {
+        assert!(glob(b"/opt/jobs/*", b"/opt/jobs/backup"));
+        assert!(glob(b"/opt/*/run", b"/opt/jobs/run"));
+        assert!(glob(b"backup-?", b"backup-1"));
+        assert!(glob(b"*", b""));
+        assert!(!glob(b"/opt/jobs/*", b"/opt/jobs/sub/backup"));
+        assert!(!glob(b"/opt/jobs/*", b"/opt/other/backup"));
+        assert!(!glob(b"backup-?", b"backup-10"));
+    }
+
+    #[test]
+    fn allowlist_restricts_commands() {
+        let policy = CommandPolicy::default()
+            .with_allowed("/opt/jobs/*")
+            .with_allowed("rsync");
+
+        assert!(policy.check("/opt/jobs/backup").is_ok());
+        assert!(policy.check("rsync").is_ok());
+        assert!(policy.check("/usr/bin/rsync").is_ok());
+        assert!(policy.check("/usr/bin/curl").is_err());
+        assert!(policy.check("/opt/jobs/../../bin/curl").is_err());
+    }
+
+    #[test]
+    fn denylist_wins_over_allowlist() {
+        let policy = CommandPolicy::default()
+            .with_allowed("/usr/bin/*")
+            .with_denied("rm");
+
+        assert!(policy.check("/usr/bin/ls").is_ok());
+        match policy.check("/usr/bin/rm") {
+            Err(RunnerError::CommandDenied { command, reason }) => {
+                assert_eq!(command, "/usr/bin/rm");
+                assert!(reason.contains("'rm'"), "{reason}");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn shells_can_be_denied() {
+        let policy = CommandPolicy::default().with_deny_shells(true);
+        assert!(policy.check("sh").is_err());
+        assert!(policy.check("/usr/local/bin/bash").is_err());
+        assert!(policy.check("/bin/busybox").is_err());
+        assert!(policy.check("/usr/bin/python3").is_ok());
+    }
+}
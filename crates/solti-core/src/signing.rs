@@ -0,0 +1,281 @@
+//! Signed task spec verification.
+//!
+//! A control plane signs the spec it submits and attaches the detached signature as labels:
+//! [`LABEL_SIGNATURE`] holds the base64 ed25519 signature, [`LABEL_SIGNATURE_KEY`] the id of
+//! the signing key. [`SpecVerifier`] is an [`AdmissionPolicy`] that checks the signature against
+//! configured public keys before the spec reaches a runner, so only trusted control planes can
+//! run arbitrary commands.
+//!
+//! The signed bytes are [`signing_payload`]: the spec serialized as compact JSON (field names as
+//! accepted by the HTTP API) with object keys sorted and the signature labels and [`LABEL_SIGNER`]
+//! removed.
+use std::{collections::HashMap, fmt};
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use solti_model::{CreateSpec, LABEL_SIGNATURE, LABEL_SIGNATURE_KEY, LABEL_SIGNER};
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    admission::{AdmissionContext, AdmissionPolicy},
+    dedup::sorted,
+};
+
+/// Reasons a spec signature is not accepted.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SignatureError {
+    #[error("spec is not signed")]
+    Missing,
+
+    #[error("signature key id is missing")]
+    MissingKeyId,
+
+    #[error("unknown signature key: {0}")]
+    UnknownKey(String),
+
+    #[error("malformed signature: {0}")]
+    Malformed(String),
+
+    #[error("invalid public key: {0}")]
+    InvalidKey(String),
+
+    #[error("signature does not match the spec")]
+    Mismatch,
+}
+
+/// Bytes covered by a spec signature.
+pub fn signing_payload(spec: &CreateSpec) -> Vec<u8> {
+    let mut spec = spec.clone();
+    for key in [LABEL_SIGNATURE, LABEL_SIGNATURE_KEY, LABEL_SIGNER] {
+        spec.labels.remove(key);
+    }
+    let value = serde_json::to_value(&spec).expect("CreateSpec serializes to JSON");
+    serde_json::to_vec(&sorted(value)).expect("JSON value serializes")
+}
+
+/// Sign `spec` with the ed25519 `secret_key` and attach the signature labels.
+///
+/// Meant for control planes and tests; agents only need [`SpecVerifier`].
+pub fn sign_spec(spec: &mut CreateSpec, key_id: &str, secret_key: &[u8; 32]) {
+    let signature = SigningKey::from_bytes(secret_key).sign(&signing_payload(spec));
+    spec.labels
+        .insert(LABEL_SIGNATURE, STANDARD.encode(signature.to_bytes()))
+        .insert(LABEL_SIGNATURE_KEY, key_id);
+}
+
+/// Admission policy verifying spec signatures against trusted public keys.
+///
+/// Admitted specs have the signature labels replaced by [`LABEL_SIGNER`] naming the key,
+/// so the signer shows up in task labels, and every verification is logged at `info`.
+/// Unsigned specs are rejected unless [`SpecVerifier::with_unsigned_allowed`] is set.
+///
+/// Follow-ups (see [`AdmissionContext::follow_up_of`]) are not verified again: they were covered
+/// by the signature of their parent spec, whose signer they inherit.
+///
+/// ```rust,ignore
+/// let verifier = SpecVerifier::new().with_key("control-plane", &public_key)?;
+/// let api = api.with_admission_policy(verifier);
+/// ```
+#[derive(Clone, Default)]
+pub struct SpecVerifier {
+    keys: HashMap<String, VerifyingKey>,
+    allow_unsigned: bool,
+}
+
+impl SpecVerifier {
+    /// Verifier without trusted keys.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Trust the ed25519 public key `public_key` under `id`.
+    pub fn with_key(
+        mut self,
+        id: impl Into<String>,
+        public_key: &[u8; 32],
+    ) -> Result<Self, SignatureError> {
+        let key = VerifyingKey::from_bytes(public_key)
+            .map_err(|e| SignatureError::InvalidKey(e.to_string()))?;
+        self.keys.insert(id.into(), key);
+        Ok(self)
+    }
+
+    /// Admit unsigned specs (without a signer) instead of rejecting them.
+    ///
+    /// Meant for migrations; signed specs are still verified.
+    pub fn with_unsigned_allowed(mut self, allow: bool) -> Self {
+        self.allow_unsigned = allow;
+        self
+    }
+
+    /// Verify the signature labels of `spec`, returning the id of the signing key.
+    pub fn verify(&self, spec: &CreateSpec) -> Result<String, SignatureError> {
+        let encoded = spec
+            .labels
+            .get(LABEL_SIGNATURE)
+            .ok_or(SignatureError::Missing)?;
+        let key_id = spec
+            .labels
+            .get(LABEL_SIGNATURE_KEY)
+            .ok_or(SignatureError::MissingKeyId)?;
+        let key = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| SignatureError::UnknownKey(key_id.to_string()))?;
+
+        let bytes = STANDARD
+            .decode(encoded)
+            .map_err(|e| SignatureError::Malformed(e.to_string()))?;
+        let signature =
+            Signature::from_slice(&bytes).map_err(|e| SignatureError::Malformed(e.to_string()))?;
+        key.verify(&signing_payload(spec), &signature)
+            .map_err(|_| SignatureError::Mismatch)?;
+        Ok(key_id.to_string())
+    }
+}
+
+impl AdmissionPolicy for SpecVerifier {
+    fn admit(&self, spec: &mut CreateSpec, ctx: &AdmissionContext<'_>) -> Result<(), String> {
+        let signer = match ctx.follow_up_of() {
+            Some(parent) => parent.labels.get(LABEL_SIGNER).map(str::to_string),
+            None => match self.verify(spec) {
+                Ok(signer) => Some(signer),
+                Err(SignatureError::Missing) if self.allow_unsigned => None,
+                Err(e) => return Err(e.to_string()),
+            },
+        };
+
+        spec.labels.remove(LABEL_SIGNATURE);
+        spec.labels.remove(LABEL_SIGNATURE_KEY);
+        spec.labels.remove(LABEL_SIGNER);
+        if let Some(signer) = &signer {
+            spec.labels.insert(LABEL_SIGNER, signer.as_str());
+        }
+        info!(
+            slot = %spec.slot,
+            signer = signer.as_deref().unwrap_or("-"),
+            caller = ctx.caller().identity.as_deref().unwrap_or("-"),
+            "spec signature checked"
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Debug for SpecVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<_> = self.keys.keys().collect();
+        keys.sort();
+        f.debug_struct("SpecVerifier")
+            .field("keys", &keys)
+            .field("allow_unsigned", &self.allow_unsigned)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, Flag, JitterStrategy, RestartStrategy, RunnerLabels,
+        TaskEnv, TaskKind,
+    };
+
+    use super::*;
+    use crate::{admission::Caller, state::TaskState};
+
+    const SECRET: [u8; 32] = [7; 32];
+
+    fn spec() -> CreateSpec {
+        CreateSpec {
+            slot: "deploy".into(),
+            kind: TaskKind::Subprocess {
+                command: "/opt/deploy.sh".into(),
+                args: vec!["--prod".into()],
+                env: TaskEnv::default(),
+                cwd: None,
+                fail_on_non_zero: Flag::enabled(),
+            },
+            timeout_ms: 60_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+        .with_namespace("prod")
+    }
+
+    fn verifier() -> SpecVerifier {
+        let public = SigningKey::from_bytes(&SECRET).verifying_key().to_bytes();
+        SpecVerifier::new().with_key("cp-1", &public).unwrap()
+    }
+
+    fn admit(verifier: &SpecVerifier, spec: &mut CreateSpec) -> Result<(), String> {
+        let state = TaskState::new();
+        let caller = Caller::new("cp");
+        let ctx = AdmissionContext::new(&caller, spec, &state);
+        verifier.admit(spec, &ctx)
+    }
+
+    #[test]
+    fn payload_is_canonical() {
+        let payload = String::from_utf8(signing_payload(&spec())).unwrap();
+        assert!(payload.starts_with(r#"{"admission":"dropIfRunning","backoff":{"factor":1.0"#));
+        assert!(!payload.contains(' '));
+
+        let mut signed = spec();
+        sign_spec(&mut signed, "cp-1", &SECRET);
+        assert_eq!(signing_payload(&signed), signing_payload(&spec()));
+    }
+
+    #[test]
+    fn signed_spec_is_admitted_with_signer() {
+        let mut s = spec();
+        sign_spec(&mut s, "cp-1", &SECRET);
+        s.labels.insert(LABEL_SIGNER, "spoofed");
+
+        admit(&verifier(), &mut s).unwrap();
+        assert_eq!(s.labels.get(LABEL_SIGNER), Some("cp-1"));
+        assert_eq!(s.labels.get(LABEL_SIGNATURE), None);
+        assert_eq!(s.labels.get(LABEL_SIGNATURE_KEY), None);
+    }
+
+    #[test]
+    fn tampered_spec_is_rejected() {
+        let mut s = spec();
+        sign_spec(&mut s, "cp-1", &SECRET);
+        if let TaskKind::Subprocess { command, .. } = &mut s.kind {
+            *command = "/bin/sh".into();
+        }
+        assert_eq!(verifier().verify(&s), Err(SignatureError::Mismatch));
+    }
+
+    #[test]
+    fn unknown_keys_and_unsigned_specs_are_rejected() {
+        let mut s = spec();
+        sign_spec(&mut s, "cp-2", &SECRET);
+        assert_eq!(
+            verifier().verify(&s),
+            Err(SignatureError::UnknownKey("cp-2".into()))
+        );
+
+        let mut unsigned = spec();
+        assert_eq!(
+            admit(&verifier(), &mut unsigned).unwrap_err(),
+            "spec is not signed"
+        );
+
+        unsigned.labels.insert(LABEL_SIGNER, "spoofed");
+        admit(&verifier().with_unsigned_allowed(true), &mut unsigned).unwrap();
+        assert_eq!(unsigned.labels.get(LABEL_SIGNER), None);
+    }
+}
@@ -0,0 +1,33 @@
+use solti_model::{TaskId, TaskInfo};
+
+/// Number of changes buffered per watcher before it starts lagging.
+pub const STATE_WATCH_CAPACITY: usize = 1024;
+
+/// Change of a tracked task, delivered by [`SupervisorApi::watch_tasks`](crate::SupervisorApi::watch_tasks).
+#[derive(Debug, Clone)]
+pub enum StateChange {
+    /// Task started being tracked.
+    Added(TaskInfo),
+    /// Status, attempt or error of a task changed.
+    Updated {
+        before: Box<TaskInfo>,
+        after: Box<TaskInfo>,
+    },
+    /// Task was removed from the supervisor; carries its last known info.
+    ///
+    /// Terminal tasks stay listed after removal while a terminal task limit is set,
+    /// until they are [`StateChange::Evicted`].
+    Removed(TaskInfo),
+    /// Terminal task was dropped to stay within the retention limit.
+    Evicted(TaskInfo),
+}
+
+impl StateChange {
+    /// Id of the task the change belongs to.
+    pub fn task_id(&self) -> &TaskId {
+        match self {
+            Self::Added(info) | Self::Removed(info) | Self::Evicted(info) => &info.id,
+            Self::Updated { after, .. } => &after.id,
+        }
+    }
+}
@@ -1,163 +1,556 @@
+mod change;
+pub use change::{STATE_WATCH_CAPACITY, StateChange};
+
 mod subscriber;
 pub use subscriber::StateSubscriber;
 
 use std::{
-    collections::HashMap,
-    sync::{Arc, RwLock},
+    collections::{BTreeMap, HashMap, HashSet, hash_map::DefaultHasher},
+    hash::{Hash, Hasher},
+    sync::{
+        Arc, RwLock, RwLockReadGuard,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
     time::SystemTime,
 };
 
-use solti_model::{Slot, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus};
+use solti_model::{
+    CreateSpec, GroupInfo, RestartStrategy, RunnerLabels, Slot, TaskId, TaskInfo, TaskPage,
+    TaskQuery, TaskStatus,
+};
+use tokio::sync::broadcast;
+
+/// Number of independently locked shards.
+const SHARD_COUNT: usize = 16;
 
 /// In-memory task state storage.
+///
+/// Tasks are spread over [`SHARD_COUNT`] shards by id hash, each with its own
+/// lock and indexes, so status updates of different tasks rarely contend.
+/// Multi-task reads lock every shard for reading and see a consistent snapshot.
 #[derive(Clone)]
 pub struct TaskState {
-    inner: Arc<RwLock<TaskStateInner>>,
+    shards: Arc<[RwLock<Shard>]>,
+    changes: broadcast::Sender<StateChange>,
+    /// Maximum number of terminal tasks retained per shard; `0` means unlimited.
+    terminal_limit: Arc<AtomicUsize>,
+    /// Bumped on every change; see [`TaskState::revision`].
+    revision: Arc<AtomicU64>,
 }
 
-struct TaskStateInner {
+#[derive(Default)]
+struct Shard {
     /// Tasks indexed by TaskId.
     tasks: HashMap<TaskId, TaskInfo>,
     /// Index: slot -> list of task IDs in that slot.
     by_slot: HashMap<Slot, Vec<TaskId>>,
+    /// Index: group -> list of task IDs in that group.
+    by_group: HashMap<String, Vec<TaskId>>,
+    /// Index: status -> task IDs currently in that status.
+    by_status: HashMap<TaskStatus, HashSet<TaskId>>,
+    /// Index: label key -> label value -> task IDs carrying that label.
+    by_label: HashMap<String, HashMap<String, HashSet<TaskId>>>,
+    /// Tasks whose current run was skipped by their execution window.
+    skipped: HashSet<TaskId>,
+    /// Tasks the supervisor may run again after a terminal status (restarts, periodic runs).
+    restartable: HashSet<TaskId>,
+    /// Terminal tasks, least recently updated first.
+    terminal: TerminalLru,
+}
+
+/// Recency order of terminal tasks within a shard.
+#[derive(Default)]
+struct TerminalLru {
+    order: BTreeMap<u64, TaskId>,
+    ticks: HashMap<TaskId, u64>,
+    next: u64,
+}
+
+impl TerminalLru {
+    /// Mark a task as the most recently updated terminal task.
+    fn touch(&mut self, id: &TaskId) {
+        self.forget(id);
+        self.next += 1;
+        self.order.insert(self.next, id.clone());
+        self.ticks.insert(id.clone(), self.next);
+    }
+
+    fn forget(&mut self, id: &TaskId) {
+        if let Some(tick) = self.ticks.remove(id) {
+            self.order.remove(&tick);
+        }
+    }
+
+    fn pop_oldest(&mut self) -> Option<TaskId> {
+        let (_, id) = self.order.pop_first()?;
+        self.ticks.remove(&id);
+        Some(id)
+    }
+
+    fn contains(&self, id: &TaskId) -> bool {
+        self.ticks.contains_key(id)
+    }
+
+    fn len(&self) -> usize {
+        self.ticks.len()
+    }
+}
+
+impl Shard {
+    /// Drop a task and its index entries.
+    fn remove(&mut self, id: &TaskId) -> Option<TaskInfo> {
+        self.skipped.remove(id);
+        self.restartable.remove(id);
+        self.terminal.forget(id);
+        let info = self.tasks.remove(id)?;
+        self.unindex_status(id, info.status);
+        for (key, value) in info.labels.iter() {
+            let Some(values) = self.by_label.get_mut(key) else {
+                continue;
+            };
+            if let Some(ids) = values.get_mut(value) {
+                ids.remove(id);
+                if ids.is_empty() {
+                    values.remove(value);
+                }
+            }
+            if values.is_empty() {
+                self.by_label.remove(key);
+            }
+        }
+        if let Some(ids) = self.by_slot.get_mut(&info.slot) {
+            ids.retain(|task_id| task_id != id);
+            if ids.is_empty() {
+                self.by_slot.remove(&info.slot);
+            }
+        }
+        if let Some(group) = &info.group
+            && let Some(ids) = self.by_group.get_mut(group)
+        {
+            ids.retain(|task_id| task_id != id);
+            if ids.is_empty() {
+                self.by_group.remove(group);
+            }
+        }
+        Some(info)
+    }
+
+    /// Tasks listed in an index entry of this shard.
+    fn indexed<'a>(
+        &'a self,
+        index: &'a HashMap<String, Vec<TaskId>>,
+        key: &str,
+    ) -> impl Iterator<Item = &'a TaskInfo> + 'a {
+        index
+            .get(key)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.tasks.get(id))
+    }
+
+    /// Tasks currently in the given status.
+    fn with_status(&self, status: TaskStatus) -> impl Iterator<Item = &TaskInfo> + '_ {
+        self.by_status
+            .get(&status)
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.tasks.get(id))
+    }
+
+    /// Candidates for a label selector, read from its smallest index entry.
+    ///
+    /// Callers still filter by the full selector. An empty selector yields nothing.
+    fn with_labels<'a>(&'a self, selector: &RunnerLabels) -> impl Iterator<Item = &'a TaskInfo> {
+        // A selector entry without any task yields `None`, which sorts first: nothing matches.
+        let smallest = selector
+            .iter()
+            .map(|(key, value)| self.by_label.get(key).and_then(|values| values.get(value)))
+            .min_by_key(|ids| ids.map_or(0, HashSet::len))
+            .flatten();
+        smallest
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.tasks.get(id))
+    }
+
+    /// Move a task between status index entries.
+    fn reindex_status(&mut self, id: &TaskId, from: TaskStatus, to: TaskStatus) {
+        if from == to {
+            return;
+        }
+        self.unindex_status(id, from);
+        self.by_status.entry(to).or_default().insert(id.clone());
+    }
+
+    fn unindex_status(&mut self, id: &TaskId, status: TaskStatus) {
+        if let Some(ids) = self.by_status.get_mut(&status) {
+            ids.remove(id);
+            if ids.is_empty() {
+                self.by_status.remove(&status);
+            }
+        }
+    }
 }
 
 impl TaskState {
     /// Create empty task state.
     pub fn new() -> Self {
         Self {
-            inner: Arc::new(RwLock::new(TaskStateInner {
-                tasks: HashMap::new(),
-                by_slot: HashMap::new(),
-            })),
+            shards: (0..SHARD_COUNT)
+                .map(|_| RwLock::new(Shard::default()))
+                .collect(),
+            changes: broadcast::channel(STATE_WATCH_CAPACITY).0,
+            terminal_limit: Arc::new(AtomicUsize::new(0)),
+            revision: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Cap the number of retained terminal tasks; `None` removes the cap.
+    ///
+    /// While a cap is set, terminal tasks outlive their removal (see [`TaskState::remove_task`]).
+    /// When the cap is exceeded, the least recently updated terminal tasks are
+    /// dropped and published as [`StateChange::Evicted`]. Restartable tasks (see
+    /// [`TaskState::set_restartable`]) are never evicted: a terminal status only marks the
+    /// end of their current run. The cap is split evenly
+    /// across shards, so up to `limit` rounded up to a multiple of the shard count
+    /// may be retained.
+    pub fn set_terminal_limit(&self, limit: Option<usize>) {
+        let per_shard = limit.map_or(0, |l| l.div_ceil(self.shards.len()).max(1));
+        self.terminal_limit.store(per_shard, Ordering::Relaxed);
+        for shard in self.shards.iter() {
+            self.evict_terminal(&mut shard.write().unwrap());
+        }
+    }
+
+    /// Evict least recently updated terminal tasks above the per-shard limit.
+    fn evict_terminal(&self, shard: &mut Shard) {
+        let limit = self.terminal_limit.load(Ordering::Relaxed);
+        if limit == 0 {
+            return;
+        }
+        while shard.terminal.len() > limit {
+            let Some(id) = shard.terminal.pop_oldest() else {
+                break;
+            };
+            if let Some(info) = shard.remove(&id) {
+                self.notify(|| StateChange::Evicted(info));
+            }
+        }
+    }
+
+    /// Subscribe to task changes made after this call.
+    ///
+    /// Changes of a task are delivered in the order they were applied. A receiver
+    /// that falls more than [`STATE_WATCH_CAPACITY`] changes behind gets
+    /// [`RecvError::Lagged`](broadcast::error::RecvError::Lagged) and should
+    /// resynchronize from [`TaskState::list_all`].
+    pub fn watch(&self) -> broadcast::Receiver<StateChange> {
+        self.changes.subscribe()
+    }
+
+    /// Revision of the task state, incremented on every change.
+    ///
+    /// Equal revisions mean no task was added, updated or removed in between,
+    /// so cached listings are still current.
+    pub fn revision(&self) -> u64 {
+        self.revision.load(Ordering::Acquire)
+    }
+
+    /// Publish a change; called with the shard lock held so watchers see changes in order.
+    fn notify(&self, change: impl FnOnce() -> StateChange) {
+        self.revision.fetch_add(1, Ordering::Release);
+        if self.changes.receiver_count() > 0 {
+            let _ = self.changes.send(change());
         }
     }
 
+    /// Shard owning the given task.
+    fn shard(&self, id: &TaskId) -> &RwLock<Shard> {
+        let mut hasher = DefaultHasher::new();
+        id.hash(&mut hasher);
+        &self.shards[hasher.finish() as usize % self.shards.len()]
+    }
+
+    /// Read-lock every shard, in a fixed order.
+    fn read_all(&self) -> Vec<RwLockReadGuard<'_, Shard>> {
+        self.shards.iter().map(|s| s.read().unwrap()).collect()
+    }
+
     /// Register a new task (called on TaskAdded event).
     pub fn add_task(&self, id: TaskId, slot: Slot) {
-        let mut inner = self.inner.write().unwrap();
+        self.insert(TaskInfo::pending(id, slot));
+    }
 
-        let now = SystemTime::now();
-        let info = TaskInfo {
-            id: id.clone(),
-            slot: slot.clone(),
-            status: TaskStatus::Pending,
-            attempt: 0,
-            created_at: now,
-            updated_at: now,
-            error: None,
-        };
+    /// Register a task built from a spec by the named runner.
+    ///
+    /// Keeps the spec kind and labels; the [`solti_model::LABEL_GROUP`] label makes
+    /// the task a member of that group. Tasks whose restart strategy is not
+    /// [`RestartStrategy::Never`] are registered as restartable.
+    pub fn add_spec_task(&self, id: TaskId, spec: &CreateSpec, runner: &str) {
+        let mut info = TaskInfo::pending(id.clone(), spec.slot.clone());
+        info.group = spec.group().map(str::to_string);
+        info.labels = spec.labels.clone();
+        info.kind = Some(spec.kind.kind().to_string());
+        info.runner = Some(runner.to_string());
+        self.insert(info);
+        if spec.restart != RestartStrategy::Never {
+            self.set_restartable(&id);
+        }
+    }
 
-        inner.tasks.insert(id.clone(), info);
-        inner.by_slot.entry(slot).or_default().push(id);
+    /// Mark a task as one the supervisor may run again after a terminal status.
+    ///
+    /// Restartable tasks stay in state until the supervisor removes them,
+    /// regardless of the terminal task limit.
+    pub fn set_restartable(&self, id: &TaskId) {
+        let mut shard = self.shard(id).write().unwrap();
+        if shard.tasks.contains_key(id) {
+            shard.restartable.insert(id.clone());
+            shard.terminal.forget(id);
+        }
+    }
+
+    /// Track a new task, replacing any previous entry with the same id.
+    fn insert(&self, info: TaskInfo) {
+        let id = info.id.clone();
+        let mut shard = self.shard(&id).write().unwrap();
+        shard.remove(&id);
+
+        if let Some(group) = &info.group {
+            shard
+                .by_group
+                .entry(group.clone())
+                .or_default()
+                .push(id.clone());
+        }
+        for (key, value) in info.labels.iter() {
+            shard
+                .by_label
+                .entry(key.to_string())
+                .or_default()
+                .entry(value.to_string())
+                .or_default()
+                .insert(id.clone());
+        }
+        shard
+            .by_status
+            .entry(info.status)
+            .or_default()
+            .insert(id.clone());
+        shard
+            .by_slot
+            .entry(info.slot.clone())
+            .or_default()
+            .push(id.clone());
+        self.notify(|| StateChange::Added(info.clone()));
+        shard.tasks.insert(id, info);
     }
 
     /// Update task status (called on state transition events).
     pub fn update_status(&self, id: &TaskId, status: TaskStatus, error: Option<String>) {
-        let mut inner = self.inner.write().unwrap();
+        let mut shard = self.shard(id).write().unwrap();
 
-        if let Some(info) = inner.tasks.get_mut(id) {
-            info.status = status;
-            info.updated_at = SystemTime::now();
-            if let Some(err) = error {
-                info.error = Some(err);
-            }
+        let Some(info) = shard.tasks.get_mut(id) else {
+            return;
+        };
+        let before = info.clone();
+        let from = before.status;
+        let now = SystemTime::now();
+        info.status = status;
+        info.updated_at = now;
+        if let Some(err) = error {
+            info.error = Some(err);
+        }
+        if status.is_terminal()
+            && info.finished_at.is_none()
+            && let Some(started) = info.started_at
+        {
+            info.finished_at = Some(now);
+            info.duration_ms =
+                Some(now.duration_since(started).unwrap_or_default().as_millis() as u64);
+        }
+        self.notify(|| StateChange::Updated {
+            before: Box::new(before),
+            after: Box::new(info.clone()),
+        });
+        shard.reindex_status(id, from, status);
+        if status.is_terminal() && !shard.restartable.contains(id) {
+            shard.terminal.touch(id);
+            self.evict_terminal(&mut shard);
+        } else {
+            shard.terminal.forget(id);
         }
     }
 
     /// Increment attempt counter (called on TaskStarting event).
     pub fn increment_attempt(&self, id: &TaskId) {
-        let mut inner = self.inner.write().unwrap();
+        let mut shard = self.shard(id).write().unwrap();
 
-        if let Some(info) = inner.tasks.get_mut(id) {
+        if let Some(info) = shard.tasks.get_mut(id) {
+            let before = info.clone();
+            let now = SystemTime::now();
             info.attempt += 1;
-            info.updated_at = SystemTime::now();
+            info.updated_at = now;
+            info.started_at = Some(now);
+            info.finished_at = None;
+            info.duration_ms = None;
+            self.notify(|| StateChange::Updated {
+                before: Box::new(before),
+                after: Box::new(info.clone()),
+            });
+        }
+    }
+
+    /// Mark the current run of a task as skipped by its execution window.
+    ///
+    /// The mark is consumed by the next stop event, which then records
+    /// [`TaskStatus::Skipped`] instead of [`TaskStatus::Succeeded`].
+    pub fn mark_skipped(&self, id: &TaskId) {
+        let mut shard = self.shard(id).write().unwrap();
+        if shard.tasks.contains_key(id) {
+            shard.skipped.insert(id.clone());
         }
     }
 
+    /// Take the skip mark of a task, returning `true` if it was set.
+    pub fn take_skipped(&self, id: &TaskId) -> bool {
+        self.shard(id).write().unwrap().skipped.remove(id)
+    }
+
     /// Remove task from state (called on TaskRemoved event).
+    ///
+    /// With a terminal task limit set (see [`TaskState::set_terminal_limit`]), a task that
+    /// ended in a terminal status and will not run again stays listed until it is evicted;
+    /// watchers still receive [`StateChange::Removed`] for it.
     pub fn remove_task(&self, id: &TaskId) {
-        let mut inner = self.inner.write().unwrap();
-
-        if let Some(info) = inner.tasks.remove(id)
-            && let Some(ids) = inner.by_slot.get_mut(&info.slot)
-        {
-            ids.retain(|task_id| task_id != id);
+        let mut shard = self.shard(id).write().unwrap();
+        let retained =
+            self.terminal_limit.load(Ordering::Relaxed) > 0 && shard.terminal.contains(id);
+        let info = if retained {
+            shard.tasks.get(id).cloned()
+        } else {
+            shard.remove(id)
+        };
+        if let Some(info) = info {
+            self.notify(|| StateChange::Removed(info));
         }
     }
 
     /// Get task info by ID.
     pub fn get(&self, id: &TaskId) -> Option<TaskInfo> {
-        let inner = self.inner.read().unwrap();
-        inner.tasks.get(id).cloned()
+        let shard = self.shard(id).read().unwrap();
+        shard.tasks.get(id).cloned()
     }
 
     /// List all tasks in a specific slot.
     pub fn list_by_slot(&self, slot: &str) -> Vec<TaskInfo> {
-        let inner = self.inner.read().unwrap();
+        let shards = self.read_all();
+        shards
+            .iter()
+            .flat_map(|shard| shard.indexed(&shard.by_slot, slot))
+            .cloned()
+            .collect()
+    }
 
-        inner
-            .by_slot
-            .get(slot)
-            .map(|ids| {
-                ids.iter()
-                    .filter_map(|id| inner.tasks.get(id).cloned())
-                    .collect()
-            })
-            .unwrap_or_default()
+    /// List all tasks in a group.
+    pub fn list_by_group(&self, group: &str) -> Vec<TaskInfo> {
+        let shards = self.read_all();
+        shards
+            .iter()
+            .flat_map(|shard| shard.indexed(&shard.by_group, group))
+            .cloned()
+            .collect()
+    }
+
+    /// Aggregate status of a group, or `None` if the group has no tracked members.
+    pub fn group_info(&self, group: &str) -> Option<GroupInfo> {
+        let shards = self.read_all();
+        let mut members = shards
+            .iter()
+            .flat_map(|shard| shard.indexed(&shard.by_group, group))
+            .peekable();
+        members.peek()?;
+        Some(GroupInfo::from_tasks(group, members))
     }
 
     /// List all tasks.
     pub fn list_all(&self) -> Vec<TaskInfo> {
-        let inner = self.inner.read().unwrap();
-        inner.tasks.values().cloned().collect()
+        let shards = self.read_all();
+        shards
+            .iter()
+            .flat_map(|shard| shard.tasks.values())
+            .cloned()
+            .collect()
     }
 
-    /// List tasks matching a status filter.
+    /// List tasks matching a status filter, using the per-shard status indexes.
     pub fn list_by_status(&self, status: TaskStatus) -> Vec<TaskInfo> {
-        let inner = self.inner.read().unwrap();
-        inner
-            .tasks
-            .values()
-            .filter(|info| info.status == status)
+        let shards = self.read_all();
+        shards
+            .iter()
+            .flat_map(|shard| shard.with_status(status))
             .cloned()
             .collect()
     }
 
+    /// Number of tracked tasks in every status, in [`TaskStatus::ALL`] order.
+    pub fn status_counts(&self) -> [(TaskStatus, usize); TaskStatus::ALL.len()] {
+        let shards = self.read_all();
+        TaskStatus::ALL.map(|status| {
+            let count = shards
+                .iter()
+                .filter_map(|shard| shard.by_status.get(&status))
+                .map(HashSet::len)
+                .sum();
+            (status, count)
+        })
+    }
+
+    /// Number of slots with at least one pending or running task.
+    pub fn active_slots(&self) -> usize {
+        let shards = self.read_all();
+        let slots: HashSet<&str> = shards
+            .iter()
+            .flat_map(|shard| {
+                shard
+                    .with_status(TaskStatus::Pending)
+                    .chain(shard.with_status(TaskStatus::Running))
+            })
+            .map(|info| info.slot.as_str())
+            .collect();
+        slots.len()
+    }
+
     /// Query tasks with combined filters and pagination.
     ///
-    /// Filters are applied inside a single read lock.
-    /// When `slot` is specified, uses the `by_slot` index to narrow the scan.
+    /// Filters are applied while holding the read locks of all shards.
+    /// Uses the per-shard `by_slot`, `by_label` or `by_status` indexes (in that order
+    /// of preference) to narrow the scan.
     /// `total` in the result reflects the count *after* filtering, *before* pagination.
     pub fn query(&self, q: &TaskQuery) -> TaskPage<TaskInfo> {
-        let inner = self.inner.read().unwrap();
-
-        // Choose the iterator source based on whether slot filter is present.
-        // When slot is given we use the by_slot index to avoid full scan.
-        let iter: Box<dyn Iterator<Item = &TaskInfo>> = match &q.slot {
-            Some(slot) => {
-                let ids = inner.by_slot.get(slot.as_str());
-                match ids {
-                    Some(ids) => Box::new(ids.iter().filter_map(|id| inner.tasks.get(id))),
-                    None => {
-                        return TaskPage {
-                            items: vec![],
-                            total: 0,
-                        };
-                    }
-                }
-            }
-            None => Box::new(inner.tasks.values()),
-        };
-
-        // Apply status filter if present.
-        let iter: Box<dyn Iterator<Item = &TaskInfo>> = match &q.status {
-            Some(status) => {
-                let status = *status;
-                Box::new(iter.filter(move |info| info.status == status))
-            }
-            None => iter,
+        let shards = self.read_all();
+
+        // Choose the iterator source based on the filters present: the slot index,
+        // then the label index, then the status index avoid a full scan.
+        // The remaining filters are applied to the candidates.
+        let iter: Box<dyn Iterator<Item = &TaskInfo>> = if let Some(slot) = &q.slot {
+            Box::new(
+                shards
+                    .iter()
+                    .flat_map(move |shard| shard.indexed(&shard.by_slot, slot)),
+            )
+        } else if !q.labels.is_empty() {
+            Box::new(shards.iter().flat_map(|shard| shard.with_labels(&q.labels)))
+        } else if let Some(status) = q.status {
+            Box::new(
+                shards
+                    .iter()
+                    .flat_map(move |shard| shard.with_status(status)),
+            )
+        } else {
+            Box::new(shards.iter().flat_map(|shard| shard.tasks.values()))
         };
+        let iter = iter.filter(|info| q.matches(info));
 
         // Collect refs that pass all filters — we need total count
         // and then paginate, so we must know the full filtered set size.
@@ -185,6 +578,31 @@ impl Default for TaskState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use solti_model::{
+        AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, TaskKind,
+    };
+
+    fn spec(slot: &str, labels: RunnerLabels) -> CreateSpec {
+        CreateSpec {
+            slot: slot.into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels,
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
 
     #[test]
     fn add_and_get_task() {
@@ -201,6 +619,32 @@ mod tests {
         assert_eq!(info.attempt, 0);
     }
 
+    #[test]
+    fn spec_task_records_kind_runner_and_timings() {
+        let state = TaskState::new();
+        let id = TaskId::from("task-1");
+        state.add_spec_task(id.clone(), &spec("slot", RunnerLabels::new()), "runner-a");
+
+        let info = state.get(&id).unwrap();
+        assert_eq!(info.kind.as_deref(), Some("none"));
+        assert_eq!(info.runner.as_deref(), Some("runner-a"));
+        assert!(info.started_at.is_none());
+
+        state.increment_attempt(&id);
+        let started = state.get(&id).unwrap().started_at.expect("attempt started");
+        state.update_status(&id, TaskStatus::Failed, Some("boom".into()));
+
+        let info = state.get(&id).unwrap();
+        assert_eq!(info.started_at, Some(started));
+        assert!(info.finished_at.unwrap() >= started);
+        assert!(info.duration_ms.is_some());
+
+        state.increment_attempt(&id);
+        let info = state.get(&id).unwrap();
+        assert!(info.finished_at.is_none());
+        assert!(info.duration_ms.is_none());
+    }
+
     #[test]
     fn update_status_changes_task_state() {
         let state = TaskState::new();
@@ -214,6 +658,30 @@ mod tests {
         assert!(info.error.is_none());
     }
 
+    #[test]
+    fn revision_changes_only_on_mutation() {
+        let state = TaskState::new();
+        let id = TaskId::from("task-1");
+        let initial = state.revision();
+
+        state.add_task(id.clone(), "slot".to_string());
+        let added = state.revision();
+        assert!(added > initial);
+
+        let _ = state.list_all();
+        let _ = state.get(&id);
+        assert_eq!(state.revision(), added);
+
+        state.update_status(&id, TaskStatus::Running, None);
+        assert!(state.revision() > added);
+        let updated = state.revision();
+
+        state.remove_task(&TaskId::from("missing"));
+        assert_eq!(state.revision(), updated);
+        state.remove_task(&id);
+        assert!(state.revision() > updated);
+    }
+
     #[test]
     fn update_status_with_error() {
         let state = TaskState::new();
@@ -298,6 +766,324 @@ mod tests {
         assert_eq!(all_tasks.len(), 3);
     }
 
+    #[test]
+    fn watch_reports_changes_in_order() {
+        let state = TaskState::new();
+        let id = TaskId::from("task-1");
+
+        state.add_task(TaskId::from("before-watch"), "slot".to_string());
+        let mut rx = state.watch();
+
+        state.add_task(id.clone(), "slot".to_string());
+        state.increment_attempt(&id);
+        state.update_status(&id, TaskStatus::Running, None);
+        state.update_status(&TaskId::from("missing"), TaskStatus::Running, None);
+        state.remove_task(&id);
+
+        assert!(matches!(rx.try_recv().unwrap(), StateChange::Added(info) if info.id == id));
+        match rx.try_recv().unwrap() {
+            StateChange::Updated { before, after } => {
+                assert_eq!(before.attempt, 0);
+                assert_eq!(after.attempt, 1);
+            }
+            other => panic!("unexpected change: {other:?}"),
+        }
+        match rx.try_recv().unwrap() {
+            StateChange::Updated { before, after } => {
+                assert_eq!(before.status, TaskStatus::Pending);
+                assert_eq!(after.status, TaskStatus::Running);
+            }
+            other => panic!("unexpected change: {other:?}"),
+        }
+        let removed = rx.try_recv().unwrap();
+        assert!(
+            matches!(&removed, StateChange::Removed(info) if info.status == TaskStatus::Running)
+        );
+        assert_eq!(removed.task_id(), &id);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn tasks_spread_across_shards_stay_indexed() {
+        let state = TaskState::new();
+        for i in 0..200 {
+            let slot = if i % 2 == 0 { "even" } else { "odd" };
+            state.add_task(TaskId::from(format!("task-{i}")), slot.to_string());
+        }
+        let used = state
+            .shards
+            .iter()
+            .filter(|s| !s.read().unwrap().tasks.is_empty())
+            .count();
+        assert!(used > 1, "tasks should land in several shards");
+
+        assert_eq!(state.list_all().len(), 200);
+        assert_eq!(state.list_by_slot("even").len(), 100);
+        assert_eq!(state.query(&TaskQuery::new().with_slot("odd")).total, 100);
+
+        for i in (0..200).step_by(2) {
+            state.remove_task(&TaskId::from(format!("task-{i}")));
+        }
+        assert!(state.list_by_slot("even").is_empty());
+        assert!(
+            state
+                .shards
+                .iter()
+                .all(|s| !s.read().unwrap().by_slot.contains_key("even"))
+        );
+    }
+
+    /// Rough throughput check: `cargo test -p solti-core --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn scales_to_100k_tasks() {
+        use std::time::Instant;
+
+        const TASKS: usize = 100_000;
+        const THREADS: usize = 8;
+
+        let state = TaskState::new();
+        let ids: Vec<TaskId> = (0..TASKS)
+            .map(|i| TaskId::from(format!("runner-slot{}-{i:x}", i % 100)))
+            .collect();
+
+        let start = Instant::now();
+        for (i, id) in ids.iter().enumerate() {
+            state.add_task(id.clone(), format!("slot{}", i % 100));
+        }
+        let insert = start.elapsed();
+
+        let start = Instant::now();
+        std::thread::scope(|scope| {
+            for chunk in ids.chunks(TASKS / THREADS) {
+                let state = &state;
+                scope.spawn(move || {
+                    for id in chunk {
+                        state.increment_attempt(id);
+                        state.update_status(id, TaskStatus::Running, None);
+                        state.update_status(id, TaskStatus::Succeeded, None);
+                    }
+                });
+            }
+        });
+        let updates = start.elapsed();
+
+        let start = Instant::now();
+        let page = state.query(&TaskQuery::new().with_status(TaskStatus::Succeeded));
+        let query = start.elapsed();
+
+        assert_eq!(page.total, TASKS);
+        println!(
+            "{TASKS} tasks: insert {insert:?}, {} concurrent updates {updates:?}, query {query:?}",
+            TASKS * 3
+        );
+    }
+
+    #[test]
+    fn status_index_follows_transitions() {
+        let state = TaskState::new();
+        let id = TaskId::from("task-1");
+
+        state.add_task(id.clone(), "slot".to_string());
+        state.update_status(&id, TaskStatus::Running, None);
+        state.update_status(&id, TaskStatus::Running, None);
+        assert!(state.list_by_status(TaskStatus::Pending).is_empty());
+        assert_eq!(state.list_by_status(TaskStatus::Running).len(), 1);
+
+        state.update_status(&id, TaskStatus::Failed, Some("boom".into()));
+        assert!(state.list_by_status(TaskStatus::Running).is_empty());
+        assert_eq!(state.list_by_status(TaskStatus::Failed).len(), 1);
+
+        state.add_task(id.clone(), "slot".to_string());
+        assert!(state.list_by_status(TaskStatus::Failed).is_empty());
+        assert_eq!(state.list_by_status(TaskStatus::Pending).len(), 1);
+
+        state.remove_task(&id);
+        assert!(state.list_by_status(TaskStatus::Pending).is_empty());
+        assert!(
+            state
+                .shards
+                .iter()
+                .all(|s| s.read().unwrap().by_status.is_empty())
+        );
+    }
+
+    #[test]
+    fn terminal_limit_evicts_least_recently_updated() {
+        let state = TaskState::new();
+        state.set_terminal_limit(Some(1));
+        let mut rx = state.watch();
+
+        // Same shard, so the per-shard limit of one applies to both.
+        let first = TaskId::from("task-0");
+        let shard = state.shard(&first) as *const _;
+        let second = (1..)
+            .map(|i| TaskId::from(format!("task-{i}")))
+            .find(|id| std::ptr::eq(state.shard(id), shard))
+            .unwrap();
+
+        state.add_task(first.clone(), "slot".to_string());
+        state.add_task(second.clone(), "slot".to_string());
+        state.update_status(&first, TaskStatus::Succeeded, None);
+        state.update_status(&second, TaskStatus::Running, None);
+        assert!(state.get(&first).is_some());
+
+        state.update_status(&second, TaskStatus::Failed, Some("boom".into()));
+        assert!(state.get(&first).is_none());
+        assert!(state.get(&second).is_some());
+        assert!(state.list_by_slot("slot").iter().all(|t| t.id == second));
+
+        let evicted: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|c| match c {
+                StateChange::Evicted(info) => Some(info.id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(evicted, vec![first]);
+    }
+
+    #[test]
+    fn terminal_limit_spares_active_tasks() {
+        let state = TaskState::new();
+        for i in 0..64 {
+            let id = TaskId::from(format!("task-{i}"));
+            state.add_task(id.clone(), "slot".to_string());
+            state.update_status(&id, TaskStatus::Succeeded, None);
+        }
+        state.add_task(TaskId::from("active"), "slot".to_string());
+
+        state.set_terminal_limit(Some(16));
+        let retained = state.list_by_status(TaskStatus::Succeeded).len();
+        assert!((1..=16).contains(&retained), "retained {retained}");
+        assert!(state.get(&TaskId::from("active")).is_some());
+
+        state.set_terminal_limit(None);
+        let id = TaskId::from("late");
+        state.add_task(id.clone(), "slot".to_string());
+        state.update_status(&id, TaskStatus::Succeeded, None);
+        assert_eq!(
+            state.list_by_status(TaskStatus::Succeeded).len(),
+            retained + 1
+        );
+    }
+
+    #[test]
+    fn terminal_limit_spares_restartable_tasks() {
+        let state = TaskState::new();
+        state.set_terminal_limit(Some(1));
+        let mut periodic = spec("cron", RunnerLabels::new());
+        periodic.restart = RestartStrategy::periodic(60_000);
+        let id = TaskId::from("periodic");
+        state.add_spec_task(id.clone(), &periodic, "runner");
+        // Succeeded between runs, then plenty of one-shot tasks finish after it.
+        state.update_status(&id, TaskStatus::Succeeded, None);
+
+        for i in 0..64 {
+            let other = TaskId::from(format!("task-{i}"));
+            state.add_task(other.clone(), "slot".to_string());
+            state.update_status(&other, TaskStatus::Succeeded, None);
+        }
+        assert!(state.list_by_status(TaskStatus::Succeeded).len() <= SHARD_COUNT + 1);
+        assert_eq!(state.get(&id).unwrap().status, TaskStatus::Succeeded);
+
+        state.increment_attempt(&id);
+        state.update_status(&id, TaskStatus::Running, None);
+        assert_eq!(state.get(&id).unwrap().attempt, 1);
+    }
+
+    #[test]
+    fn counts_statuses_and_active_slots() {
+        let state = setup_query_state();
+        let counts: HashMap<_, _> = state.status_counts().into_iter().collect();
+        assert_eq!(counts[&TaskStatus::Running], 2);
+        assert_eq!(counts[&TaskStatus::Pending], 2);
+        assert_eq!(counts[&TaskStatus::Failed], 1);
+        assert_eq!(counts[&TaskStatus::Succeeded], 0);
+        assert_eq!(state.active_slots(), 2);
+
+        state.update_status(&TaskId::from("b2"), TaskStatus::Succeeded, None);
+        assert_eq!(state.active_slots(), 1);
+    }
+
+    #[test]
+    fn label_index_answers_selectors() {
+        let state = TaskState::new();
+        for i in 0..20 {
+            let mut labels = RunnerLabels::new();
+            labels.insert("team", if i % 2 == 0 { "core" } else { "infra" });
+            if i % 5 == 0 {
+                labels.insert("tier", "gold");
+            }
+            state.add_spec_task(TaskId::from(format!("t{i}")), &spec("slot", labels), "r");
+        }
+
+        let core = state.query(&TaskQuery::new().with_label("team", "core"));
+        assert_eq!(core.total, 10);
+        assert!(
+            core.items
+                .iter()
+                .all(|t| t.labels.get("team") == Some("core"))
+        );
+
+        let gold_core = state.query(
+            &TaskQuery::new()
+                .with_label("team", "core")
+                .with_label("tier", "gold"),
+        );
+        assert_eq!(gold_core.total, 2);
+
+        let none = state.query(&TaskQuery::new().with_label("team", "ops"));
+        assert_eq!(none.total, 0);
+
+        state.remove_task(&TaskId::from("t0"));
+        state.remove_task(&TaskId::from("t10"));
+        let gold_core = state.query(
+            &TaskQuery::new()
+                .with_label("tier", "gold")
+                .with_label("team", "core"),
+        );
+        assert_eq!(gold_core.total, 0);
+        assert_eq!(
+            state
+                .query(&TaskQuery::new().with_label("tier", "gold"))
+                .total,
+            2
+        );
+
+        for i in 0..20 {
+            state.remove_task(&TaskId::from(format!("t{i}")));
+        }
+        assert!(
+            state
+                .shards
+                .iter()
+                .all(|s| s.read().unwrap().by_label.is_empty())
+        );
+    }
+
+    #[test]
+    fn group_index_tracks_members() {
+        let state = TaskState::new();
+        let id1 = TaskId::from("task-1");
+        let id2 = TaskId::from("task-2");
+
+        let batch = |slot| spec(slot, RunnerLabels::new()).with_group("batch");
+        state.add_spec_task(id1.clone(), &batch("slot-a"), "r");
+        state.add_spec_task(id2.clone(), &batch("slot-b"), "r");
+        state.add_task(TaskId::from("task-3"), "slot-a".to_string());
+        state.update_status(&id1, TaskStatus::Succeeded, None);
+
+        assert_eq!(state.list_by_group("batch").len(), 2);
+        let info = state.group_info("batch").expect("group should exist");
+        assert_eq!(info.total, 2);
+        assert_eq!(info.succeeded, 1);
+        assert_eq!(info.status, TaskStatus::Pending);
+
+        state.remove_task(&id1);
+        state.remove_task(&id2);
+        assert!(state.group_info("batch").is_none());
+    }
+
     fn setup_query_state() -> TaskState {
         let state = TaskState::new();
         // slot-a: 3 tasks (2 running, 1 pending)
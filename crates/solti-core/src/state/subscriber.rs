@@ -40,9 +40,14 @@ impl Subscribe for StateSubscriber {
                     .update_status(&task_id, TaskStatus::Running, None);
             }
             EventKind::TaskStopped => {
-                trace!(task = %task_id, "task stopped (success)");
-                self.state
-                    .update_status(&task_id, TaskStatus::Succeeded, None);
+                let status = if self.state.take_skipped(&task_id) {
+                    trace!(task = %task_id, "task stopped (skipped by window)");
+                    TaskStatus::Skipped
+                } else {
+                    trace!(task = %task_id, "task stopped (success)");
+                    TaskStatus::Succeeded
+                };
+                self.state.update_status(&task_id, status, None);
             }
             EventKind::TaskFailed => {
                 let reason = event
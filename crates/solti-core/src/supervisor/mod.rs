@@ -4,21 +4,43 @@
 //! - owns a [`Supervisor`] instance and runs its event loop in the background;
 //! - uses [`RunnerRouter`] to build concrete tasks from [`CreateSpec`];
 //! - maps model-level specs / policies into controller specs and submits them.
-use std::{sync::Arc, time::Duration};
+use std::{
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
 
-use solti_model::{CreateSpec, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus};
+use solti_model::{
+    AdmissionStrategy, CreateSpec, EventQuery, GroupInfo, LABEL_FOLLOW_UP_OF, LABEL_SPEC_HASH,
+    ResourceCapacity, RestartStrategy, SubscriberHealth, TaskEvent, TaskId, TaskInfo, TaskKind,
+    TaskOutput, TaskPage, TaskQuery, TaskQuota, TaskStatus,
+};
 use taskvisor::{
     ControllerConfig, ControllerSpec, Subscribe, Supervisor, SupervisorConfig, TaskRef, TaskSpec,
 };
-use tracing::{debug, info, instrument};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{debug, info, instrument, warn};
 
 use crate::system::init_uptime;
 use crate::{
+    admission::{AdmissionContext, AdmissionPolicy, AllowAll, Caller},
+    bus::{BroadcastEventBus, EventBus, EventBusHandle, EventSubscriber, SubscriberOptions},
+    capacity::CapacityTracker,
+    catch_up::FireHistory,
+    dedup::{find_active, spec_hash},
     error::CoreError,
+    events::{EventLog, EventLogSubscriber},
+    follow_up::FollowUps,
+    limiter::RestartLimiter,
+    maintenance::MaintenanceMode,
     map::{to_admission_policy, to_backoff_policy, to_restart_policy},
+    metrics::{AdmissionDecision, spawn_state_gauges},
     policy::TaskPolicy,
+    queue::QueueLimits,
+    quota::QuotaTracker,
     router::RunnerRouter,
-    state::{StateSubscriber, TaskState},
+    runner::TaskIdGenerator,
+    state::{StateChange, StateSubscriber, TaskState},
+    window::wrap_windowed,
 };
 
 /// Thin wrapper around taskvisor [`Supervisor`] with a runner router.
@@ -29,8 +51,20 @@ use crate::{
 /// - mapping model-level specs into controller specs and submitting them.
 pub struct SupervisorApi {
     sup: Arc<Supervisor>,
-    router: RunnerRouter,
+    router: Arc<RunnerRouter>,
     state: TaskState,
+    events: EventLog,
+    bus: Arc<RwLock<EventBusHandle>>,
+    quotas: Option<Arc<QuotaTracker>>,
+    capacity: Option<Arc<CapacityTracker>>,
+    admission: Arc<dyn AdmissionPolicy>,
+    limiter: Arc<RestartLimiter>,
+    queue_limits: Arc<QueueLimits>,
+    maintenance: Arc<MaintenanceMode>,
+    fires: Option<Arc<FireHistory>>,
+    /// Set when deduplication is enabled; serializes the lookup and registration of a spec.
+    dedup: Option<Arc<Mutex<()>>>,
+    follow_ups: Arc<FollowUps>,
 }
 
 impl SupervisorApi {
@@ -46,10 +80,23 @@ impl SupervisorApi {
         sup_cfg: SupervisorConfig,
         ctrl_cfg: ControllerConfig,
         mut subscribers: Vec<Arc<dyn Subscribe>>,
-        router: RunnerRouter,
+        mut router: RunnerRouter,
     ) -> Result<Self, CoreError> {
+        let limiter = Arc::new(RestartLimiter::new());
+        router.set_restart_limiter(Arc::clone(&limiter));
         let state = TaskState::new();
         subscribers.push(Arc::new(StateSubscriber::new(state.clone())));
+        let events = EventLog::default();
+        let bus: Arc<RwLock<EventBusHandle>> = Arc::new(RwLock::new(Arc::new(
+            BroadcastEventBus::default().with_metrics(router.metrics().clone()),
+        )));
+        subscribers.push(Arc::new(EventLogSubscriber::new(
+            events.clone(),
+            state.clone(),
+            Arc::clone(&bus),
+        )));
+
+        spawn_state_gauges(state.clone(), router.metrics().clone());
 
         let sup = Supervisor::builder(sup_cfg)
             .with_subscribers(subscribers)
@@ -67,7 +114,162 @@ impl SupervisorApi {
         init_uptime();
 
         info!("supervisor is ready to accept tasks");
-        Ok(Self { sup, router, state })
+        Ok(Self {
+            sup,
+            router: Arc::new(router),
+            state,
+            events,
+            bus,
+            quotas: None,
+            capacity: None,
+            admission: Arc::new(AllowAll),
+            limiter,
+            queue_limits: Arc::new(QueueLimits::new()),
+            maintenance: Arc::new(MaintenanceMode::new()),
+            fires: None,
+            dedup: None,
+            follow_ups: Arc::new(FollowUps::default()),
+        })
+    }
+
+    /// Enforce task quotas on [`SupervisorApi::submit`].
+    ///
+    /// Submissions exceeding any quota whose scope matches the spec labels
+    /// are rejected with [`CoreError::QuotaExceeded`].
+    /// Tasks submitted via [`SupervisorApi::submit_with_task`] are not subject to quotas.
+    pub fn with_quotas(mut self, quotas: Vec<TaskQuota>) -> Self {
+        self.quotas = (!quotas.is_empty()).then(|| Arc::new(QuotaTracker::new(quotas)));
+        self
+    }
+
+    /// Refuse [`SupervisorApi::submit`]s whose resource requests do not fit into `allocatable`.
+    ///
+    /// Requests of active tasks (see [`CreateSpec::resources`]) are subtracted from the
+    /// allocatable capacity; a task that does not fit into the rest is rejected with
+    /// [`CoreError::InsufficientCapacity`]. Unbounded capacity disables the check.
+    pub fn with_capacity(mut self, allocatable: ResourceCapacity) -> Self {
+        self.capacity =
+            (!allocatable.is_unbounded()).then(|| Arc::new(CapacityTracker::new(allocatable)));
+        self
+    }
+
+    /// Deduplicate identical submissions by content hash.
+    ///
+    /// When enabled, [`SupervisorApi::submit`] tags every admitted spec with
+    /// [`LABEL_SPEC_HASH`] (see [`crate::spec_hash`]). Submitting a spec identical to an active
+    /// (pending or running) task of the same slot returns the id of that task instead of
+    /// creating a new one. Tasks submitted via [`SupervisorApi::submit_with_task`] are never
+    /// deduplicated.
+    pub fn with_dedup(mut self, enabled: bool) -> Self {
+        self.dedup = enabled.then(|| Arc::new(Mutex::new(())));
+        self
+    }
+
+    /// Allocatable capacity, or `None` when capacity is not tracked.
+    pub fn allocatable_capacity(&self) -> Option<ResourceCapacity> {
+        self.capacity.as_ref().map(|c| c.allocatable())
+    }
+
+    /// Capacity not reserved by active tasks, or `None` when capacity is not tracked.
+    pub fn free_capacity(&self) -> Option<ResourceCapacity> {
+        self.capacity.as_ref().map(|c| c.free(&self.state))
+    }
+
+    /// Evaluate `policy` on every [`SupervisorApi::submit`] before the spec is routed.
+    ///
+    /// The policy may rewrite the spec or reject it with [`CoreError::AdmissionDenied`];
+    /// combine several with [`crate::AdmissionChain`]. Defaults to [`AllowAll`].
+    /// Tasks submitted via [`SupervisorApi::submit_with_task`] bypass the policy.
+    pub fn with_admission_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.admission = Arc::new(policy);
+        self
+    }
+
+    /// Track fire times of periodic tasks and catch up on missed runs.
+    ///
+    /// Periodic tasks submitted via [`SupervisorApi::submit`] record every execution
+    /// in `history`. On submission, runs missed since the slot's last recorded fire are
+    /// handled according to the spec's [`solti_model::CatchUpPolicy`]
+    /// (see [`CreateSpec::catch_up`]; the default is to run once).
+    pub fn with_fire_history(mut self, history: FireHistory) -> Self {
+        self.fires = Some(Arc::new(history));
+        self
+    }
+
+    /// Retain up to `capacity` recent lifecycle events
+    /// (default [`crate::DEFAULT_EVENT_LOG_CAPACITY`]).
+    pub fn with_event_capacity(self, capacity: usize) -> Self {
+        self.events.set_capacity(capacity);
+        self
+    }
+
+    /// Retain at most about `limit` terminal tasks in memory.
+    ///
+    /// Finished tasks stay in the task listing after the supervisor removes them, so their
+    /// final status can still be queried. Above the limit, the least recently updated
+    /// terminal tasks are dropped from the task listing. Tasks that may run again (restart strategy other than
+    /// [`RestartStrategy::Never`]) are kept until the supervisor removes them.
+    /// Subscribers of [`SupervisorApi::watch_tasks`] receive evicted tasks as
+    /// [`StateChange::Evicted`] and may persist them elsewhere.
+    pub fn with_terminal_task_limit(self, limit: usize) -> Self {
+        self.state.set_terminal_limit(Some(limit));
+        self
+    }
+
+    /// Generate ids of tasks built via [`SupervisorApi::submit`] with `generator`.
+    ///
+    /// Defaults to [`crate::RunIdGenerator`] (`{runner}-{slot}-{seq:x}`); use
+    /// [`crate::UuidV7Generator`] or [`crate::PrefixSequenceGenerator`] when downstream
+    /// systems sort or shard by task id. Runners overriding [`crate::Runner::build_run_id`]
+    /// keep their own format.
+    pub fn with_task_id_generator(self, generator: impl TaskIdGenerator + 'static) -> Self {
+        self.router.context().set_task_ids(Arc::new(generator));
+        self
+    }
+
+    /// Publish lifecycle events to `bus` instead of the default [`BroadcastEventBus`].
+    ///
+    /// Subscribers attached before the swap stay on the previous bus,
+    /// so call this before [`SupervisorApi::attach_subscriber`].
+    pub fn with_event_bus(self, bus: impl EventBus + 'static) -> Self {
+        *self.bus.write().unwrap() = Arc::new(bus);
+        self
+    }
+
+    /// Attach a subscriber to the lifecycle events of this supervisor.
+    pub fn attach_subscriber(&self, subscriber: Arc<dyn EventSubscriber>) {
+        self.bus.read().unwrap().attach(subscriber);
+    }
+
+    /// Attach a subscriber with its own queue capacity and [`crate::OverflowPolicy`].
+    pub fn attach_subscriber_with(
+        &self,
+        subscriber: Arc<dyn EventSubscriber>,
+        options: SubscriberOptions,
+    ) {
+        self.bus.read().unwrap().attach_with(subscriber, options);
+    }
+
+    /// Health of the lifecycle event subscribers attached to the current bus.
+    pub fn subscriber_health(&self) -> Vec<SubscriberHealth> {
+        self.bus.read().unwrap().subscribers()
+    }
+
+    /// Recent lifecycle events matching the query, oldest first.
+    pub fn recent_events(&self, query: &EventQuery) -> Vec<TaskEvent> {
+        self.events.query(query)
+    }
+
+    /// Subscribe to task state changes (added, updated, removed).
+    ///
+    /// See [`StateChange`] for the delivered payloads.
+    pub fn watch_tasks(&self) -> broadcast::Receiver<StateChange> {
+        self.state.watch()
+    }
+
+    /// Subscribe to output lines captured by runners (see [`crate::TaskOutputBus`]).
+    pub fn watch_output(&self) -> broadcast::Receiver<TaskOutput> {
+        self.router.context().output().subscribe()
     }
 
     /// Get task information by ID.
@@ -90,36 +292,326 @@ impl SupervisorApi {
         self.state.list_by_status(status)
     }
 
+    /// Number of tracked tasks in every status, in [`TaskStatus::ALL`] order.
+    ///
+    /// Cheaper than counting [`SupervisorApi::list_all_tasks`], as no task is cloned.
+    pub fn status_counts(&self) -> [(TaskStatus, usize); TaskStatus::ALL.len()] {
+        self.state.status_counts()
+    }
+
     /// Query tasks with combined filters and pagination.
     pub fn query_tasks(&self, query: &TaskQuery) -> TaskPage<TaskInfo> {
         self.state.query(query)
     }
 
+    /// Revision of the task state; changes whenever any task changes.
+    ///
+    /// Suitable as a cache validator for task listings.
+    pub fn tasks_revision(&self) -> u64 {
+        self.state.revision()
+    }
+
+    /// List all tasks submitted in a group.
+    pub fn list_tasks_by_group(&self, group: &str) -> Vec<TaskInfo> {
+        self.state.list_by_group(group)
+    }
+
+    /// Get the aggregated status of a group.
+    ///
+    /// Returns `None` if no member of the group is tracked.
+    pub fn get_group(&self, group: &str) -> Option<GroupInfo> {
+        self.state.group_info(group)
+    }
+
+    /// Get a handle to the per-slot restart rate limiter.
+    ///
+    /// Limits apply to every task submitted through this API and can be
+    /// changed at runtime; the next execution of each task uses the new limit.
+    pub fn restart_limiter(&self) -> Arc<RestartLimiter> {
+        Arc::clone(&self.limiter)
+    }
+
+    /// Get a handle to the per-slot queue length caps.
+    ///
+    /// Submissions with [`AdmissionStrategy::Queue`] that would queue behind more than the
+    /// slot's cap of pending tasks are rejected with [`CoreError::QueueFull`].
+    /// Caps can be changed at runtime and apply to the next submission.
+    pub fn queue_limits(&self) -> Arc<QueueLimits> {
+        Arc::clone(&self.queue_limits)
+    }
+
+    /// Get a handle to the agent-wide maintenance flag.
+    ///
+    /// While maintenance is enabled, runs of periodic tasks submitted via
+    /// [`SupervisorApi::submit`] are skipped; the tasks stay registered and
+    /// resume on their next tick once maintenance is disabled.
+    pub fn maintenance(&self) -> Arc<MaintenanceMode> {
+        Arc::clone(&self.maintenance)
+    }
+
+    /// Enable or disable maintenance mode, returning the previous value.
+    pub fn set_maintenance(&self, enabled: bool) -> bool {
+        self.maintenance.set(enabled)
+    }
+
+    /// Returns `true` if maintenance mode is enabled.
+    pub fn is_maintenance(&self) -> bool {
+        self.maintenance.is_enabled()
+    }
+
     /// Get a clone of the underlying supervisor handle.
     pub fn supervisor(&self) -> Arc<Supervisor> {
         Arc::clone(&self.sup)
     }
 
+    /// Get a clone of the runner router handle.
+    ///
+    /// Useful for wiring [`crate::runner_health_check`] or inspecting runner availability.
+    pub fn router(&self) -> Arc<RunnerRouter> {
+        Arc::clone(&self.router)
+    }
+
     /// Build and submit a task described by [`CreateSpec`].
     ///
+    /// Same as [`SupervisorApi::submit_as`] with an anonymous [`Caller`].
+    pub async fn submit(&self, spec: &CreateSpec) -> Result<TaskId, CoreError> {
+        self.submit_as(spec, &Caller::anonymous()).await
+    }
+
+    /// Build and submit a task described by [`CreateSpec`] on behalf of `caller`.
+    ///
     /// Steps:
-    /// 1. Ask the [`RunnerRouter`] to pick a runner and build a [`TaskRef`].
-    /// 2. Convert [`CreateSpec`] into [`TaskPolicy`] (dropping the [`solti_model::TaskKind`] information).
-    /// 3. Delegate to [`SupervisorApi::submit_with_task`].
+    /// 1. Run the admission policy (see [`SupervisorApi::with_admission_policy`]),
+    ///    which may rewrite or reject the spec.
+    /// 2. With deduplication enabled (see [`SupervisorApi::with_dedup`]), return the id of an
+    ///    identical active task of the slot if there is one.
+    /// 3. Ask the [`RunnerRouter`] to pick a runner and build a [`TaskRef`].
+    /// 4. Check the slot queue cap, configured quotas and capacity
+    ///    (see [`SupervisorApi::queue_limits`], [`SupervisorApi::with_quotas`],
+    ///    [`SupervisorApi::with_capacity`]).
+    /// 5. Convert [`CreateSpec`] into [`TaskPolicy`] (dropping the [`solti_model::TaskKind`] information).
+    /// 6. Make periodic tasks honor maintenance mode (see [`SupervisorApi::maintenance`])
+    ///    and missed-run catch-up (see [`SupervisorApi::with_fire_history`]).
+    /// 7. Submit the task to the controller.
+    /// 8. Remember the spec's follow-ups (see [`solti_model::FollowUp`]); once the task
+    ///    terminates, the matching follow-up is submitted the same way on behalf of `caller`,
+    ///    labeled with [`LABEL_FOLLOW_UP_OF`]. Canceled tasks trigger no follow-up.
     ///
     /// This is the primary entrypoint for tasks that are fully described by the public [`solti_model::TaskKind`] model.
-    #[instrument(level = "debug", skip(self, spec), fields(slot = %spec.slot, kind = ?spec.kind))]
-    pub async fn submit(&self, spec: &CreateSpec) -> Result<TaskId, CoreError> {
-        let task = self.router.build(spec)?;
+    #[instrument(
+        level = "debug",
+        skip(self, spec, caller),
+        fields(slot = %spec.slot, kind = ?spec.kind, caller = ?caller.identity)
+    )]
+    pub async fn submit_as(&self, spec: &CreateSpec, caller: &Caller) -> Result<TaskId, CoreError> {
+        self.submit_spec(spec, caller, None).await
+    }
+
+    /// [`SupervisorApi::submit_as`], with the task that triggered `spec` as its follow-up.
+    async fn submit_spec(
+        &self,
+        spec: &CreateSpec,
+        caller: &Caller,
+        follow_up_of: Option<&TaskInfo>,
+    ) -> Result<TaskId, CoreError> {
+        let metrics = self.router.metrics();
+        let mut admitted = spec.clone();
+        let mut ctx = AdmissionContext::new(caller, spec, &self.state);
+        if let Some(parent) = follow_up_of {
+            ctx = ctx.with_follow_up_of(parent);
+        }
+        if let Err(reason) = self.admission.admit(&mut admitted, &ctx) {
+            debug!(%reason, "admission policy rejected spec");
+            metrics.record_admission(spec.admission.as_str(), AdmissionDecision::Rejected);
+            return Err(CoreError::AdmissionDenied(reason));
+        }
+
+        let dedup = match &self.dedup {
+            Some(lock) => {
+                let guard = lock.lock().unwrap();
+                let hash = spec_hash(&admitted);
+                let slot_tasks = self.state.list_by_slot(&admitted.slot);
+                if let Some(id) = find_active(&slot_tasks, &hash) {
+                    debug!(task_id = %id, "identical task already active");
+                    metrics.record_admission(
+                        admitted.admission.as_str(),
+                        AdmissionDecision::Deduplicated,
+                    );
+                    return Ok(id);
+                }
+                admitted.labels.insert(LABEL_SPEC_HASH, hash);
+                Some(guard)
+            }
+            None => None,
+        };
+        let spec = &admitted;
+
+        let (task, runner) = self.router.build_with_runner(spec)?;
         let task_id = TaskId::from(task.name());
+        let strategy = spec.admission.as_str();
 
-        self.state.add_task(task_id.clone(), spec.slot.clone());
+        self.queue_limits
+            .check(spec, &self.state)
+            .inspect_err(|_| metrics.record_admission(strategy, AdmissionDecision::QueueFull))?;
+        if let Some(quotas) = &self.quotas {
+            quotas
+                .admit(&task_id, spec, &self.state)
+                .inspect_err(|_| metrics.record_admission(strategy, AdmissionDecision::Rejected))?;
+        }
+        if let Some(capacity) = &self.capacity
+            && let Err(e) = capacity.admit(&task_id, spec, &self.state)
+        {
+            metrics.record_admission(strategy, AdmissionDecision::Rejected);
+            if let Some(quotas) = &self.quotas {
+                quotas.release(&task_id);
+            }
+            return Err(e);
+        }
+        let decision = admission_decision(spec.admission, self.slot_running(&spec.slot));
+        self.state.add_spec_task(task_id.clone(), spec, runner);
+        if let Some(quotas) = &self.quotas {
+            quotas.confirm(&task_id);
+        }
+        if let Some(capacity) = &self.capacity {
+            capacity.confirm(&task_id);
+        }
+        drop(dedup);
         let policy = TaskPolicy::from_spec(spec);
+        let task = match (&self.fires, spec.restart) {
+            (
+                Some(fires),
+                RestartStrategy::Always {
+                    interval_ms: Some(interval_ms),
+                },
+            ) => fires.wrap(
+                spec.slot.clone(),
+                interval_ms,
+                spec.catch_up.unwrap_or_default(),
+                self.state.clone(),
+                task,
+            ),
+            _ => task,
+        };
+        let task = if matches!(spec.restart, RestartStrategy::Always { .. }) {
+            self.maintenance.wrap(self.state.clone(), task)
+        } else {
+            task
+        };
 
-        self.submit_with_task(task, &policy).await?;
+        // Subscribe before the task can terminate, so its removal is not missed.
+        let follow_up = spec
+            .follow_up
+            .clone()
+            .filter(|f| !f.is_empty())
+            .map(|f| (f, self.state.watch()));
+
+        // Watch tasks apply the timeout and restart limit to each action run themselves.
+        let per_execution = !matches!(spec.kind, TaskKind::Watch { .. });
+        if let Err(e) = self.enqueue(task, &policy, per_execution).await {
+            metrics.record_admission(strategy, AdmissionDecision::Rejected);
+            if let Some(quotas) = &self.quotas {
+                quotas.release(&task_id);
+            }
+            if let Some(capacity) = &self.capacity {
+                capacity.release(&task_id);
+            }
+            self.state.remove_task(&task_id);
+            return Err(e);
+        }
+        metrics.record_admission(strategy, decision);
+        if let Some((follow_up, changes)) = follow_up {
+            self.follow_ups.register(task_id.clone(), follow_up);
+            self.spawn_follow_up(task_id.clone(), caller.clone(), changes);
+        }
         Ok(task_id)
     }
 
+    /// Submit the follow-up of `parent` once it terminates.
+    fn spawn_follow_up(
+        &self,
+        parent: TaskId,
+        caller: Caller,
+        mut changes: broadcast::Receiver<StateChange>,
+    ) {
+        let api = self.handle();
+        tokio::spawn(async move {
+            // Outcome of the last run: tasks whose restart policy is used up end as `Exhausted`
+            // after their last run, whichever way it went.
+            let mut outcome = None;
+            let info = loop {
+                match changes.recv().await {
+                    Ok(StateChange::Removed(info) | StateChange::Evicted(info))
+                        if info.id == parent =>
+                    {
+                        break info;
+                    }
+                    Ok(StateChange::Updated { after, .. })
+                        if after.id == parent
+                            && after.status.is_terminal()
+                            && after.status != TaskStatus::Exhausted =>
+                    {
+                        outcome = Some(after.status);
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) if api.state.get(&parent).is_some() => continue,
+                    Err(RecvError::Lagged(_)) => {
+                        warn!(task_id = %parent, "missed task removal, dropping follow-ups");
+                        api.follow_ups.forget(&parent);
+                        return;
+                    }
+                    Err(RecvError::Closed) => return,
+                }
+            };
+
+            let Some(follow_up) = api.follow_ups.take(&parent) else {
+                debug!(task_id = %parent, "task canceled, skipping follow-ups");
+                return;
+            };
+            let status = match info.status {
+                TaskStatus::Exhausted => outcome.unwrap_or(TaskStatus::Exhausted),
+                status => status,
+            };
+            let Some(spec) = follow_up.for_status(status) else {
+                return;
+            };
+            let mut spec = spec.clone();
+            spec.follow_up = None;
+            spec.labels.insert(LABEL_FOLLOW_UP_OF, parent.as_str());
+            match api.submit_spec(&spec, &caller, Some(&info)).await {
+                Ok(id) => info!(task_id = %parent, follow_up = %id, ?status, "follow-up submitted"),
+                Err(e) => warn!(task_id = %parent, ?status, error = %e, "follow-up rejected"),
+            }
+        });
+    }
+
+    /// Another handle to the same supervisor, for background work.
+    fn handle(&self) -> Self {
+        Self {
+            sup: Arc::clone(&self.sup),
+            router: Arc::clone(&self.router),
+            state: self.state.clone(),
+            events: self.events.clone(),
+            bus: Arc::clone(&self.bus),
+            quotas: self.quotas.clone(),
+            capacity: self.capacity.clone(),
+            admission: Arc::clone(&self.admission),
+            limiter: Arc::clone(&self.limiter),
+            queue_limits: Arc::clone(&self.queue_limits),
+            maintenance: Arc::clone(&self.maintenance),
+            fires: self.fires.clone(),
+            dedup: self.dedup.clone(),
+            follow_ups: Arc::clone(&self.follow_ups),
+        }
+    }
+
+    /// Whether the slot currently has a running task.
+    fn slot_running(&self, slot: &str) -> bool {
+        self.state
+            .list_by_slot(slot)
+            .iter()
+            .any(|info| info.status == TaskStatus::Running)
+    }
+
     /// Submit a pre-built task together with its runtime policy.
     ///
     /// This API is intended for in-process / code-defined tasks (without `TaskKind`).
@@ -134,12 +626,38 @@ impl SupervisorApi {
     ) -> Result<TaskId, CoreError> {
         let task_id = TaskId::from(task.name());
         self.state.add_task(task_id.clone(), policy.slot.clone());
+        if policy.restart != RestartStrategy::Never {
+            self.state.set_restartable(&task_id);
+        }
+
+        self.enqueue(task, policy, true).await?;
+        Ok(task_id)
+    }
 
+    /// Map the policy into a controller spec and hand the task to the controller.
+    ///
+    /// With `per_execution`, every execution of the task waits for a restart permit and is
+    /// bounded by the policy timeout.
+    async fn enqueue(
+        &self,
+        task: TaskRef,
+        policy: &TaskPolicy,
+        per_execution: bool,
+    ) -> Result<(), CoreError> {
+        let task = if per_execution {
+            self.limiter.wrap(policy.slot.clone(), task)
+        } else {
+            task
+        };
+        let task = match &policy.window {
+            Some(window) => wrap_windowed(window.clone(), self.state.clone(), task),
+            None => task,
+        };
         let task_spec = TaskSpec::new(
             task,
             to_restart_policy(policy.restart),
             to_backoff_policy(&policy.backoff),
-            Some(Duration::from_millis(policy.timeout_ms)),
+            per_execution.then(|| Duration::from_millis(policy.timeout_ms)),
         );
         let controller_spec = ControllerSpec {
             admission: to_admission_policy(policy.admission),
@@ -150,8 +668,7 @@ impl SupervisorApi {
         self.sup
             .submit(controller_spec)
             .await
-            .map_err(|e| CoreError::Supervisor(e.to_string()))?;
-        Ok(task_id)
+            .map_err(|e| CoreError::Supervisor(e.to_string()))
     }
 
     /// Cancel a running task by ID.
@@ -162,6 +679,8 @@ impl SupervisorApi {
     /// The task must be cooperative and respect the `CancellationToken`
     /// passed during execution.
     ///
+    /// Follow-ups of the task (see [`solti_model::FollowUp`]) are dropped.
+    ///
     /// Returns:
     /// - `Ok(())` if task was found and successfully cancelled
     /// - `Err(CoreError::Supervisor)` if task not found or cancellation timed out
@@ -177,6 +696,7 @@ impl SupervisorApi {
         if self.state.get(id).is_none() {
             return Err(CoreError::Supervisor(format!("task not found: {}", id)));
         }
+        self.follow_ups.forget(id);
 
         let was_cancelled = self
             .sup
@@ -194,6 +714,240 @@ impl SupervisorApi {
         debug!("task cancelled successfully: {}", id);
         Ok(())
     }
+
+    /// Gracefully stop every running task, e.g. when the host service is stopped.
+    ///
+    /// Tasks are canceled concurrently; each gets the supervisor grace period to exit.
+    /// Returns the number of tasks that were stopped.
+    #[instrument(level = "debug", skip(self))]
+    pub async fn shutdown(&self) -> usize {
+        self.follow_ups.clear();
+        let mut stops = tokio::task::JoinSet::new();
+        for name in self.sup.snapshot().await {
+            let sup = Arc::clone(&self.sup);
+            stops.spawn(async move { (sup.cancel(&name).await, name) });
+        }
+
+        let mut stopped = 0;
+        while let Some(joined) = stops.join_next().await {
+            match joined {
+                Ok((Ok(true), _)) => stopped += 1,
+                Ok((Ok(false), name)) => debug!(task_id = %name, "task already gone"),
+                Ok((Err(e), name)) => warn!(task_id = %name, error = %e, "failed to stop task"),
+                Err(e) => warn!(error = %e, "task stop panicked"),
+            }
+        }
+        info!(stopped, "supervisor tasks stopped");
+        stopped
+    }
+
+    /// Cancel all active (pending or running) members of a group.
+    ///
+    /// Members that finish or disappear while being canceled are skipped.
+    ///
+    /// Returns:
+    /// - `Ok(n)` with the number of canceled members
+    /// - `Err(CoreError::GroupNotFound)` if the group has no tracked members
+    #[instrument(level = "debug", skip(self))]
+    pub async fn cancel_group(&self, group: &str) -> Result<usize, CoreError> {
+        let members = self.state.list_by_group(group);
+        if members.is_empty() {
+            return Err(CoreError::GroupNotFound(group.to_string()));
+        }
+
+        let mut canceled = 0;
+        for info in members.iter().filter(|t| t.status.is_active()) {
+            self.follow_ups.forget(&info.id);
+            match self.sup.cancel(info.id.as_str()).await {
+                Ok(true) => canceled += 1,
+                Ok(false) => debug!(task_id = %info.id, "group member already gone"),
+                Err(e) => warn!(task_id = %info.id, error = %e, "failed to cancel group member"),
+            }
+        }
+
+        debug!(canceled, total = members.len(), "group cancelled");
+        Ok(canceled)
+    }
+
+    /// Wait until a task reaches a terminal state.
+    ///
+    /// Driven by task state changes, so the result is available as soon as the status changes.
+    /// Tasks with [`RestartStrategy::Always`] resolve on their first terminal run.
+    ///
+    /// Returns:
+    /// - `Ok(TaskInfo)` with the terminal task info
+    /// - `Err(CoreError::TaskNotFound)` if the task is not tracked or is removed while active
+    /// - `Err(CoreError::WaitTimeout)` if the task is still active after `timeout`
+    #[instrument(level = "debug", skip(self), fields(task_id = %id))]
+    pub async fn wait(&self, id: &TaskId, timeout: Duration) -> Result<TaskInfo, CoreError> {
+        self.wait_until(id, timeout, |info| info.status.is_terminal())
+            .await
+    }
+
+    /// Wait until the status of a task differs from `from`.
+    ///
+    /// Resolves immediately if the task is no longer in `from`, so clients long-polling
+    /// with the last status they saw never miss a transition.
+    ///
+    /// Returns:
+    /// - `Ok(TaskInfo)` with the task info in its new status
+    /// - `Err(CoreError::TaskNotFound)` if the task is not tracked or is removed in `from`
+    /// - `Err(CoreError::WaitTimeout)` if the task is still in `from` after `timeout`
+    #[instrument(level = "debug", skip(self), fields(task_id = %id))]
+    pub async fn wait_status_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        timeout: Duration,
+    ) -> Result<TaskInfo, CoreError> {
+        self.wait_until(id, timeout, |info| info.status != from)
+            .await
+    }
+
+    /// Submit a task described by [`CreateSpec`] and wait until it reaches a terminal state.
+    ///
+    /// Subscribes to task state changes before submitting, so tasks that finish
+    /// (and are removed) before the wait starts are still reported.
+    ///
+    /// Returns:
+    /// - `Ok((TaskId, Some(TaskInfo)))` with the terminal task info
+    /// - `Ok((TaskId, None))` if the task is still active after `timeout`
+    /// - `Err(CoreError)` if the submission fails or the task disappears while active
+    #[instrument(level = "debug", skip(self, spec), fields(slot = %spec.slot))]
+    pub async fn submit_and_wait(
+        &self,
+        spec: &CreateSpec,
+        timeout: Duration,
+    ) -> Result<(TaskId, Option<TaskInfo>), CoreError> {
+        let changes = self.state.watch();
+        let id = self.submit(spec).await?;
+        match self
+            .wait_with(changes, &id, timeout, |info| info.status.is_terminal())
+            .await
+        {
+            Ok(info) => Ok((id, Some(info))),
+            Err(CoreError::WaitTimeout(_)) => Ok((id, None)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Wait until `done` holds for the task info, driven by task state changes.
+    async fn wait_until(
+        &self,
+        id: &TaskId,
+        timeout: Duration,
+        done: impl Fn(&TaskInfo) -> bool,
+    ) -> Result<TaskInfo, CoreError> {
+        self.wait_with(self.state.watch(), id, timeout, done).await
+    }
+
+    /// [`SupervisorApi::wait_until`] with `changes` subscribed by the caller.
+    ///
+    /// Changes buffered in `changes` are replayed, so a task already gone from the state
+    /// still resolves if it was removed after `changes` was subscribed.
+    async fn wait_with(
+        &self,
+        mut changes: broadcast::Receiver<StateChange>,
+        id: &TaskId,
+        timeout: Duration,
+        done: impl Fn(&TaskInfo) -> bool,
+    ) -> Result<TaskInfo, CoreError> {
+        let not_found = || CoreError::TaskNotFound(id.to_string());
+
+        let wait = async {
+            let mut info = match self.state.get(id) {
+                Some(info) => info,
+                None => {
+                    let mut last = None;
+                    while let Ok(change) = changes.try_recv() {
+                        if let StateChange::Removed(info) = change
+                            && info.id == *id
+                        {
+                            last = Some(info);
+                        }
+                    }
+                    return last.filter(|info| done(info)).ok_or_else(not_found);
+                }
+            };
+            while !done(&info) {
+                info = match changes.recv().await {
+                    Ok(change) if change.task_id() == id => match change {
+                        StateChange::Added(info) | StateChange::Evicted(info) => info,
+                        StateChange::Updated { after, .. } => *after,
+                        StateChange::Removed(info) if done(&info) => info,
+                        StateChange::Removed(_) => return Err(not_found()),
+                    },
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(_)) => self.state.get(id).ok_or_else(not_found)?,
+                    Err(RecvError::Closed) => {
+                        return Err(CoreError::Store("task state watch closed".into()));
+                    }
+                };
+            }
+            Ok(info)
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| CoreError::WaitTimeout(format!("task {id}")))?
+    }
+
+    /// Wait until every member of a group reaches a terminal state.
+    ///
+    /// Returns:
+    /// - `Ok(GroupInfo)` with the final aggregate once the group is complete
+    /// - `Err(CoreError::GroupNotFound)` if the group has no tracked members
+    /// - `Err(CoreError::WaitTimeout)` if the group is still active after `timeout`
+    #[instrument(level = "debug", skip(self))]
+    pub async fn wait_group(&self, group: &str, timeout: Duration) -> Result<GroupInfo, CoreError> {
+        let mut changes = self.state.watch();
+
+        let wait = async {
+            loop {
+                let info = self
+                    .state
+                    .group_info(group)
+                    .ok_or_else(|| CoreError::GroupNotFound(group.to_string()))?;
+                if info.is_complete() {
+                    return Ok(info);
+                }
+                // Re-aggregate on the next change of a member (or after missing some).
+                loop {
+                    match changes.recv().await {
+                        Ok(change) if change_group(&change) == Some(group) => break,
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(_)) => break,
+                        Err(RecvError::Closed) => {
+                            return Err(CoreError::Store("task state watch closed".into()));
+                        }
+                    }
+                }
+            }
+        };
+
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| CoreError::WaitTimeout(format!("group {group}")))?
+    }
+}
+
+/// Group of the task a state change belongs to.
+fn change_group(change: &StateChange) -> Option<&str> {
+    match change {
+        StateChange::Added(info) | StateChange::Removed(info) | StateChange::Evicted(info) => {
+            info.group.as_deref()
+        }
+        StateChange::Updated { after, .. } => after.group.as_deref(),
+    }
+}
+
+/// Decision the controller applies to a task admitted with `strategy`.
+fn admission_decision(strategy: AdmissionStrategy, slot_running: bool) -> AdmissionDecision {
+    match strategy {
+        AdmissionStrategy::DropIfRunning if slot_running => AdmissionDecision::DroppedRunning,
+        AdmissionStrategy::Replace if slot_running => AdmissionDecision::Replaced,
+        _ => AdmissionDecision::Queued,
+    }
 }
 
 #[cfg(test)]
@@ -250,6 +1004,209 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn group_operations_report_unknown_group() {
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        assert!(api.get_group("missing").is_none());
+        assert!(matches!(
+            api.cancel_group("missing").await,
+            Err(CoreError::GroupNotFound(_))
+        ));
+        assert!(matches!(
+            api.wait_group("missing", Duration::from_millis(10)).await,
+            Err(CoreError::GroupNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn wait_group_wakes_on_member_changes() {
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let member = |id: &str| {
+            let spec = command_spec("test-slot-group", "true").with_group("batch");
+            api.state.add_spec_task(TaskId::from(id), &spec, "exits");
+            TaskId::from(id)
+        };
+        let (a, b) = (member("a"), member("b"));
+
+        let waiter = api.handle();
+        let wait =
+            tokio::spawn(async move { waiter.wait_group("batch", Duration::from_secs(5)).await });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!wait.is_finished());
+
+        api.state.update_status(&a, TaskStatus::Succeeded, None);
+        api.state
+            .update_status(&b, TaskStatus::Failed, Some("exit status 1".into()));
+        let info = wait.await.unwrap().unwrap();
+        assert!(info.is_complete());
+        assert_eq!((info.succeeded, info.failed), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn wait_resolves_on_terminal_status() {
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let policy = TaskPolicy::new(
+            "wait-slot".to_string(),
+            5_000,
+            RestartStrategy::Never,
+            mk_backoff(),
+            AdmissionStrategy::DropIfRunning,
+        );
+        let quick: TaskRef = TaskFn::arc("quick", |_ctx: CancellationToken| async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok::<(), TaskError>(())
+        });
+        let id = api.submit_with_task(quick, &policy).await.unwrap();
+        let info = api.wait(&id, Duration::from_secs(5)).await.unwrap();
+        assert_eq!(info.status, TaskStatus::Succeeded);
+
+        let slow: TaskRef = TaskFn::arc("slow", |ctx: CancellationToken| async move {
+            ctx.cancelled().await;
+            Ok::<(), TaskError>(())
+        });
+        let policy = TaskPolicy::new(
+            "wait-slow".to_string(),
+            5_000,
+            RestartStrategy::Never,
+            mk_backoff(),
+            AdmissionStrategy::DropIfRunning,
+        );
+        let id = api.submit_with_task(slow, &policy).await.unwrap();
+        assert!(matches!(
+            api.wait(&id, Duration::from_millis(50)).await,
+            Err(CoreError::WaitTimeout(_))
+        ));
+        assert!(matches!(
+            api.wait(&TaskId::from("missing"), Duration::from_millis(10))
+                .await,
+            Err(CoreError::TaskNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn shutdown_stops_running_tasks() {
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let (started_tx, mut started) = tokio::sync::mpsc::unbounded_channel();
+        let endless: TaskRef = TaskFn::arc("endless", move |ctx: CancellationToken| {
+            let started_tx = started_tx.clone();
+            async move {
+                let _ = started_tx.send(());
+                ctx.cancelled().await;
+                Err::<(), TaskError>(TaskError::Canceled)
+            }
+        });
+        let policy = TaskPolicy::new(
+            "shutdown-slot".to_string(),
+            60_000,
+            RestartStrategy::Never,
+            mk_backoff(),
+            AdmissionStrategy::DropIfRunning,
+        );
+        let id = api.submit_with_task(endless, &policy).await.unwrap();
+        started.recv().await.unwrap();
+
+        assert_eq!(api.shutdown().await, 1);
+        assert!(
+            api.get_task(&id)
+                .is_none_or(|info| !info.status.is_active())
+        );
+        assert_eq!(api.shutdown().await, 0);
+    }
+
+    #[tokio::test]
+    async fn attached_subscribers_receive_events_with_slot() {
+        struct Starts(tokio::sync::mpsc::UnboundedSender<solti_model::TaskEvent>);
+
+        #[async_trait::async_trait]
+        impl EventSubscriber for Starts {
+            async fn on_event(&self, event: &solti_model::TaskEvent) {
+                if event.kind == solti_model::TaskEventKind::TaskStarting {
+                    let _ = self.0.send(event.clone());
+                }
+            }
+
+            fn name(&self) -> &'static str {
+                "starts"
+            }
+        }
+
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi")
+        .with_event_bus(BroadcastEventBus::new(16));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        api.attach_subscriber(Arc::new(Starts(tx)));
+
+        let task: TaskRef = TaskFn::arc("bus-task", |_ctx: CancellationToken| async move {
+            Ok::<(), TaskError>(())
+        });
+        let policy = TaskPolicy::new(
+            "bus-slot".to_string(),
+            1_000,
+            RestartStrategy::Never,
+            mk_backoff(),
+            AdmissionStrategy::DropIfRunning,
+        );
+        let id = api.submit_with_task(task, &policy).await.unwrap();
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("no event delivered")
+            .unwrap();
+        assert_eq!(event.task.as_ref(), Some(&id));
+        assert_eq!(event.slot.as_deref(), Some("bus-slot"));
+        assert_eq!(event.attempt, Some(1));
+    }
+
+    #[test]
+    fn admission_decision_depends_on_running_slot() {
+        use AdmissionDecision::*;
+        use AdmissionStrategy::*;
+
+        assert_eq!(admission_decision(DropIfRunning, false), Queued);
+        assert_eq!(admission_decision(DropIfRunning, true), DroppedRunning);
+        assert_eq!(admission_decision(Replace, false), Queued);
+        assert_eq!(admission_decision(Replace, true), Replaced);
+        assert_eq!(admission_decision(Queue, true), Queued);
+    }
+
     #[tokio::test]
     async fn submit_rejects_taskkind_none() {
         let router = RunnerRouter::new();
@@ -270,6 +1227,11 @@ mod tests {
             backoff: mk_backoff(),
             admission: AdmissionStrategy::DropIfRunning,
             labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
         };
         let res = api.submit(&spec).await;
 
@@ -281,4 +1243,474 @@ mod tests {
             Err(e) => panic!("expected CoreError::NoRunner, got {e:?}"),
         }
     }
+
+    #[tokio::test]
+    async fn admission_policy_rejects_before_routing() {
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            RunnerRouter::new(),
+        )
+        .await
+        .expect("failed to create SupervisorApi")
+        .with_admission_policy(
+            |_: &mut CreateSpec, ctx: &crate::AdmissionContext<'_>| match &ctx.caller().identity {
+                Some(id) if id == "ops" => Ok(()),
+                _ => Err("only ops may submit".to_string()),
+            },
+        );
+
+        let spec = CreateSpec {
+            slot: "test-slot-admission".to_string(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: mk_backoff(),
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+
+        match api.submit(&spec).await {
+            Err(CoreError::AdmissionDenied(reason)) => assert_eq!(reason, "only ops may submit"),
+            other => panic!("expected CoreError::AdmissionDenied, got {other:?}"),
+        }
+        // Admitted specs continue to routing, which has no runner for `TaskKind::None`.
+        assert!(matches!(
+            api.submit_as(&spec, &crate::Caller::new("ops")).await,
+            Err(CoreError::NoRunner(_))
+        ));
+    }
+
+    /// Runner whose tasks run until canceled.
+    struct UntilCanceled;
+
+    impl crate::Runner for UntilCanceled {
+        fn name(&self) -> &'static str {
+            "until-canceled"
+        }
+
+        fn supports(&self, spec: &CreateSpec) -> bool {
+            matches!(spec.kind, TaskKind::Subprocess { .. })
+        }
+
+        fn build_task(
+            &self,
+            spec: &CreateSpec,
+            ctx: &crate::BuildContext,
+        ) -> Result<TaskRef, crate::RunnerError> {
+            Ok(TaskFn::arc(
+                self.build_run_id(&spec.slot, ctx),
+                |cancel: CancellationToken| async move {
+                    cancel.cancelled().await;
+                    Err(TaskError::Canceled)
+                },
+            ))
+        }
+    }
+
+    async fn wait_status(
+        api: &SupervisorApi,
+        id: &TaskId,
+        done: impl Fn(Option<TaskStatus>) -> bool,
+    ) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !done(api.get_task(id).map(|info| info.status)) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("task status did not change in time");
+    }
+
+    #[tokio::test]
+    async fn dedup_returns_active_identical_task() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(UntilCanceled));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi")
+        .with_dedup(true);
+
+        let spec = CreateSpec {
+            slot: "test-slot-dedup".to_string(),
+            kind: TaskKind::Subprocess {
+                command: "sleep".to_string(),
+                args: vec!["60".into()],
+                env: Default::default(),
+                cwd: None,
+                fail_on_non_zero: Default::default(),
+            },
+            timeout_ms: 60_000,
+            restart: RestartStrategy::Never,
+            backoff: mk_backoff(),
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+
+        let first = api.submit(&spec).await.unwrap();
+        assert_eq!(api.submit(&spec).await.unwrap(), first);
+        assert_eq!(
+            api.get_task(&first).unwrap().labels.get(LABEL_SPEC_HASH),
+            Some(spec_hash(&spec).as_str())
+        );
+
+        let mut other = spec.clone();
+        other.timeout_ms = 30_000;
+        let second = api.submit(&other).await.unwrap();
+        assert_ne!(second, first);
+
+        wait_status(&api, &first, |s| s == Some(TaskStatus::Running)).await;
+        api.cancel_task(&first).await.unwrap();
+        wait_status(&api, &first, |s| !s.is_some_and(|s| s.is_active())).await;
+        let third = api.submit(&spec).await.unwrap();
+        assert_ne!(third, first);
+        api.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn wait_status_change_resolves_on_transition() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(UntilCanceled));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let spec = CreateSpec {
+            slot: "test-slot-long-poll".to_string(),
+            kind: TaskKind::Subprocess {
+                command: "sleep".to_string(),
+                args: Vec::new(),
+                env: Default::default(),
+                cwd: None,
+                fail_on_non_zero: Default::default(),
+            },
+            timeout_ms: 60_000,
+            restart: RestartStrategy::Never,
+            backoff: mk_backoff(),
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        };
+        let id = api.submit(&spec).await.unwrap();
+
+        let info = api
+            .wait_status_change(&id, TaskStatus::Pending, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(info.status, TaskStatus::Running);
+        assert!(matches!(
+            api.wait_status_change(&id, TaskStatus::Running, Duration::from_millis(50))
+                .await,
+            Err(CoreError::WaitTimeout(_))
+        ));
+
+        let wait = api.wait_status_change(&id, TaskStatus::Running, Duration::from_secs(5));
+        let (changed, canceled) = tokio::join!(wait, api.cancel_task(&id));
+        canceled.unwrap();
+        assert!(changed.unwrap().status.is_terminal());
+    }
+
+    /// Runner whose tasks exit like the `true` / `false` commands, or run until canceled.
+    struct Exits;
+
+    impl crate::Runner for Exits {
+        fn name(&self) -> &'static str {
+            "exits"
+        }
+
+        fn supports(&self, spec: &CreateSpec) -> bool {
+            matches!(spec.kind, TaskKind::Subprocess { .. })
+        }
+
+        fn build_task(
+            &self,
+            spec: &CreateSpec,
+            ctx: &crate::BuildContext,
+        ) -> Result<TaskRef, crate::RunnerError> {
+            let TaskKind::Subprocess { command, .. } = &spec.kind else {
+                unreachable!("supports() accepts subprocess tasks only");
+            };
+            let command = command.clone();
+            Ok(TaskFn::arc(
+                self.build_run_id(&spec.slot, ctx),
+                move |cancel: CancellationToken| {
+                    let command = command.clone();
+                    async move {
+                        match command.as_str() {
+                            "true" => Ok(()),
+                            "false" => Err(TaskError::Fail {
+                                reason: "exit status 1".into(),
+                            }),
+                            _ => {
+                                cancel.cancelled().await;
+                                Err(TaskError::Canceled)
+                            }
+                        }
+                    }
+                },
+            ))
+        }
+    }
+
+    fn command_spec(slot: &str, command: &str) -> CreateSpec {
+        CreateSpec {
+            slot: slot.to_string(),
+            kind: TaskKind::Subprocess {
+                command: command.to_string(),
+                args: Vec::new(),
+                env: Default::default(),
+                cwd: None,
+                fail_on_non_zero: Default::default(),
+            },
+            timeout_ms: 5_000,
+            restart: RestartStrategy::Never,
+            backoff: mk_backoff(),
+            admission: AdmissionStrategy::Queue,
+            labels: RunnerLabels::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    /// Slots of tasks added until the task `id` is removed, plus a short grace period.
+    async fn slots_added_until_removed(
+        changes: &mut broadcast::Receiver<StateChange>,
+        id: &TaskId,
+    ) -> Vec<(String, Option<String>)> {
+        let mut added = Vec::new();
+        let mut collect = async |until_removed: bool| {
+            while let Ok(change) = changes.recv().await {
+                match change {
+                    StateChange::Added(info) => added.push((
+                        info.slot.clone(),
+                        info.labels.get(LABEL_FOLLOW_UP_OF).map(str::to_string),
+                    )),
+                    StateChange::Removed(info) if until_removed && &info.id == id => return,
+                    _ => {}
+                }
+            }
+        };
+        tokio::time::timeout(Duration::from_secs(5), collect(true))
+            .await
+            .expect("task was not removed in time");
+        let _ = tokio::time::timeout(Duration::from_millis(200), collect(false)).await;
+        added
+    }
+
+    #[tokio::test]
+    async fn follow_up_matches_final_status() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(Exits));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        let with_follow_ups = |command| {
+            command_spec("test-slot-follow-up", command)
+                .with_on_success(command_spec("test-slot-report", "true"))
+                .with_on_failure(command_spec("test-slot-notify", "true"))
+        };
+
+        let mut changes = api.watch_tasks();
+        let succeeding = api.submit(&with_follow_ups("true")).await.unwrap();
+        let added = slots_added_until_removed(&mut changes, &succeeding).await;
+        assert_eq!(
+            added,
+            [
+                ("test-slot-follow-up".to_string(), None),
+                (
+                    "test-slot-report".to_string(),
+                    Some(succeeding.as_str().to_string())
+                ),
+            ]
+        );
+
+        let mut changes = api.watch_tasks();
+        let failing = api.submit(&with_follow_ups("false")).await.unwrap();
+        let added = slots_added_until_removed(&mut changes, &failing).await;
+        assert_eq!(
+            added,
+            [
+                ("test-slot-follow-up".to_string(), None),
+                (
+                    "test-slot-notify".to_string(),
+                    Some(failing.as_str().to_string())
+                ),
+            ]
+        );
+
+        let mut changes = api.watch_tasks();
+        let canceled = api.submit(&with_follow_ups("sleep")).await.unwrap();
+        wait_status(&api, &canceled, |s| s == Some(TaskStatus::Running)).await;
+        api.cancel_task(&canceled).await.unwrap();
+        let added = slots_added_until_removed(&mut changes, &canceled).await;
+        assert_eq!(added, [("test-slot-follow-up".to_string(), None)]);
+    }
+
+    #[tokio::test]
+    async fn submit_and_wait_reports_instantly_completing_tasks() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(Exits));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi");
+
+        for _ in 0..20 {
+            let spec = command_spec("test-slot-submit-wait", "true");
+            let (id, info) = api
+                .submit_and_wait(&spec, Duration::from_secs(5))
+                .await
+                .unwrap();
+            let info = info.expect("task did not complete in time");
+            assert_eq!(info.id, id);
+            assert_eq!(info.status, TaskStatus::Succeeded);
+        }
+
+        let (_, info) = api
+            .submit_and_wait(
+                &command_spec("test-slot-submit-wait", "sleep"),
+                Duration::from_millis(50),
+            )
+            .await
+            .unwrap();
+        assert!(info.is_none());
+    }
+
+    #[tokio::test]
+    async fn terminal_task_limit_retains_removed_tasks() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(Exits));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi")
+        .with_terminal_task_limit(1);
+
+        let mut changes = api.watch_tasks();
+        let mut ids = Vec::new();
+        for _ in 0..64 {
+            ids.push(
+                api.submit(&command_spec("test-slot-retained", "true"))
+                    .await
+                    .unwrap(),
+            );
+        }
+        // Tasks evicted before the supervisor removes them are not reported as removed.
+        let (mut gone, mut evicted) = (std::collections::HashSet::new(), Vec::new());
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while gone.len() < ids.len() {
+                match changes.recv().await.unwrap() {
+                    StateChange::Removed(info) => {
+                        gone.insert(info.id);
+                    }
+                    StateChange::Evicted(info) => {
+                        gone.insert(info.id.clone());
+                        evicted.push(info.id);
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+        .expect("tasks were not removed in time");
+
+        // The limit of one applies per shard, so some tasks outlive their removal...
+        let retained = api.list_all_tasks();
+        assert!(!retained.is_empty());
+        assert!(retained.iter().all(|t| t.status.is_terminal()));
+        // ...but no more than the limit allows.
+        assert!(retained.len() <= 16, "retained {}", retained.len());
+        assert!(!evicted.is_empty());
+        assert!(evicted.iter().all(|id| api.get_task(id).is_none()));
+    }
+
+    #[cfg(feature = "signing")]
+    #[tokio::test]
+    async fn signed_spec_follow_ups_inherit_the_signer() {
+        use crate::{SpecVerifier, sign_spec};
+        use solti_model::LABEL_SIGNER;
+
+        const SECRET: [u8; 32] = [7; 32];
+        let public = ed25519_dalek::SigningKey::from_bytes(&SECRET)
+            .verifying_key()
+            .to_bytes();
+
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(Exits));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .expect("failed to create SupervisorApi")
+        .with_admission_policy(SpecVerifier::new().with_key("cp-1", &public).unwrap());
+
+        let mut spec = command_spec("test-slot-signed", "true")
+            .with_on_success(command_spec("test-slot-signed-report", "true"));
+        sign_spec(&mut spec, "cp-1", &SECRET);
+
+        let mut changes = api.watch_tasks();
+        let parent = api.submit(&spec).await.unwrap();
+        let follow_up = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Ok(StateChange::Added(info)) = changes.recv().await
+                    && info.slot == "test-slot-signed-report"
+                {
+                    return info;
+                }
+            }
+        })
+        .await
+        .expect("follow-up was not submitted");
+
+        assert_eq!(
+            follow_up.labels.get(LABEL_FOLLOW_UP_OF),
+            Some(parent.as_str())
+        );
+        assert_eq!(follow_up.labels.get(LABEL_SIGNER), Some("cp-1"));
+    }
 }
@@ -14,6 +14,29 @@ pub fn uptime_seconds() -> u64 {
     start.elapsed().as_secs()
 }
 
+/// Version and build metadata of the agent, see [`build_info`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildInfo {
+    /// `solti-core` crate version.
+    pub version: &'static str,
+    /// Git commit the SDK was built from (`None` outside a git checkout).
+    pub git_sha: Option<&'static str>,
+    /// Build time in unix seconds (`SOURCE_DATE_EPOCH` when set).
+    pub build_timestamp: u64,
+}
+
+/// Get build metadata captured at compile time.
+///
+/// `SOLTI_GIT_SHA` may be set in the build environment to provide the commit
+/// when building without a git checkout (e.g. in a container build).
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        version: env!("CARGO_PKG_VERSION"),
+        git_sha: option_env!("SOLTI_GIT_SHA"),
+        build_timestamp: env!("SOLTI_BUILD_TIMESTAMP").parse().unwrap_or(0),
+    }
+}
+
 /// Get platform (OS family).
 #[inline]
 pub fn platform() -> &'static str {
@@ -42,6 +65,62 @@ pub fn agent_id() -> &'static str {
     })
 }
 
+/// Point-in-time host and process load (best effort; fields are `None` where unsupported).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadSnapshot {
+    /// 1, 5 and 15 minute load averages of the host.
+    pub load_avg: Option<[f64; 3]>,
+    /// Resident memory of the agent process in bytes.
+    pub rss_bytes: Option<u64>,
+    /// CPU time consumed by the agent process (user + system) in seconds.
+    pub cpu_seconds: Option<f64>,
+    /// Number of CPUs available to the agent.
+    pub cpu_count: usize,
+}
+
+/// Collect a [`LoadSnapshot`] (reads `/proc` on Linux).
+pub fn load_snapshot() -> LoadSnapshot {
+    LoadSnapshot {
+        load_avg: read_load_avg(),
+        rss_bytes: read_rss_bytes(),
+        cpu_seconds: read_cpu_seconds(),
+        cpu_count: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+    }
+}
+
+fn read_load_avg() -> Option<[f64; 3]> {
+    let content = fs::read_to_string("/proc/loadavg").ok()?;
+    let mut fields = content.split_whitespace().map(|f| f.parse::<f64>().ok());
+    Some([fields.next()??, fields.next()??, fields.next()??])
+}
+
+fn read_rss_bytes() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kb = status
+        .lines()
+        .find_map(|l| l.strip_prefix("VmRSS:"))?
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kb * 1024)
+}
+
+fn read_cpu_seconds() -> Option<f64> {
+    let stat = fs::read_to_string("/proc/self/stat").ok()?;
+    // Fields after the parenthesised command name; utime and stime are fields 14 and 15.
+    let rest = &stat[stat.rfind(')')? + 1..];
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    // SAFETY: sysconf has no preconditions.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    (ticks > 0).then(|| (utime + stime) as f64 / ticks as f64)
+}
+
 /// Get OS distribution info (Linux only, best effort).
 ///
 /// Returns OS name from `/etc/os-release` or generic platform name.
@@ -139,6 +218,24 @@ mod tests {
         assert!(!id1.is_empty());
     }
 
+    #[test]
+    fn load_snapshot_reads_proc() {
+        let load = load_snapshot();
+        assert!(load.cpu_count >= 1);
+        if cfg!(target_os = "linux") {
+            assert!(load.load_avg.is_some());
+            assert!(load.rss_bytes.is_some_and(|b| b > 0));
+            assert!(load.cpu_seconds.is_some());
+        }
+    }
+
+    #[test]
+    fn build_info_is_captured() {
+        let info = build_info();
+        assert_eq!(info.version, env!("CARGO_PKG_VERSION"));
+        assert!(info.build_timestamp > 0);
+    }
+
     #[test]
     fn test_platform() {
         assert!(!platform().is_empty());
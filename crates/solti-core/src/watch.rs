@@ -0,0 +1,284 @@
+//! Filesystem watch tasks.
+//!
+//! [`TaskKind::Watch`](solti_model::TaskKind::Watch) specs are built by the router: the action
+//! is routed to a runner as if it were submitted on its own, and the resulting task is wrapped
+//! so that every execution watches the path and runs the action once per debounced burst of
+//! matching events.
+//!
+//! A watch runs until canceled, so the spec timeout bounds each action run rather than the
+//! watch itself; likewise the restart rate limit is applied per action run (see
+//! [`crate::RestartLimiter`]).
+use std::{future::Future, path::PathBuf, pin::Pin, sync::Arc, time::Duration};
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use solti_model::WatchEvent;
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+/// Wrap `action` so that it runs whenever matching events fire under `path`.
+///
+/// Each action run is bounded by `timeout` (`None` = no timeout).
+pub(crate) fn wrap_watch(
+    path: PathBuf,
+    events: Vec<WatchEvent>,
+    debounce: Duration,
+    timeout: Option<Duration>,
+    action: TaskRef,
+) -> TaskRef {
+    Arc::new(WatchTask {
+        path,
+        events: Arc::new(events),
+        debounce,
+        timeout,
+        action,
+    })
+}
+
+/// Task watching a path and running its action on matching events.
+struct WatchTask {
+    path: PathBuf,
+    events: Arc<Vec<WatchEvent>>,
+    debounce: Duration,
+    timeout: Option<Duration>,
+    action: TaskRef,
+}
+
+impl Task for WatchTask {
+    fn name(&self) -> &str {
+        self.action.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        let path = self.path.clone();
+        let events = Arc::clone(&self.events);
+        let debounce = self.debounce;
+        let timeout = self.timeout;
+        let action = Arc::clone(&self.action);
+
+        Box::pin(async move {
+            let (tx, mut rx) = mpsc::unbounded_channel();
+            let mut watcher = notify::recommended_watcher(move |event| {
+                let _ = tx.send(event);
+            })
+            .map_err(watch_failed)?;
+            watcher
+                .watch(&path, RecursiveMode::Recursive)
+                .map_err(watch_failed)?;
+            debug!(path = %path.display(), "watching path");
+
+            loop {
+                tokio::select! {
+                    _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                    matched = next_match(&mut rx, &events) => matched?,
+                }
+                // Let the burst settle: every further matching event restarts the quiet period.
+                loop {
+                    tokio::select! {
+                        _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                        _ = tokio::time::sleep(debounce) => break,
+                        matched = next_match(&mut rx, &events) => matched?,
+                    }
+                }
+
+                debug!(path = %path.display(), "watch triggered, running action");
+                run_action(&action, &ctx, timeout).await?;
+            }
+        })
+    }
+}
+
+/// Run the action once, canceling it if it outlives `timeout`.
+async fn run_action(
+    action: &TaskRef,
+    ctx: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Result<(), TaskError> {
+    let run_ctx = ctx.child_token();
+    let run = action.spawn(run_ctx.clone());
+    let Some(timeout) = timeout else {
+        return run.await;
+    };
+    match tokio::time::timeout(timeout, run).await {
+        Ok(res) => res,
+        Err(_) => {
+            run_ctx.cancel();
+            Err(TaskError::Timeout { timeout })
+        }
+    }
+}
+
+/// Wait for the next event matching `events`.
+async fn next_match(
+    rx: &mut mpsc::UnboundedReceiver<notify::Result<Event>>,
+    events: &[WatchEvent],
+) -> Result<(), TaskError> {
+    loop {
+        match rx.recv().await {
+            Some(Ok(event)) if event_matches(&event.kind, events) => return Ok(()),
+            Some(Ok(_)) => {}
+            Some(Err(e)) => warn!(error = %e, "filesystem watch error"),
+            None => {
+                return Err(TaskError::Fail {
+                    reason: "filesystem watcher stopped".into(),
+                });
+            }
+        }
+    }
+}
+
+/// Whether a notify event kind is one of `events` (any event when empty).
+fn event_matches(kind: &EventKind, events: &[WatchEvent]) -> bool {
+    let event = match kind {
+        EventKind::Create(_) => WatchEvent::Create,
+        EventKind::Modify(_) => WatchEvent::Modify,
+        EventKind::Remove(_) => WatchEvent::Remove,
+        _ => return false,
+    };
+    events.is_empty() || events.contains(&event)
+}
+
+fn watch_failed(e: notify::Error) -> TaskError {
+    TaskError::Fail {
+        reason: format!("cannot watch path: {e}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::{AccessKind, CreateKind, ModifyKind, RemoveKind};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use taskvisor::TaskFn;
+
+    #[test]
+    fn empty_event_filter_matches_everything_but_access() {
+        let create = EventKind::Create(CreateKind::File);
+        let remove = EventKind::Remove(RemoveKind::File);
+        assert!(event_matches(&create, &[]));
+        assert!(event_matches(&EventKind::Modify(ModifyKind::Any), &[]));
+        assert!(!event_matches(&EventKind::Access(AccessKind::Any), &[]));
+
+        assert!(event_matches(&remove, &[WatchEvent::Remove]));
+        assert!(!event_matches(&create, &[WatchEvent::Remove]));
+    }
+
+    async fn wait_runs(runs: &AtomicUsize, expected: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while runs.load(Ordering::SeqCst) < expected {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("watch action did not run in time");
+    }
+
+    #[tokio::test]
+    async fn runs_action_on_matching_events() {
+        let dir = std::env::temp_dir().join(format!("solti-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        let action: TaskRef = TaskFn::arc("watch-action", move |_ctx: CancellationToken| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        });
+        let task = wrap_watch(
+            dir.clone(),
+            vec![WatchEvent::Remove],
+            Duration::from_millis(20),
+            None,
+            action,
+        );
+        assert_eq!(task.name(), "watch-action");
+
+        let cancel = CancellationToken::new();
+        let run = tokio::spawn(task.spawn(cancel.clone()));
+        // Give the watcher time to start before touching the directory.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let file = dir.join("app.conf");
+        std::fs::write(&file, "a = 1").unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(runs.load(Ordering::SeqCst), 0, "create is filtered out");
+
+        std::fs::remove_file(&file).unwrap();
+        wait_runs(&runs, 1).await;
+
+        cancel.cancel();
+        assert!(matches!(run.await.unwrap(), Err(TaskError::Canceled)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_path_fails_the_run() {
+        let action: TaskRef = TaskFn::arc("watch-action", |_ctx: CancellationToken| async {
+            Ok::<(), TaskError>(())
+        });
+        let task = wrap_watch(
+            "/nonexistent/solti-watch".into(),
+            Vec::new(),
+            Duration::ZERO,
+            None,
+            action,
+        );
+        assert!(matches!(
+            task.spawn(CancellationToken::new()).await,
+            Err(TaskError::Fail { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn timeout_bounds_each_action_run() {
+        let dir = std::env::temp_dir().join(format!("solti-watch-timeout-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&runs);
+        let action: TaskRef = TaskFn::arc("watch-action", move |_ctx: CancellationToken| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        });
+        let task = wrap_watch(
+            dir.clone(),
+            Vec::new(),
+            Duration::from_millis(10),
+            Some(Duration::from_millis(50)),
+            action,
+        );
+
+        let cancel = CancellationToken::new();
+        let run = tokio::spawn(task.spawn(cancel.clone()));
+        // The watch outlives the timeout; only action runs are bounded by it.
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert!(!run.is_finished());
+
+        std::fs::write(dir.join("app.conf"), "a = 1").unwrap();
+        wait_runs(&runs, 1).await;
+        assert!(!run.is_finished());
+
+        cancel.cancel();
+        assert!(matches!(run.await.unwrap(), Err(TaskError::Canceled)));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn slow_action_times_out() {
+        let action: TaskRef = TaskFn::arc("watch-action", |ctx: CancellationToken| async move {
+            ctx.cancelled().await;
+            Err(TaskError::Canceled)
+        });
+        let res = run_action(
+            &action,
+            &CancellationToken::new(),
+            Some(Duration::from_millis(20)),
+        )
+        .await;
+        assert!(matches!(res, Err(TaskError::Timeout { .. })));
+    }
+}
@@ -0,0 +1,133 @@
+//! Execution windows.
+//!
+//! Tasks with an [`ExecutionWindow`] are wrapped so that every execution first checks
+//! the current time. Runs outside the window complete immediately without calling the
+//! task body and are recorded as [`solti_model::TaskStatus::Skipped`].
+use std::{future::Future, pin::Pin};
+
+use solti_model::{ExecutionWindow, TaskId};
+use taskvisor::{Task, TaskError, TaskRef};
+use tokio_util::sync::CancellationToken;
+use tracing::debug;
+
+use crate::state::TaskState;
+
+/// Wrap a task so that it only runs inside `window`.
+pub(crate) fn wrap_windowed(window: ExecutionWindow, state: TaskState, task: TaskRef) -> TaskRef {
+    std::sync::Arc::new(WindowedTask {
+        window,
+        state,
+        inner: task,
+    })
+}
+
+/// Task wrapper skipping executions outside of an [`ExecutionWindow`].
+struct WindowedTask {
+    window: ExecutionWindow,
+    state: TaskState,
+    inner: TaskRef,
+}
+
+impl Task for WindowedTask {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn spawn(
+        &self,
+        ctx: CancellationToken,
+    ) -> Pin<Box<dyn Future<Output = Result<(), TaskError>> + Send + 'static>> {
+        if !self.window.contains_now() {
+            let id = TaskId::from(self.inner.name());
+            debug!(task = %id, "outside execution window, skipping run");
+            self.state.mark_skipped(&id);
+            return Box::pin(async { Ok(()) });
+        }
+        self.inner.spawn(ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicUsize, Ordering},
+        },
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use solti_model::TimeOfDay;
+    use taskvisor::TaskFn;
+
+    use super::*;
+
+    fn counting_task(runs: Arc<AtomicUsize>) -> TaskRef {
+        TaskFn::arc("windowed", move |_ctx: CancellationToken| {
+            let runs = Arc::clone(&runs);
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        })
+    }
+
+    fn window_from_now(offset_minutes: u64, len_minutes: u64) -> ExecutionWindow {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            / 60;
+        let at = |m: u64| {
+            let m = (now + m) % (24 * 60);
+            TimeOfDay::new((m / 60) as u8, (m % 60) as u8).unwrap()
+        };
+        ExecutionWindow {
+            days: vec![],
+            start: at(offset_minutes),
+            end: at(offset_minutes + len_minutes),
+            utc_offset_minutes: 0,
+            timezone: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn runs_inside_window() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let state = TaskState::new();
+        let id = TaskId::from("windowed");
+        state.add_task(id.clone(), "slot".into());
+
+        let window = ExecutionWindow {
+            days: vec![],
+            start: TimeOfDay::new(0, 0).unwrap(),
+            end: TimeOfDay::new(0, 0).unwrap(),
+            utc_offset_minutes: 0,
+            timezone: None,
+        };
+        let task = wrap_windowed(window, state.clone(), counting_task(Arc::clone(&runs)));
+        task.spawn(CancellationToken::new()).await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(!state.take_skipped(&id));
+    }
+
+    #[tokio::test]
+    async fn skips_outside_window() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let state = TaskState::new();
+        let id = TaskId::from("windowed");
+        state.add_task(id.clone(), "slot".into());
+
+        let task = wrap_windowed(
+            window_from_now(120, 60),
+            state.clone(),
+            counting_task(Arc::clone(&runs)),
+        );
+        task.spawn(CancellationToken::new()).await.unwrap();
+
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+        assert!(state.take_skipped(&id));
+        assert!(!state.take_skipped(&id));
+    }
+}
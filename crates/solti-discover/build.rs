@@ -12,6 +12,8 @@ fn main() -> Result<(), Box<dyn Error>> {
         .build_server(true)
         .build_client(true)
         .type_attribute(".", "#[derive(serde::Serialize, serde::Deserialize)]")
+        .type_attribute("solti.discover.v1.SyncResponse", "#[serde(default)]")
+        .type_attribute("solti.discover.v1.DeregisterResponse", "#[serde(default)]")
         .compile_protos(&["proto/v1/sync.proto"], &["proto"])?;
     Ok(())
 }
@@ -1,9 +1,18 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, path::PathBuf};
 
-#[derive(Clone, Debug)]
+use solti_model::{RunnerLabels, Taint};
+
+use crate::token::TokenSource;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum DiscoveryTransport {
     Grpc,
     Http,
+    /// Announce via mDNS/DNS-SD and sync with a control plane discovered on the LAN;
+    /// `control_plane_endpoint` is ignored. Requires the `mdns` feature.
+    Mdns,
+    /// Persistent WebSocket connection to `control_plane_endpoint`. Requires the `websocket` feature.
+    WebSocket,
 }
 
 #[derive(Debug, Clone)]
@@ -14,4 +23,29 @@ pub struct DiscoverConfig {
     pub agent_endpoint: String,
     pub name: String,
     pub delay_ms: u64,
+    /// TLS settings for the control plane connection (`None` = plaintext).
+    pub tls: Option<TlsConfig>,
+    /// Registration token sent with every sync (`None` = unauthenticated).
+    pub token: Option<TokenSource>,
+    /// Scheduling labels of the agent (see [`solti_model::AGENT_LABEL_REGION`] and friends).
+    ///
+    /// Unlike `metadata`, labels are meant for placement decisions via [`solti_model::Placement`].
+    pub labels: RunnerLabels,
+    /// Taints repelling work that does not tolerate them.
+    pub taints: Vec<Taint>,
+}
+
+/// TLS / mTLS settings for the control plane connection.
+///
+/// - `ca_cert` — PEM bundle used to verify the server; platform roots are used when unset.
+/// - `client_cert` / `client_key` — PEM client certificate chain and private key for mTLS;
+///   both must be set together.
+/// - `server_name` — overrides the name used for SNI and certificate verification
+///   (gRPC transport only; the HTTP transport always verifies against the endpoint host).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsConfig {
+    pub ca_cert: Option<PathBuf>,
+    pub client_cert: Option<PathBuf>,
+    pub client_key: Option<PathBuf>,
+    pub server_name: Option<String>,
 }
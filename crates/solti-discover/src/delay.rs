@@ -0,0 +1,73 @@
+//! Runtime-adjustable delay between discovery syncs.
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::watch;
+
+/// Delay between discovery syncs, shared by the sync task and whoever adjusts it
+/// (e.g. a settings reload hook).
+///
+/// A new delay takes effect immediately: a sync already waiting is rescheduled against it.
+#[derive(Clone, Debug)]
+pub struct SyncDelay {
+    tx: Arc<watch::Sender<u64>>,
+}
+
+impl SyncDelay {
+    /// Create a delay of `delay_ms` milliseconds.
+    pub fn new(delay_ms: u64) -> Self {
+        Self {
+            tx: Arc::new(watch::Sender::new(delay_ms)),
+        }
+    }
+
+    /// Current delay in milliseconds.
+    pub fn get(&self) -> u64 {
+        *self.tx.borrow()
+    }
+
+    /// Change the delay.
+    pub fn set(&self, delay_ms: u64) {
+        self.tx.send_replace(delay_ms);
+    }
+
+    /// Wait until the current delay has passed since `since`, following changes made meanwhile.
+    pub(crate) async fn wait_since(&self, since: Instant) {
+        let mut rx = self.tx.subscribe();
+        loop {
+            let due = since + Duration::from_millis(*rx.borrow_and_update());
+            tokio::select! {
+                _ = tokio::time::sleep_until(due.into()) => return,
+                Ok(()) = rx.changed() => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn shorter_delay_reschedules_waiting_sync() {
+        let delay = SyncDelay::new(60_000);
+        let since = Instant::now();
+        let waiting = tokio::spawn({
+            let delay = delay.clone();
+            async move { delay.wait_since(since).await }
+        });
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!waiting.is_finished());
+
+        delay.set(50);
+        assert_eq!(delay.get(), 50);
+        tokio::time::timeout(Duration::from_secs(5), waiting)
+            .await
+            .expect("wait did not follow the new delay")
+            .unwrap();
+        assert!(since.elapsed() >= Duration::from_millis(50));
+    }
+}
@@ -11,10 +11,25 @@ pub enum DiscoverError {
     #[error("http request failed: {0}")]
     HttpRequest(#[from] reqwest::Error),
 
+    #[error("tls configuration error: {0}")]
+    Tls(String),
+
+    #[error("registration token error: {0}")]
+    Token(String),
+
+    #[error("mdns error: {0}")]
+    Mdns(String),
+
+    #[error("websocket error: {0}")]
+    WebSocket(String),
+
     #[error("invalid response: {0}")]
     InvalidResponse(String),
 
-    #[error("control plane rejected sync")]
+    #[error("request timed out after {0:?}")]
+    Timeout(std::time::Duration),
+
+    #[error("control plane rejected request")]
     Rejected,
 }
 
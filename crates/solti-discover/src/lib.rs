@@ -4,11 +4,32 @@ pub mod proto {
 pub use proto::*;
 
 mod tasks;
-pub use tasks::sync;
+pub use tasks::{Deregistration, SyncBuilder, sync, sync_with_maintenance};
 
 mod config;
 pub use config::DiscoverConfig;
 pub use config::DiscoveryTransport;
+pub use config::TlsConfig;
+
+mod tls;
+
+mod mdns;
+pub use mdns::{AGENT_SERVICE_TYPE, CONTROL_PLANE_SERVICE_TYPE};
+
+mod ws;
+pub use ws::KEEPALIVE_INTERVAL;
+
+mod stats;
+pub use stats::ConnectionStats;
+
+mod delay;
+pub use delay::SyncDelay;
+
+mod reconcile;
+pub use reconcile::ReconcileSummary;
+
+mod token;
+pub use token::TokenSource;
 
 mod errors;
 pub use errors::DiscoverError;
@@ -0,0 +1,270 @@
+//! mDNS / DNS-SD discovery for networks without static endpoints.
+//!
+//! With [`DiscoveryTransport::Mdns`](crate::DiscoveryTransport::Mdns) the agent:
+//! - announces itself as a `_solti-agent._tcp.local.` service, with the sync payload
+//!   (id, endpoint, platform, version, maintenance) in TXT records;
+//! - browses `_solti-cp._tcp.local.` for a control plane and, once one is resolved,
+//!   syncs with it over the transport named by its `transport` TXT record
+//!   (`grpc`, `websocket`, or `http` by default).
+//!
+//! Requires the `mdns` feature; without it the transport fails on every sync.
+use crate::SyncRequest;
+use crate::config::DiscoveryTransport;
+use crate::errors::DiscoverError;
+
+/// Service type announced by agents.
+pub const AGENT_SERVICE_TYPE: &str = "_solti-agent._tcp.local.";
+
+/// Service type browsed for control planes.
+pub const CONTROL_PLANE_SERVICE_TYPE: &str = "_solti-cp._tcp.local.";
+
+/// Control plane resolved via mDNS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ControlPlane {
+    pub fullname: String,
+    pub transport: DiscoveryTransport,
+    pub endpoint: String,
+}
+
+/// Port of the agent endpoint announced in the SRV record.
+#[cfg_attr(not(feature = "mdns"), allow(dead_code))]
+fn endpoint_port(endpoint: &str) -> Result<u16, DiscoverError> {
+    let uri: http::Uri = endpoint
+        .parse()
+        .map_err(|e| DiscoverError::Mdns(format!("invalid agent endpoint {endpoint:?}: {e}")))?;
+    match (uri.port_u16(), uri.scheme_str()) {
+        (Some(port), _) => Ok(port),
+        (None, Some("https")) => Ok(443),
+        (None, Some("http")) => Ok(80),
+        _ => Err(DiscoverError::Mdns(format!(
+            "agent endpoint {endpoint:?} has no port"
+        ))),
+    }
+}
+
+/// TXT records describing the agent.
+#[cfg_attr(not(feature = "mdns"), allow(dead_code))]
+fn txt_records(request: &SyncRequest) -> Vec<(String, String)> {
+    vec![
+        ("id".into(), request.id.clone()),
+        ("name".into(), request.name.clone()),
+        ("endpoint".into(), request.endpoint.clone()),
+        ("platform".into(), request.platform.clone()),
+        ("arch".into(), request.arch.clone()),
+        ("version".into(), request.agent_version.clone()),
+        ("maintenance".into(), request.maintenance.to_string()),
+    ]
+}
+
+/// Build a control plane endpoint from a resolved service.
+#[cfg_attr(not(feature = "mdns"), allow(dead_code))]
+fn control_plane(
+    fullname: &str,
+    addrs: &[std::net::IpAddr],
+    port: u16,
+    transport: Option<&str>,
+    tls: bool,
+) -> Option<ControlPlane> {
+    let addr = addrs
+        .iter()
+        .find(|a| a.is_ipv4())
+        .or_else(|| addrs.first())?;
+    let transport = match transport.map(str::to_ascii_lowercase).as_deref() {
+        Some("grpc") => DiscoveryTransport::Grpc,
+        Some("websocket") => DiscoveryTransport::WebSocket,
+        _ => DiscoveryTransport::Http,
+    };
+    let scheme = if tls { "https" } else { "http" };
+    let host = match addr {
+        std::net::IpAddr::V4(v4) => v4.to_string(),
+        std::net::IpAddr::V6(v6) => format!("[{v6}]"),
+    };
+    Some(ControlPlane {
+        fullname: fullname.to_string(),
+        transport,
+        endpoint: format!("{scheme}://{host}:{port}"),
+    })
+}
+
+#[cfg(feature = "mdns")]
+mod imp {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use mdns_sd::{Receiver, ServiceDaemon, ServiceEvent, ServiceInfo};
+    use tracing::{debug, info};
+
+    use super::*;
+
+    fn mdns_err(e: mdns_sd::Error) -> DiscoverError {
+        DiscoverError::Mdns(e.to_string())
+    }
+
+    /// Running mDNS responder and control plane browser.
+    pub(crate) struct Mdns {
+        daemon: ServiceDaemon,
+        browse: Receiver<ServiceEvent>,
+        control_plane: Mutex<Option<ControlPlane>>,
+        announced: Mutex<Option<String>>,
+    }
+
+    impl Mdns {
+        pub fn new() -> Result<Self, DiscoverError> {
+            let daemon = ServiceDaemon::new().map_err(mdns_err)?;
+            let browse = daemon
+                .browse(CONTROL_PLANE_SERVICE_TYPE)
+                .map_err(mdns_err)?;
+            Ok(Self {
+                daemon,
+                browse,
+                control_plane: Mutex::new(None),
+                announced: Mutex::new(None),
+            })
+        }
+
+        /// Announce (or refresh) this agent with the current sync payload.
+        pub fn announce(&self, request: &SyncRequest) -> Result<(), DiscoverError> {
+            let port = endpoint_port(&request.endpoint)?;
+            let instance = if request.name.is_empty() {
+                &request.id
+            } else {
+                &request.name
+            };
+            let host = format!("{}.local.", request.id);
+            let txt: HashMap<String, String> = txt_records(request).into_iter().collect();
+            let info = ServiceInfo::new(AGENT_SERVICE_TYPE, instance, &host, "", port, txt)
+                .map_err(mdns_err)?
+                .enable_addr_auto();
+            let fullname = info.get_fullname().to_string();
+            self.daemon.register(info).map_err(mdns_err)?;
+
+            let mut announced = self.announced.lock().expect("mdns lock poisoned");
+            if announced.as_deref() != Some(fullname.as_str()) {
+                info!(%fullname, "announcing agent via mdns");
+                *announced = Some(fullname);
+            }
+            Ok(())
+        }
+
+        /// Withdraw the announcement (sends mDNS goodbye packets).
+        pub fn withdraw(&self) {
+            let announced = self.announced.lock().expect("mdns lock poisoned").take();
+            if let Some(fullname) = announced {
+                let _ = self.daemon.unregister(&fullname);
+            }
+        }
+
+        /// Latest resolved control plane, after applying pending browse events.
+        pub fn control_plane(&self, tls: bool) -> Option<ControlPlane> {
+            let mut current = self.control_plane.lock().expect("mdns lock poisoned");
+            while let Ok(event) = self.browse.try_recv() {
+                match event {
+                    ServiceEvent::ServiceResolved(service) => {
+                        let addrs: Vec<_> = service.get_addresses().iter().copied().collect();
+                        let found = super::control_plane(
+                            service.get_fullname(),
+                            &addrs,
+                            service.get_port(),
+                            service.get_property_val_str("transport"),
+                            tls,
+                        );
+                        if let Some(found) = found {
+                            if current.as_ref() != Some(&found) {
+                                info!(endpoint = %found.endpoint, "control plane discovered via mdns");
+                            }
+                            *current = Some(found);
+                        }
+                    }
+                    ServiceEvent::ServiceRemoved(_, fullname)
+                        if current.as_ref().is_some_and(|cp| cp.fullname == fullname) =>
+                    {
+                        debug!(%fullname, "control plane left");
+                        *current = None;
+                    }
+                    _ => {}
+                }
+            }
+            current.clone()
+        }
+    }
+
+    impl Drop for Mdns {
+        fn drop(&mut self) {
+            self.withdraw();
+            let _ = self.daemon.shutdown();
+        }
+    }
+}
+
+#[cfg(not(feature = "mdns"))]
+mod imp {
+    use super::*;
+
+    enum Never {}
+
+    /// Placeholder used when the crate is built without the `mdns` feature.
+    pub(crate) struct Mdns {
+        never: Never,
+    }
+
+    impl Mdns {
+        pub fn new() -> Result<Self, DiscoverError> {
+            Err(DiscoverError::Mdns(
+                "solti-discover was built without the `mdns` feature".into(),
+            ))
+        }
+
+        pub fn announce(&self, _request: &SyncRequest) -> Result<(), DiscoverError> {
+            match self.never {}
+        }
+
+        pub fn withdraw(&self) {
+            match self.never {}
+        }
+
+        pub fn control_plane(&self, _tls: bool) -> Option<ControlPlane> {
+            match self.never {}
+        }
+    }
+}
+
+pub(crate) use imp::Mdns;
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+    use super::*;
+
+    #[test]
+    fn endpoint_port_defaults_by_scheme() {
+        assert_eq!(endpoint_port("http://0.0.0.0:8085").unwrap(), 8085);
+        assert_eq!(endpoint_port("https://agent.local").unwrap(), 443);
+        assert_eq!(endpoint_port("http://agent.local").unwrap(), 80);
+        assert!(endpoint_port("agent.local").is_err());
+    }
+
+    #[test]
+    fn control_plane_prefers_ipv4_and_reads_transport() {
+        let addrs = [
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 7)),
+        ];
+        let cp = control_plane(
+            "cp._solti-cp._tcp.local.",
+            &addrs,
+            8082,
+            Some("GRPC"),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(cp.transport, DiscoveryTransport::Grpc));
+        assert_eq!(cp.endpoint, "http://10.0.0.7:8082");
+
+        let cp = control_plane("cp", &addrs[..1], 8082, None, true).unwrap();
+        assert!(matches!(cp.transport, DiscoveryTransport::Http));
+        assert_eq!(cp.endpoint, "https://[::1]:8082");
+
+        assert!(control_plane("cp", &[], 8082, None, false).is_none());
+    }
+}
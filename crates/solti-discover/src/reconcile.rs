@@ -0,0 +1,220 @@
+//! Reconciliation of control-plane task assignments.
+//!
+//! When the control plane sets `manage_tasks` in a [`SyncResponse`](crate::SyncResponse),
+//! its `tasks` list is the complete desired set of assigned tasks. [`Reconciler`] compares it
+//! with the tasks it submitted earlier and converges:
+//! - new keys are submitted;
+//! - keys whose spec changed are canceled and resubmitted;
+//! - keys no longer present are canceled and forgotten.
+//!
+//! Assignments are keyed by [`DesiredTask::key`](crate::DesiredTask); finished tasks are not
+//! resubmitted while their key stays assigned with the same spec.
+use std::{collections::HashMap, sync::Arc};
+
+use solti_core::SupervisorApi;
+use solti_model::{CreateSpec, TaskId};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::DesiredTask;
+
+/// Outcome of one reconciliation pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReconcileSummary {
+    /// Newly submitted (or resubmitted) assignments.
+    pub submitted: usize,
+    /// Canceled assignments (removed or replaced).
+    pub canceled: usize,
+    /// Assignments left as they were.
+    pub unchanged: usize,
+    /// Assignments that could not be parsed or submitted.
+    pub failed: usize,
+}
+
+struct Assigned {
+    task_id: TaskId,
+    spec: serde_json::Value,
+}
+
+/// Converges locally submitted tasks to the control plane's desired set.
+pub(crate) struct Reconciler {
+    api: Arc<SupervisorApi>,
+    assigned: Mutex<HashMap<String, Assigned>>,
+}
+
+impl Reconciler {
+    pub fn new(api: Arc<SupervisorApi>) -> Self {
+        Self {
+            api,
+            assigned: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Apply the desired set of assignments.
+    pub async fn apply(&self, desired: Vec<DesiredTask>) -> ReconcileSummary {
+        let mut summary = ReconcileSummary::default();
+        let mut assigned = self.assigned.lock().await;
+
+        let mut wanted: HashMap<String, serde_json::Value> = HashMap::new();
+        for task in desired {
+            match serde_json::from_str::<serde_json::Value>(&task.spec_json) {
+                Ok(spec) => {
+                    wanted.insert(task.key, spec);
+                }
+                Err(e) => {
+                    warn!(key = %task.key, error = %e, "invalid assigned spec");
+                    summary.failed += 1;
+                }
+            }
+        }
+
+        let stale: Vec<String> = assigned
+            .iter()
+            .filter(|(key, a)| wanted.get(*key) != Some(&a.spec))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            if let Some(old) = assigned.remove(&key) {
+                self.cancel(&key, &old.task_id).await;
+                summary.canceled += 1;
+            }
+        }
+
+        for (key, spec) in wanted {
+            if assigned.contains_key(&key) {
+                summary.unchanged += 1;
+                continue;
+            }
+            match self.submit(&key, &spec).await {
+                Some(task_id) => {
+                    assigned.insert(key, Assigned { task_id, spec });
+                    summary.submitted += 1;
+                }
+                None => summary.failed += 1,
+            }
+        }
+
+        if summary.submitted + summary.canceled + summary.failed > 0 {
+            info!(?summary, "reconciled assigned tasks");
+        }
+        summary
+    }
+
+    async fn submit(&self, key: &str, spec: &serde_json::Value) -> Option<TaskId> {
+        let spec: CreateSpec = match serde_json::from_value(spec.clone()) {
+            Ok(spec) => spec,
+            Err(e) => {
+                warn!(%key, error = %e, "assigned spec is not a valid CreateSpec");
+                return None;
+            }
+        };
+        match self.api.submit(&spec).await {
+            Ok(task_id) => {
+                debug!(%key, %task_id, "assigned task submitted");
+                Some(task_id)
+            }
+            Err(e) => {
+                warn!(%key, error = %e, "failed to submit assigned task");
+                None
+            }
+        }
+    }
+
+    async fn cancel(&self, key: &str, task_id: &TaskId) {
+        let active = self
+            .api
+            .get_task(task_id)
+            .is_some_and(|info| info.status.is_active());
+        if !active {
+            debug!(%key, %task_id, "unassigned task already finished");
+            return;
+        }
+        if let Err(e) = self.api.cancel_task(task_id).await {
+            warn!(%key, %task_id, error = %e, "failed to cancel unassigned task");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use solti_core::{BuildContext, Runner, RunnerError, RunnerRouter};
+    use solti_model::TaskKind;
+    use taskvisor::{ControllerConfig, SupervisorConfig, TaskFn, TaskRef};
+    use tokio_util::sync::CancellationToken;
+
+    struct InstantRunner;
+
+    impl Runner for InstantRunner {
+        fn name(&self) -> &'static str {
+            "instant"
+        }
+
+        fn supports(&self, spec: &CreateSpec) -> bool {
+            matches!(spec.kind, TaskKind::Subprocess { .. })
+        }
+
+        fn build_task(
+            &self,
+            spec: &CreateSpec,
+            ctx: &BuildContext,
+        ) -> Result<TaskRef, RunnerError> {
+            Ok(TaskFn::arc(
+                self.build_run_id(&spec.slot, ctx),
+                |_ctx: CancellationToken| async move { Ok(()) },
+            ))
+        }
+    }
+
+    fn desired(key: &str, command: &str) -> DesiredTask {
+        let spec = serde_json::json!({
+            "slot": key,
+            "kind": { "subprocess": { "command": command } },
+            "timeoutMs": 1000,
+            "restart": { "type": "never" },
+            "backoff": { "jitter": "none", "firstMs": 0, "maxMs": 0, "factor": 1.0 },
+            "admission": "dropIfRunning"
+        });
+        DesiredTask {
+            key: key.to_string(),
+            spec_json: spec.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn converges_to_desired_set() {
+        let mut router = RunnerRouter::new();
+        router.register(Arc::new(InstantRunner));
+        let api = SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            Vec::new(),
+            router,
+        )
+        .await
+        .unwrap();
+        let reconciler = Reconciler::new(Arc::new(api));
+
+        let s = reconciler
+            .apply(vec![desired("a", "true"), desired("b", "true")])
+            .await;
+        assert_eq!((s.submitted, s.canceled, s.unchanged), (2, 0, 0));
+
+        let s = reconciler
+            .apply(vec![desired("a", "true"), desired("b", "false")])
+            .await;
+        assert_eq!((s.submitted, s.canceled, s.unchanged), (1, 1, 1));
+
+        let s = reconciler.apply(vec![desired("a", "true")]).await;
+        assert_eq!((s.submitted, s.canceled, s.unchanged), (0, 1, 1));
+
+        let s = reconciler
+            .apply(vec![DesiredTask {
+                key: "bad".into(),
+                spec_json: "{".into(),
+            }])
+            .await;
+        assert_eq!((s.canceled, s.failed), (1, 1));
+    }
+}
@@ -0,0 +1,107 @@
+//! Control plane connection counters.
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+
+/// Counters for connections opened and syncs sent by the discovery sync task.
+///
+/// The channel is opened once and reused across syncs; it is dropped after a failed call
+/// and reopened on the next sync, which counts as a reconnect. Failed syncs are retried
+/// with the sync task's jittered backoff.
+#[derive(Debug, Default)]
+pub struct ConnectionStats {
+    connects: AtomicU64,
+    reconnects: AtomicU64,
+    syncs: AtomicU64,
+    sync_failures: AtomicU64,
+    consecutive_failures: AtomicU64,
+    last_success: AtomicI64,
+}
+
+impl ConnectionStats {
+    /// Create zeroed counters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Total number of channels established (including reconnects).
+    pub fn connects(&self) -> u64 {
+        self.connects.load(Ordering::Relaxed)
+    }
+
+    /// Number of channels established to replace one dropped after an error.
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Number of successful syncs.
+    pub fn syncs(&self) -> u64 {
+        self.syncs.load(Ordering::Relaxed)
+    }
+
+    /// Number of failed syncs.
+    pub fn sync_failures(&self) -> u64 {
+        self.sync_failures.load(Ordering::Relaxed)
+    }
+
+    /// Failed syncs since the last successful one.
+    pub fn consecutive_failures(&self) -> u64 {
+        self.consecutive_failures.load(Ordering::Relaxed)
+    }
+
+    /// Unix timestamp (seconds) of the last successful sync, `None` before the first one.
+    pub fn last_success(&self) -> Option<i64> {
+        match self.last_success.load(Ordering::Relaxed) {
+            0 => None,
+            ts => Some(ts),
+        }
+    }
+
+    pub(crate) fn record_sync(&self, ok: bool, now: i64) {
+        if ok {
+            self.syncs.fetch_add(1, Ordering::Relaxed);
+            self.consecutive_failures.store(0, Ordering::Relaxed);
+            self.last_success.store(now, Ordering::Relaxed);
+        } else {
+            self.sync_failures.fetch_add(1, Ordering::Relaxed);
+            self.consecutive_failures.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn record_connect(&self, reconnect: bool) {
+        self.connects.fetch_add(1, Ordering::Relaxed);
+        if reconnect {
+            self.reconnects.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_connects_and_reconnects() {
+        let stats = ConnectionStats::new();
+        stats.record_connect(false);
+        stats.record_connect(true);
+        stats.record_connect(true);
+        assert_eq!(stats.connects(), 3);
+        assert_eq!(stats.reconnects(), 2);
+    }
+
+    #[test]
+    fn counts_sync_outcomes() {
+        let stats = ConnectionStats::new();
+        assert_eq!(stats.last_success(), None);
+
+        stats.record_sync(false, 100);
+        stats.record_sync(false, 110);
+        assert_eq!(stats.consecutive_failures(), 2);
+
+        stats.record_sync(true, 120);
+        stats.record_sync(false, 130);
+        assert_eq!(stats.syncs(), 1);
+        assert_eq!(stats.sync_failures(), 3);
+        assert_eq!(stats.consecutive_failures(), 1);
+        assert_eq!(stats.last_success(), Some(120));
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{debug, info, warn};
+
+use crate::config::DiscoveryTransport;
+use crate::errors::DiscoverError;
+use crate::ws::WsMessage;
+use crate::{DeregisterRequest, DeregisterResponse};
+
+use super::sync::{SyncContext, unix_now};
+
+/// Handle for telling the control plane that the agent is going away.
+///
+/// Obtained from [`SyncBuilder::build_with_deregistration`](super::SyncBuilder::build_with_deregistration)
+/// and called from the agent's shutdown path (signal handler, admin API, ...), so the control plane
+/// removes the agent immediately instead of waiting for its sync to expire.
+#[derive(Clone)]
+pub struct Deregistration {
+    ctx: Arc<SyncContext>,
+}
+
+impl Deregistration {
+    pub(super) fn new(ctx: Arc<SyncContext>) -> Self {
+        Self { ctx }
+    }
+
+    /// Send the deregistration message, giving up after `timeout`.
+    pub async fn send(&self, reason: &str, timeout: Duration) -> Result<(), DiscoverError> {
+        let request = DeregisterRequest {
+            id: self.ctx.base_request.id.clone(),
+            name: self.ctx.base_request.name.clone(),
+            reason: reason.to_string(),
+            ts: unix_now(),
+        };
+        let response = tokio::time::timeout(timeout, self.invoke(request))
+            .await
+            .map_err(|_| DiscoverError::Timeout(timeout))??;
+        if !response.success {
+            return Err(DiscoverError::Rejected);
+        }
+        Ok(())
+    }
+
+    /// Best-effort variant of [`send`](Self::send): failures are logged, never returned.
+    pub async fn send_best_effort(&self, reason: &str, timeout: Duration) {
+        match self.send(reason, timeout).await {
+            Ok(()) => info!("deregistered from control plane"),
+            Err(e) => warn!("deregistration failed: {}", e),
+        }
+    }
+
+    async fn invoke(
+        &self,
+        request: DeregisterRequest,
+    ) -> Result<DeregisterResponse, DiscoverError> {
+        let ctx = &self.ctx;
+        ctx.check_tls()?;
+        if let Some(mdns) = ctx.mdns()? {
+            mdns.withdraw();
+        }
+        let Some(target) = ctx.target()? else {
+            debug!("no control plane discovered, nothing to deregister from");
+            return Ok(DeregisterResponse { success: true });
+        };
+        debug!("sending deregister request to control plane");
+
+        match target.transport {
+            DiscoveryTransport::Grpc => {
+                let request = ctx.grpc_request(request)?;
+                let mut client = ctx.grpc_client(&target.endpoint).await?;
+                match client.deregister(request).await {
+                    Ok(response) => Ok(response.into_inner()),
+                    Err(status) => {
+                        ctx.reset_grpc().await;
+                        Err(status.into())
+                    }
+                }
+            }
+            DiscoveryTransport::WebSocket => {
+                match ctx
+                    .ws_call(&target.endpoint, WsMessage::Deregister(request))
+                    .await?
+                {
+                    WsMessage::DeregisterResult(response) => Ok(response),
+                    other => Err(DiscoverError::InvalidResponse(format!(
+                        "unexpected websocket reply: {other:?}"
+                    ))),
+                }
+            }
+            DiscoveryTransport::Http | DiscoveryTransport::Mdns => {
+                let response = ctx
+                    .http_post(&target.endpoint, "/api/v1/discovery/deregister")?
+                    .json(&request)
+                    .send()
+                    .await?;
+                let body = response.text().await?;
+                serde_json::from_str(&body).map_err(|e| {
+                    DiscoverError::InvalidResponse(format!(
+                        "failed to parse response: {}, body: {}",
+                        e, body
+                    ))
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{DiscoverConfig, SyncBuilder};
+
+    #[tokio::test]
+    async fn send_times_out_on_silent_control_plane() {
+        // Bound but never accepted: the request is written, no response ever arrives.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let config = DiscoverConfig {
+            metadata: HashMap::new(),
+            control_plane_endpoint: format!("http://{}", listener.local_addr().unwrap()),
+            transport: DiscoveryTransport::Http,
+            agent_endpoint: "http://127.0.0.1:0".into(),
+            name: "test-agent".into(),
+            delay_ms: 1000,
+            tls: None,
+            token: None,
+            labels: Default::default(),
+            taints: Vec::new(),
+        };
+        let (_, _, deregistration) = SyncBuilder::new(config).build_with_deregistration();
+
+        let err = deregistration
+            .send("shutdown", Duration::from_millis(100))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DiscoverError::Timeout(_)));
+    }
+}
@@ -1,2 +1,5 @@
+mod deregister;
+pub use deregister::Deregistration;
+
 mod sync;
-pub use sync::sync;
+pub use sync::{SyncBuilder, sync, sync_with_maintenance};
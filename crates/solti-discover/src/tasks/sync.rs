@@ -1,24 +1,182 @@
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use tonic::transport::Channel;
+
+use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, warn};
 
-use solti_core::{agent_id, arch, os_info, platform, uptime_seconds};
+use solti_core::{
+    LoadSnapshot, MaintenanceMode, RunnerRouter, SupervisorApi, agent_id, arch, build_info,
+    load_snapshot, os_info, platform, uptime_seconds,
+};
 use solti_model::{
-    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, RunnerLabels,
-    TaskKind,
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, ResourceCapacity,
+    RestartStrategy, RunnerLabels, TaskKind, TaskStatus,
 };
 use taskvisor::{TaskError, TaskFn, TaskRef};
 
 use crate::config::{DiscoverConfig, DiscoveryTransport};
+use crate::delay::SyncDelay;
 use crate::errors::DiscoverError;
-use crate::{SyncRequest, SyncResponse, discover_service_client::DiscoverServiceClient};
+use crate::mdns::Mdns;
+use crate::reconcile::Reconciler;
+use crate::stats::ConnectionStats;
+use crate::tls::TlsSetup;
+use crate::token::TokenProvider;
+use crate::ws::{KEEPALIVE_INTERVAL, WsClient, WsMessage};
+
+use super::deregister::Deregistration;
+use crate::{
+    BuildInfo, CapacityInfo, LoadInfo, ResourceAmounts, RunnerInfo, SyncRequest, SyncResponse,
+    Taint, TaskSummary, discover_service_client::DiscoverServiceClient,
+};
 
 const SLOT: &str = "solti-discover-sync";
 
+/// Build the periodic discovery sync task.
 pub fn sync(config: DiscoverConfig) -> (TaskRef, CreateSpec) {
-    let delay_ms = config.delay_ms;
+    SyncBuilder::new(config).build()
+}
+
+/// Build the periodic discovery sync task advertising the agent's maintenance mode.
+pub fn sync_with_maintenance(
+    config: DiscoverConfig,
+    maintenance: Arc<MaintenanceMode>,
+) -> (TaskRef, CreateSpec) {
+    SyncBuilder::new(config)
+        .with_maintenance(maintenance)
+        .build()
+}
+
+/// Builder for the discovery sync task with optional runtime sources.
+///
+/// Every attached source is read on each sync, so the control plane always
+/// sees the current maintenance flag and runner health.
+pub struct SyncBuilder {
+    config: DiscoverConfig,
+    maintenance: Option<Arc<MaintenanceMode>>,
+    router: Option<Arc<RunnerRouter>>,
+    supervisor: Option<Arc<SupervisorApi>>,
+    summary: Option<Arc<SupervisorApi>>,
+    capacity: Option<Arc<SupervisorApi>>,
+    stats: Arc<ConnectionStats>,
+    delay: Option<SyncDelay>,
+    agent_version: String,
+    features: Vec<String>,
+}
+
+impl SyncBuilder {
+    /// Start from a discovery config; the agent version defaults to the SDK version.
+    pub fn new(config: DiscoverConfig) -> Self {
+        Self {
+            config,
+            maintenance: None,
+            router: None,
+            supervisor: None,
+            summary: None,
+            capacity: None,
+            stats: Arc::new(ConnectionStats::new()),
+            delay: None,
+            agent_version: env!("CARGO_PKG_VERSION").to_string(),
+            features: Vec::new(),
+        }
+    }
+
+    /// Advertise the agent's maintenance mode.
+    pub fn with_maintenance(mut self, maintenance: Arc<MaintenanceMode>) -> Self {
+        self.maintenance = Some(maintenance);
+        self
+    }
+
+    /// Advertise registered runners (names, task kinds, labels and health).
+    pub fn with_router(mut self, router: Arc<RunnerRouter>) -> Self {
+        self.router = Some(router);
+        self
+    }
+
+    /// Let the control plane assign tasks to this agent.
+    ///
+    /// When a sync response sets `manage_tasks`, its task list is reconciled
+    /// against the tasks previously assigned through this sync loop.
+    pub fn with_reconciler(mut self, supervisor: Arc<SupervisorApi>) -> Self {
+        self.supervisor = Some(supervisor);
+        self
+    }
+
+    /// Advertise per-status task counts and running slots of `supervisor`.
+    pub fn with_task_summary(mut self, supervisor: Arc<SupervisorApi>) -> Self {
+        self.summary = Some(supervisor);
+        self
+    }
+
+    /// Advertise allocatable and free capacity of `supervisor` (see [`SupervisorApi::with_capacity`]).
+    pub fn with_capacity(mut self, supervisor: Arc<SupervisorApi>) -> Self {
+        self.capacity = Some(supervisor);
+        self
+    }
+
+    /// Record connects/reconnects and sync outcomes into shared counters.
+    pub fn with_connection_stats(mut self, stats: Arc<ConnectionStats>) -> Self {
+        self.stats = stats;
+        self
+    }
+
+    /// Read the delay between syncs from `delay` instead of `config.delay_ms`,
+    /// so that it can be changed while the task runs.
+    pub fn with_delay(mut self, delay: SyncDelay) -> Self {
+        self.delay = Some(delay);
+        self
+    }
+
+    /// Override the advertised agent version.
+    pub fn with_agent_version(mut self, version: impl Into<String>) -> Self {
+        self.agent_version = version.into();
+        self
+    }
+
+    /// Advertise agent feature flags.
+    pub fn with_features<I, S>(mut self, features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.features = features.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Build the task and its model-level specification.
+    pub fn build(self) -> (TaskRef, CreateSpec) {
+        let (task, spec, _) = build_sync(self);
+        (task, spec)
+    }
+
+    /// Build the task together with a [`Deregistration`] handle to call on shutdown.
+    ///
+    /// The handle shares the sync task's transport, TLS and token settings.
+    pub fn build_with_deregistration(self) -> (TaskRef, CreateSpec, Deregistration) {
+        let (task, spec, ctx) = build_sync(self);
+        (task, spec, Deregistration::new(ctx))
+    }
+}
+
+fn build_sync(builder: SyncBuilder) -> (TaskRef, CreateSpec, Arc<SyncContext>) {
+    let SyncBuilder {
+        config,
+        maintenance,
+        router,
+        supervisor,
+        summary,
+        capacity,
+        stats,
+        delay,
+        agent_version,
+        features,
+    } = builder;
+    let delay = delay.unwrap_or_else(|| SyncDelay::new(config.delay_ms));
+    // Backoff after failed syncs follows the delay the task was built with.
+    let delay_ms = delay.get();
 
     let backoff = BackoffStrategy {
         jitter: JitterStrategy::Equal,
@@ -28,89 +186,395 @@ pub fn sync(config: DiscoverConfig) -> (TaskRef, CreateSpec) {
     };
     let spec = CreateSpec {
         slot: SLOT.to_string(),
-        timeout_ms: delay_ms,
-        restart: RestartStrategy::periodic(delay_ms),
+        // Runs are spaced and bounded by the current delay inside the task (see `run_sync`),
+        // so that the delay can change at runtime.
+        timeout_ms: 0,
+        restart: RestartStrategy::always(),
         backoff,
         admission: AdmissionStrategy::Replace,
         kind: TaskKind::None,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
-    let base_request = build_base_request(&config);
-    let http_client = reqwest::Client::new();
+    let mut base_request = build_base_request(&config);
+    base_request.agent_version = agent_version;
+    base_request.features = features;
+    let tls = config
+        .tls
+        .as_ref()
+        .map(TlsSetup::load)
+        .transpose()
+        .map(|tls| tls.map(Arc::new))
+        .map_err(|e| {
+            warn!("discovery tls setup failed: {}", e);
+            e.to_string()
+        });
+    let http_client = build_http_client(&tls);
+    let token = config.token.clone().map(TokenProvider::new);
+    let mdns = matches!(config.transport, DiscoveryTransport::Mdns).then(|| {
+        Mdns::new().map_err(|e| {
+            warn!("discovery mdns setup failed: {}", e);
+            e.to_string()
+        })
+    });
     let ctx = Arc::new(SyncContext {
         base_request,
         http_client,
         config,
+        maintenance,
+        router,
+        reconciler: supervisor.map(Reconciler::new),
+        summary,
+        capacity,
+        grpc: Mutex::new(GrpcSlot::default()),
+        ws: Mutex::new(WsSlot::default()),
+        stats,
+        delay,
+        last_sync: std::sync::Mutex::new(None),
+        tls,
+        token,
+        mdns,
     });
 
+    let task_ctx = Arc::clone(&ctx);
     let task: TaskRef = TaskFn::arc(SLOT, move |cancel: CancellationToken| {
-        let ctx = Arc::clone(&ctx);
+        let ctx = Arc::clone(&task_ctx);
+        async move { run_sync(&ctx, &cancel).await }
+    });
+    (task, spec, ctx)
+}
+
+/// One execution of the sync task: wait for the delay since the last successful sync,
+/// then sync once, bounded by the delay.
+///
+/// The first sync, and retries after a failure, happen right away.
+async fn run_sync(ctx: &SyncContext, cancel: &CancellationToken) -> Result<(), TaskError> {
+    if cancel.is_cancelled() {
+        return Err(TaskError::Canceled);
+    }
+    let last_sync = *ctx.last_sync.lock().unwrap();
+    if let Some(last_sync) = last_sync {
+        tokio::select! {
+            _ = cancel.cancelled() => return Err(TaskError::Canceled),
+            _ = ctx.delay.wait_since(last_sync) => {}
+        }
+    }
+    debug!("sending sync request to control plane");
+
+    let result = match Duration::from_millis(ctx.delay.get()) {
+        timeout if timeout.is_zero() => invoke_sync(ctx).await,
+        timeout => tokio::time::timeout(timeout, invoke_sync(ctx))
+            .await
+            .map_err(|_| TaskError::Timeout { timeout })?,
+    };
+    ctx.stats.record_sync(result.is_ok(), unix_now());
+    match result {
+        Ok(()) => {
+            debug!("sync completed successfully");
+            *ctx.last_sync.lock().unwrap() = Some(Instant::now());
+            Ok(())
+        }
+        Err(e) => {
+            warn!(
+                consecutive_failures = ctx.stats.consecutive_failures(),
+                "sync failed: {}", e
+            );
+            Err(TaskError::Fail {
+                reason: format!("sync failed: {}", e),
+            })
+        }
+    }
+}
+
+pub(super) struct SyncContext {
+    pub(super) config: DiscoverConfig,
+    pub(super) base_request: SyncRequest,
+    pub(super) http_client: reqwest::Client,
+    maintenance: Option<Arc<MaintenanceMode>>,
+    router: Option<Arc<RunnerRouter>>,
+    reconciler: Option<Reconciler>,
+    summary: Option<Arc<SupervisorApi>>,
+    capacity: Option<Arc<SupervisorApi>>,
+    /// Cached gRPC client, reused across syncs until a call fails.
+    grpc: Mutex<GrpcSlot>,
+    /// Persistent WebSocket connection, reopened after it drops.
+    ws: Mutex<WsSlot>,
+    stats: Arc<ConnectionStats>,
+    /// Delay between syncs.
+    delay: SyncDelay,
+    /// When the last successful sync finished.
+    last_sync: std::sync::Mutex<Option<Instant>>,
+    /// Loaded TLS material, or the load error reported on every sync.
+    tls: Result<Option<Arc<TlsSetup>>, String>,
+    token: Option<TokenProvider>,
+    /// mDNS responder/browser (mDNS transport only), or its setup error.
+    mdns: Option<Result<Mdns, String>>,
+}
+
+/// Control plane endpoint and the transport used to reach it.
+pub(super) struct Target {
+    pub(super) transport: DiscoveryTransport,
+    pub(super) endpoint: String,
+}
+
+#[derive(Default)]
+struct GrpcSlot {
+    client: Option<DiscoverServiceClient<Channel>>,
+    /// Endpoint the cached client is connected to.
+    endpoint: String,
+    /// Whether a channel was ever established (the next connect is a reconnect).
+    connected: bool,
+}
+
+#[derive(Default)]
+struct WsSlot {
+    client: Option<WsClient>,
+    endpoint: String,
+    connected: bool,
+}
+
+impl SyncContext {
+    /// `Bearer <token>` value for the authorization header, if a token is configured.
+    pub(super) fn authorization(&self) -> Result<Option<String>, DiscoverError> {
+        self.token
+            .as_ref()
+            .map(|t| t.token().map(|token| format!("Bearer {token}")))
+            .transpose()
+    }
+
+    /// Fail fast if the TLS material could not be loaded.
+    pub(super) fn check_tls(&self) -> Result<(), DiscoverError> {
+        match &self.tls {
+            Err(e) => Err(DiscoverError::Tls(e.clone())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// mDNS handle, if the mDNS transport is configured.
+    pub(super) fn mdns(&self) -> Result<Option<&Mdns>, DiscoverError> {
+        match &self.mdns {
+            None => Ok(None),
+            Some(Ok(mdns)) => Ok(Some(mdns)),
+            Some(Err(e)) => Err(DiscoverError::Mdns(e.clone())),
+        }
+    }
+
+    /// Where to send the next request; `None` while no control plane has been discovered.
+    pub(super) fn target(&self) -> Result<Option<Target>, DiscoverError> {
+        if let Some(mdns) = self.mdns()? {
+            let tls = matches!(self.tls, Ok(Some(_)));
+            return Ok(mdns.control_plane(tls).map(|cp| Target {
+                transport: cp.transport,
+                endpoint: cp.endpoint,
+            }));
+        }
+        Ok(Some(Target {
+            transport: self.config.transport.clone(),
+            endpoint: self.config.control_plane_endpoint.clone(),
+        }))
+    }
+
+    /// Return the cached gRPC client for `endpoint`, connecting if there is none.
+    pub(super) async fn grpc_client(
+        &self,
+        endpoint: &str,
+    ) -> Result<DiscoverServiceClient<Channel>, DiscoverError> {
+        let mut slot = self.grpc.lock().await;
+        if let Some(client) = &slot.client
+            && slot.endpoint == endpoint
+        {
+            return Ok(client.clone());
+        }
+        let client = self.connect_grpc(endpoint).await?;
+        self.stats.record_connect(slot.connected);
+        if slot.connected {
+            debug!("reconnected to control plane");
+        }
+        slot.connected = true;
+        slot.endpoint = endpoint.to_string();
+        slot.client = Some(client.clone());
+        Ok(client)
+    }
 
-        async move {
-            if cancel.is_cancelled() {
-                return Err(TaskError::Canceled);
+    /// Drop the cached gRPC client so the next call reconnects.
+    pub(super) async fn reset_grpc(&self) {
+        self.grpc.lock().await.client = None;
+    }
+
+    async fn connect_grpc(
+        &self,
+        endpoint: &str,
+    ) -> Result<DiscoverServiceClient<Channel>, DiscoverError> {
+        let endpoint = tonic::transport::Endpoint::from_shared(endpoint.to_string())?;
+        let channel = match &self.tls {
+            Ok(Some(tls)) => {
+                endpoint
+                    .connect_with_connector(tls.grpc_connector())
+                    .await?
             }
-            debug!("sending sync request to control plane");
-
-            match invoke_sync(&ctx).await {
-                Ok(()) => {
-                    debug!("sync completed successfully");
-                    Ok(())
-                }
-                Err(e) => {
-                    warn!("sync failed: {}", e);
-                    Err(TaskError::Fail {
-                        reason: format!("sync failed: {}", e),
-                    })
-                }
+            _ => endpoint.connect().await?,
+        };
+        Ok(DiscoverServiceClient::new(channel))
+    }
+
+    /// Send `message` over the persistent WebSocket connection to `endpoint` and await the reply.
+    pub(super) async fn ws_call(
+        &self,
+        endpoint: &str,
+        message: WsMessage,
+    ) -> Result<WsMessage, DiscoverError> {
+        let mut slot = self.ws.lock().await;
+        let live = slot
+            .client
+            .as_ref()
+            .is_some_and(|c| !c.is_closed() && slot.endpoint == endpoint);
+        if !live {
+            let tls = match &self.tls {
+                Ok(Some(tls)) => Some(Arc::new(tls.http.clone())),
+                _ => None,
+            };
+            let client =
+                WsClient::connect(endpoint, self.authorization()?, tls, KEEPALIVE_INTERVAL).await?;
+            self.stats.record_connect(slot.connected);
+            if slot.connected {
+                debug!("reconnected to control plane");
             }
+            slot.connected = true;
+            slot.endpoint = endpoint.to_string();
+            slot.client = Some(client);
         }
-    });
-    (task, spec)
-}
+        let client = slot.client.as_ref().expect("websocket client just set");
+        let result = client.call(message).await;
+        if result.is_err() {
+            slot.client = None;
+        }
+        result
+    }
 
-struct SyncContext {
-    config: DiscoverConfig,
-    base_request: SyncRequest,
-    http_client: reqwest::Client,
+    /// Wrap a message into a gRPC request carrying the authorization metadata.
+    pub(super) fn grpc_request<T>(&self, message: T) -> Result<tonic::Request<T>, DiscoverError> {
+        let mut request = tonic::Request::new(message);
+        if let Some(auth) = self.authorization()? {
+            let value = auth
+                .parse()
+                .map_err(|_| DiscoverError::Token("token contains invalid characters".into()))?;
+            request.metadata_mut().insert("authorization", value);
+        }
+        Ok(request)
+    }
+
+    /// Start an HTTP POST to `path` on `endpoint` with the authorization header.
+    pub(super) fn http_post(
+        &self,
+        endpoint: &str,
+        path: &str,
+    ) -> Result<reqwest::RequestBuilder, DiscoverError> {
+        let mut builder = self.http_client.post(format!("{}{}", endpoint, path));
+        if let Some(auth) = self.authorization()? {
+            builder = builder.header(reqwest::header::AUTHORIZATION, auth);
+        }
+        Ok(builder)
+    }
 }
 
 async fn invoke_sync(ctx: &SyncContext) -> Result<(), DiscoverError> {
-    match ctx.config.transport {
-        DiscoveryTransport::Grpc => invoke_grpc_sync(ctx).await,
-        DiscoveryTransport::Http => invoke_http_sync(ctx).await,
+    ctx.check_tls()?;
+    let request = stamp_request(ctx);
+    if let Some(mdns) = ctx.mdns()? {
+        mdns.announce(&request)?;
     }
-}
+    let Some(target) = ctx.target()? else {
+        debug!("no control plane discovered yet, announced only");
+        return Ok(());
+    };
+    let response = match target.transport {
+        DiscoveryTransport::Grpc => invoke_grpc_sync(ctx, &target.endpoint, request).await?,
+        DiscoveryTransport::WebSocket => invoke_ws_sync(ctx, &target.endpoint, request).await?,
+        DiscoveryTransport::Http | DiscoveryTransport::Mdns => {
+            invoke_http_sync(ctx, &target.endpoint, request).await?
+        }
+    };
+    let response = validate_response(response)?;
 
-async fn invoke_grpc_sync(ctx: &SyncContext) -> Result<(), DiscoverError> {
-    let mut client =
-        DiscoverServiceClient::connect(ctx.config.control_plane_endpoint.clone()).await?;
-    let request = tonic::Request::new(stamp_request(&ctx.base_request));
-    let response = client.sync(request).await?.into_inner();
+    if response.manage_tasks {
+        match &ctx.reconciler {
+            Some(reconciler) => {
+                reconciler.apply(response.tasks).await;
+            }
+            None => debug!("control plane assigned tasks, but reconciliation is not enabled"),
+        }
+    }
+    Ok(())
+}
 
-    validate_response(response)
+async fn invoke_grpc_sync(
+    ctx: &SyncContext,
+    endpoint: &str,
+    request: SyncRequest,
+) -> Result<SyncResponse, DiscoverError> {
+    let request = ctx.grpc_request(request)?;
+    let mut client = ctx.grpc_client(endpoint).await?;
+    match client.sync(request).await {
+        Ok(response) => Ok(response.into_inner()),
+        Err(status) => {
+            ctx.reset_grpc().await;
+            Err(status.into())
+        }
+    }
 }
 
-async fn invoke_http_sync(ctx: &SyncContext) -> Result<(), DiscoverError> {
-    let request = stamp_request(&ctx.base_request);
+async fn invoke_ws_sync(
+    ctx: &SyncContext,
+    endpoint: &str,
+    request: SyncRequest,
+) -> Result<SyncResponse, DiscoverError> {
+    match ctx
+        .ws_call(endpoint, WsMessage::Sync(Box::new(request)))
+        .await?
+    {
+        WsMessage::SyncResult(response) => Ok(response),
+        other => Err(DiscoverError::InvalidResponse(format!(
+            "unexpected websocket reply: {other:?}"
+        ))),
+    }
+}
 
+async fn invoke_http_sync(
+    ctx: &SyncContext,
+    endpoint: &str,
+    request: SyncRequest,
+) -> Result<SyncResponse, DiscoverError> {
     let response = ctx
-        .http_client
-        .post(format!(
-            "{}/api/v1/discovery/sync",
-            ctx.config.control_plane_endpoint
-        ))
+        .http_post(endpoint, "/api/v1/discovery/sync")?
         .json(&request)
         .send()
         .await?;
 
     let body = response.text().await?;
-    let sync_response: SyncResponse = serde_json::from_str(&body).map_err(|e| {
+    serde_json::from_str(&body).map_err(|e| {
         DiscoverError::InvalidResponse(format!("failed to parse response: {}, body: {}", e, body))
-    })?;
+    })
+}
 
-    validate_response(sync_response)
+fn build_http_client(tls: &Result<Option<Arc<TlsSetup>>, String>) -> reqwest::Client {
+    let Ok(Some(tls)) = tls else {
+        return reqwest::Client::new();
+    };
+    reqwest::Client::builder()
+        .use_preconfigured_tls(tls.http.clone())
+        .build()
+        .unwrap_or_else(|e| {
+            warn!(
+                "failed to build tls http client, falling back to defaults: {}",
+                e
+            );
+            reqwest::Client::new()
+        })
 }
 
 fn build_base_request(cfg: &DiscoverConfig) -> SyncRequest {
@@ -124,25 +588,206 @@ fn build_base_request(cfg: &DiscoverConfig) -> SyncRequest {
         metadata: cfg.metadata.clone(),
         ts: 0,
         uptime_seconds: 0,
+        maintenance: false,
+        runners: Vec::new(),
+        agent_version: String::new(),
+        features: Vec::new(),
+        labels: cfg
+            .labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        taints: cfg.taints.iter().cloned().map(Taint::from).collect(),
+        tasks: None,
+        load: None,
+        build: Some(BuildInfo::from(build_info())),
+        capacity: None,
     }
 }
 
-fn stamp_request(base: &SyncRequest) -> SyncRequest {
-    let now = SystemTime::now()
+pub(super) fn unix_now() -> i64 {
+    SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .expect("system time before unix epoch")
-        .as_secs() as i64;
+        .as_secs() as i64
+}
 
+fn stamp_request(ctx: &SyncContext) -> SyncRequest {
     SyncRequest {
-        ts: now,
+        ts: unix_now(),
         uptime_seconds: uptime_seconds() as i64,
-        ..base.clone()
+        maintenance: ctx.maintenance.as_ref().is_some_and(|m| m.is_enabled()),
+        runners: ctx
+            .router
+            .as_ref()
+            .map(|r| r.runners().into_iter().map(RunnerInfo::from).collect())
+            .unwrap_or_default(),
+        tasks: ctx.summary.as_ref().map(|api| task_summary(api)),
+        load: Some(LoadInfo::from(load_snapshot())),
+        capacity: ctx.capacity.as_ref().and_then(|api| capacity_info(api)),
+        ..ctx.base_request.clone()
+    }
+}
+
+fn task_summary(api: &SupervisorApi) -> TaskSummary {
+    let mut summary = TaskSummary::default();
+    for (status, count) in api.status_counts() {
+        if count > 0 {
+            summary.total += count as u32;
+            summary
+                .by_status
+                .insert(status.as_str().to_string(), count as u32);
+        }
+    }
+    summary.running_slots = api
+        .list_tasks_by_status(TaskStatus::Running)
+        .into_iter()
+        .map(|task| task.slot)
+        .collect();
+    summary.running_slots.sort();
+    summary.running_slots.dedup();
+    summary
+}
+
+fn capacity_info(api: &SupervisorApi) -> Option<CapacityInfo> {
+    Some(CapacityInfo {
+        allocatable: Some(api.allocatable_capacity()?.into()),
+        free: api.free_capacity().map(Into::into),
+    })
+}
+
+impl From<ResourceCapacity> for ResourceAmounts {
+    fn from(capacity: ResourceCapacity) -> Self {
+        ResourceAmounts {
+            cpu_millis: capacity.cpu_millis,
+            memory_bytes: capacity.memory_bytes,
+            gpus: capacity.gpus,
+        }
+    }
+}
+
+impl From<LoadSnapshot> for LoadInfo {
+    fn from(load: LoadSnapshot) -> Self {
+        let [load1, load5, load15] = load.load_avg.unwrap_or_default();
+        LoadInfo {
+            load1,
+            load5,
+            load15,
+            rss_bytes: load.rss_bytes.unwrap_or_default(),
+            cpu_seconds: load.cpu_seconds.unwrap_or_default(),
+            cpu_count: load.cpu_count as u32,
+        }
     }
 }
 
-fn validate_response(response: SyncResponse) -> Result<(), DiscoverError> {
+impl From<solti_core::BuildInfo> for BuildInfo {
+    fn from(info: solti_core::BuildInfo) -> Self {
+        BuildInfo {
+            version: info.version.to_string(),
+            git_sha: info.git_sha.unwrap_or_default().to_string(),
+            build_timestamp: info.build_timestamp as i64,
+        }
+    }
+}
+
+impl From<solti_model::RunnerInfo> for RunnerInfo {
+    fn from(info: solti_model::RunnerInfo) -> Self {
+        RunnerInfo {
+            name: info.name,
+            kinds: info.kinds,
+            labels: info
+                .labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+            healthy: info.healthy,
+        }
+    }
+}
+
+impl From<solti_model::Taint> for Taint {
+    fn from(taint: solti_model::Taint) -> Self {
+        Taint {
+            key: taint.key,
+            value: taint.value,
+            effect: taint.effect.as_str().to_string(),
+        }
+    }
+}
+
+impl TryFrom<Taint> for solti_model::Taint {
+    type Error = solti_model::ModelError;
+
+    fn try_from(taint: Taint) -> Result<Self, Self::Error> {
+        Ok(solti_model::Taint::new(
+            taint.key,
+            taint.value,
+            taint.effect.parse()?,
+        ))
+    }
+}
+
+fn validate_response(response: SyncResponse) -> Result<SyncResponse, DiscoverError> {
     if !response.success {
         return Err(DiscoverError::Rejected);
     }
-    Ok(())
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn runner_info_converts_to_proto() {
+        let mut labels = RunnerLabels::new();
+        labels.insert("runner-tag", "gpu");
+        let info = RunnerInfo::from(solti_model::RunnerInfo {
+            name: "default-runner".into(),
+            kinds: vec!["subprocess".into()],
+            labels,
+            healthy: false,
+        });
+
+        assert_eq!(info.name, "default-runner");
+        assert_eq!(info.kinds, vec!["subprocess".to_string()]);
+        assert_eq!(
+            info.labels.get("runner-tag").map(String::as_str),
+            Some("gpu")
+        );
+        assert!(!info.healthy);
+    }
+
+    #[test]
+    fn load_info_zeroes_unavailable_fields() {
+        let info = LoadInfo::from(LoadSnapshot {
+            load_avg: Some([0.5, 0.25, 0.125]),
+            rss_bytes: None,
+            cpu_seconds: None,
+            cpu_count: 4,
+        });
+        assert_eq!((info.load1, info.load5, info.load15), (0.5, 0.25, 0.125));
+        assert_eq!((info.rss_bytes, info.cpu_seconds), (0, 0.0));
+        assert_eq!(info.cpu_count, 4);
+    }
+
+    #[test]
+    fn resource_amounts_keep_unbounded_dimensions_unset() {
+        let amounts = ResourceAmounts::from(
+            ResourceCapacity::unbounded()
+                .with_cpu_millis(4_000)
+                .with_gpus(0),
+        );
+        assert_eq!(amounts.cpu_millis, Some(4_000));
+        assert_eq!(amounts.memory_bytes, None);
+        assert_eq!(amounts.gpus, Some(0));
+    }
+
+    #[test]
+    fn taint_roundtrips_through_proto() {
+        let taint = solti_model::Taint::new("gpu", "true", solti_model::TaintEffect::NoSchedule);
+        let proto = Taint::from(taint.clone());
+        assert_eq!(proto.effect, "NoSchedule");
+        assert_eq!(solti_model::Taint::try_from(proto).unwrap(), taint);
+    }
 }
@@ -0,0 +1,221 @@
+//! TLS setup for control plane connections.
+//!
+//! Builds rustls client configs from [`TlsConfig`] and provides a connector that lets
+//! tonic channels speak TLS without enabling tonic's own `tls` feature.
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use http::Uri;
+use hyper_util::rt::TokioIo;
+use rustls::{
+    ClientConfig, RootCertStore,
+    pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject},
+};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tower_service::Service;
+
+use crate::{config::TlsConfig, errors::DiscoverError};
+
+const ALPN_H2: &[u8] = b"h2";
+const ALPN_HTTP11: &[u8] = b"http/1.1";
+
+/// Client TLS material shared by all syncs.
+pub(crate) struct TlsSetup {
+    /// Config for the gRPC transport (ALPN `h2`).
+    pub grpc: Arc<ClientConfig>,
+    /// Config for the HTTP transport (ALPN `http/1.1`).
+    pub http: ClientConfig,
+    /// Optional SNI / verification name override.
+    pub server_name: Option<ServerName<'static>>,
+}
+
+impl TlsSetup {
+    /// Load certificates and keys referenced by `cfg`.
+    pub fn load(cfg: &TlsConfig) -> Result<Self, DiscoverError> {
+        let base = client_config(cfg)?;
+
+        let mut grpc = base.clone();
+        grpc.alpn_protocols = vec![ALPN_H2.to_vec()];
+        let mut http = base;
+        http.alpn_protocols = vec![ALPN_HTTP11.to_vec()];
+
+        let server_name = cfg
+            .server_name
+            .as_deref()
+            .map(|name| {
+                ServerName::try_from(name.to_string())
+                    .map_err(|e| DiscoverError::Tls(format!("invalid server name '{name}': {e}")))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            grpc: Arc::new(grpc),
+            http,
+            server_name,
+        })
+    }
+
+    /// Connector for tonic channels using the gRPC config.
+    pub fn grpc_connector(&self) -> TlsConnector {
+        TlsConnector {
+            config: Arc::clone(&self.grpc),
+            server_name: self.server_name.clone(),
+        }
+    }
+}
+
+fn client_config(cfg: &TlsConfig) -> Result<ClientConfig, DiscoverError> {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|e| DiscoverError::Tls(e.to_string()))?
+        .with_root_certificates(root_store(cfg)?);
+
+    match (&cfg.client_cert, &cfg.client_key) {
+        (Some(cert), Some(key)) => {
+            let chain = read_certs(cert)?;
+            let bytes = read(key)?;
+            let key = PrivateKeyDer::from_pem_slice(&bytes).map_err(|e| {
+                DiscoverError::Tls(format!("invalid private key {}: {e}", key.display()))
+            })?;
+            builder
+                .with_client_auth_cert(chain, key)
+                .map_err(|e| DiscoverError::Tls(format!("invalid client certificate: {e}")))
+        }
+        (None, None) => Ok(builder.with_no_client_auth()),
+        _ => Err(DiscoverError::Tls(
+            "client_cert and client_key must be set together".into(),
+        )),
+    }
+}
+
+fn root_store(cfg: &TlsConfig) -> Result<RootCertStore, DiscoverError> {
+    let mut roots = RootCertStore::empty();
+    match &cfg.ca_cert {
+        Some(path) => {
+            for cert in read_certs(path)? {
+                roots.add(cert).map_err(|e| {
+                    DiscoverError::Tls(format!("invalid CA certificate in {}: {e}", path.display()))
+                })?;
+            }
+        }
+        None => {
+            let native = rustls_native_certs::load_native_certs();
+            let (added, _) = roots.add_parsable_certificates(native.certs);
+            if added == 0 {
+                return Err(DiscoverError::Tls(
+                    "no CA bundle configured and no platform root certificates found".into(),
+                ));
+            }
+        }
+    }
+    Ok(roots)
+}
+
+fn read(path: &std::path::Path) -> Result<Vec<u8>, DiscoverError> {
+    std::fs::read(path).map_err(|e| DiscoverError::Tls(format!("{}: {e}", path.display())))
+}
+
+fn read_certs(path: &std::path::Path) -> Result<Vec<CertificateDer<'static>>, DiscoverError> {
+    let bytes = read(path)?;
+    let certs = CertificateDer::pem_slice_iter(&bytes)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| DiscoverError::Tls(format!("invalid PEM in {}: {e}", path.display())))?;
+    if certs.is_empty() {
+        return Err(DiscoverError::Tls(format!(
+            "no certificates found in {}",
+            path.display()
+        )));
+    }
+    Ok(certs)
+}
+
+/// Tonic connector establishing TCP + TLS connections.
+#[derive(Clone)]
+pub(crate) struct TlsConnector {
+    config: Arc<ClientConfig>,
+    server_name: Option<ServerName<'static>>,
+}
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+impl Service<Uri> for TlsConnector {
+    type Response = TokioIo<TlsStream<TcpStream>>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let config = Arc::clone(&self.config);
+        let server_name = self.server_name.clone();
+
+        Box::pin(async move {
+            let host = uri.host().ok_or("endpoint uri has no host")?.to_string();
+            let port = uri.port_u16().unwrap_or(443);
+            let name = match server_name {
+                Some(name) => name,
+                None => ServerName::try_from(host.clone())?,
+            };
+
+            let tcp = TcpStream::connect((host.as_str(), port)).await?;
+            let tls = tokio_rustls::TlsConnector::from(config)
+                .connect(name, tcp)
+                .await?;
+            Ok(TokioIo::new(tls))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_cert_requires_key() {
+        let cfg = TlsConfig {
+            client_cert: Some("/nonexistent/cert.pem".into()),
+            ..Default::default()
+        };
+        match TlsSetup::load(&cfg) {
+            Err(DiscoverError::Tls(msg)) => assert!(msg.contains("set together")),
+            other => panic!("expected tls error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn missing_ca_file_is_reported() {
+        let cfg = TlsConfig {
+            ca_cert: Some("/nonexistent/ca.pem".into()),
+            ..Default::default()
+        };
+        match TlsSetup::load(&cfg) {
+            Err(DiscoverError::Tls(msg)) => assert!(msg.contains("/nonexistent/ca.pem")),
+            other => panic!("expected tls error, got {:?}", other.err()),
+        }
+    }
+
+    #[test]
+    fn empty_ca_bundle_is_rejected() {
+        let dir = std::env::temp_dir().join(format!("solti-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let ca = dir.join("empty.pem");
+        std::fs::write(&ca, b"").unwrap();
+
+        let cfg = TlsConfig {
+            ca_cert: Some(ca),
+            ..Default::default()
+        };
+        assert!(
+            matches!(TlsSetup::load(&cfg), Err(DiscoverError::Tls(msg)) if msg.contains("no certificates"))
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,117 @@
+//! Registration token used to authenticate the agent to the control plane.
+//!
+//! The token is sent as `authorization: Bearer <token>` — an HTTP header for the HTTP
+//! transport and request metadata for gRPC. File-based tokens are re-read whenever the
+//! file's modification time changes, so credentials can be rotated without a restart.
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::SystemTime,
+};
+
+use tracing::info;
+
+use crate::errors::DiscoverError;
+
+/// Where the registration token comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Fixed token.
+    Static(String),
+    /// Token read from a file (surrounding whitespace is trimmed).
+    File(PathBuf),
+}
+
+/// Resolves the current token, caching file contents until the file changes.
+pub(crate) struct TokenProvider {
+    source: TokenSource,
+    cached: Mutex<Option<(Option<SystemTime>, String)>>,
+}
+
+impl TokenProvider {
+    pub fn new(source: TokenSource) -> Self {
+        Self {
+            source,
+            cached: Mutex::new(None),
+        }
+    }
+
+    /// Current token value.
+    pub fn token(&self) -> Result<String, DiscoverError> {
+        match &self.source {
+            TokenSource::Static(token) => Ok(token.clone()),
+            TokenSource::File(path) => self.file_token(path),
+        }
+    }
+
+    fn file_token(&self, path: &Path) -> Result<String, DiscoverError> {
+        let token_err =
+            |e: std::io::Error| DiscoverError::Token(format!("{}: {e}", path.display()));
+        let modified = std::fs::metadata(path).map_err(token_err)?.modified().ok();
+
+        let mut cached = self.cached.lock().unwrap();
+        if let Some((seen, token)) = cached.as_ref()
+            && modified.is_some()
+            && *seen == modified
+        {
+            return Ok(token.clone());
+        }
+
+        let token = std::fs::read_to_string(path)
+            .map_err(token_err)?
+            .trim()
+            .to_string();
+        if token.is_empty() {
+            return Err(DiscoverError::Token(format!(
+                "{}: token file is empty",
+                path.display()
+            )));
+        }
+        if cached.is_some() {
+            info!(path = %path.display(), "registration token reloaded");
+        }
+        *cached = Some((modified, token.clone()));
+        Ok(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_token() {
+        let p = TokenProvider::new(TokenSource::Static("secret".into()));
+        assert_eq!(p.token().unwrap(), "secret");
+    }
+
+    #[test]
+    fn file_token_is_trimmed_and_reloaded() {
+        let path = std::env::temp_dir().join(format!("solti-token-{}", std::process::id()));
+        std::fs::write(&path, "first\n").unwrap();
+
+        let p = TokenProvider::new(TokenSource::File(path.clone()));
+        assert_eq!(p.token().unwrap(), "first");
+
+        // Force a distinct mtime so the change is observed on coarse filesystems.
+        std::fs::write(&path, "second").unwrap();
+        let later = SystemTime::now() + std::time::Duration::from_secs(5);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        assert_eq!(p.token().unwrap(), "second");
+
+        std::fs::write(&path, "  ").unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(later + std::time::Duration::from_secs(5))
+            .unwrap();
+        assert!(matches!(p.token(), Err(DiscoverError::Token(_))));
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,311 @@
+//! Persistent WebSocket transport for networks that only allow outbound HTTP(S).
+//!
+//! The agent keeps one connection to `<endpoint>/api/v1/discovery/ws` (`http` → `ws`,
+//! `https` → `wss`) and exchanges JSON envelopes over it:
+//!
+//! ```json
+//! {"type": "sync", "payload": { ...SyncRequest }}
+//! {"type": "syncResult", "payload": { ...SyncResponse }}
+//! ```
+//!
+//! Requests are answered in order. The agent pings the control plane every
+//! [`KEEPALIVE_INTERVAL`] and drops the connection if the previous ping got no pong;
+//! the next sync then reconnects. Unsolicited messages are reserved for command push
+//! and are currently ignored.
+//!
+//! Requires the `websocket` feature; without it the transport fails on every sync.
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::errors::DiscoverError;
+use crate::{DeregisterRequest, DeregisterResponse, SyncRequest, SyncResponse};
+
+/// Interval between keepalive pings.
+pub const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Path of the WebSocket endpoint on the control plane.
+const WS_PATH: &str = "/api/v1/discovery/ws";
+
+/// Envelope exchanged over the WebSocket connection.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "payload", rename_all = "camelCase")]
+pub(crate) enum WsMessage {
+    Sync(Box<SyncRequest>),
+    SyncResult(SyncResponse),
+    Deregister(DeregisterRequest),
+    DeregisterResult(DeregisterResponse),
+}
+
+impl WsMessage {
+    /// Whether this message answers a request sent by the agent.
+    #[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+    fn is_reply(&self) -> bool {
+        matches!(
+            self,
+            WsMessage::SyncResult(_) | WsMessage::DeregisterResult(_)
+        )
+    }
+}
+
+/// WebSocket URL for a control plane endpoint.
+#[cfg_attr(not(feature = "websocket"), allow(dead_code))]
+pub(crate) fn ws_url(endpoint: &str) -> Result<String, DiscoverError> {
+    let endpoint = endpoint.trim_end_matches('/');
+    let rest = |prefix: &str| endpoint.strip_prefix(prefix);
+    let url = if let Some(host) = rest("http://") {
+        format!("ws://{host}")
+    } else if let Some(host) = rest("https://") {
+        format!("wss://{host}")
+    } else if endpoint.starts_with("ws://") || endpoint.starts_with("wss://") {
+        endpoint.to_string()
+    } else {
+        return Err(DiscoverError::WebSocket(format!(
+            "unsupported endpoint scheme: {endpoint}"
+        )));
+    };
+    Ok(format!("{url}{WS_PATH}"))
+}
+
+#[cfg(feature = "websocket")]
+mod imp {
+    use std::collections::VecDeque;
+    use std::sync::Arc;
+
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::sync::{mpsc, oneshot};
+    use tokio_tungstenite::tungstenite::{Message, client::IntoClientRequest};
+    use tokio_tungstenite::{Connector, connect_async_tls_with_config};
+    use tracing::{debug, warn};
+
+    use super::*;
+
+    type Reply = oneshot::Sender<Result<WsMessage, DiscoverError>>;
+
+    fn ws_err(e: impl std::fmt::Display) -> DiscoverError {
+        DiscoverError::WebSocket(e.to_string())
+    }
+
+    /// Handle to a live connection; dropping it closes the connection.
+    pub(crate) struct WsClient {
+        calls: mpsc::Sender<(WsMessage, Reply)>,
+    }
+
+    impl WsClient {
+        /// Connect and spawn the connection driver.
+        pub async fn connect(
+            endpoint: &str,
+            authorization: Option<String>,
+            tls: Option<Arc<rustls::ClientConfig>>,
+            keepalive: Duration,
+        ) -> Result<Self, DiscoverError> {
+            let mut request = ws_url(endpoint)?.into_client_request().map_err(ws_err)?;
+            if let Some(auth) = authorization {
+                let value = auth.parse().map_err(|_| {
+                    DiscoverError::Token("token contains invalid characters".into())
+                })?;
+                request
+                    .headers_mut()
+                    .insert(http::header::AUTHORIZATION, value);
+            }
+            let connector = tls.map(Connector::Rustls);
+            let (stream, _) = connect_async_tls_with_config(request, None, true, connector)
+                .await
+                .map_err(ws_err)?;
+
+            let (calls, rx) = mpsc::channel(8);
+            tokio::spawn(drive(stream, rx, keepalive));
+            Ok(Self { calls })
+        }
+
+        /// Whether the connection driver has stopped.
+        pub fn is_closed(&self) -> bool {
+            self.calls.is_closed()
+        }
+
+        /// Send a request and wait for its reply.
+        pub async fn call(&self, message: WsMessage) -> Result<WsMessage, DiscoverError> {
+            let (reply, rx) = oneshot::channel();
+            self.calls
+                .send((message, reply))
+                .await
+                .map_err(|_| ws_err("connection closed"))?;
+            rx.await.map_err(|_| ws_err("connection closed"))?
+        }
+    }
+
+    async fn drive<S>(stream: S, mut calls: mpsc::Receiver<(WsMessage, Reply)>, keepalive: Duration)
+    where
+        S: futures_util::Stream<Item = Result<Message, tokio_tungstenite::tungstenite::Error>>
+            + futures_util::Sink<Message, Error = tokio_tungstenite::tungstenite::Error>
+            + Unpin,
+    {
+        let (mut sink, mut source) = stream.split();
+        let mut pending: VecDeque<Reply> = VecDeque::new();
+        let mut ticker =
+            tokio::time::interval_at(tokio::time::Instant::now() + keepalive, keepalive);
+        let mut awaiting_pong = false;
+
+        loop {
+            tokio::select! {
+                call = calls.recv() => {
+                    let Some((message, reply)) = call else { break };
+                    let text = match serde_json::to_string(&message) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            let _ = reply.send(Err(ws_err(e)));
+                            continue;
+                        }
+                    };
+                    if let Err(e) = sink.send(Message::Text(text.into())).await {
+                        let _ = reply.send(Err(ws_err(e)));
+                        break;
+                    }
+                    pending.push_back(reply);
+                }
+                frame = source.next() => match frame {
+                    Some(Ok(Message::Text(text))) => {
+                        match serde_json::from_str::<WsMessage>(text.as_str()) {
+                            Ok(message) if message.is_reply() => {
+                                if let Some(reply) = pending.pop_front() {
+                                    let _ = reply.send(Ok(message));
+                                } else {
+                                    debug!("ignoring reply without a pending request");
+                                }
+                            }
+                            Ok(message) => debug!(?message, "ignoring unsolicited websocket message"),
+                            Err(e) => warn!("invalid websocket message: {}", e),
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) => awaiting_pong = false,
+                    Some(Ok(Message::Close(_))) | None => {
+                        debug!("websocket closed by control plane");
+                        break;
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        warn!("websocket error: {}", e);
+                        break;
+                    }
+                },
+                _ = ticker.tick() => {
+                    if awaiting_pong {
+                        warn!("websocket keepalive timed out");
+                        break;
+                    }
+                    if sink.send(Message::Ping(Default::default())).await.is_err() {
+                        break;
+                    }
+                    awaiting_pong = true;
+                }
+            }
+        }
+
+        for reply in pending {
+            let _ = reply.send(Err(ws_err("connection closed")));
+        }
+        let _ = sink.close().await;
+    }
+}
+
+#[cfg(not(feature = "websocket"))]
+mod imp {
+    use std::sync::Arc;
+
+    use super::*;
+
+    enum Never {}
+
+    /// Placeholder used when the crate is built without the `websocket` feature.
+    pub(crate) struct WsClient {
+        never: Never,
+    }
+
+    impl WsClient {
+        pub async fn connect(
+            _endpoint: &str,
+            _authorization: Option<String>,
+            _tls: Option<Arc<rustls::ClientConfig>>,
+            _keepalive: Duration,
+        ) -> Result<Self, DiscoverError> {
+            Err(DiscoverError::WebSocket(
+                "solti-discover was built without the `websocket` feature".into(),
+            ))
+        }
+
+        pub fn is_closed(&self) -> bool {
+            match self.never {}
+        }
+
+        pub async fn call(&self, _message: WsMessage) -> Result<WsMessage, DiscoverError> {
+            match self.never {}
+        }
+    }
+}
+
+pub(crate) use imp::WsClient;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ws_url_maps_scheme_and_appends_path() {
+        assert_eq!(
+            ws_url("http://cp:8082/").unwrap(),
+            "ws://cp:8082/api/v1/discovery/ws"
+        );
+        assert_eq!(
+            ws_url("https://cp").unwrap(),
+            "wss://cp/api/v1/discovery/ws"
+        );
+        assert_eq!(
+            ws_url("wss://cp:443").unwrap(),
+            "wss://cp:443/api/v1/discovery/ws"
+        );
+        assert!(ws_url("cp:8082").is_err());
+    }
+
+    #[test]
+    fn envelope_is_tagged() {
+        let json = serde_json::to_value(WsMessage::SyncResult(SyncResponse {
+            success: true,
+            ..Default::default()
+        }))
+        .unwrap();
+        assert_eq!(json["type"], "syncResult");
+        assert_eq!(json["payload"]["success"], true);
+    }
+
+    #[cfg(feature = "websocket")]
+    #[tokio::test]
+    async fn sync_round_trip() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let (tcp, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(tcp).await.unwrap();
+            while let Some(Ok(Message::Text(text))) = ws.next().await {
+                let reply = match serde_json::from_str(text.as_str()).unwrap() {
+                    WsMessage::Sync(_) => WsMessage::SyncResult(SyncResponse {
+                        success: true,
+                        ..Default::default()
+                    }),
+                    _ => continue,
+                };
+                let text = serde_json::to_string(&reply).unwrap();
+                ws.send(Message::Text(text.into())).await.unwrap();
+            }
+        });
+
+        let client = WsClient::connect(&format!("http://{addr}"), None, None, KEEPALIVE_INTERVAL)
+            .await
+            .unwrap();
+        let reply = client.call(WsMessage::Sync(Box::default())).await.unwrap();
+        assert!(matches!(reply, WsMessage::SyncResult(r) if r.success));
+        assert!(!client.is_closed());
+    }
+}
@@ -20,6 +20,8 @@ pub struct SubprocessBackendConfig {
     security: Option<SecurityConfig>,
     /// Subprocess output logging configuration.
     logger: LogConfig,
+    /// Inject W3C trace context (`TRACEPARENT`) into subprocesses.
+    trace_context: bool,
 }
 
 impl SubprocessBackendConfig {
@@ -52,6 +54,22 @@ impl SubprocessBackendConfig {
         self
     }
 
+    /// Enable W3C trace context propagation.
+    ///
+    /// The `task_attempt` tracing span of every attempt additionally carries
+    /// `trace_id`/`span_id`, and the child receives the matching `TRACEPARENT`
+    /// so instrumented scripts continue the trace. A `TRACEPARENT` set in the
+    /// task environment takes precedence.
+    pub fn with_trace_context(mut self, enabled: bool) -> Self {
+        self.trace_context = enabled;
+        self
+    }
+
+    /// Check if trace context propagation is enabled.
+    pub(crate) fn trace_context(&self) -> bool {
+        self.trace_context
+    }
+
     // Get log configuration.
     pub(crate) fn log_config(&self) -> &LogConfig {
         &self.logger
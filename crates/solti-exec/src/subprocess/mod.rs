@@ -11,6 +11,9 @@ pub use logger::LogConfig;
 mod runner;
 pub use runner::SubprocessRunner;
 
+mod trace;
+pub use trace::{TRACEPARENT_ENV, TraceContext};
+
 use std::sync::Arc;
 
 use solti_core::RunnerRouter;
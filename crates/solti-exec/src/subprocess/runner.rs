@@ -1,5 +1,9 @@
 use std::{
     process::Stdio,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
     time::{Duration as StdDuration, Instant, SystemTime, UNIX_EPOCH},
 };
 
@@ -9,14 +13,17 @@ use tokio::{
     process::Command,
 };
 use tokio_util::sync::CancellationToken;
-use tracing::{debug, info, trace, warn};
+use tracing::{Instrument, debug, field, info, info_span, trace, warn};
 
-use solti_core::{BuildContext, Runner, RunnerError};
-use solti_model::{CreateSpec, TaskKind};
+use solti_core::{BuildContext, OutputPublisher, Runner, RunnerError};
+use solti_model::{CreateSpec, OutputStream, TaskId, TaskKind};
 
 use crate::metrics::{RUNNER_TYPE_SUBPROCESS, task_error_to_outcome};
 use crate::subprocess::{
-    backend::SubprocessBackendConfig, logger::LogConfig, task::SubprocessTaskConfig,
+    backend::SubprocessBackendConfig,
+    logger::LogConfig,
+    task::SubprocessTaskConfig,
+    trace::{TRACEPARENT_ENV, TraceContext},
 };
 
 /// Runner that executes `TaskKind::Subprocess` as OS subprocesses.
@@ -54,14 +61,17 @@ impl SubprocessRunner {
                 env,
                 cwd,
                 fail_on_non_zero,
-            } => SubprocessTaskConfig {
-                run_id: self.build_run_id(&spec.slot),
-                command: command.clone(),
-                args: args.clone(),
-                env: ctx.env().merged(env),
-                cwd: cwd.clone(),
-                fail_on_non_zero: *fail_on_non_zero,
-            },
+            } => {
+                ctx.command_policy().check(command)?;
+                SubprocessTaskConfig {
+                    run_id: self.build_run_id(&spec.slot, ctx),
+                    command: command.clone(),
+                    args: args.clone(),
+                    env: ctx.env().merged(env),
+                    cwd: cwd.clone(),
+                    fail_on_non_zero: *fail_on_non_zero,
+                }
+            }
             other => {
                 return Err(RunnerError::UnsupportedKind {
                     runner: self.name,
@@ -84,10 +94,17 @@ impl Runner for SubprocessRunner {
         matches!(spec.kind, TaskKind::Subprocess { .. })
     }
 
+    fn kinds(&self) -> &'static [&'static str] {
+        &["subprocess"]
+    }
+
     fn build_task(&self, spec: &CreateSpec, ctx: &BuildContext) -> Result<TaskRef, RunnerError> {
         let task_cfg = self.build_task_config(spec, ctx)?;
         let runner_cfg = self.config.clone();
         let metrics = ctx.metrics().clone();
+        let output_bus = ctx.output().clone();
+        let slot = spec.slot.clone();
+        let submitted = Instant::now();
 
         trace!(
             slot = %spec.slot,
@@ -115,20 +132,52 @@ impl Runner for SubprocessRunner {
             None
         };
 
+        let trace_root = runner_cfg
+            .as_ref()
+            .filter(|c| c.trace_context())
+            .map(|_| TraceContext::inherit_or_root());
+        let attempts = Arc::new(AtomicU32::new(0));
+
         let task: TaskRef = TaskFn::arc(
             task_cfg.run_id.clone(),
             move |cancel: CancellationToken| {
                 let task_cfg = task_cfg.clone();
+                let slot = slot.clone();
                 let runner_cfg = runner_cfg.clone();
                 let cgroup_name = cgroup_name.clone();
                 let metrics = metrics.clone();
 
+                let attempt = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                let output = Arc::new(output_bus.publisher(
+                    TaskId::from(task_cfg.run_id.as_str()),
+                    slot.clone(),
+                    attempt,
+                ));
+                let trace_ctx = trace_root.as_ref().map(TraceContext::child);
+                let span = info_span!(
+                    "task_attempt",
+                    task_id = %task_cfg.run_id,
+                    slot = %slot,
+                    attempt,
+                    trace_id = field::Empty,
+                    span_id = field::Empty,
+                );
+                if let Some(ctx) = &trace_ctx {
+                    span.record("trace_id", field::display(ctx.trace_id()));
+                    span.record("span_id", field::display(ctx.span_id()));
+                }
+
                 async move {
+                    if attempt == 1 {
+                        let wait_ms = submitted.elapsed().as_millis() as u64;
+                        metrics.record_task_queue_wait(RUNNER_TYPE_SUBPROCESS, wait_ms);
+                    }
                     metrics.record_task_started(RUNNER_TYPE_SUBPROCESS);
                     let start = Instant::now();
 
                     trace!(
                         task = %task_cfg.run_id,
+                        slot = %slot,
                         command = %task_cfg.command,
                         args = ?task_cfg.args,
                         cwd = ?task_cfg.cwd,
@@ -140,6 +189,9 @@ impl Runner for SubprocessRunner {
                     if let Some(cwd) = &task_cfg.cwd {
                         cmd.current_dir(cwd);
                     }
+                    if let Some(ctx) = &trace_ctx {
+                        cmd.env(TRACEPARENT_ENV, ctx.traceparent());
+                    }
                     for kv in task_cfg.env.iter() {
                         cmd.env(kv.key(), kv.value());
                     }
@@ -177,17 +229,21 @@ impl Runner for SubprocessRunner {
                         reason: "failed to capture stdout".into(),
                     })?;
                     let run_id_stdout = task_cfg.run_id.clone();
+                    let slot_stdout = slot.clone();
+                    let output_stdout = Arc::clone(&output);
                     let stdout_task = tokio::spawn(async move {
-                        log_stream(stdout, &run_id_stdout, "stdout", &log_cfg).await;
-                    });
+                        log_stream(stdout, &run_id_stdout, &slot_stdout, OutputStream::Stdout, &log_cfg, &output_stdout).await;
+                    }.in_current_span());
 
                     let stderr = child.stderr.take().ok_or_else(|| TaskError::Fatal {
                         reason: "failed to capture stderr".into(),
                     })?;
                     let run_id_stderr = task_cfg.run_id.clone();
+                    let slot_stderr = slot.clone();
+                    let output_stderr = Arc::clone(&output);
                     let stderr_task = tokio::spawn(async move {
-                        log_stream(stderr, &run_id_stderr, "stderr", &log_cfg).await;
-                    });
+                        log_stream(stderr, &run_id_stderr, &slot_stderr, OutputStream::Stderr, &log_cfg, &output_stderr).await;
+                    }.in_current_span());
 
                     let status_fut = child.wait();
                     let result = tokio::select! {
@@ -202,14 +258,14 @@ impl Runner for SubprocessRunner {
                                 };
                                 Err(TaskError::Fail { reason })
                             } else {
-                                debug!(task = %task_cfg.run_id, "subprocess exited successfully");
+                                debug!(task = %task_cfg.run_id, slot = %slot, "subprocess exited successfully");
                                 Ok(())
                             }
                         }
                         _ = cancel.cancelled() => {
-                            debug!(task = %task_cfg.run_id, "cancellation requested; killing subprocess");
+                            debug!(task = %task_cfg.run_id, slot = %slot, "cancellation requested; killing subprocess");
                             if let Err(e) = child.kill().await {
-                                debug!(task = %task_cfg.run_id, "failed to kill subprocess: {e}");
+                                debug!(task = %task_cfg.run_id, slot = %slot, "failed to kill subprocess: {e}");
                             }
                             Err(TaskError::Canceled)
                         }
@@ -223,11 +279,16 @@ impl Runner for SubprocessRunner {
                     metrics.record_task_completed(RUNNER_TYPE_SUBPROCESS, outcome, duration_ms);
 
                     let _ = tokio::join!(stdout_task, stderr_task);
+                    let dropped = output.dropped();
+                    if dropped > 0 {
+                        warn!(task = %task_cfg.run_id, slot = %slot, dropped, "output lines dropped by rate limit");
+                    }
                     if let Some(cgroup_name) = cgroup_name {
                         let _ = crate::utils::cleanup_cgroup(&cgroup_name);
                     }
                     result
                 }
+                .instrument(span)
             },
         );
         Ok(task)
@@ -249,9 +310,15 @@ fn truncate_line(line: &str, max_chars: usize) -> String {
     format!("{truncated}... (truncated {skipped} chars)")
 }
 
-/// Log subprocess output stream with truncation.
-async fn log_stream<R>(reader: R, run_id: &str, stream: &str, config: &LogConfig)
-where
+/// Log subprocess output stream with truncation and forward it to output subscribers.
+async fn log_stream<R>(
+    reader: R,
+    run_id: &str,
+    slot: &str,
+    stream: OutputStream,
+    config: &LogConfig,
+    output: &OutputPublisher,
+) where
     R: tokio::io::AsyncRead + Unpin,
 {
     let mut lines = BufReader::new(reader).lines();
@@ -263,7 +330,8 @@ where
             Err(e) => {
                 warn!(
                     task = %run_id,
-                    stream = %stream,
+                    slot = %slot,
+                    stream = stream.as_str(),
                     error = %e,
                     line_num = line_count,
                     "error while reading subprocess stream"
@@ -279,12 +347,14 @@ where
         };
 
         line_count += 1;
+        output.publish(stream, &line);
 
         match stream {
-            "stdout" => {
+            OutputStream::Stdout => {
                 if config.stdout_info {
                     info!(
                         task = %run_id,
+                        slot = %slot,
                         stream = "stdout",
                         line_num = line_count,
                         "{}",
@@ -293,6 +363,7 @@ where
                 } else {
                     debug!(
                         task = %run_id,
+                        slot = %slot,
                         stream = "stdout",
                         line_num = line_count,
                         "{}",
@@ -300,10 +371,11 @@ where
                     );
                 }
             }
-            "stderr" => {
+            OutputStream::Stderr => {
                 if config.stderr_warn {
                     warn!(
                         task = %run_id,
+                        slot = %slot,
                         stream = "stderr",
                         line_num = line_count,
                         "{}",
@@ -312,6 +384,7 @@ where
                 } else {
                     debug!(
                         task = %run_id,
+                        slot = %slot,
                         stream = "stderr",
                         line_num = line_count,
                         "{}",
@@ -319,13 +392,13 @@ where
                     );
                 }
             }
-            _ => unreachable!(),
         }
     }
 
     debug!(
         task = %run_id,
-        stream = %stream,
+        slot = %slot,
+        stream = stream.as_str(),
         total_lines = line_count,
         "stream closed"
     );
@@ -0,0 +1,189 @@
+use std::fmt;
+
+/// Environment variable carrying the W3C trace context into subprocesses.
+pub const TRACEPARENT_ENV: &str = "TRACEPARENT";
+
+/// W3C trace context (`traceparent`) of a task attempt.
+///
+/// All attempts of a task share the trace id; every attempt gets its own span id,
+/// so instrumented children continue the trace as a child of that attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    sampled: bool,
+}
+
+impl TraceContext {
+    /// Start a new trace.
+    pub fn root() -> Self {
+        let mut trace_id = [0u8; 16];
+        fill_random(&mut trace_id);
+        Self::in_trace(trace_id, true)
+    }
+
+    /// Continue the trace of the current process if it was started with a
+    /// valid `TRACEPARENT`, otherwise start a new one.
+    pub fn inherit_or_root() -> Self {
+        std::env::var(TRACEPARENT_ENV)
+            .ok()
+            .and_then(|v| Self::parse(&v))
+            .map(|parent| parent.child())
+            .unwrap_or_else(Self::root)
+    }
+
+    /// New span in the same trace.
+    pub fn child(&self) -> Self {
+        Self::in_trace(self.trace_id, self.sampled)
+    }
+
+    /// Parse a `traceparent` header value (version `00`).
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('-');
+        let (version, trace, span, flags) =
+            (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        if parts.next().is_some() || version != "00" {
+            return None;
+        }
+
+        let trace_id: [u8; 16] = decode_hex(trace)?;
+        let span_id: [u8; 8] = decode_hex(span)?;
+        let [flags]: [u8; 1] = decode_hex(flags)?;
+        if trace_id == [0; 16] || span_id == [0; 8] {
+            return None;
+        }
+        Some(Self {
+            trace_id,
+            span_id,
+            sampled: flags & 0x01 != 0,
+        })
+    }
+
+    /// Trace id as 32 lowercase hex characters.
+    pub fn trace_id(&self) -> String {
+        encode_hex(&self.trace_id)
+    }
+
+    /// Span id as 16 lowercase hex characters.
+    pub fn span_id(&self) -> String {
+        encode_hex(&self.span_id)
+    }
+
+    /// `traceparent` value for this context.
+    pub fn traceparent(&self) -> String {
+        self.to_string()
+    }
+
+    fn in_trace(trace_id: [u8; 16], sampled: bool) -> Self {
+        let mut span_id = [0u8; 8];
+        fill_random(&mut span_id);
+        Self {
+            trace_id,
+            span_id,
+            sampled,
+        }
+    }
+}
+
+impl fmt::Display for TraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "00-{}-{}-{:02x}",
+            self.trace_id(),
+            self.span_id(),
+            u8::from(self.sampled)
+        )
+    }
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn decode_hex<const N: usize>(s: &str) -> Option<[u8; N]> {
+    if s.len() != N * 2 || !s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+        return None;
+    }
+    let mut out = [0u8; N];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+/// Fill `buf` with random bytes, never leaving it all-zero (invalid per W3C).
+fn fill_random(buf: &mut [u8]) {
+    // SAFETY: the pointer and length describe a valid, writable buffer.
+    let n = unsafe { libc::getrandom(buf.as_mut_ptr().cast(), buf.len(), 0) };
+    if n < 0 || n as usize != buf.len() {
+        fallback_random(buf);
+    }
+    if buf.iter().all(|b| *b == 0) {
+        buf[buf.len() - 1] = 1;
+    }
+}
+
+/// Time and counter based bytes for systems where `getrandom` is unavailable.
+fn fallback_random(buf: &mut [u8]) {
+    use std::hash::{BuildHasher, Hasher};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let state = std::collections::hash_map::RandomState::new();
+    for chunk in buf.chunks_mut(8) {
+        let mut hasher = state.build_hasher();
+        hasher.write_u64(COUNTER.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u128(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or_default(),
+        );
+        let bytes = hasher.finish().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn traceparent_roundtrip() {
+        let ctx = TraceContext::root();
+        let value = ctx.traceparent();
+        assert_eq!(value.len(), 55);
+        assert!(value.starts_with("00-"));
+        assert!(value.ends_with("-01"));
+        assert_eq!(TraceContext::parse(&value), Some(ctx));
+    }
+
+    #[test]
+    fn child_keeps_trace_and_changes_span() {
+        let root = TraceContext::root();
+        let child = root.child();
+        assert_eq!(root.trace_id(), child.trace_id());
+        assert_ne!(root.span_id(), child.span_id());
+    }
+
+    #[test]
+    fn parse_rejects_invalid_values() {
+        let valid = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let ctx = TraceContext::parse(valid).expect("valid traceparent");
+        assert_eq!(ctx.trace_id(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(ctx.span_id(), "00f067aa0ba902b7");
+
+        for bad in [
+            "",
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert_eq!(TraceContext::parse(bad), None, "{bad}");
+        }
+    }
+}
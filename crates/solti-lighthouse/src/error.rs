@@ -0,0 +1,41 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum LighthouseError {
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    #[error("agent not found: {0}")]
+    AgentNotFound(String),
+}
+
+impl From<LighthouseError> for tonic::Status {
+    fn from(err: LighthouseError) -> Self {
+        match err {
+            LighthouseError::Unauthorized(msg) => tonic::Status::unauthenticated(msg),
+            LighthouseError::InvalidRequest(msg) => tonic::Status::invalid_argument(msg),
+            LighthouseError::AgentNotFound(msg) => tonic::Status::not_found(msg),
+        }
+    }
+}
+
+impl axum::response::IntoResponse for LighthouseError {
+    fn into_response(self) -> axum::response::Response {
+        use axum::http::StatusCode;
+
+        let (status, message) = match self {
+            LighthouseError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg),
+            LighthouseError::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg),
+            LighthouseError::AgentNotFound(msg) => (StatusCode::NOT_FOUND, msg),
+        };
+
+        let body = serde_json::json!({
+            "error": message
+        });
+
+        (status, axum::Json(body)).into_response()
+    }
+}
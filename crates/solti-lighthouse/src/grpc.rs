@@ -0,0 +1,51 @@
+use std::sync::Arc;
+
+use solti_discover::discover_service_server::{DiscoverService, DiscoverServiceServer};
+use solti_discover::{DeregisterRequest, DeregisterResponse, SyncRequest, SyncResponse};
+use tonic::{Request, Response, Status};
+
+use crate::error::LighthouseError;
+use crate::lighthouse::Lighthouse;
+
+/// gRPC `DiscoverService` implementation backed by a [`Lighthouse`].
+pub struct LighthouseGrpc {
+    lighthouse: Arc<Lighthouse>,
+}
+
+impl LighthouseGrpc {
+    /// Create a new gRPC service.
+    pub fn new(lighthouse: Arc<Lighthouse>) -> Self {
+        Self { lighthouse }
+    }
+
+    /// Wrap into a tonic server ready to be added to a `Router`.
+    pub fn into_server(self) -> DiscoverServiceServer<Self> {
+        DiscoverServiceServer::new(self)
+    }
+
+    fn authorize<T>(&self, request: &Request<T>) -> Result<(), LighthouseError> {
+        let auth = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok());
+        self.lighthouse.authorize(auth)
+    }
+}
+
+#[tonic::async_trait]
+impl DiscoverService for LighthouseGrpc {
+    async fn sync(&self, request: Request<SyncRequest>) -> Result<Response<SyncResponse>, Status> {
+        self.authorize(&request)?;
+        let response = self.lighthouse.sync(request.into_inner())?;
+        Ok(Response::new(response))
+    }
+
+    async fn deregister(
+        &self,
+        request: Request<DeregisterRequest>,
+    ) -> Result<Response<DeregisterResponse>, Status> {
+        self.authorize(&request)?;
+        let response = self.lighthouse.deregister(request.into_inner())?;
+        Ok(Response::new(response))
+    }
+}
@@ -0,0 +1,29 @@
+use solti_discover::DesiredTask;
+
+use crate::registry::{AgentRecord, LeaveReason};
+
+/// Extension points for building a control plane on top of [`crate::Lighthouse`].
+///
+/// All methods have no-op defaults. They are called synchronously from request handlers,
+/// so long-running work should be handed off to a background task.
+pub trait LighthouseHooks: Send + Sync + 'static {
+    /// An agent synced for the first time (or after it left).
+    fn on_join(&self, _agent: &AgentRecord) {}
+
+    /// An agent deregistered or expired.
+    fn on_leave(&self, _agent: &AgentRecord, _reason: LeaveReason) {}
+
+    /// Tasks to assign to `agent`, returned in its sync response.
+    ///
+    /// `None` leaves the agent's tasks unmanaged; `Some` is the complete desired set
+    /// (an empty list cancels everything previously assigned).
+    fn assignments(&self, _agent: &AgentRecord) -> Option<Vec<DesiredTask>> {
+        None
+    }
+}
+
+/// Hooks that do nothing.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoHooks;
+
+impl LighthouseHooks for NoHooks {}
@@ -0,0 +1,132 @@
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Path, State},
+    http::HeaderMap,
+    routing::{get, post},
+};
+use solti_discover::{DeregisterRequest, DeregisterResponse, SyncRequest, SyncResponse};
+
+use crate::error::LighthouseError;
+use crate::lighthouse::Lighthouse;
+use crate::registry::AgentRecord;
+
+/// HTTP API builder backed by a [`Lighthouse`].
+pub struct LighthouseHttp {
+    lighthouse: Arc<Lighthouse>,
+}
+
+impl LighthouseHttp {
+    /// Create a new HTTP API.
+    pub fn new(lighthouse: Arc<Lighthouse>) -> Self {
+        Self { lighthouse }
+    }
+
+    /// Build axum router with mounted endpoints.
+    ///
+    /// Routes:
+    /// - POST /api/v1/discovery/sync - Agent sync
+    /// - POST /api/v1/discovery/deregister - Agent deregistration
+    /// - GET /api/v1/agents - List registered agents
+    /// - GET /api/v1/agents/:id - Get a registered agent
+    ///
+    /// Every route requires the registration token when one is configured.
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/api/v1/discovery/sync", post(sync))
+            .route("/api/v1/discovery/deregister", post(deregister))
+            .route("/api/v1/agents", get(list_agents))
+            .route("/api/v1/agents/{id}", get(get_agent))
+            .with_state(self.lighthouse)
+    }
+}
+
+fn authorize(lighthouse: &Lighthouse, headers: &HeaderMap) -> Result<(), LighthouseError> {
+    let auth = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok());
+    lighthouse.authorize(auth)
+}
+
+async fn sync(
+    State(lighthouse): State<Arc<Lighthouse>>,
+    headers: HeaderMap,
+    Json(request): Json<SyncRequest>,
+) -> Result<Json<SyncResponse>, LighthouseError> {
+    authorize(&lighthouse, &headers)?;
+    lighthouse.sync(request).map(Json)
+}
+
+async fn deregister(
+    State(lighthouse): State<Arc<Lighthouse>>,
+    headers: HeaderMap,
+    Json(request): Json<DeregisterRequest>,
+) -> Result<Json<DeregisterResponse>, LighthouseError> {
+    authorize(&lighthouse, &headers)?;
+    lighthouse.deregister(request).map(Json)
+}
+
+async fn list_agents(
+    State(lighthouse): State<Arc<Lighthouse>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<AgentRecord>>, LighthouseError> {
+    authorize(&lighthouse, &headers)?;
+    Ok(Json(lighthouse.registry().list()))
+}
+
+async fn get_agent(
+    State(lighthouse): State<Arc<Lighthouse>>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> Result<Json<AgentRecord>, LighthouseError> {
+    authorize(&lighthouse, &headers)?;
+    lighthouse
+        .registry()
+        .get(&id)
+        .map(Json)
+        .ok_or(LighthouseError::AgentNotFound(id))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn sync_registers_agent() {
+        let lighthouse = Arc::new(Lighthouse::new());
+        let app = LighthouseHttp::new(lighthouse.clone()).router();
+
+        let body = serde_json::to_vec(&SyncRequest {
+            id: "agent-1".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/api/v1/discovery/sync")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(lighthouse.registry().get("agent-1").is_some());
+
+        let response = app
+            .oneshot(
+                Request::get("/api/v1/agents/missing")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,20 @@
+mod error;
+pub use error::LighthouseError;
+
+mod registry;
+pub use registry::{AgentRecord, AgentRegistry, LeaveReason};
+
+mod hooks;
+pub use hooks::{LighthouseHooks, NoHooks};
+
+mod lighthouse;
+pub use lighthouse::{DEFAULT_AGENT_TTL, Lighthouse};
+
+mod grpc;
+pub use grpc::LighthouseGrpc;
+
+mod http;
+pub use http::LighthouseHttp;
+
+mod tasks;
+pub use tasks::{EXPIRY_SLOT, expiry};
@@ -0,0 +1,210 @@
+use std::{sync::Arc, time::Duration};
+
+use solti_discover::{DeregisterRequest, DeregisterResponse, SyncRequest, SyncResponse};
+use tracing::{debug, info};
+
+use crate::error::LighthouseError;
+use crate::hooks::{LighthouseHooks, NoHooks};
+use crate::registry::{AgentRecord, AgentRegistry, LeaveReason, unix_now};
+
+/// Agents that have not synced for this long are dropped.
+pub const DEFAULT_AGENT_TTL: Duration = Duration::from_secs(60);
+
+/// Server side of the discovery protocol.
+///
+/// Transport-agnostic core shared by [`crate::LighthouseGrpc`] and [`crate::LighthouseHttp`]:
+/// keeps the agent registry, checks registration tokens and calls [`LighthouseHooks`].
+pub struct Lighthouse {
+    registry: AgentRegistry,
+    hooks: Arc<dyn LighthouseHooks>,
+    token: Option<String>,
+    ttl: Duration,
+}
+
+impl Default for Lighthouse {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Lighthouse {
+    /// Create a lighthouse without authentication, hooks or custom TTL.
+    pub fn new() -> Self {
+        Self {
+            registry: AgentRegistry::new(),
+            hooks: Arc::new(NoHooks),
+            token: None,
+            ttl: DEFAULT_AGENT_TTL,
+        }
+    }
+
+    /// Require agents to present `Bearer <token>`.
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Install control plane hooks.
+    pub fn with_hooks(mut self, hooks: Arc<dyn LighthouseHooks>) -> Self {
+        self.hooks = hooks;
+        self
+    }
+
+    /// Drop agents that have not synced within `ttl`.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Agent TTL.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Registered agents.
+    pub fn registry(&self) -> &AgentRegistry {
+        &self.registry
+    }
+
+    /// Check the `authorization` header value against the configured token.
+    pub fn authorize(&self, authorization: Option<&str>) -> Result<(), LighthouseError> {
+        let Some(expected) = &self.token else {
+            return Ok(());
+        };
+        match authorization.and_then(|v| v.strip_prefix("Bearer ")) {
+            Some(token) if token == expected => Ok(()),
+            Some(_) => Err(LighthouseError::Unauthorized("invalid token".into())),
+            None => Err(LighthouseError::Unauthorized("missing bearer token".into())),
+        }
+    }
+
+    /// Handle an agent sync.
+    pub fn sync(&self, request: SyncRequest) -> Result<SyncResponse, LighthouseError> {
+        if request.id.is_empty() {
+            return Err(LighthouseError::InvalidRequest("missing agent id".into()));
+        }
+        let (record, new) = self.registry.upsert(request, unix_now());
+        if new {
+            info!(agent = %record.id(), name = %record.info.name, "agent joined");
+            self.hooks.on_join(&record);
+        } else {
+            debug!(agent = %record.id(), "agent synced");
+        }
+
+        let assignments = self.hooks.assignments(&record);
+        Ok(SyncResponse {
+            success: true,
+            manage_tasks: assignments.is_some(),
+            tasks: assignments.unwrap_or_default(),
+        })
+    }
+
+    /// Handle an agent deregistration.
+    pub fn deregister(
+        &self,
+        request: DeregisterRequest,
+    ) -> Result<DeregisterResponse, LighthouseError> {
+        let record = self
+            .registry
+            .remove(&request.id)
+            .ok_or_else(|| LighthouseError::AgentNotFound(request.id.clone()))?;
+        info!(agent = %record.id(), reason = %request.reason, "agent deregistered");
+        self.hooks.on_leave(&record, LeaveReason::Deregistered);
+        Ok(DeregisterResponse { success: true })
+    }
+
+    /// Drop agents whose last sync is older than the TTL; returns them.
+    pub fn expire(&self) -> Vec<AgentRecord> {
+        let expired = self.registry.expire(unix_now(), self.ttl.as_secs() as i64);
+        for record in &expired {
+            info!(agent = %record.id(), last_seen = record.last_seen, "agent expired");
+            self.hooks.on_leave(record, LeaveReason::Expired);
+        }
+        expired
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use solti_discover::DesiredTask;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl LighthouseHooks for Recorder {
+        fn on_join(&self, agent: &AgentRecord) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("join {}", agent.id()));
+        }
+
+        fn on_leave(&self, agent: &AgentRecord, reason: LeaveReason) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("leave {} {:?}", agent.id(), reason));
+        }
+
+        fn assignments(&self, _agent: &AgentRecord) -> Option<Vec<DesiredTask>> {
+            Some(vec![DesiredTask {
+                key: "job".into(),
+                spec_json: "{}".into(),
+            }])
+        }
+    }
+
+    fn sync_request(id: &str) -> SyncRequest {
+        SyncRequest {
+            id: id.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn sync_and_deregister_call_hooks() {
+        let hooks = Arc::new(Recorder::default());
+        let lighthouse = Lighthouse::new().with_hooks(hooks.clone());
+
+        let response = lighthouse.sync(sync_request("a")).unwrap();
+        assert!(response.success && response.manage_tasks);
+        assert_eq!(response.tasks.len(), 1);
+        lighthouse.sync(sync_request("a")).unwrap();
+
+        lighthouse
+            .deregister(DeregisterRequest {
+                id: "a".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert!(matches!(
+            lighthouse.deregister(DeregisterRequest {
+                id: "a".into(),
+                ..Default::default()
+            }),
+            Err(LighthouseError::AgentNotFound(_))
+        ));
+        assert_eq!(
+            *hooks.events.lock().unwrap(),
+            vec!["join a".to_string(), "leave a Deregistered".to_string()]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_id_and_bad_token() {
+        let lighthouse = Lighthouse::new().with_token("secret");
+        assert!(lighthouse.authorize(Some("Bearer secret")).is_ok());
+        assert!(lighthouse.authorize(Some("Bearer nope")).is_err());
+        assert!(lighthouse.authorize(None).is_err());
+        assert!(matches!(
+            lighthouse.sync(SyncRequest::default()),
+            Err(LighthouseError::InvalidRequest(_))
+        ));
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::Serialize;
+use solti_discover::SyncRequest;
+use solti_model::{Placement, RunnerLabels, Taint};
+
+/// Last known state of a registered agent.
+#[derive(Debug, Clone, Serialize)]
+pub struct AgentRecord {
+    /// Latest sync payload sent by the agent.
+    pub info: SyncRequest,
+    /// Unix time (seconds, lighthouse clock) of the first sync.
+    pub first_seen: i64,
+    /// Unix time (seconds, lighthouse clock) of the latest sync.
+    pub last_seen: i64,
+}
+
+impl AgentRecord {
+    /// Agent id.
+    pub fn id(&self) -> &str {
+        &self.info.id
+    }
+
+    /// Scheduling labels advertised by the agent.
+    pub fn labels(&self) -> RunnerLabels {
+        let mut labels = RunnerLabels::new();
+        for (k, v) in &self.info.labels {
+            labels.insert(k.clone(), v.clone());
+        }
+        labels
+    }
+
+    /// Taints advertised by the agent; entries with an unknown effect are skipped.
+    pub fn taints(&self) -> Vec<Taint> {
+        self.info
+            .taints
+            .iter()
+            .cloned()
+            .filter_map(|t| Taint::try_from(t).ok())
+            .collect()
+    }
+
+    /// Returns `true` if work with `placement` may run on this agent.
+    pub fn admits(&self, placement: &Placement) -> bool {
+        placement.matches(&self.labels(), &self.taints())
+    }
+}
+
+/// Why an agent left the registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaveReason {
+    /// The agent sent a deregistration request.
+    Deregistered,
+    /// The agent did not sync within the TTL.
+    Expired,
+}
+
+/// In-memory agent registry keyed by agent id.
+#[derive(Debug, Default)]
+pub struct AgentRegistry {
+    agents: RwLock<HashMap<String, AgentRecord>>,
+}
+
+impl AgentRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert or refresh an agent; returns the record and whether it is new.
+    pub fn upsert(&self, info: SyncRequest, now: i64) -> (AgentRecord, bool) {
+        let mut agents = self.agents.write().expect("registry lock poisoned");
+        match agents.get_mut(&info.id) {
+            Some(record) => {
+                record.info = info;
+                record.last_seen = now;
+                (record.clone(), false)
+            }
+            None => {
+                let record = AgentRecord {
+                    info,
+                    first_seen: now,
+                    last_seen: now,
+                };
+                agents.insert(record.info.id.clone(), record.clone());
+                (record, true)
+            }
+        }
+    }
+
+    /// Remove an agent.
+    pub fn remove(&self, id: &str) -> Option<AgentRecord> {
+        self.agents
+            .write()
+            .expect("registry lock poisoned")
+            .remove(id)
+    }
+
+    /// Look up an agent.
+    pub fn get(&self, id: &str) -> Option<AgentRecord> {
+        self.agents
+            .read()
+            .expect("registry lock poisoned")
+            .get(id)
+            .cloned()
+    }
+
+    /// All agents, ordered by id.
+    pub fn list(&self) -> Vec<AgentRecord> {
+        let mut agents: Vec<_> = self
+            .agents
+            .read()
+            .expect("registry lock poisoned")
+            .values()
+            .cloned()
+            .collect();
+        agents.sort_by(|a, b| a.info.id.cmp(&b.info.id));
+        agents
+    }
+
+    /// Number of registered agents.
+    pub fn len(&self) -> usize {
+        self.agents.read().expect("registry lock poisoned").len()
+    }
+
+    /// Returns `true` if no agent is registered.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove agents whose last sync is older than `ttl_secs`; returns them.
+    pub fn expire(&self, now: i64, ttl_secs: i64) -> Vec<AgentRecord> {
+        let mut agents = self.agents.write().expect("registry lock poisoned");
+        let stale: Vec<String> = agents
+            .values()
+            .filter(|a| now - a.last_seen > ttl_secs)
+            .map(|a| a.info.id.clone())
+            .collect();
+        stale.iter().filter_map(|id| agents.remove(id)).collect()
+    }
+}
+
+/// Current unix time in seconds.
+pub(crate) fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system time before unix epoch")
+        .as_secs() as i64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn agent(id: &str) -> SyncRequest {
+        SyncRequest {
+            id: id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn admits_checks_advertised_labels_and_taints() {
+        let mut info = agent("a");
+        info.labels.insert("zone".into(), "z1".into());
+        info.taints.push(solti_discover::Taint {
+            key: "gpu".into(),
+            value: String::new(),
+            effect: "NoSchedule".into(),
+        });
+        let record = AgentRecord {
+            info,
+            first_seen: 0,
+            last_seen: 0,
+        };
+
+        let mut placement = Placement::default();
+        placement.selector.insert("zone", "z1");
+        assert!(!record.admits(&placement));
+        placement.tolerations.push(solti_model::Toleration {
+            key: "gpu".into(),
+            ..Default::default()
+        });
+        assert!(record.admits(&placement));
+    }
+
+    #[test]
+    fn upsert_refreshes_and_expire_removes_stale() {
+        let registry = AgentRegistry::new();
+        assert!(registry.upsert(agent("a"), 100).1);
+        assert!(registry.upsert(agent("b"), 100).1);
+        let (record, new) = registry.upsert(agent("a"), 150);
+        assert!(!new);
+        assert_eq!((record.first_seen, record.last_seen), (100, 150));
+
+        let expired = registry.expire(170, 30);
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id(), "b");
+        assert_eq!(registry.list().len(), 1);
+        assert!(registry.remove("a").is_some());
+        assert!(registry.is_empty());
+    }
+}
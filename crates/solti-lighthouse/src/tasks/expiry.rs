@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use solti_model::{
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, RunnerLabels,
+    TaskKind,
+};
+use taskvisor::{TaskError, TaskFn, TaskRef};
+use tokio_util::sync::CancellationToken;
+
+use crate::lighthouse::Lighthouse;
+
+/// Logical slot name used for the agent expiry task.
+pub const EXPIRY_SLOT: &str = "solti-lighthouse-expiry";
+
+/// Build the periodic agent expiry task and its model-level specification.
+///
+/// The task runs every quarter of the lighthouse TTL (at least once a second)
+/// and drops agents that have not synced within the TTL.
+pub fn expiry(lighthouse: Arc<Lighthouse>) -> (TaskRef, CreateSpec) {
+    let every_ms = (lighthouse.ttl().as_millis() as u64 / 4).max(1_000);
+
+    let task: TaskRef = TaskFn::arc(EXPIRY_SLOT, move |ctx: CancellationToken| {
+        let lighthouse = Arc::clone(&lighthouse);
+        async move {
+            if ctx.is_cancelled() {
+                return Err(TaskError::Canceled);
+            }
+            lighthouse.expire();
+            Ok(())
+        }
+    });
+
+    let backoff = BackoffStrategy {
+        jitter: JitterStrategy::None,
+        first_ms: every_ms,
+        max_ms: every_ms,
+        factor: 1.0,
+    };
+    let spec = CreateSpec {
+        slot: EXPIRY_SLOT.to_string(),
+        timeout_ms: every_ms,
+        restart: RestartStrategy::periodic(every_ms),
+        backoff,
+        admission: AdmissionStrategy::Replace,
+        kind: TaskKind::None,
+        labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
+    };
+    (task, spec)
+}
@@ -0,0 +1,2 @@
+mod expiry;
+pub use expiry::{EXPIRY_SLOT, expiry};
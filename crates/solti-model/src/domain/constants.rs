@@ -10,3 +10,41 @@
 ///
 /// This constant provides a single source of truth for the label key used in runner selection logic.
 pub const LABEL_RUNNER_TAG: &str = "runner-tag";
+
+/// Label key used to put a task into a group.
+///
+/// Tasks sharing the same `labels["group"]` value can be inspected, canceled and awaited together
+/// (e.g. fan-out batch jobs submitted at once).
+pub const LABEL_GROUP: &str = "group";
+
+/// Agent label key holding the region the agent runs in.
+pub const AGENT_LABEL_REGION: &str = "region";
+
+/// Agent label key holding the availability zone the agent runs in.
+pub const AGENT_LABEL_ZONE: &str = "zone";
+
+/// Agent label key holding the role of the agent (e.g. `worker`, `edge`).
+pub const AGENT_LABEL_ROLE: &str = "role";
+
+/// Label key holding a detached signature of the spec (base64 ed25519).
+///
+/// The signature covers the spec without the signature labels; see `solti_core::SpecVerifier`.
+pub const LABEL_SIGNATURE: &str = "signature";
+
+/// Label key naming the public key that produced [`LABEL_SIGNATURE`].
+pub const LABEL_SIGNATURE_KEY: &str = "signature-key";
+
+/// Label key set by the agent to the content hash of a spec when deduplication is enabled.
+///
+/// See `solti_core::spec_hash`; submitted values are overwritten.
+pub const LABEL_SPEC_HASH: &str = "spec-hash";
+
+/// Label key set by the agent to the id of the key whose signature was verified.
+///
+/// Never trusted from submitters: verification overwrites or removes it.
+pub const LABEL_SIGNER: &str = "signer";
+
+/// Label key set by the agent on a follow-up task to the id of the task that triggered it.
+///
+/// See [`crate::FollowUp`]; submitted values are overwritten.
+pub const LABEL_FOLLOW_UP_OF: &str = "follow-up-of";
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{TaskInfo, TaskStatus};
+
+/// Aggregated status of a task group.
+///
+/// Built from the current state of all group members.
+/// Failed, timed out and exhausted members are counted together as `failed`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GroupInfo {
+    /// Group identifier.
+    pub group: String,
+    /// Number of tracked members.
+    pub total: usize,
+    /// Members waiting to start.
+    pub pending: usize,
+    /// Members currently executing.
+    pub running: usize,
+    /// Members completed successfully or skipped by their execution window.
+    pub succeeded: usize,
+    /// Members failed, timed out or exhausted.
+    pub failed: usize,
+    /// Members canceled.
+    pub canceled: usize,
+    /// Aggregate status of the group.
+    ///
+    /// - `Running`   — at least one member is running;
+    /// - `Pending`   — nothing is running, but some members have not started yet;
+    /// - `Failed`    — all members finished and at least one of them failed;
+    /// - `Canceled`  — all members finished, none failed, at least one canceled;
+    /// - `Succeeded` — all members succeeded.
+    pub status: TaskStatus,
+}
+
+impl GroupInfo {
+    /// Aggregate group members into a summary.
+    pub fn from_tasks<'a>(
+        group: impl Into<String>,
+        tasks: impl IntoIterator<Item = &'a TaskInfo>,
+    ) -> Self {
+        let mut info = Self {
+            group: group.into(),
+            total: 0,
+            pending: 0,
+            running: 0,
+            succeeded: 0,
+            failed: 0,
+            canceled: 0,
+            status: TaskStatus::Pending,
+        };
+
+        for task in tasks {
+            info.total += 1;
+            match task.status {
+                TaskStatus::Pending => info.pending += 1,
+                TaskStatus::Running => info.running += 1,
+                TaskStatus::Succeeded | TaskStatus::Skipped => info.succeeded += 1,
+                TaskStatus::Failed | TaskStatus::Timeout | TaskStatus::Exhausted => {
+                    info.failed += 1
+                }
+                TaskStatus::Canceled => info.canceled += 1,
+            }
+        }
+
+        info.status = if info.running > 0 {
+            TaskStatus::Running
+        } else if info.pending > 0 {
+            TaskStatus::Pending
+        } else if info.failed > 0 {
+            TaskStatus::Failed
+        } else if info.canceled > 0 {
+            TaskStatus::Canceled
+        } else {
+            TaskStatus::Succeeded
+        };
+        info
+    }
+
+    /// Returns `true` if every member reached a terminal state.
+    pub fn is_complete(&self) -> bool {
+        self.pending == 0 && self.running == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::TaskId;
+
+    fn task(id: &str, status: TaskStatus) -> TaskInfo {
+        let mut info = TaskInfo::pending(TaskId::from(id), format!("slot-{id}"));
+        info.status = status;
+        info.attempt = 1;
+        info.group = Some("batch".to_string());
+        info
+    }
+
+    #[test]
+    fn running_member_makes_group_running() {
+        let tasks = [
+            task("a", TaskStatus::Succeeded),
+            task("b", TaskStatus::Running),
+            task("c", TaskStatus::Pending),
+        ];
+        let info = GroupInfo::from_tasks("batch", &tasks);
+
+        assert_eq!(info.total, 3);
+        assert_eq!(info.status, TaskStatus::Running);
+        assert!(!info.is_complete());
+    }
+
+    #[test]
+    fn finished_group_with_failure_is_failed() {
+        let tasks = [
+            task("a", TaskStatus::Succeeded),
+            task("b", TaskStatus::Timeout),
+            task("c", TaskStatus::Canceled),
+        ];
+        let info = GroupInfo::from_tasks("batch", &tasks);
+
+        assert_eq!(info.failed, 1);
+        assert_eq!(info.canceled, 1);
+        assert_eq!(info.status, TaskStatus::Failed);
+        assert!(info.is_complete());
+    }
+
+    #[test]
+    fn all_succeeded_group_is_succeeded() {
+        let tasks = [
+            task("a", TaskStatus::Succeeded),
+            task("b", TaskStatus::Succeeded),
+        ];
+        let info = GroupInfo::from_tasks("batch", &tasks);
+
+        assert_eq!(info.status, TaskStatus::Succeeded);
+        assert!(info.is_complete());
+    }
+}
@@ -10,8 +10,17 @@ pub use flag::Flag;
 mod runner_labels;
 pub use runner_labels::RunnerLabels;
 
+mod runner_info;
+pub use runner_info::RunnerInfo;
+
+mod placement;
+pub use placement::{Placement, Taint, TaintEffect, Toleration};
+
 mod constants;
-pub use constants::LABEL_RUNNER_TAG;
+pub use constants::{
+    AGENT_LABEL_REGION, AGENT_LABEL_ROLE, AGENT_LABEL_ZONE, LABEL_FOLLOW_UP_OF, LABEL_GROUP,
+    LABEL_RUNNER_TAG, LABEL_SIGNATURE, LABEL_SIGNATURE_KEY, LABEL_SIGNER, LABEL_SPEC_HASH,
+};
 
 mod task_id;
 pub use task_id::TaskId;
@@ -19,12 +28,36 @@ pub use task_id::TaskId;
 mod task_info;
 pub use task_info::TaskInfo;
 
+mod group_info;
+pub use group_info::GroupInfo;
+
+mod quota;
+pub use quota::{QuotaScope, TaskQuota};
+
+mod resources;
+pub use resources::{ResourceCapacity, ResourceRequests};
+
 mod task_status;
 pub use task_status::TaskStatus;
 
 mod task_query;
 pub use task_query::{TaskPage, TaskQuery};
 
+mod task_event;
+pub use task_event::{EventQuery, TaskEvent, TaskEventKind};
+
+mod task_output;
+pub use task_output::{OutputStream, TaskOutput};
+
+mod window;
+pub use window::{ExecutionWindow, TimeOfDay, Weekday};
+
+mod reload_report;
+pub use reload_report::ReloadReport;
+
+mod subscriber_health;
+pub use subscriber_health::{SubscriberHealth, SubscriberState};
+
 /// Logical identifier for a controller slot.
 ///
 /// A slot groups tasks that must not run concurrently.
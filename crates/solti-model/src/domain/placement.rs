@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::RunnerLabels;
+use crate::error::{ModelError, ModelResult};
+
+/// What a taint does to work that does not tolerate it.
+///
+/// - `NoSchedule`: untolerating work must not be placed on the agent.
+/// - `PreferNoSchedule`: schedulers should avoid the agent but may still use it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TaintEffect {
+    /// Hard constraint.
+    NoSchedule,
+    /// Soft constraint.
+    PreferNoSchedule,
+}
+
+impl TaintEffect {
+    /// Canonical representation (`"NoSchedule"`, `"PreferNoSchedule"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaintEffect::NoSchedule => "NoSchedule",
+            TaintEffect::PreferNoSchedule => "PreferNoSchedule",
+        }
+    }
+}
+
+impl fmt::Display for TaintEffect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TaintEffect {
+    type Err = ModelError;
+
+    fn from_str(s: &str) -> ModelResult<Self> {
+        match s
+            .trim()
+            .to_ascii_lowercase()
+            .replace(['-', '_'], "")
+            .as_str()
+        {
+            "noschedule" => Ok(TaintEffect::NoSchedule),
+            "prefernoschedule" => Ok(TaintEffect::PreferNoSchedule),
+            other => Err(ModelError::Invalid(format!(
+                "unknown taint effect: {other}"
+            ))),
+        }
+    }
+}
+
+/// Agent-level taint repelling work that does not tolerate it.
+///
+/// Text form: `key[=value]:Effect`, e.g. `gpu=true:NoSchedule` or `maintenance:PreferNoSchedule`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Taint {
+    /// Taint key.
+    pub key: String,
+    /// Optional taint value (empty when unset).
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub value: String,
+    /// Effect on untolerating work.
+    pub effect: TaintEffect,
+}
+
+impl Taint {
+    /// Create a taint.
+    pub fn new(key: impl Into<String>, value: impl Into<String>, effect: TaintEffect) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+            effect,
+        }
+    }
+}
+
+impl fmt::Display for Taint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.value.is_empty() {
+            write!(f, "{}:{}", self.key, self.effect)
+        } else {
+            write!(f, "{}={}:{}", self.key, self.value, self.effect)
+        }
+    }
+}
+
+impl FromStr for Taint {
+    type Err = ModelError;
+
+    fn from_str(s: &str) -> ModelResult<Self> {
+        let (kv, effect) = s
+            .trim()
+            .rsplit_once(':')
+            .ok_or_else(|| ModelError::Invalid(format!("taint without effect: {s}")))?;
+        let (key, value) = kv.split_once('=').unwrap_or((kv, ""));
+        if key.is_empty() {
+            return Err(ModelError::Invalid(format!("taint without key: {s}")));
+        }
+        Ok(Taint::new(key, value, effect.parse()?))
+    }
+}
+
+/// Permission for work to run on agents carrying matching taints.
+///
+/// Matches a taint when the keys are equal and, if set, the value and effect are equal too.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Toleration {
+    /// Taint key to tolerate.
+    pub key: String,
+    /// Required taint value; any value when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Required taint effect; any effect when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect: Option<TaintEffect>,
+}
+
+impl Toleration {
+    /// Returns `true` if this toleration matches `taint`.
+    pub fn tolerates(&self, taint: &Taint) -> bool {
+        self.key == taint.key
+            && self.value.as_ref().is_none_or(|v| *v == taint.value)
+            && self.effect.is_none_or(|e| e == taint.effect)
+    }
+}
+
+/// Placement constraints of a piece of work against agent labels and taints.
+///
+/// - `selector`: every entry must be present with the same value in the agent labels.
+/// - `tolerations`: every `NoSchedule` taint of the agent must be tolerated.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Placement {
+    /// Required agent labels.
+    #[serde(default, skip_serializing_if = "RunnerLabels::is_empty")]
+    pub selector: RunnerLabels,
+    /// Tolerated agent taints.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tolerations: Vec<Toleration>,
+}
+
+impl Placement {
+    /// Returns `true` if the work may be placed on an agent with these labels and taints.
+    pub fn matches(&self, labels: &RunnerLabels, taints: &[Taint]) -> bool {
+        let selected = self.selector.iter().all(|(k, v)| labels.get(k) == Some(v));
+        selected
+            && taints
+                .iter()
+                .filter(|t| t.effect == TaintEffect::NoSchedule)
+                .all(|t| self.is_tolerated(t))
+    }
+
+    /// Number of untolerated `PreferNoSchedule` taints (lower is better).
+    pub fn penalty(&self, taints: &[Taint]) -> usize {
+        taints
+            .iter()
+            .filter(|t| t.effect == TaintEffect::PreferNoSchedule && !self.is_tolerated(t))
+            .count()
+    }
+
+    fn is_tolerated(&self, taint: &Taint) -> bool {
+        self.tolerations.iter().any(|t| t.tolerates(taint))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AGENT_LABEL_REGION;
+
+    #[test]
+    fn taint_parse_roundtrip() {
+        for text in ["gpu=true:NoSchedule", "maintenance:PreferNoSchedule"] {
+            let taint: Taint = text.parse().unwrap();
+            assert_eq!(taint.to_string(), text);
+        }
+        assert_eq!(
+            "gpu:no-schedule".parse::<Taint>().unwrap().effect,
+            TaintEffect::NoSchedule
+        );
+        assert!("gpu".parse::<Taint>().is_err());
+        assert!(":NoSchedule".parse::<Taint>().is_err());
+        assert!("gpu:Never".parse::<Taint>().is_err());
+    }
+
+    #[test]
+    fn placement_checks_selector_and_hard_taints() {
+        let mut labels = RunnerLabels::new();
+        labels.insert(AGENT_LABEL_REGION, "eu-west");
+        let taints = vec![
+            Taint::new("gpu", "true", TaintEffect::NoSchedule),
+            Taint::new("spot", "", TaintEffect::PreferNoSchedule),
+        ];
+
+        let mut placement = Placement::default();
+        placement.selector.insert(AGENT_LABEL_REGION, "eu-west");
+        assert!(!placement.matches(&labels, &taints));
+
+        placement.tolerations.push(Toleration {
+            key: "gpu".into(),
+            value: Some("true".into()),
+            effect: None,
+        });
+        assert!(placement.matches(&labels, &taints));
+        assert_eq!(placement.penalty(&taints), 1);
+
+        placement.selector.insert(AGENT_LABEL_REGION, "us-east");
+        assert!(!placement.matches(&labels, &taints));
+    }
+}
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::RunnerLabels;
+
+/// Set of tasks a quota applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum QuotaScope {
+    /// Every task in the namespace (see [`crate::CreateSpec::namespace`]).
+    Namespace(String),
+    /// Every task carrying `labels[key] == value`.
+    Label {
+        /// Label key.
+        key: String,
+        /// Required label value.
+        value: String,
+    },
+}
+
+impl QuotaScope {
+    /// Scope covering all tasks in a namespace.
+    pub fn namespace(namespace: impl Into<String>) -> Self {
+        Self::Namespace(namespace.into())
+    }
+
+    /// Scope covering all tasks with the given label.
+    pub fn label(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self::Label {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Returns `true` if a task with this namespace and labels falls into this scope.
+    pub fn matches(&self, namespace: Option<&str>, labels: &RunnerLabels) -> bool {
+        match self {
+            Self::Namespace(ns) => namespace == Some(ns.as_str()),
+            Self::Label { key, value } => labels.get(key) == Some(value.as_str()),
+        }
+    }
+}
+
+impl fmt::Display for QuotaScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Namespace(ns) => write!(f, "namespace {ns}"),
+            Self::Label { key, value } => write!(f, "{key}={value}"),
+        }
+    }
+}
+
+/// Limits applied to all active tasks within a [`QuotaScope`].
+///
+/// Unset limits are not enforced. Resource limits are checked against the
+/// requests declared by each task (see [`crate::CreateSpec::resources`]).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskQuota {
+    /// Tasks the quota applies to.
+    pub scope: QuotaScope,
+    /// Max number of active (pending or running) tasks; restartable tasks count between runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tasks: Option<usize>,
+    /// Max number of pending or running tasks.
+    ///
+    /// Unlike `max_tasks`, restartable tasks waiting for their next run do not count.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_running: Option<usize>,
+    /// Max sum of CPU requests of active tasks, in millicores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_millis: Option<u64>,
+    /// Max sum of memory requests of active tasks, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_bytes: Option<u64>,
+}
+
+impl TaskQuota {
+    /// Create a quota without limits for the given scope.
+    pub fn new(scope: QuotaScope) -> Self {
+        Self {
+            scope,
+            max_tasks: None,
+            max_running: None,
+            max_cpu_millis: None,
+            max_memory_bytes: None,
+        }
+    }
+
+    /// Limit the number of active tasks.
+    pub fn with_max_tasks(mut self, n: usize) -> Self {
+        self.max_tasks = Some(n);
+        self
+    }
+
+    /// Limit the number of pending or running tasks.
+    pub fn with_max_running(mut self, n: usize) -> Self {
+        self.max_running = Some(n);
+        self
+    }
+
+    /// Limit the total CPU request in millicores.
+    pub fn with_max_cpu_millis(mut self, millis: u64) -> Self {
+        self.max_cpu_millis = Some(millis);
+        self
+    }
+
+    /// Limit the total memory request in bytes.
+    pub fn with_max_memory_bytes(mut self, bytes: u64) -> Self {
+        self.max_memory_bytes = Some(bytes);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scopes_match_namespace_or_label() {
+        let mut labels = RunnerLabels::new();
+        labels.insert("tier", "gold");
+
+        assert!(QuotaScope::namespace("team-a").matches(Some("team-a"), &labels));
+        assert!(!QuotaScope::namespace("team-b").matches(Some("team-a"), &labels));
+        assert!(!QuotaScope::namespace("team-a").matches(None, &labels));
+        assert!(QuotaScope::label("tier", "gold").matches(None, &labels));
+        assert!(!QuotaScope::label("tier", "silver").matches(None, &labels));
+    }
+
+    #[test]
+    fn serde_skips_unset_limits() {
+        let quota = TaskQuota::new(QuotaScope::namespace("ns")).with_max_tasks(5);
+        let json = serde_json::to_value(&quota).unwrap();
+
+        assert_eq!(json["scope"], serde_json::json!({ "namespace": "ns" }));
+        assert_eq!(json["maxTasks"], 5);
+        assert!(json.get("maxRunning").is_none());
+    }
+}
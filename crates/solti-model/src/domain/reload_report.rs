@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a configuration reload.
+///
+/// Lists changed settings by their dotted path (e.g. `"logger.level"`), split by
+/// whether the change took effect immediately or needs a process restart.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReloadReport {
+    /// Settings applied to the running process.
+    pub applied: Vec<String>,
+    /// Settings that changed but take effect only after restart.
+    pub requires_restart: Vec<String>,
+}
+
+impl ReloadReport {
+    /// Returns `true` if the reloaded configuration differs from the running one.
+    pub fn has_changes(&self) -> bool {
+        !self.applied.is_empty() || !self.requires_restart.is_empty()
+    }
+
+    /// Returns `true` if the given setting was applied at runtime.
+    pub fn is_applied(&self, key: &str) -> bool {
+        self.applied.iter().any(|k| k == key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_report_has_no_changes() {
+        assert!(!ReloadReport::default().has_changes());
+    }
+
+    #[test]
+    fn serde_uses_camel_case() {
+        let report = ReloadReport {
+            applied: vec!["logger.level".into()],
+            requires_restart: vec!["api.http_addr".into()],
+        };
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["applied"][0], "logger.level");
+        assert_eq!(json["requiresRestart"][0], "api.http_addr");
+        assert!(report.is_applied("logger.level"));
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// Resources requested by a task (see [`crate::CreateSpec::resources`]).
+///
+/// Zero means "nothing requested"; such tasks always fit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceRequests {
+    /// CPU in millicores.
+    #[serde(default)]
+    pub cpu_millis: u64,
+    /// Memory in bytes.
+    #[serde(default)]
+    pub memory_bytes: u64,
+    /// Number of GPUs.
+    #[serde(default)]
+    pub gpus: u64,
+}
+
+impl ResourceRequests {
+    /// Returns `true` if nothing is requested.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Component-wise sum, saturating at `u64::MAX`.
+    pub fn saturating_add(self, other: ResourceRequests) -> Self {
+        Self {
+            cpu_millis: self.cpu_millis.saturating_add(other.cpu_millis),
+            memory_bytes: self.memory_bytes.saturating_add(other.memory_bytes),
+            gpus: self.gpus.saturating_add(other.gpus),
+        }
+    }
+}
+
+/// Resources an agent can allocate to tasks.
+///
+/// Unset dimensions are unbounded: they are neither enforced nor advertised.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceCapacity {
+    /// CPU in millicores.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_millis: Option<u64>,
+    /// Memory in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_bytes: Option<u64>,
+    /// Number of GPUs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gpus: Option<u64>,
+}
+
+impl ResourceCapacity {
+    /// Capacity without limits.
+    pub fn unbounded() -> Self {
+        Self::default()
+    }
+
+    /// Limit allocatable CPU in millicores.
+    pub fn with_cpu_millis(mut self, millis: u64) -> Self {
+        self.cpu_millis = Some(millis);
+        self
+    }
+
+    /// Limit allocatable memory in bytes.
+    pub fn with_memory_bytes(mut self, bytes: u64) -> Self {
+        self.memory_bytes = Some(bytes);
+        self
+    }
+
+    /// Limit the number of allocatable GPUs.
+    pub fn with_gpus(mut self, gpus: u64) -> Self {
+        self.gpus = Some(gpus);
+        self
+    }
+
+    /// Returns `true` if no dimension is limited.
+    pub fn is_unbounded(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Capacity left after `used` is allocated; unbounded dimensions stay unbounded.
+    pub fn remaining(&self, used: &ResourceRequests) -> Self {
+        Self {
+            cpu_millis: self.cpu_millis.map(|c| c.saturating_sub(used.cpu_millis)),
+            memory_bytes: self
+                .memory_bytes
+                .map(|c| c.saturating_sub(used.memory_bytes)),
+            gpus: self.gpus.map(|c| c.saturating_sub(used.gpus)),
+        }
+    }
+
+    /// First dimension where `request` does not fit, as `(name, requested, available)`.
+    pub fn shortfall(&self, request: &ResourceRequests) -> Option<(&'static str, u64, u64)> {
+        [
+            ("cpu millis", request.cpu_millis, self.cpu_millis),
+            ("memory bytes", request.memory_bytes, self.memory_bytes),
+            ("gpus", request.gpus, self.gpus),
+        ]
+        .into_iter()
+        .find_map(|(name, requested, available)| {
+            available
+                .filter(|available| requested > *available)
+                .map(|available| (name, requested, available))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remaining_keeps_unbounded_dimensions() {
+        let capacity = ResourceCapacity::unbounded()
+            .with_cpu_millis(2_000)
+            .with_gpus(1);
+        let used = ResourceRequests {
+            cpu_millis: 500,
+            memory_bytes: 1 << 30,
+            gpus: 2,
+        };
+
+        let free = capacity.remaining(&used);
+        assert_eq!(free.cpu_millis, Some(1_500));
+        assert_eq!(free.memory_bytes, None);
+        assert_eq!(free.gpus, Some(0));
+    }
+
+    #[test]
+    fn shortfall_names_first_exceeded_dimension() {
+        let free = ResourceCapacity::unbounded()
+            .with_cpu_millis(1_000)
+            .with_gpus(0);
+
+        let fits = ResourceRequests {
+            cpu_millis: 1_000,
+            memory_bytes: u64::MAX,
+            gpus: 0,
+        };
+        assert_eq!(free.shortfall(&fits), None);
+
+        let gpu = ResourceRequests {
+            gpus: 1,
+            ..Default::default()
+        };
+        assert_eq!(free.shortfall(&gpu), Some(("gpus", 1, 0)));
+    }
+}
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+
+use crate::RunnerLabels;
+
+/// Snapshot of a registered runner, used to advertise agent capabilities.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunnerInfo {
+    /// Runner name.
+    pub name: String,
+    /// Task kinds the runner can execute (see [`crate::TaskKind::kind`]).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub kinds: Vec<String>,
+    /// Static routing labels of the runner.
+    #[serde(default, skip_serializing_if = "RunnerLabels::is_empty")]
+    pub labels: RunnerLabels,
+    /// Result of the last health check.
+    pub healthy: bool,
+}
@@ -35,6 +35,11 @@ impl RunnerLabels {
         self.0.get(key).map(|s| s.as_str())
     }
 
+    /// Remove a label, returning its value if it was present.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
     /// Iterate through all labels as `(&str, &str)` pairs.
     pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
         self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+
+/// Supervision state of an event subscriber.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubscriberState {
+    /// Receiving events.
+    Running,
+    /// Backing off after a failure; events keep queuing.
+    Restarting,
+    /// Stopped after too many consecutive failures; events are discarded.
+    Disabled,
+}
+
+impl SubscriberState {
+    /// State name (`"running"`, `"restarting"` or `"disabled"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Restarting => "restarting",
+            Self::Disabled => "disabled",
+        }
+    }
+}
+
+/// Health of a lifecycle event subscriber.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriberHealth {
+    /// Subscriber name.
+    pub name: String,
+    /// Current supervision state.
+    pub state: SubscriberState,
+    /// Events delivered successfully.
+    pub delivered: u64,
+    /// Deliveries that panicked or timed out.
+    pub failures: u64,
+    /// Failures since the last successful delivery.
+    pub consecutive_failures: u32,
+    /// Events dropped or spilled because the subscriber queue was full.
+    pub overflowed: u64,
+    /// Reason of the most recent failure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+impl SubscriberHealth {
+    /// Health of a freshly attached subscriber.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            state: SubscriberState::Running,
+            delivered: 0,
+            failures: 0,
+            consecutive_failures: 0,
+            overflowed: 0,
+            last_error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_uses_camel_case_and_lowercase_states() {
+        let mut health = SubscriberHealth::new("journal");
+        health.state = SubscriberState::Disabled;
+        health.consecutive_failures = 3;
+        health.last_error = Some("panicked: boom".into());
+
+        let json = serde_json::to_string(&health).unwrap();
+        assert!(json.contains("\"state\":\"disabled\""));
+        assert!(json.contains("\"consecutiveFailures\":3"));
+        assert!(json.contains("\"lastError\":\"panicked: boom\""));
+        assert_eq!(
+            serde_json::from_str::<SubscriberHealth>(&json).unwrap(),
+            health
+        );
+        assert_eq!(SubscriberState::Restarting.as_str(), "restarting");
+    }
+}
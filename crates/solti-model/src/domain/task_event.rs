@@ -0,0 +1,207 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Slot, TaskId};
+
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+/// Kind of a [`TaskEvent`].
+///
+/// Serialized by variant name (e.g. `"TaskStarting"`); kinds unknown to this
+/// version deserialize as [`TaskEventKind::Other`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TaskEventKind {
+    /// Controller accepted a submission.
+    ControllerSubmitted,
+    /// Controller rejected a submission (queue full, add failed, ...).
+    ControllerRejected,
+    /// Controller slot changed state.
+    ControllerSlotTransition,
+    /// Task registration was requested.
+    TaskAddRequested,
+    /// Task was registered.
+    TaskAdded,
+    /// Task is starting an attempt.
+    TaskStarting,
+    /// Attempt finished successfully or was canceled gracefully.
+    TaskStopped,
+    /// Attempt failed.
+    TaskFailed,
+    /// Attempt exceeded its timeout.
+    TimeoutHit,
+    /// Next attempt was scheduled.
+    BackoffScheduled,
+    /// Restart policy is exhausted; the task will not run again.
+    ActorExhausted,
+    /// Task terminated permanently with a fatal error.
+    ActorDead,
+    /// Task removal was requested.
+    TaskRemoveRequested,
+    /// Task was removed.
+    TaskRemoved,
+    /// Shutdown was requested.
+    ShutdownRequested,
+    /// All tasks stopped within the grace period.
+    AllStoppedWithinGrace,
+    /// Grace period elapsed before all tasks stopped.
+    GraceExceeded,
+    /// Event subscriber panicked.
+    SubscriberPanicked,
+    /// Event subscriber dropped an event.
+    SubscriberOverflow,
+    /// Kind not known to this version.
+    #[serde(other)]
+    Other,
+}
+
+impl TaskEventKind {
+    /// Returns `true` for events ending a task's life (exhausted, dead or removed).
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            Self::ActorExhausted | Self::ActorDead | Self::TaskRemoved
+        )
+    }
+}
+
+/// Lifecycle event of the agent runtime.
+///
+/// Stable schema shared by the event history, API streaming, webhooks and persistence;
+/// independent of the runtime's internal event type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEvent {
+    /// Monotonic sequence number; use it as the `since` cursor of the next query.
+    pub seq: u64,
+    /// Event time, milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// Event kind.
+    pub kind: TaskEventKind,
+    /// Task the event belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task: Option<TaskId>,
+    /// Slot of the task, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot: Option<Slot>,
+    /// Attempt number for attempt-related events.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attempt: Option<u32>,
+    /// Failure or backoff reason.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Configured attempt timeout, for [`TaskEventKind::TimeoutHit`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// Delay before the next attempt, for [`TaskEventKind::BackoffScheduled`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+}
+
+/// Filter for the event history.
+#[derive(Debug, Clone, Default)]
+pub struct EventQuery {
+    /// Only events with `seq` greater than this value.
+    pub since: Option<u64>,
+    pub slot: Option<String>,
+    pub task: Option<TaskId>,
+    pub limit: usize,
+}
+
+impl EventQuery {
+    pub fn new() -> Self {
+        Self {
+            since: None,
+            slot: None,
+            task: None,
+            limit: DEFAULT_LIMIT,
+        }
+    }
+
+    pub fn with_since(mut self, seq: u64) -> Self {
+        self.since = Some(seq);
+        self
+    }
+
+    pub fn with_slot(mut self, slot: impl Into<String>) -> Self {
+        self.slot = Some(slot.into());
+        self
+    }
+
+    pub fn with_task(mut self, task: impl Into<TaskId>) -> Self {
+        self.task = Some(task.into());
+        self
+    }
+
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = limit.min(MAX_LIMIT);
+        self
+    }
+
+    /// Returns `true` if the record passes all filters.
+    pub fn matches(&self, record: &TaskEvent) -> bool {
+        self.since.is_none_or(|since| record.seq > since)
+            && self
+                .slot
+                .as_ref()
+                .is_none_or(|slot| record.slot.as_ref() == Some(slot))
+            && self
+                .task
+                .as_ref()
+                .is_none_or(|task| record.task.as_ref() == Some(task))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(seq: u64, slot: Option<&str>) -> TaskEvent {
+        TaskEvent {
+            seq,
+            timestamp_ms: 0,
+            kind: TaskEventKind::TaskStarting,
+            task: Some(TaskId::from("r-backup-1")),
+            slot: slot.map(Into::into),
+            attempt: Some(1),
+            reason: None,
+            timeout_ms: None,
+            delay_ms: None,
+        }
+    }
+
+    #[test]
+    fn query_filters_by_cursor_and_slot() {
+        let q = EventQuery::new().with_since(5).with_slot("backup");
+        assert!(q.matches(&record(6, Some("backup"))));
+        assert!(!q.matches(&record(5, Some("backup"))));
+        assert!(!q.matches(&record(7, Some("other"))));
+        assert!(!q.matches(&record(7, None)));
+        assert!(EventQuery::new().matches(&record(0, None)));
+    }
+
+    #[test]
+    fn limit_is_capped() {
+        assert_eq!(EventQuery::new().limit, DEFAULT_LIMIT);
+        assert_eq!(EventQuery::new().with_limit(10_000).limit, MAX_LIMIT);
+    }
+
+    #[test]
+    fn serde_skips_missing_fields() {
+        let json = serde_json::to_string(&record(1, None)).unwrap();
+        assert!(json.contains("\"timestampMs\""));
+        assert!(!json.contains("slot"));
+        let back: TaskEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, record(1, None));
+    }
+
+    #[test]
+    fn kinds_serialize_by_name() {
+        let json = serde_json::to_string(&record(1, None)).unwrap();
+        assert!(json.contains("\"kind\":\"TaskStarting\""));
+
+        let kind: TaskEventKind = serde_json::from_str("\"SomethingNew\"").unwrap();
+        assert_eq!(kind, TaskEventKind::Other);
+        assert!(TaskEventKind::ActorDead.is_terminal());
+        assert!(!TaskEventKind::TaskFailed.is_terminal());
+    }
+}
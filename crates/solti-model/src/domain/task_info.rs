@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::time::SystemTime;
 
-use crate::{Slot, TaskId, TaskStatus};
+use crate::{RunnerLabels, Slot, TaskId, TaskStatus};
 
 /// Detailed information about a task instance.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +24,58 @@ pub struct TaskInfo {
     /// Last error message (if status is Failed/Timeout).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    /// Group the task was submitted in (see [`crate::LABEL_GROUP`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Labels of the spec the task was created from.
+    #[serde(default, skip_serializing_if = "RunnerLabels::is_empty")]
+    pub labels: RunnerLabels,
+    /// Task kind (e.g. `"subprocess"`); `None` for code-defined tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Name of the runner that built the task; `None` for code-defined tasks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runner: Option<String>,
+    /// When the last attempt started.
+    #[serde(
+        default,
+        with = "opt_time_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub started_at: Option<SystemTime>,
+    /// When the last attempt finished; `None` while it is running.
+    #[serde(
+        default,
+        with = "opt_time_serde",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub finished_at: Option<SystemTime>,
+    /// Duration of the last finished attempt, in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+impl TaskInfo {
+    /// Newly registered task in [`TaskStatus::Pending`].
+    pub fn pending(id: TaskId, slot: Slot) -> Self {
+        let now = SystemTime::now();
+        Self {
+            id,
+            slot,
+            status: TaskStatus::Pending,
+            attempt: 0,
+            created_at: now,
+            updated_at: now,
+            error: None,
+            group: None,
+            labels: RunnerLabels::new(),
+            kind: None,
+            runner: None,
+            started_at: None,
+            finished_at: None,
+            duration_ms: None,
+        }
+    }
 }
 
 mod time_serde {
@@ -49,21 +101,45 @@ mod time_serde {
     }
 }
 
+mod opt_time_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::SystemTime;
+
+    pub fn serialize<S>(time: &Option<SystemTime>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match time {
+            Some(time) => super::time_serde::serialize(time, serializer),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<SystemTime>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let secs = Option::<u64>::deserialize(deserializer)?;
+        Ok(secs.map(|s| std::time::UNIX_EPOCH + std::time::Duration::from_secs(s)))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn task_info_serde_roundtrip() {
-        let info = TaskInfo {
-            id: TaskId::from("test-task-1"),
-            slot: "demo-slot".to_string(),
-            status: TaskStatus::Running,
-            attempt: 2,
-            created_at: SystemTime::now(),
-            updated_at: SystemTime::now(),
-            error: Some("timeout".to_string()),
-        };
+        let mut info = TaskInfo::pending(TaskId::from("test-task-1"), "demo-slot".to_string());
+        info.status = TaskStatus::Running;
+        info.attempt = 2;
+        info.error = Some("timeout".to_string());
+        info.group = Some("batch-1".to_string());
+        info.labels.insert("team", "core");
+        info.kind = Some("subprocess".to_string());
+        info.runner = Some("default".to_string());
+        info.started_at = Some(SystemTime::now());
+        info.duration_ms = Some(1500);
 
         let json = serde_json::to_string(&info).unwrap();
         let back: TaskInfo = serde_json::from_str(&json).unwrap();
@@ -73,21 +149,23 @@ mod tests {
         assert_eq!(back.status, info.status);
         assert_eq!(back.attempt, info.attempt);
         assert_eq!(back.error, info.error);
+        assert_eq!(back.group, info.group);
+        assert_eq!(back.labels, info.labels);
+        assert_eq!(back.kind, info.kind);
+        assert_eq!(back.runner, info.runner);
+        assert!(back.started_at.is_some());
+        assert!(back.finished_at.is_none());
+        assert_eq!(back.duration_ms, Some(1500));
     }
 
     #[test]
     fn task_info_optional_error() {
-        let info = TaskInfo {
-            id: TaskId::from("test-task"),
-            slot: "slot".to_string(),
-            status: TaskStatus::Succeeded,
-            attempt: 1,
-            created_at: SystemTime::now(),
-            updated_at: SystemTime::now(),
-            error: None,
-        };
+        let info = TaskInfo::pending(TaskId::from("test-task"), "slot".to_string());
 
         let json = serde_json::to_string(&info).unwrap();
         assert!(!json.contains("error"));
+        assert!(!json.contains("group"));
+        assert!(!json.contains("startedAt"));
+        assert!(!json.contains("labels"));
     }
 }
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::domain::{Slot, TaskId};
+
+/// Output stream a captured line was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    /// Stream name (`"stdout"` or `"stderr"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
+/// Single line of task output captured by a runner.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskOutput {
+    /// Task that produced the line.
+    pub task: TaskId,
+    /// Slot of the task.
+    pub slot: Slot,
+    /// Attempt number the line belongs to.
+    pub attempt: u32,
+    /// Stream the line was read from.
+    pub stream: OutputStream,
+    /// Line content without the trailing newline (possibly truncated by the runner).
+    pub line: String,
+    /// Capture time, milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_uses_lowercase_stream_names() {
+        let output = TaskOutput {
+            task: TaskId::from("r-backup-1"),
+            slot: "backup".into(),
+            attempt: 2,
+            stream: OutputStream::Stderr,
+            line: "disk almost full".into(),
+            timestamp_ms: 1,
+        };
+
+        let json = serde_json::to_string(&output).unwrap();
+        assert!(json.contains("\"stream\":\"stderr\""));
+        assert!(json.contains("\"timestampMs\":1"));
+        assert_eq!(serde_json::from_str::<TaskOutput>(&json).unwrap(), output);
+        assert_eq!(OutputStream::Stdout.as_str(), "stdout");
+    }
+}
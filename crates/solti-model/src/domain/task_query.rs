@@ -1,4 +1,4 @@
-use super::TaskStatus;
+use super::{RunnerLabels, TaskInfo, TaskStatus};
 
 const DEFAULT_LIMIT: usize = 100;
 const MAX_LIMIT: usize = 1000;
@@ -8,6 +8,8 @@ const MAX_LIMIT: usize = 1000;
 pub struct TaskQuery {
     pub slot: Option<String>,
     pub status: Option<TaskStatus>,
+    /// Label selector: every entry must be present with the same value.
+    pub labels: RunnerLabels,
     pub limit: usize,
     pub offset: usize,
 }
@@ -24,6 +26,7 @@ impl TaskQuery {
         Self {
             slot: None,
             status: None,
+            labels: RunnerLabels::new(),
             limit: DEFAULT_LIMIT,
             offset: 0,
         }
@@ -39,6 +42,11 @@ impl TaskQuery {
         self
     }
 
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = limit.min(MAX_LIMIT);
         self
@@ -48,4 +56,14 @@ impl TaskQuery {
         self.offset = offset;
         self
     }
+
+    /// Returns `true` if the task passes the slot, status and label filters.
+    pub fn matches(&self, info: &TaskInfo) -> bool {
+        self.slot.as_ref().is_none_or(|slot| &info.slot == slot)
+            && self.status.is_none_or(|status| info.status == status)
+            && self
+                .labels
+                .iter()
+                .all(|(k, v)| info.labels.get(k) == Some(v))
+    }
 }
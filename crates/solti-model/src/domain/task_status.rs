@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 /// Current execution state of a task.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum TaskStatus {
     /// Task is queued or waiting to start.
@@ -18,9 +18,37 @@ pub enum TaskStatus {
     Canceled,
     /// Task exhausted its restart policy and will not retry.
     Exhausted,
+    /// Run was skipped because it fell outside the task's execution window.
+    Skipped,
 }
 
 impl TaskStatus {
+    /// Every status, in declaration order.
+    pub const ALL: [TaskStatus; 8] = [
+        TaskStatus::Pending,
+        TaskStatus::Running,
+        TaskStatus::Succeeded,
+        TaskStatus::Failed,
+        TaskStatus::Timeout,
+        TaskStatus::Canceled,
+        TaskStatus::Exhausted,
+        TaskStatus::Skipped,
+    ];
+
+    /// Wire representation (same as the serde form, e.g. `"running"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TaskStatus::Pending => "pending",
+            TaskStatus::Running => "running",
+            TaskStatus::Succeeded => "succeeded",
+            TaskStatus::Failed => "failed",
+            TaskStatus::Timeout => "timeout",
+            TaskStatus::Canceled => "canceled",
+            TaskStatus::Exhausted => "exhausted",
+            TaskStatus::Skipped => "skipped",
+        }
+    }
+
     /// Returns `true` if the task is in a terminal state (won't transition further).
     pub fn is_terminal(&self) -> bool {
         matches!(
@@ -30,6 +58,7 @@ impl TaskStatus {
                 | TaskStatus::Timeout
                 | TaskStatus::Canceled
                 | TaskStatus::Exhausted
+                | TaskStatus::Skipped
         )
     }
 
@@ -50,6 +79,7 @@ mod tests {
         assert!(TaskStatus::Timeout.is_terminal());
         assert!(TaskStatus::Canceled.is_terminal());
         assert!(TaskStatus::Exhausted.is_terminal());
+        assert!(TaskStatus::Skipped.is_terminal());
 
         assert!(!TaskStatus::Pending.is_terminal());
         assert!(!TaskStatus::Running.is_terminal());
@@ -64,6 +94,14 @@ mod tests {
         assert!(!TaskStatus::Failed.is_active());
     }
 
+    #[test]
+    fn as_str_matches_serde() {
+        for status in TaskStatus::ALL {
+            let json = serde_json::to_string(&status).unwrap();
+            assert_eq!(json, format!("\"{}\"", status.as_str()));
+        }
+    }
+
     #[test]
     fn serde_roundtrip() {
         let status = TaskStatus::Running;
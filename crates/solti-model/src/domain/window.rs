@@ -0,0 +1,339 @@
+use std::{fmt, str::FromStr};
+
+use chrono::{Offset, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ModelError, ModelResult};
+
+/// Day of the week.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Weekday {
+    Mon,
+    Tue,
+    Wed,
+    Thu,
+    Fri,
+    Sat,
+    Sun,
+}
+
+impl Weekday {
+    /// All days in order, starting from Monday.
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ];
+
+    /// Short lowercase name (`"mon"` .. `"sun"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Weekday::Mon => "mon",
+            Weekday::Tue => "tue",
+            Weekday::Wed => "wed",
+            Weekday::Thu => "thu",
+            Weekday::Fri => "fri",
+            Weekday::Sat => "sat",
+            Weekday::Sun => "sun",
+        }
+    }
+
+    /// Day of the week for a count of days since the Unix epoch (a Thursday).
+    fn from_days_since_epoch(days: i64) -> Self {
+        Self::ALL[(days + 3).rem_euclid(7) as usize]
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = ModelError;
+
+    fn from_str(s: &str) -> ModelResult<Self> {
+        let v = s.trim().to_ascii_lowercase();
+        Weekday::ALL
+            .into_iter()
+            .find(|d| v.starts_with(d.as_str()))
+            .ok_or_else(|| ModelError::Invalid(format!("unknown weekday: {s}")))
+    }
+}
+
+/// Time of day with minute precision, written as `"HH:MM"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeOfDay(u16);
+
+impl TimeOfDay {
+    /// Create from hour and minute.
+    pub fn new(hour: u8, minute: u8) -> ModelResult<Self> {
+        if hour > 23 || minute > 59 {
+            return Err(ModelError::Invalid(format!(
+                "invalid time of day: {hour:02}:{minute:02}"
+            )));
+        }
+        Ok(Self(hour as u16 * 60 + minute as u16))
+    }
+
+    /// Minutes since midnight.
+    pub fn minutes(&self) -> u16 {
+        self.0
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}", self.0 / 60, self.0 % 60)
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ModelError;
+
+    fn from_str(s: &str) -> ModelResult<Self> {
+        let invalid =
+            || ModelError::Invalid(format!("invalid time of day: '{s}' (expected HH:MM)"));
+        let (h, m) = s.trim().split_once(':').ok_or_else(invalid)?;
+        let h = h.parse::<u8>().map_err(|_| invalid())?;
+        let m = m.parse::<u8>().map_err(|_| invalid())?;
+        Self::new(h, m)
+    }
+}
+
+impl TryFrom<String> for TimeOfDay {
+    type Error = ModelError;
+
+    fn try_from(s: String) -> ModelResult<Self> {
+        s.parse()
+    }
+}
+
+impl From<TimeOfDay> for String {
+    fn from(t: TimeOfDay) -> Self {
+        t.to_string()
+    }
+}
+
+/// Recurring time window in which a task may run.
+///
+/// Executions attempted outside the window are skipped (the task is not started
+/// and its status is recorded as [`crate::TaskStatus::Skipped`]).
+///
+/// - `days` — allowed days of week; empty means every day.
+/// - `start`/`end` — half-open time-of-day range `[start, end)`.
+///   If `start > end` the window wraps midnight (e.g. `22:00`–`06:00`);
+///   `days` then refers to the day the window opened.
+///   If `start == end` the window spans the whole day.
+/// - `timezone` — IANA time zone of the window (e.g. `Europe/Berlin`); its UTC offset is
+///   resolved on every evaluation, so the window follows daylight saving time.
+/// - `utc_offset_minutes` — fixed offset from UTC, used when `timezone` is not set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutionWindow {
+    /// Allowed days of week (empty = every day).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    /// Window start (inclusive).
+    pub start: TimeOfDay,
+    /// Window end (exclusive).
+    pub end: TimeOfDay,
+    /// Fixed offset from UTC in minutes (e.g. `180` for UTC+03:00); not adjusted for DST.
+    ///
+    /// Ignored when `timezone` is set.
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+    /// IANA time zone name (e.g. `America/New_York`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl ExecutionWindow {
+    /// Offset from UTC in seconds in effect at the given Unix timestamp (seconds).
+    ///
+    /// Resolved from `timezone` when set, otherwise `utc_offset_minutes`.
+    /// Fails if `timezone` is not a known IANA time zone name.
+    pub fn utc_offset_at(&self, unix_secs: i64) -> ModelResult<i64> {
+        let Some(name) = &self.timezone else {
+            return Ok(self.utc_offset_minutes as i64 * 60);
+        };
+        let tz = parse_timezone(name)?;
+        let at = chrono::DateTime::from_timestamp(unix_secs, 0)
+            .ok_or_else(|| ModelError::Invalid(format!("timestamp out of range: {unix_secs}")))?;
+        Ok(tz
+            .offset_from_utc_datetime(&at.naive_utc())
+            .fix()
+            .local_minus_utc() as i64)
+    }
+
+    /// Returns `true` if the given Unix timestamp (seconds) falls into the window.
+    ///
+    /// A window with an unknown `timezone` contains no time at all.
+    pub fn contains_unix(&self, unix_secs: i64) -> bool {
+        let Ok(offset) = self.utc_offset_at(unix_secs) else {
+            return false;
+        };
+        let local = unix_secs + offset;
+        let days = local.div_euclid(86_400);
+        let minute = (local.rem_euclid(86_400) / 60) as u16;
+
+        let (start, end) = (self.start.minutes(), self.end.minutes());
+        let (in_range, opened_on) = if start == end {
+            (true, days)
+        } else if start < end {
+            (start <= minute && minute < end, days)
+        } else if minute >= start {
+            (true, days)
+        } else {
+            (minute < end, days - 1)
+        };
+
+        in_range
+            && (self.days.is_empty()
+                || self
+                    .days
+                    .contains(&Weekday::from_days_since_epoch(opened_on)))
+    }
+
+    /// Returns `true` if the current system time falls into the window.
+    pub fn contains_now(&self) -> bool {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        self.contains_unix(now)
+    }
+}
+
+/// Parse an IANA time zone name.
+fn parse_timezone(name: &str) -> ModelResult<chrono_tz::Tz> {
+    name.parse()
+        .map_err(|_| ModelError::Invalid(format!("unknown time zone: {name}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01 00:00:00 UTC, a Monday.
+    const MONDAY: i64 = 1_704_067_200;
+    const HOUR: i64 = 3_600;
+
+    fn business_hours() -> ExecutionWindow {
+        ExecutionWindow {
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            start: "09:00".parse().unwrap(),
+            end: "17:00".parse().unwrap(),
+            utc_offset_minutes: 0,
+            timezone: None,
+        }
+    }
+
+    #[test]
+    fn business_hours_window() {
+        let w = business_hours();
+        assert!(w.contains_unix(MONDAY + 9 * HOUR));
+        assert!(w.contains_unix(MONDAY + 16 * HOUR + 59 * 60));
+        assert!(!w.contains_unix(MONDAY + 17 * HOUR));
+        assert!(!w.contains_unix(MONDAY + 8 * HOUR));
+        // Saturday
+        assert!(!w.contains_unix(MONDAY + 5 * 24 * HOUR + 10 * HOUR));
+    }
+
+    #[test]
+    fn offset_shifts_window() {
+        let w = ExecutionWindow {
+            utc_offset_minutes: 180,
+            ..business_hours()
+        };
+        // 06:00 UTC == 09:00 UTC+3
+        assert!(w.contains_unix(MONDAY + 6 * HOUR));
+        assert!(!w.contains_unix(MONDAY + 14 * HOUR));
+    }
+
+    #[test]
+    fn timezone_follows_daylight_saving_time() {
+        let w = ExecutionWindow {
+            timezone: Some("Europe/Berlin".into()),
+            ..business_hours()
+        };
+        // Clocks go forward on Sunday 2024-03-31, from UTC+1 to UTC+2.
+        let friday = 1_711_670_400; // 2024-03-29 00:00:00 UTC
+        let monday = 1_711_929_600; // 2024-04-01 00:00:00 UTC
+        assert_eq!(w.utc_offset_at(friday).unwrap(), HOUR);
+        assert_eq!(w.utc_offset_at(monday).unwrap(), 2 * HOUR);
+
+        // 09:00 local is 08:00 UTC before the change and 07:00 UTC after it.
+        assert!(!w.contains_unix(friday + 7 * HOUR));
+        assert!(w.contains_unix(friday + 8 * HOUR));
+        assert!(w.contains_unix(monday + 7 * HOUR));
+        assert!(!w.contains_unix(monday + 15 * HOUR));
+    }
+
+    #[test]
+    fn unknown_timezone_contains_nothing() {
+        let w = ExecutionWindow {
+            timezone: Some("Mars/Olympus_Mons".into()),
+            ..business_hours()
+        };
+        assert!(w.utc_offset_at(MONDAY).is_err());
+        assert!(!w.contains_unix(MONDAY + 10 * HOUR));
+    }
+
+    #[test]
+    fn overnight_window_uses_opening_day() {
+        let w = ExecutionWindow {
+            days: vec![Weekday::Mon],
+            start: "22:00".parse().unwrap(),
+            end: "06:00".parse().unwrap(),
+            utc_offset_minutes: 0,
+            timezone: None,
+        };
+        assert!(w.contains_unix(MONDAY + 23 * HOUR));
+        // Tuesday 03:00 belongs to Monday's window.
+        assert!(w.contains_unix(MONDAY + 27 * HOUR));
+        // Monday 03:00 belongs to Sunday's window.
+        assert!(!w.contains_unix(MONDAY + 3 * HOUR));
+    }
+
+    #[test]
+    fn equal_bounds_cover_whole_day() {
+        let w = ExecutionWindow {
+            days: vec![Weekday::Sun],
+            start: TimeOfDay::new(0, 0).unwrap(),
+            end: TimeOfDay::new(0, 0).unwrap(),
+            utc_offset_minutes: 0,
+            timezone: None,
+        };
+        assert!(w.contains_unix(MONDAY - 1));
+        assert!(w.contains_unix(MONDAY - 23 * HOUR));
+        assert!(!w.contains_unix(MONDAY));
+    }
+
+    #[test]
+    fn time_of_day_parsing() {
+        assert_eq!("07:05".parse::<TimeOfDay>().unwrap().minutes(), 425);
+        assert!("24:00".parse::<TimeOfDay>().is_err());
+        assert!("9am".parse::<TimeOfDay>().is_err());
+        assert_eq!(TimeOfDay::new(9, 30).unwrap().to_string(), "09:30");
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let w = business_hours();
+        let json = serde_json::to_value(&w).unwrap();
+        assert_eq!(json["start"], "09:00");
+        assert_eq!(json["days"][0], "mon");
+        assert_eq!(json["utcOffsetMinutes"], 0);
+
+        let back: ExecutionWindow = serde_json::from_value(json).unwrap();
+        assert_eq!(back, w);
+    }
+}
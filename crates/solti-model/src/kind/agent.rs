@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// Self-management operation run by an agent's built-in control runner.
+///
+/// The set is closed on purpose: control tasks can only trigger operations the
+/// agent host has wired up, never arbitrary code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AgentAction {
+    /// Reload the agent configuration from its source.
+    ReloadConfig,
+    /// Reopen log files so external rotation takes effect.
+    RotateLogs,
+    /// Persist in-memory state to the configured store.
+    FlushState,
+    /// Release caches and other memory that can be rebuilt.
+    CollectGarbage,
+}
+
+impl AgentAction {
+    /// All actions, in declaration order.
+    pub const ALL: [AgentAction; 4] = [
+        AgentAction::ReloadConfig,
+        AgentAction::RotateLogs,
+        AgentAction::FlushState,
+        AgentAction::CollectGarbage,
+    ];
+
+    /// Returns a short symbolic identifier (e.g. `"reload-config"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgentAction::ReloadConfig => "reload-config",
+            AgentAction::RotateLogs => "rotate-logs",
+            AgentAction::FlushState => "flush-state",
+            AgentAction::CollectGarbage => "collect-garbage",
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+
+/// Bind mount or named volume attached to a container task.
+///
+/// Applied by the container runner; has no effect until one is registered
+/// (see [`crate::TaskKind::Container`]).
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerMount {
+    /// Host path (bind mount, starts with `/`) or volume name.
+    pub source: String,
+    /// Absolute path inside the container.
+    pub target: String,
+    /// Mount read-only.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+impl ContainerMount {
+    /// Returns `true` if `source` is a host path rather than a volume name.
+    pub fn is_bind(&self) -> bool {
+        self.source.starts_with('/')
+    }
+}
+
+/// Network a container task is attached to.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NetworkMode {
+    /// Runtime default bridge network.
+    #[default]
+    Bridge,
+    /// Share the host network namespace.
+    Host,
+    /// No network access.
+    None,
+}
+
+impl NetworkMode {
+    /// Returns a short symbolic identifier (`"bridge"`, `"host"`, `"none"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkMode::Bridge => "bridge",
+            NetworkMode::Host => "host",
+            NetworkMode::None => "none",
+        }
+    }
+}
@@ -1,2 +1,11 @@
 mod task;
 pub use task::TaskKind;
+
+mod container;
+pub use container::{ContainerMount, NetworkMode};
+
+mod agent;
+pub use agent::AgentAction;
+
+mod watch;
+pub use watch::WatchEvent;
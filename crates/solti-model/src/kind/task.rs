@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
+use super::{AgentAction, ContainerMount, NetworkMode, WatchEvent};
 use crate::{Flag, TaskEnv};
 
 /// Execution configuration for a task.
@@ -43,6 +44,10 @@ pub enum TaskKind {
         env: TaskEnv,
     },
     /// Run a task inside an OCI-compatible container.
+    ///
+    /// No container runner ships with the SDK yet: container specs are accepted and validated,
+    /// but fail routing unless a runner for them is registered. `mounts`, `memory_bytes`,
+    /// `cpu_millis`, `network` and `user` have no effect until such a runner applies them.
     Container {
         /// Container image (e.g. `"nginx:latest"`, `"docker.io/library/redis:7"`).
         image: String,
@@ -57,6 +62,52 @@ pub enum TaskKind {
         /// Environment variables for the container.
         #[serde(default, skip_serializing_if = "TaskEnv::is_empty")]
         env: TaskEnv,
+        /// Bind mounts and volumes.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        mounts: Vec<ContainerMount>,
+        /// Memory limit in bytes.
+        ///
+        /// If `None`, the container is not memory-limited.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        memory_bytes: Option<u64>,
+        /// CPU limit in millicores (`1000` = one core).
+        ///
+        /// If `None`, the container is not CPU-limited.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cpu_millis: Option<u64>,
+        /// Network the container is attached to.
+        ///
+        /// If `None`, the runtime default ([`NetworkMode::Bridge`]) is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        network: Option<NetworkMode>,
+        /// User (name or `uid[:gid]`) the entrypoint runs as.
+        ///
+        /// If `None`, the image's default user is used.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        user: Option<String>,
+    },
+    /// Agent self-management operation, executed by the agent's built-in control runner.
+    AgentControl {
+        /// Operation to run.
+        action: AgentAction,
+    },
+    /// Run `action` whenever matching filesystem events fire under `path`.
+    ///
+    /// Built by the router itself: `action` is routed to a runner like any other kind
+    /// and re-run for every debounced burst of events. The task keeps watching until it is
+    /// canceled or an action fails; the spec timeout and restart rate limit apply to each
+    /// action run, not to the watch itself.
+    Watch {
+        /// File or directory to watch; directories are watched recursively.
+        path: PathBuf,
+        /// Events that trigger the action; empty means every event.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        events: Vec<WatchEvent>,
+        /// Quiet period after the last matching event before the action runs.
+        #[serde(default)]
+        debounce_ms: u64,
+        /// Task run on every trigger; cannot be another watch.
+        action: Box<TaskKind>,
     },
     /// Built-in task that does not require a runner.
     ///
@@ -73,12 +124,16 @@ impl TaskKind {
     /// - `"subprocess"`
     /// - `"wasm"`
     /// - `"container"`
+    /// - `"agent-control"`
+    /// - `"watch"`
     pub fn kind(&self) -> &'static str {
         match self {
             TaskKind::None => "none",
             TaskKind::Wasm { .. } => "wasm",
             TaskKind::Container { .. } => "container",
             TaskKind::Subprocess { .. } => "subprocess",
+            TaskKind::AgentControl { .. } => "agent-control",
+            TaskKind::Watch { .. } => "watch",
         }
     }
 }
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// Filesystem event that triggers the action of a [`crate::TaskKind::Watch`] task.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchEvent {
+    /// A file or directory was created.
+    Create,
+    /// File contents or metadata changed (including renames).
+    Modify,
+    /// A file or directory was removed.
+    Remove,
+}
+
+impl WatchEvent {
+    /// All events, in declaration order.
+    pub const ALL: [WatchEvent; 3] = [WatchEvent::Create, WatchEvent::Modify, WatchEvent::Remove];
+
+    /// Returns a short symbolic identifier (e.g. `"modify"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WatchEvent::Create => "create",
+            WatchEvent::Modify => "modify",
+            WatchEvent::Remove => "remove",
+        }
+    }
+}
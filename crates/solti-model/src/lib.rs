@@ -1,18 +1,27 @@
 mod domain;
-pub use domain::LABEL_RUNNER_TAG;
 pub use domain::{
-    Flag, KeyValue, RunnerLabels, Slot, TaskEnv, TaskId, TaskInfo, TaskPage, TaskQuery, TaskStatus,
-    TimeoutMs,
+    AGENT_LABEL_REGION, AGENT_LABEL_ROLE, AGENT_LABEL_ZONE, LABEL_FOLLOW_UP_OF, LABEL_GROUP,
+    LABEL_RUNNER_TAG, LABEL_SIGNATURE, LABEL_SIGNATURE_KEY, LABEL_SIGNER, LABEL_SPEC_HASH,
+};
+pub use domain::{
+    EventQuery, ExecutionWindow, Flag, GroupInfo, KeyValue, OutputStream, Placement, QuotaScope,
+    ReloadReport, ResourceCapacity, ResourceRequests, RunnerInfo, RunnerLabels, Slot,
+    SubscriberHealth, SubscriberState, Taint, TaintEffect, TaskEnv, TaskEvent, TaskEventKind,
+    TaskId, TaskInfo, TaskOutput, TaskPage, TaskQuery, TaskQuota, TaskStatus, TimeOfDay, TimeoutMs,
+    Toleration, Weekday,
 };
 
 mod error;
 pub use error::ModelError;
 
 mod kind;
-pub use kind::TaskKind;
+pub use kind::{AgentAction, ContainerMount, NetworkMode, TaskKind, WatchEvent};
 
 mod spec;
-pub use spec::CreateSpec;
+pub use spec::{CreateSpec, Diagnostic, FollowUp, Severity, validate};
 
 mod strategy;
-pub use strategy::{AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy};
+pub use strategy::{
+    AdmissionStrategy, BackoffStrategy, CatchUpPolicy, JitterStrategy, RestartRateLimit,
+    RestartStrategy,
+};
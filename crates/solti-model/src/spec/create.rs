@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    LABEL_RUNNER_TAG, RunnerLabels,
-    domain::{Slot, TimeoutMs},
+    LABEL_GROUP, LABEL_RUNNER_TAG, ResourceRequests, RunnerLabels,
+    domain::{ExecutionWindow, Slot, TimeoutMs},
     kind::TaskKind,
-    strategy::{AdmissionStrategy, BackoffStrategy, RestartStrategy},
+    spec::FollowUp,
+    strategy::{AdmissionStrategy, BackoffStrategy, CatchUpPolicy, RestartStrategy},
 };
 
 /// Declarative specification used when creating a new task.
@@ -15,6 +16,11 @@ use crate::{
 /// - logical grouping and concurrency control (`slot`, `admission`)
 /// - execution backend (`kind`)
 /// - lifecycle policies (`timeout_ms`, `restart`, `backoff`)
+/// - optional execution window (`window`)
+/// - optional follow-up tasks (`follow_up`)
+/// - optional resource requests (`resources`)
+/// - optional namespace (`namespace`)
+/// - optional missed-run catch-up policy (`catch_up`)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateSpec {
@@ -49,6 +55,35 @@ pub struct CreateSpec {
     /// Router uses key `runner-tag` (if present) to select a specific runner among those that support this `TaskKind`.
     #[serde(default, skip_serializing_if = "RunnerLabels::is_empty")]
     pub labels: RunnerLabels,
+    /// Optional execution window.
+    ///
+    /// When set, runs attempted outside the window are not started and are
+    /// recorded with [`crate::TaskStatus::Skipped`]; periodic tasks simply try again on the next tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub window: Option<ExecutionWindow>,
+    /// Optional tasks submitted once this task terminates.
+    ///
+    /// See [`FollowUp`]; follow-ups are submitted by the supervisor with the same caller
+    /// and go through admission like any other submission.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow_up: Option<FollowUp>,
+    /// Optional resource requests.
+    ///
+    /// Checked against the agent's allocatable capacity and task quotas on submission;
+    /// `None` requests nothing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resources: Option<ResourceRequests>,
+    /// Optional namespace of the task.
+    ///
+    /// Namespaces are the default scope for task quotas (see [`crate::QuotaScope`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Optional policy for periodic runs missed while the agent was down.
+    ///
+    /// Only applies to periodic tasks (`restart: always` with an interval) when the
+    /// supervisor tracks fire history; `None` runs once to catch up.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catch_up: Option<CatchUpPolicy>,
 }
 
 impl CreateSpec {
@@ -83,6 +118,11 @@ impl CreateSpec {
     ///     },
     ///     admission: AdmissionStrategy::DropIfRunning,
     ///     labels: RunnerLabels::new(),
+    ///     window: None,
+    ///     follow_up: None,
+    ///     resources: None,
+    ///     namespace: None,
+    ///     catch_up: None,
     /// }
     /// .with_runner_tag("runner-a");
     /// ```
@@ -98,4 +138,68 @@ impl CreateSpec {
     pub fn runner_tag(&self) -> Option<&str> {
         self.labels.get(LABEL_RUNNER_TAG)
     }
+
+    /// Put the task into a group.
+    ///
+    /// The group is stored under the [`LABEL_GROUP`] key; members of a group
+    /// can be inspected, canceled and awaited together.
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.labels.insert(LABEL_GROUP, group);
+        self
+    }
+
+    /// Return the group label (if present).
+    pub fn group(&self) -> Option<&str> {
+        self.labels.get(LABEL_GROUP)
+    }
+
+    /// Put the task into a namespace (see [`CreateSpec::namespace`]).
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = Some(namespace.into());
+        self
+    }
+
+    /// Declare the CPU request in millicores (see [`CreateSpec::resources`]).
+    pub fn with_cpu_request(mut self, millis: u64) -> Self {
+        self.resources
+            .get_or_insert_with(Default::default)
+            .cpu_millis = millis;
+        self
+    }
+
+    /// Set the missed-run catch-up policy (see [`CreateSpec::catch_up`]).
+    pub fn with_catch_up(mut self, policy: CatchUpPolicy) -> Self {
+        self.catch_up = Some(policy);
+        self
+    }
+
+    /// Declare the memory request in bytes (see [`CreateSpec::resources`]).
+    pub fn with_memory_request(mut self, bytes: u64) -> Self {
+        self.resources
+            .get_or_insert_with(Default::default)
+            .memory_bytes = bytes;
+        self
+    }
+
+    /// Declare the number of GPUs requested (see [`CreateSpec::resources`]).
+    pub fn with_gpu_request(mut self, gpus: u64) -> Self {
+        self.resources.get_or_insert_with(Default::default).gpus = gpus;
+        self
+    }
+
+    /// Submit `spec` once this task succeeds (see [`FollowUp`]).
+    pub fn with_on_success(mut self, spec: CreateSpec) -> Self {
+        self.follow_up
+            .get_or_insert_with(FollowUp::default)
+            .on_success = Some(Box::new(spec));
+        self
+    }
+
+    /// Submit `spec` once this task fails, times out or exhausts its restarts (see [`FollowUp`]).
+    pub fn with_on_failure(mut self, spec: CreateSpec) -> Self {
+        self.follow_up
+            .get_or_insert_with(FollowUp::default)
+            .on_failure = Some(Box::new(spec));
+        self
+    }
 }
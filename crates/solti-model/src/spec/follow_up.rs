@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CreateSpec, TaskStatus};
+
+/// Tasks submitted automatically once a task terminates.
+///
+/// Follow-ups are one level deep: a follow-up spec cannot declare follow-ups of its own
+/// (see [`crate::validate`]). They are meant for simple patterns such as "notify on failure";
+/// anything longer belongs in a workflow engine.
+///
+/// - `on_success` — submitted when the task ends with [`TaskStatus::Succeeded`];
+/// - `on_failure` — submitted when the task ends with [`TaskStatus::Failed`],
+///   [`TaskStatus::Timeout`] or [`TaskStatus::Exhausted`].
+///
+/// Canceled and skipped tasks trigger nothing.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowUp {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_success: Option<Box<CreateSpec>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<Box<CreateSpec>>,
+}
+
+impl FollowUp {
+    /// Follow-up submitted when the task succeeds.
+    pub fn on_success(spec: CreateSpec) -> Self {
+        Self {
+            on_success: Some(Box::new(spec)),
+            on_failure: None,
+        }
+    }
+
+    /// Follow-up submitted when the task fails.
+    pub fn on_failure(spec: CreateSpec) -> Self {
+        Self {
+            on_success: None,
+            on_failure: Some(Box::new(spec)),
+        }
+    }
+
+    /// Returns `true` if no follow-up is declared.
+    pub fn is_empty(&self) -> bool {
+        self.on_success.is_none() && self.on_failure.is_none()
+    }
+
+    /// Follow-up to submit for a task that terminated with `status`, if any.
+    pub fn for_status(&self, status: TaskStatus) -> Option<&CreateSpec> {
+        match status {
+            TaskStatus::Succeeded => self.on_success.as_deref(),
+            TaskStatus::Failed | TaskStatus::Timeout | TaskStatus::Exhausted => {
+                self.on_failure.as_deref()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AdmissionStrategy, BackoffStrategy, JitterStrategy, RestartStrategy, TaskKind};
+
+    fn spec(slot: &str) -> CreateSpec {
+        CreateSpec {
+            slot: slot.into(),
+            kind: TaskKind::None,
+            timeout_ms: 1_000,
+            restart: RestartStrategy::Never,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 0,
+                max_ms: 0,
+                factor: 1.0,
+            },
+            admission: AdmissionStrategy::Queue,
+            labels: Default::default(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    #[test]
+    fn picks_follow_up_by_terminal_status() {
+        let follow_up = spec("backup")
+            .with_on_success(spec("report"))
+            .with_on_failure(spec("notify"))
+            .follow_up
+            .unwrap();
+
+        let slot = |status| follow_up.for_status(status).map(|s| s.slot.as_str());
+        assert_eq!(slot(TaskStatus::Succeeded), Some("report"));
+        assert_eq!(slot(TaskStatus::Failed), Some("notify"));
+        assert_eq!(slot(TaskStatus::Timeout), Some("notify"));
+        assert_eq!(slot(TaskStatus::Exhausted), Some("notify"));
+        assert_eq!(slot(TaskStatus::Canceled), None);
+        assert_eq!(slot(TaskStatus::Skipped), None);
+    }
+
+    #[test]
+    fn serializes_camel_case() {
+        let value = serde_json::to_value(spec("backup").with_on_failure(spec("notify"))).unwrap();
+        assert_eq!(value["followUp"]["onFailure"]["slot"], "notify");
+        assert!(value["followUp"].get("onSuccess").is_none());
+
+        let plain = serde_json::to_value(spec("backup")).unwrap();
+        assert!(plain.get("followUp").is_none());
+    }
+}
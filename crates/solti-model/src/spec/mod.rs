@@ -1,2 +1,8 @@
 mod create;
 pub use create::CreateSpec;
+
+mod follow_up;
+pub use follow_up::FollowUp;
+
+mod validate;
+pub use validate::{Diagnostic, Severity, validate};
@@ -0,0 +1,673 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ContainerMount, CreateSpec, FollowUp, RestartStrategy, TaskKind};
+
+/// Largest accepted execution window offset from UTC (±14:00).
+const MAX_UTC_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The spec cannot be submitted.
+    Error,
+    /// The spec is accepted but probably does not do what was intended.
+    Warning,
+}
+
+/// Single finding produced by [`validate`].
+///
+/// - `code` — stable snake_case identifier (e.g. `empty_command`, `timeout_below_backoff`).
+/// - `field` — dotted path of the offending field, using the Rust / protobuf field
+///   names (e.g. `backoff.first_ms`, `kind.subprocess.command`).
+/// - `message` — human-readable explanation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub field: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Diagnostic with [`Severity::Error`].
+    pub fn error(code: &str, field: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, field, message)
+    }
+
+    /// Diagnostic with [`Severity::Warning`].
+    pub fn warning(code: &str, field: &str, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, field, message)
+    }
+
+    /// Whether the diagnostic rejects the spec.
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    fn new(severity: Severity, code: &str, field: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: code.to_string(),
+            field: field.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+/// Check a spec before it is submitted.
+///
+/// Returns every problem found, errors and warnings mixed, in field order;
+/// an empty list means the spec is fine. Transports reject specs with at least
+/// one [`Severity::Error`] diagnostic and only log warnings.
+///
+/// Backoff settings are only checked when the restart strategy can schedule another run.
+/// Follow-up specs are checked like top-level specs, with fields prefixed by their
+/// position (e.g. `follow_up.on_failure.slot`).
+///
+/// ```rust
+/// # use solti_model::{
+/// #   AdmissionStrategy, BackoffStrategy, CreateSpec, Flag, JitterStrategy,
+/// #   RestartStrategy, RunnerLabels, Severity, TaskEnv, TaskKind, validate,
+/// # };
+/// let spec = CreateSpec {
+///     slot: "demo".into(),
+///     kind: TaskKind::Subprocess {
+///         command: "ls".into(),
+///         args: vec![],
+///         env: TaskEnv::default(),
+///         cwd: None,
+///         fail_on_non_zero: Flag::enabled(),
+///     },
+///     timeout_ms: 500,
+///     restart: RestartStrategy::OnFailure,
+///     backoff: BackoffStrategy {
+///         jitter: JitterStrategy::None,
+///         first_ms: 1_000,
+///         max_ms: 10_000,
+///         factor: 2.0,
+///     },
+///     admission: AdmissionStrategy::DropIfRunning,
+///     labels: RunnerLabels::new(),
+///     window: None,
+///     follow_up: None,
+///     resources: None,
+///     namespace: None,
+///     catch_up: None,
+/// };
+///
+/// let diagnostics = validate(&spec);
+/// assert_eq!(diagnostics.len(), 1);
+/// assert_eq!(diagnostics[0].severity, Severity::Warning);
+/// assert_eq!(diagnostics[0].code, "timeout_below_backoff");
+/// ```
+pub fn validate(spec: &CreateSpec) -> Vec<Diagnostic> {
+    let mut out = Vec::new();
+
+    if spec.slot.trim().is_empty() {
+        out.push(Diagnostic::error(
+            "empty_slot",
+            "slot",
+            "slot cannot be empty",
+        ));
+    }
+
+    validate_kind(&spec.kind, &mut out);
+
+    if spec.timeout_ms == 0 {
+        out.push(Diagnostic::error(
+            "zero_timeout",
+            "timeout_ms",
+            "timeout_ms cannot be zero",
+        ));
+    }
+
+    if spec.restart != RestartStrategy::Never {
+        validate_backoff(spec, &mut out);
+    }
+
+    if let Some(window) = &spec.window
+        && !(-MAX_UTC_OFFSET_MINUTES..=MAX_UTC_OFFSET_MINUTES).contains(&window.utc_offset_minutes)
+    {
+        out.push(Diagnostic::error(
+            "utc_offset_out_of_range",
+            "window.utc_offset_minutes",
+            format!(
+                "window utc_offset_minutes out of range: {}",
+                window.utc_offset_minutes
+            ),
+        ));
+    }
+
+    if let Some(window) = &spec.window
+        && window.timezone.is_some()
+    {
+        if let Err(e) = window.utc_offset_at(0) {
+            out.push(Diagnostic::error(
+                "unknown_timezone",
+                "window.timezone",
+                e.to_string(),
+            ));
+        }
+        if window.utc_offset_minutes != 0 {
+            out.push(Diagnostic::warning(
+                "utc_offset_ignored",
+                "window.utc_offset_minutes",
+                "window utc_offset_minutes is ignored when timezone is set",
+            ));
+        }
+    }
+
+    if let Some(follow_up) = &spec.follow_up {
+        validate_follow_up(spec, follow_up, &mut out);
+    }
+
+    if spec
+        .namespace
+        .as_deref()
+        .is_some_and(|ns| ns.trim().is_empty())
+    {
+        out.push(Diagnostic::error(
+            "empty_namespace",
+            "namespace",
+            "namespace cannot be empty",
+        ));
+    }
+
+    if spec.catch_up.is_some()
+        && !matches!(
+            spec.restart,
+            RestartStrategy::Always {
+                interval_ms: Some(_)
+            }
+        )
+    {
+        out.push(Diagnostic::warning(
+            "catch_up_ignored",
+            "catch_up",
+            "catch_up only applies to periodic tasks (restart: always with an interval)",
+        ));
+    }
+
+    if spec.resources.is_some_and(|r| r.is_empty()) {
+        out.push(Diagnostic::error(
+            "zero_resources",
+            "resources",
+            "resource requests are all zero; omit resources to request nothing",
+        ));
+    }
+
+    out
+}
+
+fn validate_follow_up(spec: &CreateSpec, follow_up: &FollowUp, out: &mut Vec<Diagnostic>) {
+    if matches!(spec.restart, RestartStrategy::Always { .. }) && !follow_up.is_empty() {
+        out.push(Diagnostic::warning(
+            "follow_up_never_triggers",
+            "follow_up",
+            "tasks restarted always only end when canceled, so follow-ups never run",
+        ));
+    }
+
+    let specs = [
+        ("on_success", &follow_up.on_success),
+        ("on_failure", &follow_up.on_failure),
+    ];
+    for (name, child) in specs {
+        let Some(child) = child else { continue };
+        let prefix = format!("follow_up.{name}");
+        if child.follow_up.is_some() {
+            out.push(Diagnostic::error(
+                "nested_follow_up",
+                &format!("{prefix}.follow_up"),
+                "follow-up tasks cannot declare follow-ups of their own",
+            ));
+        }
+        out.extend(
+            validate(&CreateSpec {
+                follow_up: None,
+                ..(**child).clone()
+            })
+            .into_iter()
+            .map(|d| Diagnostic {
+                field: format!("{prefix}.{}", d.field),
+                ..d
+            }),
+        );
+    }
+}
+
+fn validate_kind(kind: &TaskKind, out: &mut Vec<Diagnostic>) {
+    match kind {
+        TaskKind::Subprocess { command, .. } if command.trim().is_empty() => {
+            out.push(Diagnostic::error(
+                "empty_command",
+                "kind.subprocess.command",
+                "subprocess command is empty",
+            ));
+        }
+        TaskKind::Wasm { module, .. } if module.to_string_lossy().trim().is_empty() => {
+            out.push(Diagnostic::error(
+                "empty_module",
+                "kind.wasm.module",
+                "wasm module path is empty",
+            ));
+        }
+        TaskKind::Container {
+            image,
+            mounts,
+            memory_bytes,
+            cpu_millis,
+            user,
+            ..
+        } => {
+            if image.trim().is_empty() {
+                out.push(Diagnostic::error(
+                    "empty_image",
+                    "kind.container.image",
+                    "container image is empty",
+                ));
+            }
+            validate_mounts(mounts, out);
+            if *memory_bytes == Some(0) {
+                out.push(Diagnostic::error(
+                    "zero_limit",
+                    "kind.container.memory_bytes",
+                    "container memory limit cannot be zero",
+                ));
+            }
+            if *cpu_millis == Some(0) {
+                out.push(Diagnostic::error(
+                    "zero_limit",
+                    "kind.container.cpu_millis",
+                    "container cpu limit cannot be zero",
+                ));
+            }
+            if user.as_deref().is_some_and(|u| u.trim().is_empty()) {
+                out.push(Diagnostic::error(
+                    "empty_user",
+                    "kind.container.user",
+                    "container user is empty",
+                ));
+            }
+        }
+        TaskKind::Watch { path, action, .. } => {
+            if path.to_string_lossy().trim().is_empty() {
+                out.push(Diagnostic::error(
+                    "empty_watch_path",
+                    "kind.watch.path",
+                    "watch path is empty",
+                ));
+            }
+            if let TaskKind::Watch { .. } = **action {
+                out.push(Diagnostic::error(
+                    "nested_watch",
+                    "kind.watch.action",
+                    "watch action cannot be another watch",
+                ));
+            } else {
+                let mut nested = Vec::new();
+                validate_kind(action, &mut nested);
+                out.extend(nested.into_iter().map(|d| Diagnostic {
+                    field: d.field.replacen("kind", "kind.watch.action", 1),
+                    ..d
+                }));
+            }
+        }
+        TaskKind::None => {
+            out.push(Diagnostic::error(
+                "unsupported_kind",
+                "kind",
+                "task kind `none` cannot be submitted",
+            ));
+        }
+        _ => {}
+    }
+}
+
+fn validate_mounts(mounts: &[ContainerMount], out: &mut Vec<Diagnostic>) {
+    for (i, mount) in mounts.iter().enumerate() {
+        if mount.source.trim().is_empty() {
+            out.push(Diagnostic::error(
+                "empty_mount_source",
+                &format!("kind.container.mounts[{i}].source"),
+                "mount source is empty",
+            ));
+        }
+        let field = format!("kind.container.mounts[{i}].target");
+        if !mount.target.starts_with('/') {
+            out.push(Diagnostic::error(
+                "relative_mount_target",
+                &field,
+                format!("mount target must be an absolute path: {:?}", mount.target),
+            ));
+        } else if mounts[..i].iter().any(|m| m.target == mount.target) {
+            out.push(Diagnostic::error(
+                "duplicate_mount_target",
+                &field,
+                format!("mount target is used twice: {}", mount.target),
+            ));
+        }
+    }
+}
+
+fn validate_backoff(spec: &CreateSpec, out: &mut Vec<Diagnostic>) {
+    let backoff = &spec.backoff;
+
+    if backoff.first_ms == 0 {
+        out.push(Diagnostic::error(
+            "zero_backoff",
+            "backoff.first_ms",
+            "backoff first_ms cannot be zero",
+        ));
+    }
+    if backoff.max_ms == 0 {
+        out.push(Diagnostic::error(
+            "zero_backoff",
+            "backoff.max_ms",
+            "backoff max_ms cannot be zero",
+        ));
+    }
+    if !(backoff.factor.is_finite() && backoff.factor > 0.0) {
+        out.push(Diagnostic::error(
+            "invalid_backoff_factor",
+            "backoff.factor",
+            "backoff factor must be positive",
+        ));
+    }
+
+    if backoff.max_ms > 0 && backoff.first_ms > backoff.max_ms {
+        out.push(Diagnostic::warning(
+            "backoff_first_above_max",
+            "backoff.first_ms",
+            format!(
+                "backoff first_ms ({}) exceeds max_ms ({}); delays are capped at max_ms",
+                backoff.first_ms, backoff.max_ms
+            ),
+        ));
+    }
+    if spec.timeout_ms > 0 && spec.timeout_ms < backoff.first_ms {
+        out.push(Diagnostic::warning(
+            "timeout_below_backoff",
+            "timeout_ms",
+            format!(
+                "timeout_ms ({}) is shorter than backoff first_ms ({})",
+                spec.timeout_ms, backoff.first_ms
+            ),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AdmissionStrategy, BackoffStrategy, CatchUpPolicy, ExecutionWindow, Flag, JitterStrategy,
+        RunnerLabels, TaskEnv,
+    };
+
+    fn spec() -> CreateSpec {
+        CreateSpec {
+            slot: "demo".into(),
+            kind: TaskKind::Subprocess {
+                command: "ls".into(),
+                args: vec![],
+                env: TaskEnv::default(),
+                cwd: None,
+                fail_on_non_zero: Flag::enabled(),
+            },
+            timeout_ms: 5_000,
+            restart: RestartStrategy::OnFailure,
+            backoff: BackoffStrategy {
+                jitter: JitterStrategy::Full,
+                first_ms: 1_000,
+                max_ms: 5_000,
+                factor: 2.0,
+            },
+            admission: AdmissionStrategy::DropIfRunning,
+            labels: RunnerLabels::new(),
+            window: None,
+            follow_up: None,
+            resources: None,
+            namespace: None,
+            catch_up: None,
+        }
+    }
+
+    fn codes(spec: &CreateSpec) -> Vec<String> {
+        validate(spec).into_iter().map(|d| d.code).collect()
+    }
+
+    #[test]
+    fn valid_spec_has_no_diagnostics() {
+        assert!(validate(&spec()).is_empty());
+    }
+
+    #[test]
+    fn reports_every_error() {
+        let mut s = spec();
+        s.slot = "  ".into();
+        s.timeout_ms = 0;
+        s.backoff.factor = f64::NAN;
+
+        let diagnostics = validate(&s);
+        assert!(diagnostics.iter().all(Diagnostic::is_error));
+        let fields: Vec<_> = diagnostics.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["slot", "timeout_ms", "backoff.factor"]);
+    }
+
+    #[test]
+    fn rejects_empty_runner_targets() {
+        let mut s = spec();
+        s.kind = TaskKind::Wasm {
+            module: "".into(),
+            args: vec![],
+            env: TaskEnv::default(),
+        };
+        assert_eq!(codes(&s), ["empty_module"]);
+
+        s.kind = TaskKind::Container {
+            image: " ".into(),
+            command: None,
+            args: vec![],
+            env: TaskEnv::default(),
+            mounts: vec![],
+            memory_bytes: None,
+            cpu_millis: None,
+            network: None,
+            user: None,
+        };
+        assert_eq!(codes(&s), ["empty_image"]);
+
+        s.kind = TaskKind::None;
+        assert_eq!(codes(&s), ["unsupported_kind"]);
+    }
+
+    #[test]
+    fn checks_watch_path_and_action() {
+        let watch = |path: &str, action: TaskKind| TaskKind::Watch {
+            path: path.into(),
+            events: vec![],
+            debounce_ms: 500,
+            action: Box::new(action),
+        };
+        let mut s = spec();
+        s.kind = watch("/etc/app", spec().kind);
+        assert!(validate(&s).is_empty());
+
+        s.kind = watch(" ", TaskKind::None);
+        let fields: Vec<_> = validate(&s).into_iter().map(|d| d.field).collect();
+        assert_eq!(fields, ["kind.watch.path", "kind.watch.action"]);
+
+        s.kind = watch("/etc/app", watch("/etc/other", spec().kind));
+        assert_eq!(codes(&s), ["nested_watch"]);
+    }
+
+    #[test]
+    fn checks_container_mounts_and_limits() {
+        let mount = |source: &str, target: &str| ContainerMount {
+            source: source.into(),
+            target: target.into(),
+            read_only: false,
+        };
+        let mut s = spec();
+        s.kind = TaskKind::Container {
+            image: "postgres:16".into(),
+            command: None,
+            args: vec![],
+            env: TaskEnv::default(),
+            mounts: vec![
+                mount("/srv/pg", "/var/lib/postgresql/data"),
+                mount("pg-conf", "etc/postgresql"),
+                mount("", "/var/lib/postgresql/data"),
+            ],
+            memory_bytes: Some(0),
+            cpu_millis: Some(500),
+            network: Some(crate::NetworkMode::None),
+            user: Some("".into()),
+        };
+
+        let fields: Vec<_> = validate(&s).into_iter().map(|d| d.field).collect();
+        assert_eq!(
+            fields,
+            [
+                "kind.container.mounts[1].target",
+                "kind.container.mounts[2].source",
+                "kind.container.mounts[2].target",
+                "kind.container.memory_bytes",
+                "kind.container.user",
+            ]
+        );
+    }
+
+    #[test]
+    fn backoff_is_ignored_without_restarts() {
+        let mut s = spec();
+        s.restart = RestartStrategy::Never;
+        s.backoff.first_ms = 0;
+        s.backoff.max_ms = 0;
+        s.backoff.factor = 0.0;
+        assert!(validate(&s).is_empty());
+
+        s.restart = RestartStrategy::Always { interval_ms: None };
+        assert_eq!(
+            codes(&s),
+            ["zero_backoff", "zero_backoff", "invalid_backoff_factor"]
+        );
+    }
+
+    #[test]
+    fn warns_about_suspicious_timings() {
+        let mut s = spec();
+        s.timeout_ms = 500;
+        s.backoff.first_ms = 10_000;
+
+        let diagnostics = validate(&s);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        assert_eq!(
+            codes(&s),
+            ["backoff_first_above_max", "timeout_below_backoff"]
+        );
+    }
+
+    #[test]
+    fn rejects_out_of_range_window_offset() {
+        let mut s = spec();
+        s.window = Some(ExecutionWindow {
+            days: vec![],
+            start: "09:00".parse().unwrap(),
+            end: "17:00".parse().unwrap(),
+            utc_offset_minutes: 15 * 60,
+            timezone: None,
+        });
+        assert_eq!(codes(&s), ["utc_offset_out_of_range"]);
+    }
+
+    #[test]
+    fn checks_window_timezone() {
+        let window = |timezone: &str, utc_offset_minutes| ExecutionWindow {
+            days: vec![],
+            start: "09:00".parse().unwrap(),
+            end: "17:00".parse().unwrap(),
+            utc_offset_minutes,
+            timezone: Some(timezone.into()),
+        };
+        let mut s = spec();
+        s.window = Some(window("Asia/Tokyo", 0));
+        assert!(validate(&s).is_empty());
+
+        s.window = Some(window("Asia/Tokio", 0));
+        assert_eq!(codes(&s), ["unknown_timezone"]);
+        s.window = Some(window("Asia/Tokyo", 540));
+        assert_eq!(codes(&s), ["utc_offset_ignored"]);
+    }
+
+    #[test]
+    fn rejects_empty_namespace() {
+        let mut s = spec().with_namespace("team-a");
+        assert!(validate(&s).is_empty());
+
+        s.namespace = Some(" ".into());
+        assert_eq!(codes(&s), ["empty_namespace"]);
+
+        let s = spec().with_on_failure(spec().with_namespace(""));
+        assert_eq!(validate(&s)[0].field, "follow_up.on_failure.namespace");
+    }
+
+    #[test]
+    fn rejects_zero_resource_requests() {
+        let mut s = spec().with_gpu_request(1);
+        assert!(validate(&s).is_empty());
+
+        s.resources = Some(Default::default());
+        assert_eq!(codes(&s), ["zero_resources"]);
+
+        let mut report = spec();
+        report.resources = Some(Default::default());
+        let s = spec().with_on_success(report);
+        assert_eq!(validate(&s)[0].field, "follow_up.on_success.resources");
+    }
+
+    #[test]
+    fn warns_about_catch_up_of_non_periodic_tasks() {
+        let mut s = spec().with_catch_up(CatchUpPolicy::Skip);
+        assert_eq!(codes(&s), ["catch_up_ignored"]);
+        assert_eq!(validate(&s)[0].severity, Severity::Warning);
+
+        s.restart = RestartStrategy::periodic(60_000);
+        assert!(validate(&s).is_empty());
+    }
+
+    #[test]
+    fn checks_follow_ups_one_level_deep() {
+        let mut notify = spec();
+        notify.slot = "".into();
+        let mut s = spec().with_on_failure(notify.clone());
+        assert_eq!(validate(&s)[0].field, "follow_up.on_failure.slot");
+
+        notify.slot = "notify".into();
+        s = spec().with_on_success(notify.clone().with_on_failure(spec()));
+        assert_eq!(codes(&s), ["nested_follow_up"]);
+        assert_eq!(validate(&s)[0].field, "follow_up.on_success.follow_up");
+
+        s = spec().with_on_failure(notify);
+        assert!(validate(&s).is_empty());
+        s.restart = RestartStrategy::periodic(60_000);
+        assert_eq!(codes(&s), ["follow_up_never_triggers"]);
+    }
+
+    #[test]
+    fn diagnostic_serializes_for_clients() {
+        let d = Diagnostic::warning("timeout_below_backoff", "timeout_ms", "too short");
+        assert_eq!(
+            serde_json::to_value(&d).unwrap(),
+            serde_json::json!({
+                "severity": "warning",
+                "code": "timeout_below_backoff",
+                "field": "timeout_ms",
+                "message": "too short",
+            })
+        );
+    }
+}
@@ -28,6 +28,17 @@ pub enum AdmissionStrategy {
     Queue,
 }
 
+impl AdmissionStrategy {
+    /// Strategy name as accepted by [`FromStr`] (e.g. `"drop-if-running"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdmissionStrategy::DropIfRunning => "drop-if-running",
+            AdmissionStrategy::Replace => "replace",
+            AdmissionStrategy::Queue => "queue",
+        }
+    }
+}
+
 impl FromStr for AdmissionStrategy {
     type Err = ModelError;
     fn from_str(s: &str) -> ModelResult<Self> {
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+use crate::error::{ModelError, ModelResult};
+
+/// Determines what happens to periodic runs missed while the agent was down.
+///
+/// Missed runs are detected by comparing the last recorded fire time of the task slot
+/// with the restart interval when the task is submitted again.
+///
+/// Policies:
+/// - `Skip`: Drop missed runs; the first run happens on the next regular tick.
+/// - `RunOnce`: Run once immediately on startup, regardless of how many runs were missed.
+/// - `RunAll`: Run every missed occurrence back-to-back on startup.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CatchUpPolicy {
+    /// Skip missed runs.
+    Skip,
+    /// Run once on startup.
+    #[default]
+    RunOnce,
+    /// Run all missed occurrences on startup.
+    RunAll,
+}
+
+impl CatchUpPolicy {
+    /// Policy name as accepted by [`FromStr`] (`"skip"`, `"run-once"`, `"run-all"`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CatchUpPolicy::Skip => "skip",
+            CatchUpPolicy::RunOnce => "run-once",
+            CatchUpPolicy::RunAll => "run-all",
+        }
+    }
+}
+
+impl fmt::Display for CatchUpPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CatchUpPolicy {
+    type Err = ModelError;
+
+    fn from_str(s: &str) -> ModelResult<Self> {
+        match s.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "skip" => Ok(CatchUpPolicy::Skip),
+            "run-once" | "runonce" => Ok(CatchUpPolicy::RunOnce),
+            "run-all" | "runall" => Ok(CatchUpPolicy::RunAll),
+            other => Err(ModelError::Invalid(format!(
+                "unknown catch-up policy: {other}"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_roundtrip() {
+        for policy in [
+            CatchUpPolicy::Skip,
+            CatchUpPolicy::RunOnce,
+            CatchUpPolicy::RunAll,
+        ] {
+            assert_eq!(policy.as_str().parse::<CatchUpPolicy>().unwrap(), policy);
+        }
+        assert_eq!(
+            "RUN_ALL".parse::<CatchUpPolicy>().unwrap(),
+            CatchUpPolicy::RunAll
+        );
+        assert!("later".parse::<CatchUpPolicy>().is_err());
+    }
+}
@@ -9,3 +9,9 @@ pub use jitter::JitterStrategy;
 
 mod restart;
 pub use restart::RestartStrategy;
+
+mod rate_limit;
+pub use rate_limit::RestartRateLimit;
+
+mod catch_up;
+pub use catch_up::CatchUpPolicy;
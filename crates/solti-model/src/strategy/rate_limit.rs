@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+/// Upper bound on how often tasks of one slot may start.
+///
+/// Applied on top of restart/backoff: whatever the restart policy says,
+/// no more than `max_runs` executions start within any `window_ms` window.
+/// Excess executions wait until the window frees up.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestartRateLimit {
+    /// Max executions started within one window.
+    pub max_runs: u32,
+    /// Sliding window length in milliseconds.
+    pub window_ms: u64,
+}
+
+impl RestartRateLimit {
+    /// Limit executions to `max_runs` per minute.
+    pub const fn per_minute(max_runs: u32) -> Self {
+        Self {
+            max_runs,
+            window_ms: 60_000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_minute_uses_one_minute_window() {
+        let limit = RestartRateLimit::per_minute(10);
+        assert_eq!(limit.max_runs, 10);
+        assert_eq!(limit.window_ms, 60_000);
+    }
+
+    #[test]
+    fn serde_uses_camel_case() {
+        let json = serde_json::to_string(&RestartRateLimit::per_minute(3)).unwrap();
+        assert_eq!(json, r#"{"maxRuns":3,"windowMs":60000}"#);
+    }
+}
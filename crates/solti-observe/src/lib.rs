@@ -4,6 +4,9 @@ pub use logger::*;
 #[cfg(feature = "timezone-sync")]
 pub use logger::timezone_sync;
 
+#[cfg(feature = "level-signal")]
+pub use logger::{LEVEL_SIGNAL_SLOT, level_signal};
+
 mod subscriber;
 
 #[cfg(feature = "subscriber")]
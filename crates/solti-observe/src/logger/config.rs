@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::io::IsTerminal;
 
-use crate::logger::object::{LoggerFormat, LoggerLevel, LoggerTimeZone};
+use crate::logger::{
+    object::{LoggerFormat, LoggerLevel, LoggerTimeZone},
+    task_log::TaskLogConfig,
+};
 
 /// Logger configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +20,8 @@ pub struct LoggerConfig {
     pub with_targets: bool,
     /// Whether to use colored output.
     pub use_color: bool,
+    /// Per-task log files; disabled when `None`.
+    pub task_logs: Option<TaskLogConfig>,
 }
 
 impl Default for LoggerConfig {
@@ -27,6 +32,7 @@ impl Default for LoggerConfig {
             tz: LoggerTimeZone::default(),
             with_targets: true,
             use_color: true,
+            task_logs: None,
         }
     }
 }
@@ -65,8 +71,9 @@ mod tests {
         assert_eq!(config.format, LoggerFormat::Text);
         assert_eq!(config.tz, LoggerTimeZone::Utc);
         assert_eq!(config.level.as_str(), "info");
-        assert_eq!(config.with_targets, true);
-        assert_eq!(config.use_color, true);
+        assert!(config.with_targets);
+        assert!(config.use_color);
+        assert!(config.task_logs.is_none());
     }
 
     #[test]
@@ -77,6 +84,7 @@ mod tests {
             level: "debug".parse().unwrap(),
             with_targets: false,
             use_color: false,
+            task_logs: Some(TaskLogConfig::default()),
         };
 
         let json = serde_json::to_string(&config).unwrap();
@@ -87,6 +95,7 @@ mod tests {
         assert_eq!(config.use_color, parsed.use_color);
         assert_eq!(config.format, parsed.format);
         assert_eq!(config.tz, parsed.tz);
+        assert_eq!(config.task_logs, parsed.task_logs);
     }
 
     #[test]
@@ -97,8 +106,8 @@ mod tests {
         assert_eq!(config.level.as_str(), LoggerLevel::default().as_str());
         assert_eq!(config.format, LoggerFormat::default());
         assert_eq!(config.tz, LoggerTimeZone::default());
-        assert_eq!(config.with_targets, true);
-        assert_eq!(config.use_color, true);
+        assert!(config.with_targets);
+        assert!(config.use_color);
     }
 
     #[test]
@@ -108,7 +117,7 @@ mod tests {
 
         assert_eq!(config.format, LoggerFormat::Json);
         assert_eq!(config.level.as_str(), "debug");
-        assert_eq!(config.with_targets, true);
-        assert_eq!(config.use_color, true);
+        assert!(config.with_targets);
+        assert!(config.use_color);
     }
 }
@@ -2,7 +2,7 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum LoggerError {
-    #[error("Invalid log format: {0} (expected: text|json|journald)")]
+    #[error("Invalid log format: {0} (expected: text|json|logfmt|journald|eventlog)")]
     InvalidFormat(String),
 
     #[error("Journald is not supported on this platform")]
@@ -11,6 +11,12 @@ pub enum LoggerError {
     #[error("Failed to initialize journald: {0}")]
     JournaldInitFailed(String),
 
+    #[error("Windows Event Log is not supported on this platform")]
+    EventLogNotSupported,
+
+    #[error("Failed to initialize Windows Event Log: {0}")]
+    EventLogInitFailed(String),
+
     #[error("Logger already initialized")]
     AlreadyInitialized,
 
@@ -20,6 +26,12 @@ pub enum LoggerError {
     #[error("Failed to initialize local timezone")]
     LocalTimezoneInitFailed,
 
+    #[error("Logger is not initialized")]
+    NotInitialized,
+
+    #[error("Failed to reload logger: {0}")]
+    ReloadFailed(String),
+
     #[error("Invalid log level: {0}")]
     InvalidLevel(String),
 }
@@ -0,0 +1,168 @@
+//! Windows Event Log output.
+//!
+//! Events are reported to the `Application` log under an event source named
+//! after the agent executable. Tracing levels map to event types:
+//!
+//! - `ERROR` → Error
+//! - `WARN` → Warning
+//! - `INFO`, `DEBUG`, `TRACE` → Information
+//!
+//! The report contains the message followed by the event fields as `key=value` pairs.
+//! Event Viewer renders it as-is; pre-registering the source (for example with
+//! `New-EventLog -LogName Application -Source <name>`) removes the
+//! "description cannot be found" preamble.
+
+use std::{
+    ffi::OsStr,
+    fmt::{self, Write as _},
+    os::windows::ffi::OsStrExt,
+    ptr,
+};
+
+use tracing::{Event, Level, Subscriber, field::Field};
+use tracing_subscriber::{field::Visit, layer::Context};
+use windows_sys::Win32::{
+    Foundation::HANDLE,
+    System::EventLog::{
+        DeregisterEventSource, EVENTLOG_ERROR_TYPE, EVENTLOG_INFORMATION_TYPE,
+        EVENTLOG_WARNING_TYPE, REPORT_EVENT_TYPE, RegisterEventSourceW, ReportEventW,
+    },
+};
+
+/// Source name used when the executable name cannot be determined.
+const DEFAULT_SOURCE: &str = "solti";
+
+/// Layer reporting tracing events to the Windows Event Log.
+pub(crate) struct EventLogLayer {
+    handle: HANDLE,
+}
+
+// SAFETY: event source handles may be used concurrently from any thread.
+unsafe impl Send for EventLogLayer {}
+unsafe impl Sync for EventLogLayer {}
+
+impl EventLogLayer {
+    /// Register an event source named after the current executable.
+    pub(crate) fn new() -> std::io::Result<Self> {
+        let source = std::env::current_exe()
+            .ok()
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| DEFAULT_SOURCE.to_string());
+        Self::with_source(&source)
+    }
+
+    /// Register an event source with an explicit name.
+    pub(crate) fn with_source(source: &str) -> std::io::Result<Self> {
+        let name = to_wide(source);
+        // SAFETY: `name` is a valid NUL-terminated UTF-16 string; a null server means the local machine.
+        let handle = unsafe { RegisterEventSourceW(ptr::null(), name.as_ptr()) };
+        if handle.is_null() {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(Self { handle })
+    }
+
+    fn report(&self, level: &Level, text: &str) {
+        let wide = to_wide(text);
+        let strings = [wide.as_ptr()];
+        // SAFETY: the handle is valid until drop and `strings` holds one NUL-terminated string.
+        unsafe {
+            ReportEventW(
+                self.handle,
+                event_type(level),
+                0,
+                0,
+                ptr::null_mut(),
+                1,
+                0,
+                strings.as_ptr(),
+                ptr::null(),
+            );
+        }
+    }
+}
+
+impl Drop for EventLogLayer {
+    fn drop(&mut self) {
+        // SAFETY: the handle was returned by `RegisterEventSourceW` and is released once.
+        unsafe {
+            DeregisterEventSource(self.handle);
+        }
+    }
+}
+
+impl<S: Subscriber> tracing_subscriber::Layer<S> for EventLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = EventLogVisitor::default();
+        event.record(&mut visitor);
+
+        let meta = event.metadata();
+        let mut text = visitor.message;
+        text.push_str("\r\n\r\ntarget=");
+        text.push_str(meta.target());
+        text.push_str(&visitor.fields);
+        self.report(meta.level(), &text);
+    }
+}
+
+/// Map a tracing level to an Event Log event type.
+fn event_type(level: &Level) -> REPORT_EVENT_TYPE {
+    match *level {
+        Level::ERROR => EVENTLOG_ERROR_TYPE,
+        Level::WARN => EVENTLOG_WARNING_TYPE,
+        _ => EVENTLOG_INFORMATION_TYPE,
+    }
+}
+
+/// Encode as a NUL-terminated UTF-16 string, dropping interior NULs.
+fn to_wide(s: &str) -> Vec<u16> {
+    OsStr::new(s)
+        .encode_wide()
+        .filter(|c| *c != 0)
+        .chain(std::iter::once(0))
+        .collect()
+}
+
+/// Collects the message and renders other fields as `\r\nkey=value`.
+#[derive(Default)]
+struct EventLogVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for EventLogVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, "\r\n{}={}", field.name(), value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            let _ = write!(self.fields, "\r\n{}={:?}", field.name(), value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_levels_to_event_types() {
+        assert_eq!(event_type(&Level::ERROR), EVENTLOG_ERROR_TYPE);
+        assert_eq!(event_type(&Level::WARN), EVENTLOG_WARNING_TYPE);
+        assert_eq!(event_type(&Level::INFO), EVENTLOG_INFORMATION_TYPE);
+        assert_eq!(event_type(&Level::TRACE), EVENTLOG_INFORMATION_TYPE);
+    }
+
+    #[test]
+    fn wide_strings_are_nul_terminated() {
+        assert_eq!(to_wide("ab"), vec![b'a' as u16, b'b' as u16, 0]);
+        assert_eq!(to_wide("a\0b"), vec![b'a' as u16, b'b' as u16, 0]);
+    }
+}
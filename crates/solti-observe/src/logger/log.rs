@@ -1,46 +1,143 @@
+use std::sync::{OnceLock, RwLock};
+
 use tracing::Subscriber;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{
+    EnvFilter, Registry, fmt, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
 
 use crate::logger::{
     config::LoggerConfig,
     error::{LoggerError, LoggerResult},
-    object::LoggerRfc3339,
+    object::{LoggerLevel, LoggerLogfmt, LoggerRfc3339},
+    task_log::TaskLogLayer,
 };
 
+type FilterLayer = reload::Layer<EnvFilter, Registry>;
+type FilterHandle = reload::Handle<EnvFilter, Registry>;
+
+/// Handle to the level filter of the installed global subscriber.
+static FILTER_HANDLE: OnceLock<FilterHandle> = OnceLock::new();
+
+/// Level filter currently applied by the installed global subscriber.
+static CURRENT_LEVEL: RwLock<Option<LoggerLevel>> = RwLock::new(None);
+
+/// Wraps the level filter into a reloadable layer.
+fn reloadable_filter(cfg: &LoggerConfig) -> (FilterLayer, FilterHandle) {
+    reload::Layer::new(cfg.level.to_env_filter())
+}
+
+/// Builds the per-task file layer if it is enabled.
+fn task_log_layer(cfg: &LoggerConfig) -> Option<TaskLogLayer> {
+    cfg.task_logs.clone().map(TaskLogLayer::new)
+}
+
+/// Replaces the level filter of the running logger.
+pub fn reload_level(level: &LoggerLevel) -> LoggerResult<()> {
+    let handle = FILTER_HANDLE.get().ok_or(LoggerError::NotInitialized)?;
+    handle
+        .reload(level.to_env_filter())
+        .map_err(|e| LoggerError::ReloadFailed(e.to_string()))?;
+    set_current_level(level);
+    Ok(())
+}
+
+/// Returns the level filter of the running logger.
+pub fn current_level() -> Option<LoggerLevel> {
+    CURRENT_LEVEL.read().ok().and_then(|level| level.clone())
+}
+
+fn set_current_level(level: &LoggerLevel) {
+    if let Ok(mut current) = CURRENT_LEVEL.write() {
+        *current = Some(level.clone());
+    }
+}
+
 /// Initializes text logger.
 pub fn logger_text(cfg: &LoggerConfig) -> LoggerResult<()> {
-    let filter = cfg.level.to_env_filter();
+    let (filter, handle) = reloadable_filter(cfg);
     let fmt_layer = fmt::layer()
         .with_ansi(cfg.should_use_color())
         .with_target(cfg.with_targets)
         .with_timer(LoggerRfc3339);
 
-    let subscriber = tracing_subscriber::registry().with(filter).with(fmt_layer);
-    init_subscriber(subscriber)
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(task_log_layer(cfg));
+    init_subscriber(subscriber, handle, &cfg.level)
 }
 
 /// Initializes JSON (structured) logger.
 pub fn logger_json(cfg: &LoggerConfig) -> LoggerResult<()> {
-    let filter = cfg.level.to_env_filter();
+    let (filter, handle) = reloadable_filter(cfg);
     let fmt_layer = fmt::layer()
         .json()
         .with_ansi(false)
         .with_target(cfg.with_targets)
         .with_timer(LoggerRfc3339);
 
-    let subscriber = tracing_subscriber::registry().with(filter).with(fmt_layer);
-    init_subscriber(subscriber)
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(task_log_layer(cfg));
+    init_subscriber(subscriber, handle, &cfg.level)
+}
+
+/// Initializes logfmt (`key=value`) logger.
+pub fn logger_logfmt(cfg: &LoggerConfig) -> LoggerResult<()> {
+    let (filter, handle) = reloadable_filter(cfg);
+    let fmt_layer = fmt::layer()
+        .event_format(LoggerLogfmt::new(cfg.with_targets))
+        .with_ansi(false);
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(fmt_layer)
+        .with(task_log_layer(cfg));
+    init_subscriber(subscriber, handle, &cfg.level)
+}
+
+/// Prefix of user-defined journald fields (`task_id` becomes `SOLTI_TASK_ID`).
+#[cfg(target_os = "linux")]
+const JOURNALD_FIELD_PREFIX: &str = "SOLTI";
+
+/// Syslog-style `PRIORITY` values: `INFO` is 6 and `DEBUG`/`TRACE` are 7,
+/// so `journalctl -p info` shows exactly the `info` level and above.
+#[cfg(target_os = "linux")]
+fn journald_priorities() -> tracing_journald::PriorityMappings {
+    use tracing_journald::Priority;
+
+    tracing_journald::PriorityMappings {
+        error: Priority::Error,
+        warn: Priority::Warning,
+        info: Priority::Informational,
+        debug: Priority::Debug,
+        trace: Priority::Debug,
+    }
 }
 
 /// Initializes journald logger (Linux only).
+///
+/// Event and span fields are sent as structured journal fields prefixed with
+/// `SOLTI_`, so subprocess output can be filtered per task:
+///
+/// ```text
+/// journalctl SOLTI_TASK_ID=default-backup-1f
+/// journalctl SOLTI_SLOT=backup SOLTI_ATTEMPT=2
+/// ```
 #[cfg(target_os = "linux")]
 pub fn logger_journald(cfg: &LoggerConfig) -> LoggerResult<()> {
-    let filter = cfg.level.to_env_filter();
-    let journald =
-        tracing_journald::layer().map_err(|e| LoggerError::JournaldInitFailed(e.to_string()))?;
+    let (filter, handle) = reloadable_filter(cfg);
+    let journald = tracing_journald::layer()
+        .map_err(|e| LoggerError::JournaldInitFailed(e.to_string()))?
+        .with_field_prefix(Some(JOURNALD_FIELD_PREFIX.to_string()))
+        .with_priority_mappings(journald_priorities());
 
-    let subscriber = tracing_subscriber::registry().with(filter).with(journald);
-    init_subscriber(subscriber)
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(journald)
+        .with(task_log_layer(cfg));
+    init_subscriber(subscriber, handle, &cfg.level)
 }
 
 /// Stub for journald on non-Linux platforms.
@@ -49,14 +146,37 @@ pub fn logger_journald(_cfg: &LoggerConfig) -> LoggerResult<()> {
     Err(LoggerError::JournaldNotSupported)
 }
 
-/// Installs the subscriber as the global default.
-fn init_subscriber<S>(subscriber: S) -> LoggerResult<()>
+/// Initializes Windows Event Log logger (Windows only).
+#[cfg(windows)]
+pub fn logger_eventlog(cfg: &LoggerConfig) -> LoggerResult<()> {
+    let (filter, handle) = reloadable_filter(cfg);
+    let eventlog = crate::logger::eventlog::EventLogLayer::new()
+        .map_err(|e| LoggerError::EventLogInitFailed(e.to_string()))?;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(filter)
+        .with(eventlog)
+        .with(task_log_layer(cfg));
+    init_subscriber(subscriber, handle, &cfg.level)
+}
+
+/// Stub for Windows Event Log on other platforms.
+#[cfg(not(windows))]
+pub fn logger_eventlog(_cfg: &LoggerConfig) -> LoggerResult<()> {
+    Err(LoggerError::EventLogNotSupported)
+}
+
+/// Installs the subscriber as the global default and keeps its filter handle for reloads.
+fn init_subscriber<S>(subscriber: S, handle: FilterHandle, level: &LoggerLevel) -> LoggerResult<()>
 where
     S: Subscriber + Send + Sync + 'static,
 {
     subscriber
         .try_init()
-        .map_err(|_| LoggerError::AlreadyInitialized)
+        .map_err(|_| LoggerError::AlreadyInitialized)?;
+    let _ = FILTER_HANDLE.set(handle);
+    set_current_level(level);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -73,6 +193,7 @@ mod tests {
             level: "info".parse().unwrap(),
             with_targets: true,
             use_color: false,
+            task_logs: None,
         };
 
         assert_eq!(config.format, LoggerFormat::Text);
@@ -87,6 +208,7 @@ mod tests {
             level: "debug".parse().unwrap(),
             with_targets: false,
             use_color: true,
+            task_logs: None,
         };
 
         assert_eq!(config.format, LoggerFormat::Json);
@@ -105,6 +227,29 @@ mod tests {
         assert!(matches!(result, Err(LoggerError::JournaldNotSupported)));
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn journald_uses_syslog_priorities() {
+        let p = journald_priorities();
+        assert_eq!(p.error as u8, b'3');
+        assert_eq!(p.warn as u8, b'4');
+        assert_eq!(p.info as u8, b'6');
+        assert_eq!(p.debug as u8, b'7');
+        assert_eq!(p.trace as u8, b'7');
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn init_eventlog_returns_error_when_not_supported() {
+        let config = LoggerConfig {
+            format: LoggerFormat::EventLog,
+            ..Default::default()
+        };
+
+        let result = logger_eventlog(&config);
+        assert!(matches!(result, Err(LoggerError::EventLogNotSupported)));
+    }
+
     #[test]
     fn color_is_disabled_for_json() {
         let config = LoggerConfig {
@@ -116,6 +261,14 @@ mod tests {
         assert_eq!(config.format, LoggerFormat::Json);
     }
 
+    #[test]
+    fn reload_level_requires_initialized_logger() {
+        let level: LoggerLevel = "debug".parse().unwrap();
+        let result = reload_level(&level);
+        assert!(matches!(result, Err(LoggerError::NotInitialized)));
+        assert!(current_level().is_none());
+    }
+
     #[test]
     fn env_filter_is_built_correctly() {
         let config = LoggerConfig {
@@ -1,18 +1,26 @@
 mod config;
 mod error;
+#[cfg(windows)]
+mod eventlog;
 mod log;
 mod object;
+mod task_log;
 mod tasks;
 
 pub use config::LoggerConfig;
 pub use error::LoggerError;
 pub use object::LoggerFormat;
 pub use object::LoggerLevel;
+pub use object::LoggerLogfmt;
 pub use object::{LoggerTimeZone, init_local_offset};
+pub use task_log::{TaskLogConfig, TaskLogLayer};
 
 #[cfg(feature = "timezone-sync")]
 pub use tasks::timezone_sync;
 
+#[cfg(feature = "level-signal")]
+pub use tasks::{LEVEL_SIGNAL_SLOT, level_signal};
+
 /// Initializes the global tracing subscriber with the given configuration.
 ///
 /// This function configures and installs a tracing subscriber based on the provided [`LoggerConfig`].
@@ -35,6 +43,34 @@ pub fn init_logger(cfg: &LoggerConfig) -> Result<(), LoggerError> {
     match cfg.format {
         LoggerFormat::Text => log::logger_text(cfg),
         LoggerFormat::Json => log::logger_json(cfg),
+        LoggerFormat::Logfmt => log::logger_logfmt(cfg),
         LoggerFormat::Journald => log::logger_journald(cfg),
+        LoggerFormat::EventLog => log::logger_eventlog(cfg),
     }
 }
+
+/// Replaces the log level filter of a logger installed by [`init_logger`].
+///
+/// Output format, timezone and other settings stay unchanged; only the
+/// filter expression is swapped at runtime.
+///
+/// # Errors
+/// Returns [`LoggerError::NotInitialized`] if [`init_logger`] has not installed a logger.
+///
+/// # Examples
+/// ```rust
+/// use solti_observe::{LoggerConfig, LoggerLevel, init_logger, reload_level};
+///
+/// init_logger(&LoggerConfig::default()).expect("Failed to initialize logger");
+/// reload_level(&LoggerLevel::new("debug").unwrap()).expect("Failed to reload level");
+/// ```
+pub fn reload_level(level: &LoggerLevel) -> Result<(), LoggerError> {
+    log::reload_level(level)
+}
+
+/// Returns the level filter of the logger installed by [`init_logger`].
+///
+/// Reflects the latest successful [`reload_level`]; `None` if no logger is installed.
+pub fn current_level() -> Option<LoggerLevel> {
+    log::current_level()
+}
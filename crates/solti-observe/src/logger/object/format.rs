@@ -7,22 +7,23 @@ use crate::logger::LoggerError;
 /// Output format for the logger.
 /// - `Text`     — human-friendly, colored (when enabled) text logs.
 /// - `Json`     — structured JSON logs for machines / log collectors.
+/// - `Logfmt`   — single-line `key=value` logs.
 /// - `Journald` — logs are sent to systemd-journald (Linux only).
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// - `EventLog` — logs are sent to the Windows Event Log (Windows only).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[non_exhaustive]
 pub enum LoggerFormat {
     /// Human-readable text logs (default).
+    #[default]
     Text,
     /// Structured JSON logs.
     Json,
+    /// Single-line `key=value` logs.
+    Logfmt,
     /// systemd-journald output (Linux only).
     Journald,
-}
-
-impl Default for LoggerFormat {
-    fn default() -> Self {
-        Self::Text
-    }
+    /// Windows Event Log output (Windows only).
+    EventLog,
 }
 
 impl FromStr for LoggerFormat {
@@ -32,6 +33,7 @@ impl FromStr for LoggerFormat {
         match norm.as_str() {
             "text" => Ok(Self::Text),
             "json" => Ok(Self::Json),
+            "logfmt" => Ok(Self::Logfmt),
             "journald" | "journal" => {
                 #[cfg(target_os = "linux")]
                 {
@@ -42,6 +44,16 @@ impl FromStr for LoggerFormat {
                     Err(LoggerError::JournaldNotSupported)
                 }
             }
+            "eventlog" => {
+                #[cfg(windows)]
+                {
+                    Ok(Self::EventLog)
+                }
+                #[cfg(not(windows))]
+                {
+                    Err(LoggerError::EventLogNotSupported)
+                }
+            }
             _ => Err(LoggerError::InvalidFormat(s.to_string())),
         }
     }
@@ -52,7 +64,9 @@ impl fmt::Display for LoggerFormat {
         let s = match self {
             LoggerFormat::Text => "text",
             LoggerFormat::Json => "json",
+            LoggerFormat::Logfmt => "logfmt",
             LoggerFormat::Journald => "journald",
+            LoggerFormat::EventLog => "eventlog",
         };
         f.write_str(s)
     }
@@ -93,6 +107,10 @@ mod tests {
         assert_eq!(LoggerFormat::from_str("TEXT").unwrap(), LoggerFormat::Text);
         assert_eq!(LoggerFormat::from_str("json").unwrap(), LoggerFormat::Json);
         assert_eq!(LoggerFormat::from_str("JsOn").unwrap(), LoggerFormat::Json);
+        assert_eq!(
+            LoggerFormat::from_str("LogFmt").unwrap(),
+            LoggerFormat::Logfmt
+        );
     }
 
     #[test]
@@ -109,9 +127,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn eventlog_behavior_is_platform_specific() {
+        #[cfg(windows)]
+        {
+            assert_eq!(
+                LoggerFormat::from_str("EventLog").unwrap(),
+                LoggerFormat::EventLog
+            );
+        }
+
+        #[cfg(not(windows))]
+        {
+            let err = LoggerFormat::from_str("eventlog").unwrap_err();
+            assert!(matches!(err, LoggerError::EventLogNotSupported));
+        }
+    }
+
     #[test]
     fn rejects_unknown_format() {
-        let bad = ["", "  ", "xml", "logfmt", "text-json", "unknown"];
+        let bad = ["", "  ", "xml", "log-fmt", "text-json", "unknown"];
 
         for input in bad {
             let parsed = LoggerFormat::from_str(input);
@@ -126,12 +161,14 @@ mod tests {
     fn display_returns_canonical_names() {
         assert_eq!(LoggerFormat::Text.to_string(), "text");
         assert_eq!(LoggerFormat::Json.to_string(), "json");
+        assert_eq!(LoggerFormat::Logfmt.to_string(), "logfmt");
         assert_eq!(LoggerFormat::Journald.to_string(), "journald");
+        assert_eq!(LoggerFormat::EventLog.to_string(), "eventlog");
     }
 
     #[test]
     fn serde_roundtrip() {
-        for fmt in [LoggerFormat::Text, LoggerFormat::Json] {
+        for fmt in [LoggerFormat::Text, LoggerFormat::Json, LoggerFormat::Logfmt] {
             let json = serde_json::to_string(&fmt).unwrap();
             let parsed: LoggerFormat = serde_json::from_str(&json).unwrap();
             assert_eq!(fmt, parsed, "serde roundtrip failed for {fmt:?}");
@@ -0,0 +1,196 @@
+use std::fmt::{self, Write as _};
+
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::{Event, Subscriber, field::Field};
+use tracing_subscriber::{
+    field::Visit,
+    fmt::{FmtContext, FormatEvent, FormatFields, FormattedFields, format::Writer},
+    registry::LookupSpan,
+};
+
+use crate::logger::object::timezone::get_or_detect_local_offset;
+
+/// Single-line `key=value` (logfmt) event formatter.
+///
+/// Every line starts with `ts`, `level`, optional `target` and `msg`, followed by
+/// the fields of the enclosing spans (outermost first) and of the event itself:
+///
+/// ```text
+/// ts=2025-01-01T12:00:00+03:00 level=info target=solti_exec msg="hello world" task=runner-a-1 stream=stdout
+/// ```
+///
+/// Timestamps use the same RFC3339 / timezone handling as the other formats.
+#[derive(Debug, Clone, Copy)]
+pub struct LoggerLogfmt {
+    with_target: bool,
+}
+
+impl LoggerLogfmt {
+    /// Create a formatter; `with_target` adds the `target` key.
+    pub fn new(with_target: bool) -> Self {
+        Self { with_target }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for LoggerLogfmt
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let ts = OffsetDateTime::now_utc()
+            .to_offset(get_or_detect_local_offset())
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "<invalid-time>".to_string());
+
+        write!(
+            writer,
+            "ts={} level={}",
+            ts,
+            meta.level().as_str().to_ascii_lowercase()
+        )?;
+        if self.with_target {
+            write!(writer, " target={}", quote(meta.target()))?;
+        }
+
+        let mut visitor = LogfmtVisitor::default();
+        event.record(&mut visitor);
+        write!(writer, " msg={}", quote(&visitor.message))?;
+
+        if let Some(scope) = ctx.event_scope() {
+            for span in scope.from_root() {
+                let ext = span.extensions();
+                if let Some(fields) = ext.get::<FormattedFields<N>>()
+                    && !fields.is_empty()
+                {
+                    write!(writer, " {fields}")?;
+                }
+            }
+        }
+
+        writeln!(writer, "{}", visitor.fields)
+    }
+}
+
+/// Collects the message and renders other fields as ` key=value`.
+#[derive(Default)]
+struct LogfmtVisitor {
+    message: String,
+    fields: String,
+}
+
+impl LogfmtVisitor {
+    fn put(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            let _ = write!(self.fields, " {}={}", field.name(), quote(value));
+        }
+    }
+}
+
+impl Visit for LogfmtVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.put(field, value);
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.put(field, &format!("{value:?}"));
+    }
+}
+
+/// Quote a value if it contains characters that would break logfmt parsing.
+fn quote(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| c.is_whitespace() || c == '=' || c == '"' || c.is_control());
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Buffer {
+        type Writer = Buffer;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(quote("plain"), "plain");
+        assert_eq!(quote(""), r#""""#);
+        assert_eq!(quote("two words"), r#""two words""#);
+        assert_eq!(quote("a=b"), r#""a=b""#);
+        assert_eq!(quote("say \"hi\"\n"), r#""say \"hi\"\n""#);
+    }
+
+    #[test]
+    fn formats_event_as_single_line() {
+        let buf = Buffer::default();
+        let subscriber = tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .event_format(LoggerLogfmt::new(true))
+                .with_writer(buf.clone())
+                .with_ansi(false),
+        );
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("attempt", attempt = 2);
+            let _enter = span.enter();
+            tracing::warn!(task = "runner-a-1", stream = "stderr", "disk almost full");
+        });
+
+        let out = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.starts_with("ts="));
+        assert!(out.contains(" level=warn "));
+        assert!(out.contains(" target=solti_observe::logger::object::logfmt::tests "));
+        assert!(out.contains(r#" msg="disk almost full""#));
+        assert!(out.contains(" attempt=2"));
+        assert!(out.trim_end().ends_with("task=runner-a-1 stream=stderr"));
+    }
+}
@@ -4,6 +4,9 @@ pub use format::LoggerFormat;
 pub mod level;
 pub use level::LoggerLevel;
 
+pub mod logfmt;
+pub use logfmt::LoggerLogfmt;
+
 pub mod rfc3339;
 pub use rfc3339::LoggerRfc3339;
 
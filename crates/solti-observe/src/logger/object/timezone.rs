@@ -6,6 +6,7 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 use time::UtcOffset;
+#[cfg(feature = "timezone-sync")]
 use tracing::debug;
 
 use crate::logger::error::LoggerError;
@@ -24,20 +25,15 @@ static INIT_DONE: OnceLock<()> = OnceLock::new();
 ///
 /// - `Utc`: All timestamps in UTC (always works, default)
 /// - `Local`: Uses system timezone
-#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
 pub enum LoggerTimeZone {
     /// UTC timezone.
+    #[default]
     Utc,
     /// Local system timezone.
     Local,
 }
 
-impl Default for LoggerTimeZone {
-    fn default() -> Self {
-        Self::Utc
-    }
-}
-
 impl FromStr for LoggerTimeZone {
     type Err = LoggerError;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -93,6 +89,7 @@ pub fn init_local_offset() {
 }
 
 /// Synchronizes local offset.
+#[cfg(feature = "timezone-sync")]
 pub(crate) fn sync_local_offset() -> Result<(), LoggerError> {
     match UtcOffset::current_local_offset() {
         Ok(new_offset) => {
@@ -144,6 +141,7 @@ pub(crate) fn get_or_detect_local_offset() -> UtcOffset {
 /// Formats offset as `UTC±HH` or `UTC±HH:MM`.
 ///
 /// Examples: `"UTC+00"`, `"UTC+03:30"`, `"UTC-05"`
+#[cfg(any(feature = "timezone-sync", test))]
 fn format_offset(offset: UtcOffset) -> String {
     let hours = offset.whole_hours();
     let minutes = offset.minutes_past_hour();
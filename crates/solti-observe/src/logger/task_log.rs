@@ -0,0 +1,413 @@
+//! Per-task log files.
+//!
+//! [`TaskLogLayer`] copies every event that carries a `task` field into
+//! `<dir>/<slot>/<task>.log`, so one task's output and lifecycle can be
+//! inspected without grepping the whole agent log.
+//!
+//! The slot is taken from the event's `slot` field. Events for a task whose slot is
+//! not known yet (e.g. the lifecycle events emitted before the first output line)
+//! are held back and written once the slot shows up.
+//!
+//! Files are written by a dedicated thread, so logging never waits for the disk.
+use std::{
+    collections::HashMap,
+    fmt::{self, Write as _},
+    fs::{self, File, OpenOptions},
+    io::Write as _,
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, SyncSender, sync_channel},
+    thread::JoinHandle,
+};
+
+use serde::{Deserialize, Serialize};
+use time::{OffsetDateTime, format_description::well_known::Rfc3339};
+use tracing::{Event, Subscriber, field::Field};
+use tracing_subscriber::{Layer, layer::Context};
+
+use crate::logger::object::timezone::get_or_detect_local_offset;
+
+/// Upper bound on simultaneously open task log files.
+const MAX_OPEN_FILES: usize = 128;
+/// Upper bound on tasks with a remembered slot.
+const MAX_TRACKED_TASKS: usize = 4096;
+/// Upper bound on tasks with held-back lines.
+const MAX_PENDING_TASKS: usize = 256;
+/// Upper bound on held-back lines per task.
+const MAX_PENDING_LINES: usize = 64;
+/// Upper bound on lines queued for the writer thread.
+const MAX_QUEUED_LINES: usize = 8192;
+
+/// Configuration of per-task log files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TaskLogConfig {
+    /// Root directory; files are written to `<dir>/<slot>/<task>.log`.
+    pub dir: PathBuf,
+    /// Size after which a task log file is rotated.
+    pub max_bytes: u64,
+    /// Number of rotated files kept next to the active one (`<task>.log.1`, ...).
+    pub max_files: usize,
+}
+
+impl Default for TaskLogConfig {
+    fn default() -> Self {
+        Self {
+            dir: PathBuf::from("logs"),
+            max_bytes: 10 * 1024 * 1024,
+            max_files: 3,
+        }
+    }
+}
+
+/// Tracing layer routing task events into per-task files.
+///
+/// Installed automatically by [`init_logger`](crate::init_logger) when
+/// [`LoggerConfig::task_logs`](crate::LoggerConfig::task_logs) is set; it can also be
+/// composed manually into a custom subscriber.
+///
+/// Lines are handed to a writer thread through a bounded queue; when the queue is full
+/// (the disk cannot keep up), new lines are dropped. Dropping the layer writes out
+/// the queued lines before returning.
+pub struct TaskLogLayer {
+    dir: PathBuf,
+    tx: Option<SyncSender<Line>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+/// Line of a task log on its way to the writer thread.
+struct Line {
+    task: String,
+    slot: Option<String>,
+    text: String,
+}
+
+/// Task log files, owned by the writer thread.
+struct Writer {
+    cfg: TaskLogConfig,
+    slots: HashMap<String, String>,
+    files: HashMap<String, TaskFile>,
+    pending: HashMap<String, Vec<String>>,
+}
+
+struct TaskFile {
+    path: PathBuf,
+    file: File,
+    written: u64,
+}
+
+impl TaskLogLayer {
+    /// Create a layer writing under `cfg.dir`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the writer thread cannot be spawned.
+    pub fn new(cfg: TaskLogConfig) -> Self {
+        let dir = cfg.dir.clone();
+        let (tx, rx) = sync_channel(MAX_QUEUED_LINES);
+        let writer = std::thread::Builder::new()
+            .name("solti-task-log".into())
+            .spawn(move || Writer::new(cfg).run(rx))
+            .expect("failed to spawn task log writer thread");
+        Self {
+            dir,
+            tx: Some(tx),
+            writer: Some(writer),
+        }
+    }
+
+    /// Path of the active log file for a task in a slot.
+    pub fn path_for(&self, slot: &str, task: &str) -> PathBuf {
+        path_for(&self.dir, slot, task)
+    }
+}
+
+impl Drop for TaskLogLayer {
+    fn drop(&mut self) {
+        // Closing the queue stops the writer once it has written the remaining lines.
+        drop(self.tx.take());
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+impl Writer {
+    fn new(cfg: TaskLogConfig) -> Self {
+        Self {
+            cfg,
+            slots: HashMap::new(),
+            files: HashMap::new(),
+            pending: HashMap::new(),
+        }
+    }
+
+    fn run(mut self, rx: Receiver<Line>) {
+        for line in rx {
+            self.route(&line.task, line.slot.as_deref(), line.text);
+        }
+    }
+
+    fn route(&mut self, task: &str, slot: Option<&str>, line: String) {
+        let slot = match slot {
+            Some(slot) => {
+                if !self.slots.contains_key(task) {
+                    evict_one(&mut self.slots, MAX_TRACKED_TASKS);
+                    self.slots.insert(task.to_string(), slot.to_string());
+                }
+                slot.to_string()
+            }
+            None => match self.slots.get(task) {
+                Some(slot) => slot.clone(),
+                None => {
+                    if !self.pending.contains_key(task) {
+                        evict_one(&mut self.pending, MAX_PENDING_TASKS);
+                    }
+                    let lines = self.pending.entry(task.to_string()).or_default();
+                    if lines.len() >= MAX_PENDING_LINES {
+                        lines.remove(0);
+                    }
+                    lines.push(line);
+                    return;
+                }
+            },
+        };
+
+        let held = self.pending.remove(task).unwrap_or_default();
+        for line in held.into_iter().chain(std::iter::once(line)) {
+            self.write_line(&slot, task, &line);
+        }
+    }
+
+    fn write_line(&mut self, slot: &str, task: &str, line: &str) {
+        if !self.files.contains_key(task) {
+            evict_one(&mut self.files, MAX_OPEN_FILES);
+            match open(path_for(&self.cfg.dir, slot, task)) {
+                Ok(file) => {
+                    self.files.insert(task.to_string(), file);
+                }
+                Err(_) => return,
+            }
+        }
+        let Some(file) = self.files.get_mut(task) else {
+            return;
+        };
+
+        let len = line.len() as u64;
+        if self.cfg.max_bytes > 0 && file.written > 0 && file.written + len > self.cfg.max_bytes {
+            match rotate(&file.path, self.cfg.max_files).and_then(|_| open(file.path.clone())) {
+                Ok(fresh) => *file = fresh,
+                Err(_) => {
+                    self.files.remove(task);
+                    return;
+                }
+            }
+        }
+        if file.file.write_all(line.as_bytes()).is_ok() {
+            file.written += len;
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for TaskLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = Visitor::default();
+        event.record(&mut visitor);
+        let Some(task) = visitor.task.take() else {
+            return;
+        };
+
+        let meta = event.metadata();
+        let ts = OffsetDateTime::now_utc()
+            .to_offset(get_or_detect_local_offset())
+            .format(&Rfc3339)
+            .unwrap_or_else(|_| "<invalid-time>".to_string());
+        let line = format!(
+            "{ts} {:>5} {}{}\n",
+            meta.level(),
+            visitor.message,
+            visitor.fields
+        );
+        let Some(tx) = &self.tx else {
+            return;
+        };
+        // A full queue means the writer is behind; dropping the line beats blocking the caller.
+        let _ = tx.try_send(Line {
+            task,
+            slot: visitor.slot,
+            text: line,
+        });
+    }
+}
+
+/// Collects the routing fields and renders the rest as `key=value` pairs.
+#[derive(Default)]
+struct Visitor {
+    task: Option<String>,
+    slot: Option<String>,
+    message: String,
+    fields: String,
+}
+
+impl Visitor {
+    fn put(&mut self, field: &Field, value: String) {
+        match field.name() {
+            "task" => self.task = Some(value),
+            "slot" => self.slot = Some(value),
+            "message" => self.message = value,
+            name => {
+                let _ = write!(self.fields, " {name}={value}");
+            }
+        }
+    }
+}
+
+impl tracing::field::Visit for Visitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.put(field, value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.put(field, format!("{value:?}"));
+    }
+}
+
+/// Path of the active log file for a task in a slot under `dir`.
+fn path_for(dir: &Path, slot: &str, task: &str) -> PathBuf {
+    dir.join(sanitize(slot))
+        .join(format!("{}.log", sanitize(task)))
+}
+
+/// Make a slot or task id safe to use as a single path component.
+fn sanitize(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match cleaned.as_str() {
+        "" | "." | ".." => "_".to_string(),
+        _ => cleaned,
+    }
+}
+
+fn open(path: PathBuf) -> std::io::Result<TaskFile> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(&path)?;
+    let written = file.metadata()?.len();
+    Ok(TaskFile {
+        path,
+        file,
+        written,
+    })
+}
+
+/// Shift `<path>.N-1` to `<path>.N`, ..., `<path>` to `<path>.1`, dropping the oldest.
+fn rotate(path: &Path, keep: usize) -> std::io::Result<()> {
+    let numbered = |n: usize| {
+        let mut p = path.as_os_str().to_owned();
+        p.push(format!(".{n}"));
+        PathBuf::from(p)
+    };
+    if keep == 0 {
+        return fs::remove_file(path);
+    }
+    let _ = fs::remove_file(numbered(keep));
+    for n in (1..keep).rev() {
+        let from = numbered(n);
+        if from.exists() {
+            fs::rename(&from, numbered(n + 1))?;
+        }
+    }
+    fs::rename(path, numbered(1))
+}
+
+fn evict_one<V>(map: &mut HashMap<String, V>, cap: usize) {
+    if map.len() >= cap
+        && let Some(key) = map.keys().next().cloned()
+    {
+        map.remove(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("solti-task-log-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn routes_events_by_slot_and_task() {
+        let dir = temp_dir("route");
+        let layer = TaskLogLayer::new(TaskLogConfig {
+            dir: dir.clone(),
+            ..Default::default()
+        });
+        let path = layer.path_for("backup", "runner-backup-1");
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(task = "runner-backup-1", attempt = 1, "task starting");
+            tracing::info!(
+                task = "runner-backup-1",
+                slot = "backup",
+                stream = "stdout",
+                "hello"
+            );
+            tracing::info!("agent event without a task");
+        });
+
+        let content = fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].contains("task starting attempt=1"));
+        assert!(lines[1].contains("hello stream=stdout"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rotates_when_file_exceeds_limit() {
+        let dir = temp_dir("rotate");
+        let layer = TaskLogLayer::new(TaskLogConfig {
+            dir: dir.clone(),
+            max_bytes: 200,
+            max_files: 2,
+        });
+        let path = layer.path_for("s", "t");
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..20 {
+                tracing::info!(task = "t", slot = "s", "line number {i}");
+            }
+        });
+
+        let rotated = |n: usize| PathBuf::from(format!("{}.{n}", path.display()));
+        assert!(path.exists());
+        assert!(rotated(1).exists());
+        assert!(rotated(2).exists());
+        assert!(!rotated(3).exists());
+        assert!(fs::metadata(&path).unwrap().len() <= 200);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn sanitizes_path_components() {
+        assert_eq!(sanitize("../etc"), ".._etc");
+        assert_eq!(sanitize(".."), "_");
+        assert_eq!(sanitize("a/b c"), "a_b_c");
+        assert_eq!(sanitize("runner-slot-1f"), "runner-slot-1f");
+    }
+}
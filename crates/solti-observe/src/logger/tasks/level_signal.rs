@@ -0,0 +1,98 @@
+use std::sync::{Arc, Mutex};
+
+use solti_model::{
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, RunnerLabels,
+    TaskKind,
+};
+use taskvisor::{TaskError, TaskFn, TaskRef};
+use tokio::signal::unix::{SignalKind, signal};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::logger::{LoggerLevel, current_level, reload_level};
+
+/// Logical slot name used for the SIGUSR1 level toggle listener.
+pub const LEVEL_SIGNAL_SLOT: &str = "solti-logger-level-signal";
+
+/// Build the SIGUSR1 level toggle task and its model-level specification.
+///
+/// The first `SIGUSR1` switches the running logger to `verbose`, the next one
+/// restores the level that was active before, and so on.
+///
+/// Returns:
+/// - [`TaskRef`]    — executable task body.
+/// - [`CreateSpec`] — restart/backoff/admission policy and slot binding.
+pub fn level_signal(verbose: LoggerLevel) -> (TaskRef, CreateSpec) {
+    // Level to restore on the next toggle; survives task restarts.
+    let saved: Arc<Mutex<Option<LoggerLevel>>> = Arc::new(Mutex::new(None));
+
+    let task: TaskRef = TaskFn::arc(LEVEL_SIGNAL_SLOT, move |ctx: CancellationToken| {
+        let verbose = verbose.clone();
+        let saved = Arc::clone(&saved);
+
+        async move {
+            let mut usr1 = signal(SignalKind::user_defined1()).map_err(|e| TaskError::Fatal {
+                reason: format!("failed to install SIGUSR1 handler: {e}"),
+            })?;
+
+            loop {
+                tokio::select! {
+                    _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                    received = usr1.recv() => {
+                        if received.is_none() {
+                            return Ok(());
+                        }
+                        toggle(&verbose, &saved);
+                    }
+                }
+            }
+        }
+    });
+
+    let backoff = BackoffStrategy {
+        jitter: JitterStrategy::None,
+        first_ms: 1_000,
+        max_ms: 1_000,
+        factor: 1.0,
+    };
+    let spec = CreateSpec {
+        slot: LEVEL_SIGNAL_SLOT.to_string(),
+        timeout_ms: 0,
+        restart: RestartStrategy::OnFailure,
+        backoff,
+        admission: AdmissionStrategy::Replace,
+        kind: TaskKind::None,
+        labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
+    };
+    (task, spec)
+}
+
+/// Switch between `verbose` and the previously active level.
+fn toggle(verbose: &LoggerLevel, saved: &Mutex<Option<LoggerLevel>>) {
+    let Ok(mut saved) = saved.lock() else {
+        return;
+    };
+    let next = match saved.take() {
+        Some(previous) => previous,
+        None => {
+            *saved = current_level();
+            verbose.clone()
+        }
+    };
+
+    match reload_level(&next) {
+        Ok(()) => info!(
+            level = next.as_str(),
+            "SIGUSR1 received, log level switched"
+        ),
+        Err(e) => {
+            warn!(error = %e, "SIGUSR1 received, log level switch failed");
+            *saved = None;
+        }
+    }
+}
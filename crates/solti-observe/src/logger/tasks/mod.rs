@@ -3,3 +3,9 @@ mod timezone_sync;
 
 #[cfg(feature = "timezone-sync")]
 pub use timezone_sync::timezone_sync;
+
+#[cfg(feature = "level-signal")]
+mod level_signal;
+
+#[cfg(feature = "level-signal")]
+pub use level_signal::{LEVEL_SIGNAL_SLOT, level_signal};
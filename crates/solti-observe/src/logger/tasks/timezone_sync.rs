@@ -61,6 +61,11 @@ pub fn timezone_sync() -> (TaskRef, CreateSpec) {
         admission: AdmissionStrategy::Replace,
         kind: TaskKind::None,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     (task, spec)
 }
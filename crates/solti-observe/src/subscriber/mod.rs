@@ -11,6 +11,12 @@ use async_trait::async_trait;
 use taskvisor::{Event, EventKind, Subscribe};
 use tracing::{debug, error, info, trace, warn};
 
+mod rate_limit;
+pub use rate_limit::{LogRateLimit, RateLimitedSubscriber};
+
+mod spans;
+pub use spans::SpanSubscriber;
+
 /// Subscriber that logs all Taskvisor events using the tracing framework.
 ///
 /// Events are processed asynchronously with structured fields (task, attempt, etc.).
@@ -0,0 +1,295 @@
+//! Rate-limited variant of the event logging subscriber.
+//!
+//! High-frequency periodic tasks emit the same lifecycle events (starting, stopped,
+//! backoff scheduled, ...) on every run. [`RateLimitedSubscriber`] logs at most
+//! [`LogRateLimit::max_events`] of each kind per task within [`LogRateLimit::window`] and
+//! reports the rest as a single "suppressed N events" line once the window rolls
+//! over or the task is removed.
+
+use std::{
+    collections::HashMap,
+    mem::{Discriminant, discriminant},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use taskvisor::{Event, EventKind, Subscribe};
+use tracing::info;
+
+use super::{SUBSCRIBER_QUEUE_CAPACITY, log_event};
+
+/// Limits applied by [`RateLimitedSubscriber`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRateLimit {
+    /// Events of one kind logged per task within a window.
+    pub max_events: u32,
+    /// Length of the counting window.
+    pub window: Duration,
+}
+
+impl Default for LogRateLimit {
+    fn default() -> Self {
+        Self {
+            max_events: 10,
+            window: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Subscriber that logs Taskvisor events like [`Subscriber`](super::Subscriber),
+/// but rate-limits repetitive per-task lifecycle events.
+///
+/// Shutdown, terminal and subscriber failure events are never suppressed.
+pub struct RateLimitedSubscriber {
+    limiter: Limiter,
+}
+
+impl RateLimitedSubscriber {
+    /// Create a subscriber applying the given limits.
+    pub fn new(limit: LogRateLimit) -> Self {
+        Self {
+            limiter: Limiter::new(limit),
+        }
+    }
+}
+
+impl Default for RateLimitedSubscriber {
+    fn default() -> Self {
+        Self::new(LogRateLimit::default())
+    }
+}
+
+#[async_trait]
+impl Subscribe for RateLimitedSubscriber {
+    async fn on_event(&self, event: &Event) {
+        let Some(task) = event.task.as_deref() else {
+            log_event(event);
+            return;
+        };
+
+        if event.kind == EventKind::TaskRemoved {
+            for (kind, suppressed) in self.limiter.flush(task) {
+                report(task, kind, suppressed, self.limiter.limit.window);
+            }
+            log_event(event);
+            return;
+        }
+        if !is_throttled(event.kind) {
+            log_event(event);
+            return;
+        }
+
+        match self.limiter.check(task, event.kind, Instant::now()) {
+            Decision::Log { suppressed } => {
+                if suppressed > 0 {
+                    report(task, event.kind, suppressed, self.limiter.limit.window);
+                }
+                log_event(event);
+            }
+            Decision::Drop => {}
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "rate-limited-subscriber"
+    }
+
+    fn queue_capacity(&self) -> usize {
+        SUBSCRIBER_QUEUE_CAPACITY
+    }
+}
+
+/// Event kinds that repeat on every run of a periodic or retried task.
+fn is_throttled(kind: EventKind) -> bool {
+    matches!(
+        kind,
+        EventKind::TaskStarting
+            | EventKind::TaskStopped
+            | EventKind::TaskFailed
+            | EventKind::TimeoutHit
+            | EventKind::BackoffScheduled
+            | EventKind::TaskAddRequested
+            | EventKind::TaskAdded
+            | EventKind::TaskRemoveRequested
+            | EventKind::ControllerSubmitted
+            | EventKind::ControllerRejected
+            | EventKind::ControllerSlotTransition
+    )
+}
+
+fn report(task: &str, kind: EventKind, suppressed: u64, window: Duration) {
+    info!(
+        task,
+        event = ?kind,
+        suppressed,
+        window_ms = window.as_millis() as u64,
+        "suppressed {suppressed} events"
+    );
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Decision {
+    /// Log the event; `suppressed` events of the same key were dropped in the previous window.
+    Log { suppressed: u64 },
+    /// Drop the event.
+    Drop,
+}
+
+struct Bucket {
+    kind: EventKind,
+    started: Instant,
+    seen: u32,
+    suppressed: u64,
+}
+
+/// Fixed-window counter keyed by task and event kind.
+struct Limiter {
+    limit: LogRateLimit,
+    buckets: Mutex<HashMap<(String, Discriminant<EventKind>), Bucket>>,
+}
+
+impl Limiter {
+    fn new(limit: LogRateLimit) -> Self {
+        Self {
+            limit,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn check(&self, task: &str, kind: EventKind, now: Instant) -> Decision {
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return Decision::Log { suppressed: 0 };
+        };
+        let bucket = buckets
+            .entry((task.to_string(), discriminant(&kind)))
+            .or_insert(Bucket {
+                kind,
+                started: now,
+                seen: 0,
+                suppressed: 0,
+            });
+
+        let mut carried = 0;
+        if now.duration_since(bucket.started) >= self.limit.window {
+            carried = bucket.suppressed;
+            bucket.started = now;
+            bucket.seen = 0;
+            bucket.suppressed = 0;
+        }
+
+        if bucket.seen < self.limit.max_events {
+            bucket.seen += 1;
+            Decision::Log {
+                suppressed: carried,
+            }
+        } else {
+            bucket.suppressed += 1;
+            Decision::Drop
+        }
+    }
+
+    /// Forget all counters of a task, returning pending suppression counts.
+    fn flush(&self, task: &str) -> Vec<(EventKind, u64)> {
+        let Ok(mut buckets) = self.buckets.lock() else {
+            return Vec::new();
+        };
+        let mut pending = Vec::new();
+        buckets.retain(|(key, _), bucket| {
+            if key != task {
+                return true;
+            }
+            if bucket.suppressed > 0 {
+                pending.push((bucket.kind, bucket.suppressed));
+            }
+            false
+        });
+        pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limiter(max_events: u32) -> Limiter {
+        Limiter::new(LogRateLimit {
+            max_events,
+            window: Duration::from_secs(60),
+        })
+    }
+
+    #[test]
+    fn drops_events_over_the_limit() {
+        let l = limiter(2);
+        let now = Instant::now();
+
+        assert_eq!(
+            l.check("t", EventKind::TaskStarting, now),
+            Decision::Log { suppressed: 0 }
+        );
+        assert_eq!(
+            l.check("t", EventKind::TaskStarting, now),
+            Decision::Log { suppressed: 0 }
+        );
+        assert_eq!(l.check("t", EventKind::TaskStarting, now), Decision::Drop);
+
+        // Other kinds and other tasks have their own budget.
+        assert_eq!(
+            l.check("t", EventKind::TaskStopped, now),
+            Decision::Log { suppressed: 0 }
+        );
+        assert_eq!(
+            l.check("u", EventKind::TaskStarting, now),
+            Decision::Log { suppressed: 0 }
+        );
+    }
+
+    #[test]
+    fn reports_suppressed_count_when_window_rolls_over() {
+        let l = limiter(1);
+        let now = Instant::now();
+
+        l.check("t", EventKind::BackoffScheduled, now);
+        for _ in 0..5 {
+            assert_eq!(
+                l.check("t", EventKind::BackoffScheduled, now),
+                Decision::Drop
+            );
+        }
+
+        let later = now + Duration::from_secs(61);
+        assert_eq!(
+            l.check("t", EventKind::BackoffScheduled, later),
+            Decision::Log { suppressed: 5 }
+        );
+        assert_eq!(
+            l.check("t", EventKind::BackoffScheduled, later),
+            Decision::Drop
+        );
+    }
+
+    #[test]
+    fn flush_returns_pending_counts_and_resets() {
+        let l = limiter(1);
+        let now = Instant::now();
+
+        l.check("t", EventKind::TaskStarting, now);
+        l.check("t", EventKind::TaskStarting, now);
+        l.check("t", EventKind::TaskStopped, now);
+        l.check("u", EventKind::TaskStarting, now);
+        l.check("u", EventKind::TaskStarting, now);
+
+        let pending = l.flush("t");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, EventKind::TaskStarting);
+        assert_eq!(pending[0].1, 1);
+        assert!(l.flush("t").is_empty());
+
+        assert_eq!(
+            l.check("t", EventKind::TaskStarting, now),
+            Decision::Log { suppressed: 0 }
+        );
+        assert_eq!(l.flush("u").len(), 1);
+    }
+}
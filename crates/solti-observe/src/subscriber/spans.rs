@@ -0,0 +1,258 @@
+//! Tracing spans for the task lifecycle.
+//!
+//! [`SpanSubscriber`] turns the flat Taskvisor event stream into a span tree per task:
+//!
+//! ```text
+//! task (task_id, slot, runner, outcome)
+//! ├── submitted / built                  (events)
+//! ├── attempt 1 (attempt, outcome)
+//! ├── retry scheduled                    (event)
+//! └── attempt 2 (attempt, outcome) ──follows_from──▶ attempt 1
+//! ```
+//!
+//! The task span closes on the terminal event (exhausted, dead or removed),
+//! so exported traces show a task's full retry chain instead of disconnected events.
+
+use std::{collections::HashMap, sync::Mutex};
+
+use async_trait::async_trait;
+use taskvisor::{Event, EventKind, Subscribe};
+use tracing::{Span, field, info, info_span, warn};
+
+use super::SUBSCRIBER_QUEUE_CAPACITY;
+
+/// Subscriber that records the task lifecycle as tracing spans.
+#[derive(Default)]
+pub struct SpanSubscriber {
+    runners: Vec<&'static str>,
+    tasks: Mutex<HashMap<String, TaskSpans>>,
+}
+
+struct TaskSpans {
+    task: Span,
+    attempt: Option<Span>,
+    previous: Option<Span>,
+}
+
+impl SpanSubscriber {
+    /// Create a subscriber without runner names; `slot` and `runner` stay empty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runner names used to split task ids (`{runner}-{slot}-{seq}`) into `runner` and `slot` fields.
+    pub fn with_runners<I>(mut self, runners: I) -> Self
+    where
+        I: IntoIterator<Item = &'static str>,
+    {
+        self.runners = runners.into_iter().collect();
+        // Longest first, so `default-runner` wins over `default`.
+        self.runners.sort_by_key(|r| std::cmp::Reverse(r.len()));
+        self
+    }
+
+    fn handle(&self, e: &Event) {
+        let Some(task_id) = e.task.as_deref() else {
+            return;
+        };
+        let Ok(mut tasks) = self.tasks.lock() else {
+            return;
+        };
+
+        match e.kind {
+            EventKind::TaskAddRequested => {
+                let spans = self.open(&mut tasks, task_id);
+                info!(parent: &spans.task, "submitted");
+            }
+            EventKind::TaskAdded => {
+                let spans = self.open(&mut tasks, task_id);
+                info!(parent: &spans.task, "built");
+            }
+            EventKind::TaskStarting => {
+                let spans = self.open(&mut tasks, task_id);
+                let attempt = e.attempt.unwrap_or(0);
+                let span = info_span!(
+                    parent: &spans.task,
+                    "attempt",
+                    task_id,
+                    attempt,
+                    outcome = field::Empty,
+                );
+                if let Some(prev) = spans.attempt.take().or_else(|| spans.previous.take()) {
+                    span.follows_from(&prev);
+                }
+                spans.task.record("attempts", attempt);
+                spans.attempt = Some(span);
+            }
+            EventKind::TaskStopped => finish_attempt(&mut tasks, task_id, "success", None),
+            EventKind::TaskFailed => {
+                finish_attempt(&mut tasks, task_id, "failure", e.reason.as_deref())
+            }
+            EventKind::TimeoutHit => {
+                finish_attempt(&mut tasks, task_id, "timeout", e.reason.as_deref())
+            }
+            EventKind::BackoffScheduled => {
+                if let Some(spans) = tasks.get(task_id) {
+                    let delay_ms = e.delay_ms.unwrap_or(0);
+                    match e.reason.as_deref() {
+                        Some(reason) => {
+                            info!(parent: &spans.task, delay_ms, reason, "retry scheduled")
+                        }
+                        None => info!(parent: &spans.task, delay_ms, "next run scheduled"),
+                    }
+                }
+            }
+            EventKind::ActorExhausted => close(&mut tasks, task_id, "exhausted", e),
+            EventKind::ActorDead => close(&mut tasks, task_id, "dead", e),
+            EventKind::TaskRemoved => close(&mut tasks, task_id, "removed", e),
+            _ => {}
+        }
+    }
+
+    fn open<'a>(
+        &self,
+        tasks: &'a mut HashMap<String, TaskSpans>,
+        task_id: &str,
+    ) -> &'a mut TaskSpans {
+        tasks.entry(task_id.to_string()).or_insert_with(|| {
+            let (runner, slot) = split_task_id(task_id, &self.runners).unzip();
+            let task = info_span!(
+                parent: None,
+                "task",
+                task_id,
+                slot = slot,
+                runner = runner,
+                attempts = field::Empty,
+                outcome = field::Empty,
+            );
+            TaskSpans {
+                task,
+                attempt: None,
+                previous: None,
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl Subscribe for SpanSubscriber {
+    async fn on_event(&self, event: &Event) {
+        self.handle(event);
+    }
+
+    fn name(&self) -> &'static str {
+        "span-subscriber"
+    }
+
+    fn queue_capacity(&self) -> usize {
+        SUBSCRIBER_QUEUE_CAPACITY
+    }
+}
+
+/// Record the attempt outcome and close its span, keeping it for the `follows_from` link.
+fn finish_attempt(
+    tasks: &mut HashMap<String, TaskSpans>,
+    task_id: &str,
+    outcome: &str,
+    reason: Option<&str>,
+) {
+    let Some(spans) = tasks.get_mut(task_id) else {
+        return;
+    };
+    if let Some(attempt) = spans.attempt.take() {
+        attempt.record("outcome", outcome);
+        if let Some(reason) = reason {
+            warn!(parent: &attempt, reason, "attempt {outcome}");
+        }
+        spans.previous = Some(attempt);
+    }
+}
+
+/// Record the terminal outcome and close the task span.
+fn close(tasks: &mut HashMap<String, TaskSpans>, task_id: &str, outcome: &str, e: &Event) {
+    let Some(spans) = tasks.remove(task_id) else {
+        return;
+    };
+    spans.task.record("outcome", outcome);
+    match e.reason.as_deref() {
+        Some(reason) => info!(parent: &spans.task, reason, "task {outcome}"),
+        None => info!(parent: &spans.task, "task {outcome}"),
+    }
+}
+
+/// Split `{runner}-{slot}-{seq}` into `(runner, slot)` using the known runner names.
+fn split_task_id<'a>(task_id: &'a str, runners: &[&'static str]) -> Option<(&'a str, &'a str)> {
+    let (rest, seq) = task_id.rsplit_once('-')?;
+    if seq.is_empty() || !seq.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    runners.iter().find_map(|runner| {
+        let slot = rest.strip_prefix(runner)?.strip_prefix('-')?;
+        (!slot.is_empty()).then(|| (&task_id[..runner.len()], slot))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_task_id_with_known_runners() {
+        let runners = ["default", "default-runner"];
+        let sub = SpanSubscriber::new().with_runners(runners);
+
+        assert_eq!(
+            split_task_id("default-runner-agent-heartbeat-1f", &sub.runners),
+            Some(("default-runner", "agent-heartbeat"))
+        );
+        assert_eq!(
+            split_task_id("default-backup-2", &sub.runners),
+            Some(("default", "backup"))
+        );
+        assert_eq!(split_task_id("other-backup-2", &sub.runners), None);
+        assert_eq!(split_task_id("default-runner-xyz", &sub.runners), None);
+        assert_eq!(split_task_id("solti-logger-tz-sync", &[]), None);
+    }
+
+    #[test]
+    fn tracks_attempts_until_terminal_event() {
+        let sub = SpanSubscriber::new();
+        let task = "r-slot-1";
+
+        sub.handle(&Event::new(EventKind::TaskAddRequested).with_task(task));
+        sub.handle(&Event::new(EventKind::TaskAdded).with_task(task));
+        sub.handle(
+            &Event::new(EventKind::TaskStarting)
+                .with_task(task)
+                .with_attempt(1),
+        );
+        assert!(sub.tasks.lock().unwrap()[task].attempt.is_some());
+
+        sub.handle(
+            &Event::new(EventKind::TaskFailed)
+                .with_task(task)
+                .with_reason("boom"),
+        );
+        {
+            let tasks = sub.tasks.lock().unwrap();
+            assert!(tasks[task].attempt.is_none());
+            assert!(tasks[task].previous.is_some());
+        }
+
+        sub.handle(
+            &Event::new(EventKind::TaskStarting)
+                .with_task(task)
+                .with_attempt(2),
+        );
+        sub.handle(&Event::new(EventKind::TaskStopped).with_task(task));
+        sub.handle(&Event::new(EventKind::ActorExhausted).with_task(task));
+        assert!(sub.tasks.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn ignores_events_without_task() {
+        let sub = SpanSubscriber::new();
+        sub.handle(&Event::new(EventKind::ShutdownRequested));
+        assert!(sub.tasks.lock().unwrap().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
-use prometheus::{CounterVec, HistogramVec, Opts, Registry, proto::MetricFamily};
+use prometheus::{CounterVec, Gauge, GaugeVec, HistogramVec, Opts, Registry, proto::MetricFamily};
 
-use solti_core::{MetricsBackend, TaskOutcome};
+use solti_core::{AdmissionDecision, MetricsBackend, TaskOutcome};
+
+use crate::encode::{self, EncodedMetrics, ExpositionFormat};
 
 /// Prometheus metrics backend for solti.
 ///
@@ -12,19 +14,36 @@ use solti_core::{MetricsBackend, TaskOutcome};
 /// - `solti_tasks_started_total{runner_type}` - Counter of spawned tasks
 /// - `solti_tasks_completed_total{runner_type, outcome}` - Counter of completed tasks
 /// - `solti_task_duration_seconds{runner_type}` - Histogram of task execution time
+/// - `solti_task_queue_wait_seconds{runner_type}` - Histogram of time from submission to first start
 /// - `solti_runner_errors_total{runner_type, error_kind}` - Counter of runner errors
+/// - `solti_runner_healthy{runner_type}` - Gauge (1/0) with the last health check result
+/// - `solti_tasks_by_status{status}` - Gauge of tracked tasks per status
+/// - `solti_slots_active` - Gauge of slots with at least one pending or running task
+/// - `solti_admission_total{strategy, decision}` - Counter of admission decisions for submitted tasks
+/// - `solti_event_subscriber_overflow_total{subscriber, policy}` - Counter of events that overflowed a subscriber queue
 ///
 /// ## Label cardinality
 /// All labels are bounded (low cardinality):
 /// - `runner_type`: "subprocess", "wasm", "container"
 /// - `outcome`: "success", "failure", "canceled", "timeout"
 /// - `error_kind`: "spawn_failed", "backend_config_failed", etc
+/// - `status`: "pending", "running", "succeeded", etc
+/// - `strategy`: "drop-if-running", "replace", "queue"
+/// - `decision`: "queued", "replaced", "dropped_running", "rejected"
+/// - `subscriber`: attached event subscriber names
+/// - `policy`: "drop_oldest", "block", "spill_to_disk"
 #[derive(Clone)]
 pub struct PrometheusMetrics {
     tasks_started: CounterVec,
     tasks_completed: CounterVec,
     tasks_duration: HistogramVec,
+    queue_wait: HistogramVec,
     runner_errors: CounterVec,
+    runner_healthy: GaugeVec,
+    tasks_by_status: GaugeVec,
+    slots_active: Gauge,
+    subscriber_overflow: CounterVec,
+    admissions: CounterVec,
     registry: Arc<Registry>,
 }
 
@@ -59,21 +78,97 @@ impl PrometheusMetrics {
         )?;
         registry.register(Box::new(tasks_duration.clone()))?;
 
+        let queue_wait = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "solti_task_queue_wait_seconds",
+                "Time from task submission to its first start in seconds",
+            )
+            .namespace("solti")
+            .buckets(vec![
+                0.001, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 30.0, 60.0, 300.0,
+            ]),
+            &["runner_type"],
+        )?;
+        registry.register(Box::new(queue_wait.clone()))?;
+
         let runner_errors = CounterVec::new(
             Opts::new("solti_runner_errors_total", "Total runner-level errors").namespace("solti"),
             &["runner_type", "error_kind"],
         )?;
         registry.register(Box::new(runner_errors.clone()))?;
 
+        let runner_healthy = GaugeVec::new(
+            Opts::new(
+                "solti_runner_healthy",
+                "Runner health check result (1 = healthy, 0 = unhealthy)",
+            )
+            .namespace("solti"),
+            &["runner_type"],
+        )?;
+        registry.register(Box::new(runner_healthy.clone()))?;
+
+        let tasks_by_status = GaugeVec::new(
+            Opts::new(
+                "solti_tasks_by_status",
+                "Number of tracked tasks per status",
+            )
+            .namespace("solti"),
+            &["status"],
+        )?;
+        registry.register(Box::new(tasks_by_status.clone()))?;
+
+        let slots_active = Gauge::with_opts(
+            Opts::new(
+                "solti_slots_active",
+                "Number of slots with at least one pending or running task",
+            )
+            .namespace("solti"),
+        )?;
+        registry.register(Box::new(slots_active.clone()))?;
+
+        let subscriber_overflow = CounterVec::new(
+            Opts::new(
+                "solti_event_subscriber_overflow_total",
+                "Lifecycle events dropped or spilled because a subscriber queue was full",
+            )
+            .namespace("solti"),
+            &["subscriber", "policy"],
+        )?;
+        registry.register(Box::new(subscriber_overflow.clone()))?;
+
+        let admissions = CounterVec::new(
+            Opts::new(
+                "solti_admission_total",
+                "Admission decisions for submitted tasks",
+            )
+            .namespace("solti"),
+            &["strategy", "decision"],
+        )?;
+        registry.register(Box::new(admissions.clone()))?;
+
         Ok(Self {
             tasks_started,
             tasks_completed,
             tasks_duration,
+            queue_wait,
             runner_errors,
+            runner_healthy,
+            tasks_by_status,
+            slots_active,
+            subscriber_overflow,
+            admissions,
             registry,
         })
     }
 
+    /// Create a prometheus metrics backend registering its collectors into `registry`.
+    ///
+    /// Lets hosts expose solti metrics alongside their own from a single `/metrics` body.
+    /// [`Registry`] clones share the same collectors, so pass a clone of the host registry.
+    pub fn with_registry(registry: Registry) -> Result<Self, prometheus::Error> {
+        Self::new_with_registry(Arc::new(registry))
+    }
+
     /// Create a new prometheus metrics backend with default registry.
     pub fn new() -> Result<Self, prometheus::Error> {
         Self::new_with_registry(Arc::new(Registry::new()))
@@ -94,6 +189,15 @@ impl PrometheusMetrics {
         self.registry.gather()
     }
 
+    /// Encode all metrics in `format` for a `/metrics` response.
+    ///
+    /// The returned [`EncodedMetrics::content_type`] must be sent as the `Content-Type`
+    /// header; pick the format from the scraper's `Accept` header with
+    /// [`ExpositionFormat::from_accept`].
+    pub fn encode(&self, format: ExpositionFormat) -> Result<EncodedMetrics, prometheus::Error> {
+        encode::encode(&self.gather(), format)
+    }
+
     /// Get reference to underlying prometheus registry.
     ///
     /// Useful for registering custom metrics alongside solti metrics.
@@ -119,11 +223,45 @@ impl MetricsBackend for PrometheusMetrics {
             .observe(duration_seconds);
     }
 
+    fn record_task_queue_wait(&self, runner_type: &str, wait_ms: u64) {
+        self.queue_wait
+            .with_label_values(&[runner_type])
+            .observe(wait_ms as f64 / 1000.0);
+    }
+
     fn record_runner_error(&self, runner_type: &str, error_kind: &str) {
         self.runner_errors
             .with_label_values(&[runner_type, error_kind])
             .inc();
     }
+
+    fn record_runner_health(&self, runner_type: &str, healthy: bool) {
+        self.runner_healthy
+            .with_label_values(&[runner_type])
+            .set(if healthy { 1.0 } else { 0.0 });
+    }
+
+    fn record_tasks_by_status(&self, status: &str, count: usize) {
+        self.tasks_by_status
+            .with_label_values(&[status])
+            .set(count as f64);
+    }
+
+    fn record_active_slots(&self, count: usize) {
+        self.slots_active.set(count as f64);
+    }
+
+    fn record_subscriber_overflow(&self, subscriber: &str, policy: &str, count: u64) {
+        self.subscriber_overflow
+            .with_label_values(&[subscriber, policy])
+            .inc_by(count as f64);
+    }
+
+    fn record_admission(&self, strategy: &str, decision: AdmissionDecision) {
+        self.admissions
+            .with_label_values(&[strategy, decision.as_label()])
+            .inc();
+    }
 }
 
 #[cfg(test)]
@@ -191,6 +329,161 @@ mod tests {
         assert_eq!(errors.get_metric().len(), 2);
     }
 
+    #[test]
+    fn record_runner_health_sets_gauge() {
+        let metrics = PrometheusMetrics::new().unwrap();
+
+        metrics.record_runner_health("subprocess", true);
+        metrics.record_runner_health("wasm", true);
+        metrics.record_runner_health("wasm", false);
+
+        let families = metrics.gather();
+        let healthy = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_runner_healthy")
+            .expect("health gauge not found");
+
+        assert_eq!(healthy.get_metric().len(), 2);
+        let wasm = healthy
+            .get_metric()
+            .iter()
+            .find(|m| m.get_label().iter().any(|l| l.value() == "wasm"))
+            .expect("wasm series not found");
+        assert_eq!(wasm.get_gauge().value(), 0.0);
+    }
+
+    #[test]
+    fn record_task_state_sets_gauges() {
+        let metrics = PrometheusMetrics::new().unwrap();
+
+        metrics.record_tasks_by_status("running", 3);
+        metrics.record_tasks_by_status("running", 2);
+        metrics.record_tasks_by_status("failed", 1);
+        metrics.record_active_slots(4);
+
+        let families = metrics.gather();
+        let by_status = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_tasks_by_status")
+            .expect("status gauge not found");
+        assert_eq!(by_status.get_metric().len(), 2);
+        let running = by_status
+            .get_metric()
+            .iter()
+            .find(|m| m.get_label().iter().any(|l| l.value() == "running"))
+            .expect("running series not found");
+        assert_eq!(running.get_gauge().value(), 2.0);
+
+        let slots = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_slots_active")
+            .expect("slots gauge not found");
+        assert_eq!(slots.get_metric()[0].get_gauge().value(), 4.0);
+    }
+
+    #[test]
+    fn record_subscriber_overflow_counts_events() {
+        let metrics = PrometheusMetrics::new().unwrap();
+
+        metrics.record_subscriber_overflow("journal", "drop_oldest", 3);
+        metrics.record_subscriber_overflow("journal", "drop_oldest", 2);
+
+        let families = metrics.gather();
+        let overflow = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_event_subscriber_overflow_total")
+            .expect("overflow counter not found");
+        assert_eq!(overflow.get_metric()[0].get_counter().value(), 5.0);
+    }
+
+    #[test]
+    fn record_admission_counts_decisions() {
+        let metrics = PrometheusMetrics::new().unwrap();
+
+        metrics.record_admission("replace", AdmissionDecision::Replaced);
+        metrics.record_admission("replace", AdmissionDecision::Replaced);
+        metrics.record_admission("queue", AdmissionDecision::Rejected);
+
+        let families = metrics.gather();
+        let admissions = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_admission_total")
+            .expect("admission counter not found");
+        assert_eq!(admissions.get_metric().len(), 2);
+        let replaced = admissions
+            .get_metric()
+            .iter()
+            .find(|m| m.get_label().iter().any(|l| l.value() == "replaced"))
+            .expect("replaced series not found");
+        assert_eq!(replaced.get_counter().value(), 2.0);
+    }
+
+    #[test]
+    fn record_task_queue_wait_observes_seconds() {
+        let metrics = PrometheusMetrics::new().unwrap();
+
+        metrics.record_task_queue_wait("subprocess", 1500);
+
+        let families = metrics.gather();
+        let wait = families
+            .iter()
+            .find(|f| f.name() == "solti_solti_task_queue_wait_seconds")
+            .expect("queue wait histogram not found");
+        let histogram = wait.get_metric()[0].get_histogram();
+        assert_eq!(histogram.get_sample_count(), 1);
+        assert_eq!(histogram.get_sample_sum(), 1.5);
+    }
+
+    #[test]
+    fn encodes_all_exposition_formats() {
+        let metrics = PrometheusMetrics::new().unwrap();
+        metrics.record_task_started("subprocess");
+        metrics.record_task_completed("subprocess", TaskOutcome::Success, 250);
+
+        let text = metrics.encode(ExpositionFormat::Text).unwrap();
+        assert_eq!(text.content_type, prometheus::TEXT_FORMAT);
+        let body = String::from_utf8(text.body).unwrap();
+        assert!(body.contains("# TYPE solti_solti_tasks_started_total counter"));
+
+        let om = metrics.encode(ExpositionFormat::OpenMetrics).unwrap();
+        assert_eq!(om.content_type, crate::OPENMETRICS_FORMAT);
+        let body = String::from_utf8(om.body).unwrap();
+        assert!(body.contains("# TYPE solti_solti_tasks_started counter\n"));
+        assert!(body.contains("solti_solti_tasks_started_total{runner_type=\"subprocess\"} 1.0\n"));
+        assert!(body.contains(
+            "solti_solti_task_duration_seconds_bucket{runner_type=\"subprocess\",le=\"0.5\"} 1.0\n"
+        ));
+        assert!(body.contains("le=\"+Inf\"} 1.0\n"));
+        assert!(body.ends_with("# EOF\n"));
+
+        let pb = metrics.encode(ExpositionFormat::Protobuf).unwrap();
+        assert_eq!(pb.content_type, prometheus::PROTOBUF_FORMAT);
+        assert!(!pb.body.is_empty());
+    }
+
+    #[test]
+    fn registers_into_host_registry() {
+        let registry = Registry::new();
+        let host =
+            CounterVec::new(Opts::new("host_requests_total", "Host requests"), &["path"]).unwrap();
+        registry.register(Box::new(host.clone())).unwrap();
+        host.with_label_values(&["/"]).inc();
+
+        let metrics = PrometheusMetrics::with_registry(registry.clone()).unwrap();
+        metrics.record_task_started("subprocess");
+
+        let names: Vec<String> = registry
+            .gather()
+            .iter()
+            .map(|f| f.name().to_string())
+            .collect();
+        assert!(names.contains(&"host_requests_total".to_string()));
+        assert!(names.contains(&"solti_solti_tasks_started_total".to_string()));
+
+        // Registering solti collectors twice into the same registry is rejected.
+        assert!(PrometheusMetrics::with_registry(registry).is_err());
+    }
+
     #[test]
     fn can_use_custom_registry() {
         let registry = Arc::new(Registry::new());
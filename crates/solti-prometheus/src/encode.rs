@@ -0,0 +1,228 @@
+use std::fmt::Write;
+
+use prometheus::{
+    Encoder, PROTOBUF_FORMAT, ProtobufEncoder, TEXT_FORMAT, TextEncoder,
+    proto::{Metric, MetricFamily, MetricType},
+};
+
+/// Content type of the OpenMetrics text format.
+pub const OPENMETRICS_FORMAT: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Exposition format of a `/metrics` response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExpositionFormat {
+    /// Prometheus text format 0.0.4 (default).
+    #[default]
+    Text,
+    /// OpenMetrics 1.0 text format; required by scrapers that ingest exemplars.
+    OpenMetrics,
+    /// Length-delimited `io.prometheus.client.MetricFamily` protobuf messages.
+    Protobuf,
+}
+
+impl ExpositionFormat {
+    /// Content type to send with a body in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Text => TEXT_FORMAT,
+            Self::OpenMetrics => OPENMETRICS_FORMAT,
+            Self::Protobuf => PROTOBUF_FORMAT,
+        }
+    }
+
+    /// Pick the format requested by an HTTP `Accept` header.
+    ///
+    /// Falls back to [`ExpositionFormat::Text`] when neither OpenMetrics nor protobuf is accepted.
+    pub fn from_accept(accept: &str) -> Self {
+        let accepts = |media: &str| {
+            accept
+                .split(',')
+                .filter_map(|part| part.split(';').next())
+                .any(|m| m.trim().eq_ignore_ascii_case(media))
+        };
+        if accepts("application/vnd.google.protobuf") {
+            Self::Protobuf
+        } else if accepts("application/openmetrics-text") {
+            Self::OpenMetrics
+        } else {
+            Self::Text
+        }
+    }
+}
+
+/// Metrics encoded for exposition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedMetrics {
+    /// Value for the `Content-Type` response header.
+    pub content_type: &'static str,
+    /// Response body.
+    pub body: Vec<u8>,
+}
+
+/// Encode `families` in `format`.
+pub(crate) fn encode(
+    families: &[MetricFamily],
+    format: ExpositionFormat,
+) -> Result<EncodedMetrics, prometheus::Error> {
+    let mut body = Vec::new();
+    match format {
+        ExpositionFormat::Text => TextEncoder::new().encode(families, &mut body)?,
+        ExpositionFormat::Protobuf => ProtobufEncoder::new().encode(families, &mut body)?,
+        ExpositionFormat::OpenMetrics => body = encode_openmetrics(families).into_bytes(),
+    }
+    Ok(EncodedMetrics {
+        content_type: format.content_type(),
+        body,
+    })
+}
+
+/// OpenMetrics text encoding.
+///
+/// Differs from the Prometheus text format in counter naming (the family drops the
+/// `_total` suffix, samples keep it), float formatting of `le`/`quantile` labels,
+/// timestamps in seconds and the mandatory `# EOF` terminator.
+fn encode_openmetrics(families: &[MetricFamily]) -> String {
+    let mut out = String::new();
+    for mf in families {
+        let metric_type = mf.get_field_type();
+        let name = match metric_type {
+            MetricType::COUNTER => mf.name().strip_suffix("_total").unwrap_or(mf.name()),
+            _ => mf.name(),
+        };
+        let type_name = match metric_type {
+            MetricType::COUNTER => "counter",
+            MetricType::GAUGE => "gauge",
+            MetricType::HISTOGRAM => "histogram",
+            MetricType::SUMMARY => "summary",
+            MetricType::UNTYPED => "unknown",
+        };
+
+        let _ = writeln!(out, "# TYPE {name} {type_name}");
+        if !mf.help().is_empty() {
+            let _ = writeln!(out, "# HELP {name} {}", escape(mf.help()));
+        }
+
+        for m in mf.get_metric() {
+            match metric_type {
+                MetricType::COUNTER => {
+                    sample(&mut out, name, "_total", m, None, m.get_counter().value());
+                }
+                MetricType::GAUGE => sample(&mut out, name, "", m, None, m.get_gauge().value()),
+                MetricType::UNTYPED => sample(&mut out, name, "", m, None, m.untyped.value()),
+                MetricType::HISTOGRAM => {
+                    let h = m.get_histogram();
+                    let mut inf_seen = false;
+                    for b in h.get_bucket() {
+                        let le = float(b.upper_bound());
+                        inf_seen |= b.upper_bound() == f64::INFINITY;
+                        let count = b.cumulative_count() as f64;
+                        sample(&mut out, name, "_bucket", m, Some(("le", &le)), count);
+                    }
+                    let count = h.get_sample_count() as f64;
+                    if !inf_seen {
+                        sample(&mut out, name, "_bucket", m, Some(("le", "+Inf")), count);
+                    }
+                    sample(&mut out, name, "_count", m, None, count);
+                    sample(&mut out, name, "_sum", m, None, h.get_sample_sum());
+                }
+                MetricType::SUMMARY => {
+                    let s = m.get_summary();
+                    for q in s.get_quantile() {
+                        let quantile = float(q.quantile());
+                        sample(
+                            &mut out,
+                            name,
+                            "",
+                            m,
+                            Some(("quantile", &quantile)),
+                            q.value(),
+                        );
+                    }
+                    sample(&mut out, name, "_count", m, None, s.sample_count() as f64);
+                    sample(&mut out, name, "_sum", m, None, s.sample_sum());
+                }
+            }
+        }
+    }
+    out.push_str("# EOF\n");
+    out
+}
+
+fn sample(
+    out: &mut String,
+    name: &str,
+    suffix: &str,
+    m: &Metric,
+    extra: Option<(&str, &str)>,
+    value: f64,
+) {
+    out.push_str(name);
+    out.push_str(suffix);
+
+    let labels = m
+        .get_label()
+        .iter()
+        .map(|lp| (lp.name(), lp.value()))
+        .chain(extra);
+    let mut separator = '{';
+    for (label, value) in labels {
+        let _ = write!(out, "{separator}{label}=\"{}\"", escape(value));
+        separator = ',';
+    }
+    if separator == ',' {
+        out.push('}');
+    }
+
+    let _ = write!(out, " {}", float(value));
+    if m.timestamp_ms() != 0 {
+        let _ = write!(out, " {}", m.timestamp_ms() as f64 / 1000.0);
+    }
+    out.push('\n');
+}
+
+/// Canonical OpenMetrics float: integral values keep a `.0`, infinities are `±Inf`.
+fn float(v: f64) -> String {
+    if v.is_nan() {
+        "NaN".into()
+    } else if v.is_infinite() {
+        if v > 0.0 { "+Inf" } else { "-Inf" }.into()
+    } else if v.fract() == 0.0 && v.abs() < 1e15 {
+        format!("{v:.1}")
+    } else {
+        v.to_string()
+    }
+}
+
+fn escape(v: &str) -> String {
+    v.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negotiates_format_from_accept_header() {
+        assert_eq!(ExpositionFormat::from_accept("*/*"), ExpositionFormat::Text);
+        assert_eq!(
+            ExpositionFormat::from_accept(
+                "application/openmetrics-text;version=1.0.0,text/plain;version=0.0.4;q=0.5"
+            ),
+            ExpositionFormat::OpenMetrics
+        );
+        assert_eq!(
+            ExpositionFormat::from_accept(PROTOBUF_FORMAT),
+            ExpositionFormat::Protobuf
+        );
+    }
+
+    #[test]
+    fn formats_openmetrics_floats() {
+        assert_eq!(float(1.0), "1.0");
+        assert_eq!(float(0.05), "0.05");
+        assert_eq!(float(f64::INFINITY), "+Inf");
+        assert_eq!(escape("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+}
@@ -17,10 +17,12 @@
 //! // Inject into build context
 //! let ctx = BuildContext::new(TaskEnv::default(), metrics_handle);
 //!
+//! // Or register into a registry the host already exposes:
+//! // let metrics = PrometheusMetrics::with_registry(host_registry.clone())?;
+//!
 //! // Expose /metrics endpoint (example with custom HTTP server)
-//! // let metric_families = metrics.gather();
-//! // let encoder = prometheus::TextEncoder::new();
-//! // encoder.encode(&metric_families, &mut response_buffer)?;
+//! // let encoded = metrics.encode(ExpositionFormat::from_accept(accept_header))?;
+//! // respond with encoded.body and `Content-Type: encoded.content_type`
 //! # Ok(())
 //! # }
 //! ```
@@ -29,7 +31,14 @@
 //! - `solti_tasks_started_total{runner_type}` - Counter
 //! - `solti_tasks_completed_total{runner_type, outcome}` - Counter
 //! - `solti_task_duration_seconds{runner_type}` - Histogram
+//! - `solti_task_queue_wait_seconds{runner_type}` - Histogram
 //! - `solti_runner_errors_total{runner_type, error_kind}` - Counter
+//! - `solti_runner_healthy{runner_type}` - Gauge
+//! - `solti_admission_total{strategy, decision}` - Counter
+//!
+//! ## Exposition formats
+//! [`PrometheusMetrics::encode`] produces Prometheus text, OpenMetrics text or protobuf
+//! (see [`ExpositionFormat`]) together with the matching content type.
 //!
 //! ## HTTP Server
 //! This crate does NOT provide HTTP server for `/metrics` endpoint.
@@ -38,15 +47,14 @@
 //! ```rust,ignore
 //! // Example with axum
 //! async fn metrics_handler(
-//!     State(metrics): State<Arc<PrometheusMetrics>>
+//!     State(metrics): State<Arc<PrometheusMetrics>>,
+//!     headers: HeaderMap,
 //! ) -> Response {
-//!     let families = metrics.gather();
-//!     let encoder = prometheus::TextEncoder::new();
-//!     let mut buffer = vec![];
-//!     encoder.encode(&families, &mut buffer).unwrap();
+//!     let accept = headers.get(ACCEPT).and_then(|v| v.to_str().ok()).unwrap_or("");
+//!     let encoded = metrics.encode(ExpositionFormat::from_accept(accept)).unwrap();
 //!     Response::builder()
-//!         .header("Content-Type", encoder.format_type())
-//!         .body(buffer.into())
+//!         .header("Content-Type", encoded.content_type)
+//!         .body(encoded.body.into())
 //!         .unwrap()
 //! }
 //! ```
@@ -54,4 +62,7 @@
 mod backend;
 pub use backend::PrometheusMetrics;
 
+mod encode;
+pub use encode::{EncodedMetrics, ExpositionFormat, OPENMETRICS_FORMAT};
+
 pub use prometheus::{Encoder, Registry, TextEncoder};
@@ -0,0 +1,241 @@
+//! `SOLTI_*` environment overrides.
+//!
+//! Each supported variable maps to a single settings field:
+//!
+//! | Variable                                   | Field                                  |
+//! |--------------------------------------------|----------------------------------------|
+//! | `SOLTI_SUPERVISOR_GRACE_MS`                | `supervisor.grace_ms`                  |
+//! | `SOLTI_SUPERVISOR_MAX_CONCURRENT`          | `supervisor.max_concurrent`            |
+//! | `SOLTI_SUPERVISOR_BUS_CAPACITY`            | `supervisor.bus_capacity`              |
+//! | `SOLTI_SUPERVISOR_TIMEOUT_MS`              | `supervisor.timeout_ms`                |
+//! | `SOLTI_CONTROLLER_QUEUE_CAPACITY`          | `controller.queue_capacity`            |
+//! | `SOLTI_CONTROLLER_SLOT_CAPACITY`           | `controller.slot_capacity`             |
+//! | `SOLTI_CONTROLLER_DEDUP`                   | `controller.dedup`                     |
+//! | `SOLTI_LOGGER_FORMAT`                      | `logger.format`                        |
+//! | `SOLTI_LOGGER_LEVEL`                       | `logger.level`                         |
+//! | `SOLTI_LOGGER_TZ`                          | `logger.tz`                            |
+//! | `SOLTI_LOGGER_WITH_TARGETS`                | `logger.with_targets`                  |
+//! | `SOLTI_LOGGER_USE_COLOR`                   | `logger.use_color`                     |
+//! | `SOLTI_LOGGER_TASK_LOG_DIR`                | `logger.task_logs.dir`                 |
+//! | `SOLTI_METRICS_ENABLED`                    | `metrics.enabled`                      |
+//! | `SOLTI_METRICS_PATH`                       | `metrics.path`                         |
+//! | `SOLTI_API_HTTP_ADDR`                      | `api.http_addr`                        |
+//! | `SOLTI_API_GRPC_ADDR`                      | `api.grpc_addr`                        |
+//! | `SOLTI_CAPACITY_CPU_MILLIS`                | `capacity.cpuMillis`                   |
+//! | `SOLTI_CAPACITY_MEMORY_BYTES`              | `capacity.memoryBytes`                 |
+//! | `SOLTI_CAPACITY_GPUS`                      | `capacity.gpus`                        |
+//! | `SOLTI_DISCOVERY_NAME`                     | `discovery.name`                       |
+//! | `SOLTI_DISCOVERY_CONTROL_PLANE_ENDPOINT`   | `discovery.control_plane_endpoint`     |
+//! | `SOLTI_DISCOVERY_AGENT_ENDPOINT`           | `discovery.agent_endpoint`             |
+//! | `SOLTI_DISCOVERY_TRANSPORT`                | `discovery.transport`                  |
+//! | `SOLTI_DISCOVERY_DELAY_MS`                 | `discovery.delay_ms`                   |
+//! | `SOLTI_DISCOVERY_TOKEN`                    | `discovery.token`                      |
+//! | `SOLTI_DISCOVERY_TOKEN_FILE`               | `discovery.token_file`                 |
+//!
+//! Setting any `SOLTI_DISCOVERY_*` variable enables discovery with defaults for the remaining fields.
+use std::{fmt::Display, str::FromStr};
+
+use solti_observe::TaskLogConfig;
+
+use crate::{
+    error::{SettingsError, SettingsResult},
+    settings::SupervisorSettings,
+};
+
+/// Prefix shared by all environment overrides.
+pub const ENV_PREFIX: &str = "SOLTI_";
+
+/// Apply a single environment variable to the settings.
+pub(crate) fn apply_overrides(
+    s: &mut SupervisorSettings,
+    key: &str,
+    value: &str,
+) -> SettingsResult<()> {
+    let Some(name) = key.strip_prefix(ENV_PREFIX) else {
+        return Ok(());
+    };
+
+    match name {
+        "SUPERVISOR_GRACE_MS" => s.supervisor.grace_ms = parse(key, value)?,
+        "SUPERVISOR_MAX_CONCURRENT" => s.supervisor.max_concurrent = parse(key, value)?,
+        "SUPERVISOR_BUS_CAPACITY" => s.supervisor.bus_capacity = parse(key, value)?,
+        "SUPERVISOR_TIMEOUT_MS" => s.supervisor.timeout_ms = parse(key, value)?,
+
+        "CONTROLLER_QUEUE_CAPACITY" => s.controller.queue_capacity = parse(key, value)?,
+        "CONTROLLER_SLOT_CAPACITY" => s.controller.slot_capacity = parse(key, value)?,
+        "CONTROLLER_DEDUP" => s.controller.dedup = parse_bool(key, value)?,
+
+        "LOGGER_FORMAT" => s.logger.format = parse(key, value)?,
+        "LOGGER_LEVEL" => s.logger.level = parse(key, value)?,
+        "LOGGER_TZ" => s.logger.tz = parse(key, value)?,
+        "LOGGER_WITH_TARGETS" => s.logger.with_targets = parse_bool(key, value)?,
+        "LOGGER_USE_COLOR" => s.logger.use_color = parse_bool(key, value)?,
+        "LOGGER_TASK_LOG_DIR" => {
+            s.logger.task_logs = non_empty(value).map(|dir| TaskLogConfig {
+                dir: dir.into(),
+                ..s.logger.task_logs.take().unwrap_or_default()
+            })
+        }
+
+        "METRICS_ENABLED" => s.metrics.enabled = parse_bool(key, value)?,
+        "METRICS_PATH" => s.metrics.path = value.to_string(),
+
+        "API_HTTP_ADDR" => s.api.http_addr = non_empty(value),
+        "API_GRPC_ADDR" => s.api.grpc_addr = non_empty(value),
+
+        "CAPACITY_CPU_MILLIS" => s.capacity.cpu_millis = parse_opt(key, value)?,
+        "CAPACITY_MEMORY_BYTES" => s.capacity.memory_bytes = parse_opt(key, value)?,
+        "CAPACITY_GPUS" => s.capacity.gpus = parse_opt(key, value)?,
+
+        "DISCOVERY_NAME" => discovery(s).name = value.to_string(),
+        "DISCOVERY_CONTROL_PLANE_ENDPOINT" => {
+            discovery(s).control_plane_endpoint = value.to_string()
+        }
+        "DISCOVERY_AGENT_ENDPOINT" => discovery(s).agent_endpoint = value.to_string(),
+        "DISCOVERY_TRANSPORT" => discovery(s).transport = value.to_string(),
+        "DISCOVERY_DELAY_MS" => discovery(s).delay_ms = parse(key, value)?,
+        "DISCOVERY_TOKEN" => discovery(s).token = non_empty(value),
+        "DISCOVERY_TOKEN_FILE" => discovery(s).token_file = non_empty(value).map(Into::into),
+
+        _ => {}
+    }
+    Ok(())
+}
+
+fn discovery(s: &mut SupervisorSettings) -> &mut crate::DiscoveryOptions {
+    s.discovery.get_or_insert_with(Default::default)
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let v = value.trim();
+    if v.is_empty() {
+        None
+    } else {
+        Some(v.to_string())
+    }
+}
+
+fn parse<T>(key: &str, value: &str) -> SettingsResult<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|e| SettingsError::InvalidEnv {
+            key: key.to_string(),
+            value: value.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Like [`parse`], but an empty value clears the field.
+fn parse_opt<T>(key: &str, value: &str) -> SettingsResult<Option<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    non_empty(value).map(|v| parse(key, &v)).transpose()
+}
+
+fn parse_bool(key: &str, value: &str) -> SettingsResult<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(SettingsError::InvalidEnv {
+            key: key.to_string(),
+            value: value.to_string(),
+            reason: "expected boolean (true|false|1|0|yes|no|on|off)".to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solti_observe::LoggerFormat;
+
+    #[test]
+    fn overrides_known_keys() {
+        let mut s = SupervisorSettings::default();
+        s.apply_overrides([
+            ("SOLTI_SUPERVISOR_GRACE_MS", "1500"),
+            ("SOLTI_CONTROLLER_SLOT_CAPACITY", "7"),
+            ("SOLTI_CONTROLLER_DEDUP", "true"),
+            ("SOLTI_LOGGER_FORMAT", "json"),
+            ("SOLTI_LOGGER_USE_COLOR", "off"),
+            ("SOLTI_API_HTTP_ADDR", "127.0.0.1:9000"),
+        ])
+        .unwrap();
+
+        assert_eq!(s.supervisor.grace_ms, 1500);
+        assert_eq!(s.controller.slot_capacity, 7);
+        assert!(s.controller.dedup);
+        assert_eq!(s.logger.format, LoggerFormat::Json);
+        assert!(!s.logger.use_color);
+        assert_eq!(s.api.http_addr.as_deref(), Some("127.0.0.1:9000"));
+    }
+
+    #[test]
+    fn task_log_dir_toggles_task_logs() {
+        let mut s = SupervisorSettings::default();
+        s.apply_overrides([("SOLTI_LOGGER_TASK_LOG_DIR", "/var/log/solti")])
+            .unwrap();
+        let task_logs = s.logger.task_logs.clone().expect("task logs enabled");
+        assert_eq!(task_logs.dir, std::path::PathBuf::from("/var/log/solti"));
+        assert_eq!(task_logs.max_files, TaskLogConfig::default().max_files);
+
+        s.apply_overrides([("SOLTI_LOGGER_TASK_LOG_DIR", "")])
+            .unwrap();
+        assert!(s.logger.task_logs.is_none());
+    }
+
+    #[test]
+    fn capacity_overrides_set_and_clear_limits() {
+        let mut s = SupervisorSettings::default();
+        s.apply_overrides([
+            ("SOLTI_CAPACITY_CPU_MILLIS", "4000"),
+            ("SOLTI_CAPACITY_GPUS", "2"),
+        ])
+        .unwrap();
+        assert_eq!(s.capacity.cpu_millis, Some(4000));
+        assert_eq!(s.capacity.gpus, Some(2));
+
+        s.apply_overrides([("SOLTI_CAPACITY_GPUS", "")]).unwrap();
+        assert_eq!(s.capacity.gpus, None);
+    }
+
+    #[test]
+    fn ignores_foreign_and_unknown_keys() {
+        let mut s = SupervisorSettings::default();
+        s.apply_overrides([("PATH", "/usr/bin"), ("SOLTI_SOMETHING_ELSE", "x")])
+            .unwrap();
+        assert!(s.discovery.is_none());
+    }
+
+    #[test]
+    fn discovery_override_enables_section() {
+        let mut s = SupervisorSettings::default();
+        s.apply_overrides([("SOLTI_DISCOVERY_DELAY_MS", "2500")])
+            .unwrap();
+
+        let d = s.discovery.expect("discovery enabled");
+        assert_eq!(d.delay_ms, 2500);
+        assert_eq!(d.transport, "grpc");
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        let mut s = SupervisorSettings::default();
+        let err = s
+            .apply_overrides([("SOLTI_SUPERVISOR_GRACE_MS", "soon")])
+            .unwrap_err();
+        assert!(matches!(err, SettingsError::InvalidEnv { .. }));
+
+        let err = s
+            .apply_overrides([("SOLTI_METRICS_ENABLED", "maybe")])
+            .unwrap_err();
+        assert!(matches!(err, SettingsError::InvalidEnv { .. }));
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SettingsError {
+    #[error("failed to read settings file '{path}': {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("failed to parse settings: {0}")]
+    Parse(String),
+
+    #[error("unsupported settings format: {0} (expected: toml|yaml|yml)")]
+    UnsupportedFormat(String),
+
+    #[error("settings format '{0}' is disabled (enable the corresponding crate feature)")]
+    FeatureDisabled(&'static str),
+
+    #[error("invalid value for {key}='{value}': {reason}")]
+    InvalidEnv {
+        key: String,
+        value: String,
+        reason: String,
+    },
+
+    #[error("invalid settings: {0}")]
+    Invalid(String),
+}
+
+pub type SettingsResult<T> = Result<T, SettingsError>;
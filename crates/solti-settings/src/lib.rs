@@ -0,0 +1,52 @@
+//! Declarative configuration for solti agents.
+//!
+//! [`SupervisorSettings`] gathers supervisor, controller, logger, metrics, API and discovery
+//! settings into one structure loaded from a TOML/YAML file, with `SOLTI_*` environment overrides.
+//!
+//! ## Example
+//! ```rust,ignore
+//! use solti_settings::SupervisorSettings;
+//!
+//! let settings = SupervisorSettings::load("/etc/solti/agent.toml")?;
+//! init_logger(&settings.logger)?;
+//!
+//! let api = SupervisorApi::new(
+//!     settings.supervisor.to_config(),
+//!     settings.controller.to_config(),
+//!     subscribers,
+//!     router,
+//! )
+//! .await?;
+//! ```
+//!
+//! ## Formats
+//! File format is selected by extension and gated by crate features:
+//! - `.toml` — requires feature `toml`
+//! - `.yaml` / `.yml` — requires feature `yaml`
+//!
+//! ## Reload
+//! [`ConfigReloader`] re-reads the file and applies runtime-safe changes (log level,
+//! discovery interval, rate limits), reporting the rest as requiring restart.
+//! With feature `signal` (unix only), [`sighup_reload`] triggers a reload on `SIGHUP`.
+mod error;
+pub use error::{SettingsError, SettingsResult};
+
+mod env;
+pub use env::ENV_PREFIX;
+
+mod sections;
+pub use sections::{
+    ApiOptions, ControllerOptions, DiscoveryOptions, DiscoveryTlsOptions, MetricsOptions, OsSignal,
+    QueueLimitOptions, RateLimitOptions, SignalOptions, SupervisorOptions,
+};
+
+mod settings;
+pub use settings::SupervisorSettings;
+
+mod reload;
+pub use reload::{ConfigReloader, HOOK_APPLIED_SETTINGS, ReloadHook};
+
+#[cfg(all(unix, feature = "signal"))]
+mod signal;
+#[cfg(all(unix, feature = "signal"))]
+pub use signal::{SIGHUP_RELOAD_SLOT, sighup_reload};
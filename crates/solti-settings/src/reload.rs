@@ -0,0 +1,333 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use solti_model::ReloadReport;
+use solti_observe::{LoggerError, reload_level};
+use tracing::{debug, info, warn};
+
+use crate::{
+    error::{SettingsError, SettingsResult},
+    settings::SupervisorSettings,
+};
+
+/// Callback invoked after a reload that changed at least one setting.
+///
+/// Receives the new settings and the report describing what changed.
+pub type ReloadHook = Arc<dyn Fn(&SupervisorSettings, &ReloadReport) + Send + Sync>;
+
+/// Settings that take effect at runtime only through a hook registered with
+/// [`ConfigReloader::with_hook_for`].
+pub const HOOK_APPLIED_SETTINGS: [&str; 3] = ["discovery.delay_ms", "rate_limits", "queue_limits"];
+
+/// Re-reads the settings file and applies changes that are safe at runtime.
+///
+/// Runtime-safe settings:
+/// - `logger.level` — swapped in the running logger via [`reload_level`].
+/// - `discovery.delay_ms`, `rate_limits`, `queue_limits` — applied by the hooks registered for
+///   them with [`ConfigReloader::with_hook_for`]; the agent registers hooks that update the
+///   discovery sync delay, the supervisor's restart limiter and its queue limits.
+///   Without such a hook they are treated like every other setting below.
+///
+/// Every other change is recorded in [`ReloadReport::requires_restart`] and is **not** applied:
+/// [`ConfigReloader::current`] keeps the previous value until the process restarts.
+pub struct ConfigReloader {
+    path: PathBuf,
+    current: Mutex<SupervisorSettings>,
+    hooks: Vec<ReloadHook>,
+    /// Settings from [`HOOK_APPLIED_SETTINGS`] that a hook applies.
+    handled: Vec<&'static str>,
+}
+
+impl ConfigReloader {
+    /// Create a reloader for the file the given settings were loaded from.
+    pub fn new(path: impl AsRef<Path>, current: SupervisorSettings) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            current: Mutex::new(current),
+            hooks: Vec::new(),
+            handled: Vec::new(),
+        }
+    }
+
+    /// Register a hook called after every reload that changed something.
+    pub fn with_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SupervisorSettings, &ReloadReport) + Send + Sync + 'static,
+    {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    /// Register a hook that applies `settings` (from [`HOOK_APPLIED_SETTINGS`]) to the running
+    /// process, so that changes to them are reported as applied.
+    ///
+    /// The hook is called like one registered with [`ConfigReloader::with_hook`].
+    pub fn with_hook_for<F>(mut self, settings: &[&'static str], hook: F) -> Self
+    where
+        F: Fn(&SupervisorSettings, &ReloadReport) + Send + Sync + 'static,
+    {
+        for key in settings {
+            debug_assert!(
+                HOOK_APPLIED_SETTINGS.contains(key),
+                "{key} is not applied by hooks"
+            );
+            if !self.handled.contains(key) {
+                self.handled.push(key);
+            }
+        }
+        self.with_hook(hook)
+    }
+
+    /// Path of the watched settings file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Snapshot of the settings currently in effect.
+    pub fn current(&self) -> SupervisorSettings {
+        self.current.lock().expect("settings lock poisoned").clone()
+    }
+
+    /// Re-read the settings file (with `SOLTI_*` overrides) and apply runtime-safe changes.
+    pub fn reload(&self) -> SettingsResult<ReloadReport> {
+        let next = SupervisorSettings::load(&self.path)?;
+        self.apply(next)
+    }
+
+    /// Apply already loaded settings as if they were read from the file.
+    pub fn apply(&self, next: SupervisorSettings) -> SettingsResult<ReloadReport> {
+        next.validate()?;
+        let mut current = self.current.lock().expect("settings lock poisoned");
+        let report = diff(&current, &next, &self.handled);
+
+        if !report.has_changes() {
+            debug!(path = %self.path.display(), "settings reloaded, nothing changed");
+            return Ok(report);
+        }
+
+        if report.is_applied("logger.level") {
+            match reload_level(&next.logger.level) {
+                Ok(()) | Err(LoggerError::NotInitialized) => {}
+                Err(e) => return Err(SettingsError::Invalid(e.to_string())),
+            }
+            current.logger.level = next.logger.level.clone();
+        }
+        if report.is_applied("rate_limits") {
+            current.rate_limits = next.rate_limits.clone();
+        }
+        if report.is_applied("queue_limits") {
+            current.queue_limits = next.queue_limits.clone();
+        }
+        if report.is_applied("discovery.delay_ms")
+            && let (Some(cur), Some(new)) = (current.discovery.as_mut(), next.discovery.as_ref())
+        {
+            cur.delay_ms = new.delay_ms;
+        }
+
+        if !report.requires_restart.is_empty() {
+            warn!(
+                settings = ?report.requires_restart,
+                "some changed settings require restart to take effect"
+            );
+        }
+        info!(applied = ?report.applied, "settings reloaded");
+
+        let snapshot = current.clone();
+        drop(current);
+        for hook in &self.hooks {
+            hook(&snapshot, &report);
+        }
+        Ok(report)
+    }
+}
+
+/// Compare two settings and classify every changed field.
+///
+/// Settings from [`HOOK_APPLIED_SETTINGS`] count as applied only if `handled` lists them.
+fn diff(old: &SupervisorSettings, new: &SupervisorSettings, handled: &[&str]) -> ReloadReport {
+    let mut report = ReloadReport::default();
+    let hooked = |report: &mut ReloadReport, key: &str, changed: bool| {
+        if !changed {
+            return;
+        }
+        if handled.contains(&key) {
+            report.applied.push(key.to_string());
+        } else {
+            report.requires_restart.push(key.to_string());
+        }
+    };
+    let mut restart = |key: &str, changed: bool| {
+        if changed {
+            report.requires_restart.push(key.to_string());
+        }
+    };
+
+    restart("supervisor", old.supervisor != new.supervisor);
+    restart("controller", old.controller != new.controller);
+    restart("logger.format", old.logger.format != new.logger.format);
+    restart("logger.tz", old.logger.tz != new.logger.tz);
+    restart(
+        "logger.with_targets",
+        old.logger.with_targets != new.logger.with_targets,
+    );
+    restart(
+        "logger.use_color",
+        old.logger.use_color != new.logger.use_color,
+    );
+    restart(
+        "logger.task_logs",
+        old.logger.task_logs != new.logger.task_logs,
+    );
+    restart("metrics", old.metrics != new.metrics);
+    restart("api", old.api != new.api);
+    restart("quotas", old.quotas != new.quotas);
+    restart("capacity", old.capacity != new.capacity);
+    restart("signals", old.signals != new.signals);
+
+    match (&old.discovery, &new.discovery) {
+        (Some(a), Some(b)) => {
+            let rest_changed = a.name != b.name
+                || a.control_plane_endpoint != b.control_plane_endpoint
+                || a.agent_endpoint != b.agent_endpoint
+                || a.transport != b.transport
+                || a.metadata != b.metadata
+                || a.tls != b.tls
+                || a.token != b.token
+                || a.token_file != b.token_file
+                || a.labels != b.labels
+                || a.taints != b.taints;
+            restart("discovery", rest_changed);
+            hooked(&mut report, "discovery.delay_ms", a.delay_ms != b.delay_ms);
+        }
+        (None, None) => {}
+        _ => report.requires_restart.push("discovery".to_string()),
+    }
+
+    if old.logger.level.as_str() != new.logger.level.as_str() {
+        report.applied.push("logger.level".to_string());
+    }
+    hooked(
+        &mut report,
+        "rate_limits",
+        old.rate_limits != new.rate_limits,
+    );
+    hooked(
+        &mut report,
+        "queue_limits",
+        old.queue_limits != new.queue_limits,
+    );
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::DiscoveryOptions;
+
+    fn with_discovery(delay_ms: u64) -> SupervisorSettings {
+        SupervisorSettings {
+            discovery: Some(DiscoveryOptions {
+                delay_ms,
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unchanged_settings_produce_empty_report() {
+        let r = ConfigReloader::new("agent.toml", SupervisorSettings::default());
+        let report = r.apply(SupervisorSettings::default()).unwrap();
+        assert!(!report.has_changes());
+    }
+
+    #[test]
+    fn runtime_safe_changes_are_applied() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&calls);
+        let r = ConfigReloader::new("agent.toml", with_discovery(1_000)).with_hook_for(
+            &HOOK_APPLIED_SETTINGS,
+            move |s, _| {
+                assert_eq!(s.discovery.as_ref().unwrap().delay_ms, 5_000);
+                seen.fetch_add(1, Ordering::SeqCst);
+            },
+        );
+
+        let mut next = with_discovery(5_000);
+        next.logger.level = "debug".parse().unwrap();
+        next.rate_limits.default = Some(solti_model::RestartRateLimit::per_minute(6));
+        next.queue_limits.slots.insert("backup".into(), 4);
+        let report = r.apply(next).unwrap();
+
+        assert!(report.is_applied("logger.level"));
+        assert!(report.is_applied("rate_limits"));
+        assert!(r.current().rate_limits.default.is_some());
+        assert!(report.is_applied("queue_limits"));
+        assert_eq!(r.current().queue_limits.slots["backup"], 4);
+        assert!(report.is_applied("discovery.delay_ms"));
+        assert!(report.requires_restart.is_empty());
+        assert_eq!(r.current().logger.level.as_str(), "debug");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn settings_without_hooks_require_restart() {
+        let r = ConfigReloader::new("agent.toml", with_discovery(1_000))
+            .with_hook_for(&["queue_limits"], |_, _| {});
+
+        let mut next = with_discovery(5_000);
+        next.rate_limits.default = Some(solti_model::RestartRateLimit::per_minute(6));
+        next.queue_limits.default = Some(8);
+        let report = r.apply(next).unwrap();
+
+        assert_eq!(report.applied, vec!["queue_limits"]);
+        assert_eq!(
+            report.requires_restart,
+            vec!["discovery.delay_ms", "rate_limits"]
+        );
+        assert!(r.current().rate_limits.default.is_none());
+        assert_eq!(r.current().discovery.unwrap().delay_ms, 1_000);
+    }
+
+    #[test]
+    fn restart_only_changes_are_reported_but_not_applied() {
+        let r = ConfigReloader::new("agent.toml", SupervisorSettings::default());
+
+        let mut next = SupervisorSettings::default();
+        next.api.http_addr = Some("0.0.0.0:8080".into());
+        next.controller.slot_capacity = 1;
+        next.discovery = Some(DiscoveryOptions::default());
+        let report = r.apply(next).unwrap();
+
+        assert!(report.applied.is_empty());
+        assert_eq!(
+            report.requires_restart,
+            vec!["controller", "api", "discovery"]
+        );
+        assert!(r.current().api.http_addr.is_none());
+    }
+
+    #[test]
+    fn invalid_rate_limits_are_rejected() {
+        let r = ConfigReloader::new("agent.toml", SupervisorSettings::default())
+            .with_hook_for(&["rate_limits"], |_, _| {});
+
+        let mut next = SupervisorSettings::default();
+        next.rate_limits.default = Some(solti_model::RestartRateLimit {
+            max_runs: 0,
+            window_ms: 60_000,
+        });
+        assert!(matches!(r.apply(next), Err(SettingsError::Invalid(_))));
+        assert!(r.current().rate_limits.default.is_none());
+    }
+
+    #[test]
+    fn reload_propagates_file_errors() {
+        let r = ConfigReloader::new("/nonexistent/agent.toml", SupervisorSettings::default());
+        assert!(matches!(r.reload(), Err(SettingsError::Io { .. })));
+    }
+}
@@ -0,0 +1,467 @@
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use solti_model::RestartRateLimit;
+use taskvisor::{ControllerConfig, SupervisorConfig};
+
+/// Supervisor runtime options.
+///
+/// Mirrors the tunable parts of [`SupervisorConfig`]; restart/backoff defaults stay
+/// in code because every submitted task carries its own policies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorOptions {
+    /// Grace period for task shutdown in milliseconds.
+    pub grace_ms: u64,
+    /// Global concurrency limit (`0` = unlimited).
+    pub max_concurrent: usize,
+    /// Capacity of the internal event bus.
+    pub bus_capacity: usize,
+    /// Default task timeout in milliseconds (`0` = none).
+    pub timeout_ms: u64,
+}
+
+impl Default for SupervisorOptions {
+    fn default() -> Self {
+        Self::from(&SupervisorConfig::default())
+    }
+}
+
+impl From<&SupervisorConfig> for SupervisorOptions {
+    fn from(cfg: &SupervisorConfig) -> Self {
+        Self {
+            grace_ms: cfg.grace.as_millis() as u64,
+            max_concurrent: cfg.max_concurrent,
+            bus_capacity: cfg.bus_capacity,
+            timeout_ms: cfg.timeout.as_millis() as u64,
+        }
+    }
+}
+
+impl SupervisorOptions {
+    /// Build a taskvisor [`SupervisorConfig`] from these options.
+    pub fn to_config(&self) -> SupervisorConfig {
+        SupervisorConfig {
+            grace: Duration::from_millis(self.grace_ms),
+            max_concurrent: self.max_concurrent,
+            bus_capacity: self.bus_capacity,
+            timeout: Duration::from_millis(self.timeout_ms),
+            ..SupervisorConfig::default()
+        }
+    }
+}
+
+/// Controller (slot admission) options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControllerOptions {
+    /// Capacity of the submission queue.
+    pub queue_capacity: usize,
+    /// Capacity of the slots.
+    pub slot_capacity: usize,
+    /// Return the active task of an identical spec instead of submitting a duplicate.
+    pub dedup: bool,
+}
+
+impl Default for ControllerOptions {
+    fn default() -> Self {
+        let cfg = ControllerConfig::default();
+        Self {
+            queue_capacity: cfg.queue_capacity,
+            slot_capacity: cfg.slot_capacity,
+            dedup: false,
+        }
+    }
+}
+
+impl ControllerOptions {
+    /// Build a taskvisor [`ControllerConfig`] from these options.
+    pub fn to_config(&self) -> ControllerConfig {
+        ControllerConfig {
+            queue_capacity: self.queue_capacity,
+            slot_capacity: self.slot_capacity,
+        }
+    }
+}
+
+/// Metrics exposition options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetricsOptions {
+    /// Whether the metrics backend and endpoint are enabled.
+    pub enabled: bool,
+    /// HTTP path of the metrics endpoint.
+    pub path: String,
+}
+
+impl Default for MetricsOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "/metrics".to_string(),
+        }
+    }
+}
+
+/// API listener options.
+///
+/// A transport is served only when its address is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiOptions {
+    /// Bind address of the HTTP API (e.g. `"0.0.0.0:8080"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub http_addr: Option<String>,
+    /// Bind address of the gRPC API (e.g. `"[::1]:50051"`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grpc_addr: Option<String>,
+}
+
+/// Per-slot restart rate limits.
+///
+/// Safe to change at runtime when a reload hook applies `rate_limits`
+/// (see [`crate::ConfigReloader::with_hook_for`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitOptions {
+    /// Limit for slots without their own entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<RestartRateLimit>,
+    /// Slot-specific limits.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub slots: HashMap<String, RestartRateLimit>,
+}
+
+impl RateLimitOptions {
+    /// Check that every limit allows at least one run per non-empty window.
+    pub fn validate(&self) -> Result<(), crate::SettingsError> {
+        let limits = self
+            .default
+            .iter()
+            .map(|l| ("default", l))
+            .chain(self.slots.iter().map(|(slot, l)| (slot.as_str(), l)));
+        for (name, limit) in limits {
+            if limit.max_runs == 0 || limit.window_ms == 0 {
+                return Err(crate::SettingsError::Invalid(format!(
+                    "rate limit {name}: max_runs and window_ms must be positive"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Per-slot queue length caps of slots admitted with `Queue`.
+///
+/// Safe to change at runtime when a reload hook applies `queue_limits`
+/// (see [`crate::ConfigReloader::with_hook_for`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueueLimitOptions {
+    /// Cap for slots without their own entry.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<usize>,
+    /// Slot-specific caps.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub slots: HashMap<String, usize>,
+}
+
+/// OS signal the agent can react to.
+///
+/// Only [`OsSignal::Interrupt`] (Ctrl+C) is delivered on non-unix platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum OsSignal {
+    #[serde(rename = "SIGINT", alias = "INT")]
+    Interrupt,
+    #[serde(rename = "SIGTERM", alias = "TERM")]
+    Terminate,
+    #[serde(rename = "SIGQUIT", alias = "QUIT")]
+    Quit,
+    #[serde(rename = "SIGHUP", alias = "HUP")]
+    Hangup,
+    #[serde(rename = "SIGUSR1", alias = "USR1")]
+    User1,
+    #[serde(rename = "SIGUSR2", alias = "USR2")]
+    User2,
+}
+
+impl OsSignal {
+    /// Conventional signal name, e.g. `"SIGTERM"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OsSignal::Interrupt => "SIGINT",
+            OsSignal::Terminate => "SIGTERM",
+            OsSignal::Quit => "SIGQUIT",
+            OsSignal::Hangup => "SIGHUP",
+            OsSignal::User1 => "SIGUSR1",
+            OsSignal::User2 => "SIGUSR2",
+        }
+    }
+}
+
+impl std::fmt::Display for OsSignal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which OS signals trigger which agent action.
+///
+/// A signal may be bound to one action only; unbound signals keep the process default.
+/// Note that the supervisor run loop additionally cancels its tasks on `SIGINT`, `SIGTERM`
+/// and `SIGQUIT`, whatever they are bound to here.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SignalOptions {
+    /// Stop serving, give running tasks the grace period and deregister.
+    pub shutdown: Vec<OsSignal>,
+    /// Stop serving without waiting for tasks or deregistering.
+    pub immediate: Vec<OsSignal>,
+    /// Reload the settings file.
+    pub reload: Vec<OsSignal>,
+    /// Write a JSON snapshot of the agent state into `dump_dir`.
+    pub dump: Vec<OsSignal>,
+    /// Directory for state dumps; the system temp directory when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dump_dir: Option<PathBuf>,
+}
+
+impl Default for SignalOptions {
+    fn default() -> Self {
+        Self {
+            shutdown: vec![OsSignal::Interrupt, OsSignal::Terminate],
+            immediate: vec![OsSignal::Quit],
+            reload: vec![OsSignal::Hangup],
+            dump: vec![OsSignal::User2],
+            dump_dir: None,
+        }
+    }
+}
+
+impl SignalOptions {
+    /// Check that no signal is bound to more than one action.
+    pub fn validate(&self) -> Result<(), crate::SettingsError> {
+        let mut seen = HashMap::new();
+        for (action, signals) in [
+            ("shutdown", &self.shutdown),
+            ("immediate", &self.immediate),
+            ("reload", &self.reload),
+            ("dump", &self.dump),
+        ] {
+            for signal in signals {
+                if let Some(other) = seen.insert(*signal, action)
+                    && other != action
+                {
+                    return Err(crate::SettingsError::Invalid(format!(
+                        "signal {signal} is bound to both {other} and {action}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Discovery (control plane sync) options.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryOptions {
+    /// Agent name reported to the control plane.
+    pub name: String,
+    /// Control plane endpoint (e.g. `"http://lighthouse:8082"`).
+    pub control_plane_endpoint: String,
+    /// Endpoint at which this agent is reachable.
+    pub agent_endpoint: String,
+    /// Transport used for sync: `"grpc"`, `"http"`, `"mdns"` or `"websocket"`.
+    pub transport: String,
+    /// Free-form metadata attached to every sync.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+    /// Delay between syncs in milliseconds.
+    ///
+    /// Safe to change at runtime when a reload hook applies `discovery.delay_ms`.
+    pub delay_ms: u64,
+    /// TLS / mTLS settings; plaintext when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tls: Option<DiscoveryTlsOptions>,
+    /// Registration token sent with every sync.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// File holding the registration token; re-read when it changes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_file: Option<PathBuf>,
+    /// Scheduling labels (well-known keys: `region`, `zone`, `role`).
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
+    /// Taints in `key[=value]:Effect` form (e.g. `"gpu=true:NoSchedule"`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub taints: Vec<String>,
+}
+
+/// TLS / mTLS settings for the discovery connection.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiscoveryTlsOptions {
+    /// PEM CA bundle; platform roots are used when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ca_cert: Option<PathBuf>,
+    /// PEM client certificate chain (mTLS).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+    /// PEM client private key (mTLS).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+    /// SNI / verification name override.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_name: Option<String>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            control_plane_endpoint: String::new(),
+            agent_endpoint: String::new(),
+            transport: "grpc".to_string(),
+            metadata: HashMap::new(),
+            delay_ms: 10_000,
+            tls: None,
+            token: None,
+            token_file: None,
+            labels: HashMap::new(),
+            taints: Vec::new(),
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    /// Build a [`solti_discover::DiscoverConfig`] from these options.
+    #[cfg(feature = "discover")]
+    pub fn to_config(&self) -> Result<solti_discover::DiscoverConfig, crate::SettingsError> {
+        use solti_discover::{DiscoverConfig, DiscoveryTransport, TokenSource};
+
+        let transport = match self.transport.trim().to_ascii_lowercase().as_str() {
+            "grpc" => DiscoveryTransport::Grpc,
+            "http" => DiscoveryTransport::Http,
+            "mdns" => DiscoveryTransport::Mdns,
+            "websocket" => DiscoveryTransport::WebSocket,
+            other => {
+                return Err(crate::SettingsError::Invalid(format!(
+                    "unknown discovery transport: {other} (expected: grpc|http|mdns|websocket)"
+                )));
+            }
+        };
+        let token = match (&self.token, &self.token_file) {
+            (Some(_), Some(_)) => {
+                return Err(crate::SettingsError::Invalid(
+                    "discovery token and token_file are mutually exclusive".into(),
+                ));
+            }
+            (Some(token), None) => Some(TokenSource::Static(token.clone())),
+            (None, Some(path)) => Some(TokenSource::File(path.clone())),
+            (None, None) => None,
+        };
+        let taints = self
+            .taints
+            .iter()
+            .map(|t| {
+                t.parse::<solti_model::Taint>()
+                    .map_err(|e| crate::SettingsError::Invalid(format!("discovery taint: {e}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut labels = solti_model::RunnerLabels::new();
+        for (k, v) in &self.labels {
+            labels.insert(k.clone(), v.clone());
+        }
+        Ok(DiscoverConfig {
+            metadata: self.metadata.clone(),
+            control_plane_endpoint: self.control_plane_endpoint.clone(),
+            transport,
+            agent_endpoint: self.agent_endpoint.clone(),
+            name: self.name.clone(),
+            delay_ms: self.delay_ms,
+            tls: self.tls.as_ref().map(|tls| solti_discover::TlsConfig {
+                ca_cert: tls.ca_cert.clone(),
+                client_cert: tls.client_cert.clone(),
+                client_key: tls.client_key.clone(),
+                server_name: tls.server_name.clone(),
+            }),
+            token,
+            labels,
+            taints,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn supervisor_defaults_match_taskvisor() {
+        let opts = SupervisorOptions::default();
+        let cfg = opts.to_config();
+        let def = SupervisorConfig::default();
+
+        assert_eq!(cfg.grace, def.grace);
+        assert_eq!(cfg.max_concurrent, def.max_concurrent);
+        assert_eq!(cfg.bus_capacity, def.bus_capacity);
+        assert_eq!(cfg.timeout, def.timeout);
+    }
+
+    #[test]
+    fn signals_bound_twice_are_rejected() {
+        assert!(SignalOptions::default().validate().is_ok());
+
+        let opts = SignalOptions {
+            dump: vec![OsSignal::Terminate],
+            ..Default::default()
+        };
+        let err = opts.validate().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "invalid settings: signal SIGTERM is bound to both shutdown and dump"
+        );
+    }
+
+    #[test]
+    fn rate_limits_allowing_no_runs_are_rejected() {
+        let mut opts = RateLimitOptions {
+            default: Some(RestartRateLimit::per_minute(6)),
+            ..Default::default()
+        };
+        assert!(opts.validate().is_ok());
+
+        opts.slots.insert(
+            "backup".into(),
+            RestartRateLimit {
+                max_runs: 0,
+                window_ms: 60_000,
+            },
+        );
+        let err = opts.validate().unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "invalid settings: rate limit backup: max_runs and window_ms must be positive"
+        );
+
+        opts.slots.clear();
+        opts.default = Some(RestartRateLimit {
+            max_runs: 1,
+            window_ms: 0,
+        });
+        assert!(opts.validate().is_err());
+    }
+
+    #[test]
+    fn controller_options_convert() {
+        let opts = ControllerOptions {
+            queue_capacity: 16,
+            slot_capacity: 4,
+            dedup: true,
+        };
+        let cfg = opts.to_config();
+        assert_eq!(cfg.queue_capacity, 16);
+        assert_eq!(cfg.slot_capacity, 4);
+    }
+}
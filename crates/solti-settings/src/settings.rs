@@ -0,0 +1,257 @@
+use std::{fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use solti_model::{ResourceCapacity, TaskQuota};
+use solti_observe::LoggerConfig;
+
+use crate::{
+    env::apply_overrides,
+    error::{SettingsError, SettingsResult},
+    sections::{
+        ApiOptions, ControllerOptions, DiscoveryOptions, MetricsOptions, QueueLimitOptions,
+        RateLimitOptions, SignalOptions, SupervisorOptions,
+    },
+};
+
+/// Complete agent configuration.
+///
+/// Every section is optional in the source file; missing sections fall back to defaults.
+///
+/// ```toml
+/// [supervisor]
+/// grace_ms = 30000
+///
+/// [logger]
+/// format = "json"
+/// level = "solti_core=debug,info"
+///
+/// [api]
+/// http_addr = "0.0.0.0:8080"
+///
+/// [discovery]
+/// name = "edge-01"
+/// control_plane_endpoint = "http://lighthouse:8082"
+/// transport = "http"
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SupervisorSettings {
+    /// Supervisor runtime options.
+    pub supervisor: SupervisorOptions,
+    /// Controller (slot admission) options.
+    pub controller: ControllerOptions,
+    /// Logger configuration.
+    pub logger: LoggerConfig,
+    /// Metrics exposition options.
+    pub metrics: MetricsOptions,
+    /// API listener options.
+    pub api: ApiOptions,
+    /// Per-slot restart rate limits.
+    pub rate_limits: RateLimitOptions,
+    /// Per-slot queue length caps.
+    pub queue_limits: QueueLimitOptions,
+    /// OS signal bindings of the agent.
+    pub signals: SignalOptions,
+    /// Task quotas enforced on submission.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quotas: Vec<TaskQuota>,
+    /// Resources the agent can allocate to tasks; unset dimensions are not enforced.
+    #[serde(skip_serializing_if = "ResourceCapacity::is_unbounded")]
+    pub capacity: ResourceCapacity,
+    /// Discovery options; discovery is disabled when absent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discovery: Option<DiscoveryOptions>,
+}
+
+impl SupervisorSettings {
+    /// Load settings from a file, apply `SOLTI_*` environment overrides and validate them.
+    ///
+    /// This is the primary entrypoint for agents.
+    pub fn load(path: impl AsRef<Path>) -> SettingsResult<Self> {
+        let mut settings = Self::from_file(path)?;
+        settings.apply_env()?;
+        settings.validate()?;
+        Ok(settings)
+    }
+
+    /// Check values that parse but cannot work (e.g. a rate limit allowing no runs).
+    pub fn validate(&self) -> SettingsResult<()> {
+        self.rate_limits.validate()?;
+        self.signals.validate()
+    }
+
+    /// Load settings from a file without environment overrides.
+    ///
+    /// The format is selected by file extension (`toml`, `yaml`, `yml`).
+    pub fn from_file(path: impl AsRef<Path>) -> SettingsResult<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|source| SettingsError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match ext.as_str() {
+            "toml" => Self::from_toml_str(&content),
+            "yaml" | "yml" => Self::from_yaml_str(&content),
+            other => Err(SettingsError::UnsupportedFormat(other.to_string())),
+        }
+    }
+
+    /// Parse settings from a TOML document.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(s: &str) -> SettingsResult<Self> {
+        toml::from_str(s).map_err(|e| SettingsError::Parse(e.to_string()))
+    }
+
+    /// Parse settings from a TOML document (feature `toml` disabled).
+    #[cfg(not(feature = "toml"))]
+    pub fn from_toml_str(_s: &str) -> SettingsResult<Self> {
+        Err(SettingsError::FeatureDisabled("toml"))
+    }
+
+    /// Parse settings from a YAML document.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_str(s: &str) -> SettingsResult<Self> {
+        serde_yaml::from_str(s).map_err(|e| SettingsError::Parse(e.to_string()))
+    }
+
+    /// Parse settings from a YAML document (feature `yaml` disabled).
+    #[cfg(not(feature = "yaml"))]
+    pub fn from_yaml_str(_s: &str) -> SettingsResult<Self> {
+        Err(SettingsError::FeatureDisabled("yaml"))
+    }
+
+    /// Apply `SOLTI_*` overrides from the process environment.
+    pub fn apply_env(&mut self) -> SettingsResult<()> {
+        self.apply_overrides(std::env::vars())
+    }
+
+    /// Apply `SOLTI_*` overrides from an explicit list of variables.
+    ///
+    /// Variables without the [`crate::ENV_PREFIX`] prefix and unknown keys are ignored.
+    pub fn apply_overrides<I, K, V>(&mut self, vars: I) -> SettingsResult<()>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (key, value) in vars {
+            apply_overrides(self, key.as_ref(), value.as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_have_no_api_and_no_discovery() {
+        let s = SupervisorSettings::default();
+        assert!(s.api.http_addr.is_none());
+        assert!(s.api.grpc_addr.is_none());
+        assert!(s.discovery.is_none());
+        assert!(s.metrics.enabled);
+    }
+
+    #[test]
+    fn serde_uses_defaults_for_missing_sections() {
+        let s: SupervisorSettings =
+            serde_json::from_str(r#"{"api": {"http_addr": "0.0.0.0:8080"}}"#).unwrap();
+        assert_eq!(s.api.http_addr.as_deref(), Some("0.0.0.0:8080"));
+        assert_eq!(s.controller, ControllerOptions::default());
+        assert_eq!(s.supervisor, SupervisorOptions::default());
+    }
+
+    #[test]
+    fn unsupported_extension_is_rejected() {
+        let dir = std::env::temp_dir().join("solti-settings-test.ini");
+        fs::write(&dir, "").unwrap();
+
+        let err = SupervisorSettings::from_file(&dir).unwrap_err();
+        assert!(matches!(err, SettingsError::UnsupportedFormat(_)));
+        let _ = fs::remove_file(&dir);
+    }
+
+    #[test]
+    fn missing_file_is_io_error() {
+        let err = SupervisorSettings::from_file("/nonexistent/solti.toml").unwrap_err();
+        assert!(matches!(err, SettingsError::Io { .. }));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn parses_toml() {
+        use crate::OsSignal;
+
+        let s = SupervisorSettings::from_toml_str(
+            r#"
+            [supervisor]
+            grace_ms = 5000
+
+            [logger]
+            format = "json"
+            level = "debug"
+
+            [[quotas]]
+            scope = { namespace = "batch" }
+            maxTasks = 10
+
+            [signals]
+            shutdown = ["SIGTERM"]
+            dump = ["USR1"]
+
+            [queue_limits]
+            default = 16
+            slots = { backup = 2 }
+
+            [capacity]
+            cpuMillis = 8000
+            gpus = 1
+
+            [discovery]
+            name = "edge-01"
+            control_plane_endpoint = "http://cp:8082"
+            transport = "http"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(s.supervisor.grace_ms, 5000);
+        assert_eq!(s.logger.level.as_str(), "debug");
+        assert_eq!(
+            s.quotas[0].scope,
+            solti_model::QuotaScope::namespace("batch")
+        );
+        assert_eq!(s.quotas[0].max_tasks, Some(10));
+        assert_eq!(s.queue_limits.default, Some(16));
+        assert_eq!(s.queue_limits.slots["backup"], 2);
+        assert_eq!(s.capacity.cpu_millis, Some(8000));
+        assert_eq!(s.capacity.memory_bytes, None);
+        assert_eq!(s.signals.shutdown, vec![OsSignal::Terminate]);
+        assert_eq!(s.signals.dump, vec![OsSignal::User1]);
+        assert_eq!(s.signals.reload, SignalOptions::default().reload);
+        let d = s.discovery.expect("discovery section");
+        assert_eq!(d.name, "edge-01");
+        assert_eq!(d.delay_ms, DiscoveryOptions::default().delay_ms);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn parses_yaml() {
+        let s = SupervisorSettings::from_yaml_str(
+            "controller:\n  queue_capacity: 8\napi:\n  grpc_addr: \"[::1]:50051\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(s.controller.queue_capacity, 8);
+        assert_eq!(s.api.grpc_addr.as_deref(), Some("[::1]:50051"));
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use solti_model::{
+    AdmissionStrategy, BackoffStrategy, CreateSpec, JitterStrategy, RestartStrategy, RunnerLabels,
+    TaskKind,
+};
+use taskvisor::{TaskError, TaskFn, TaskRef};
+use tokio::signal::unix::{SignalKind, signal};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::reload::ConfigReloader;
+
+/// Logical slot name used for the SIGHUP reload listener.
+pub const SIGHUP_RELOAD_SLOT: &str = "solti-settings-sighup";
+
+/// Build the SIGHUP listener task and its model-level specification.
+///
+/// Each `SIGHUP` received by the process triggers [`ConfigReloader::reload`].
+/// Reload failures are logged and the previous settings stay in effect.
+///
+/// Returns:
+/// - [`TaskRef`]    — executable task body.
+/// - [`CreateSpec`] — restart/backoff/admission policy and slot binding.
+pub fn sighup_reload(reloader: Arc<ConfigReloader>) -> (TaskRef, CreateSpec) {
+    let task: TaskRef = TaskFn::arc(SIGHUP_RELOAD_SLOT, move |ctx: CancellationToken| {
+        let reloader = Arc::clone(&reloader);
+
+        async move {
+            let mut hup = signal(SignalKind::hangup()).map_err(|e| TaskError::Fatal {
+                reason: format!("failed to install SIGHUP handler: {e}"),
+            })?;
+
+            loop {
+                tokio::select! {
+                    _ = ctx.cancelled() => return Err(TaskError::Canceled),
+                    received = hup.recv() => {
+                        if received.is_none() {
+                            return Ok(());
+                        }
+                        info!(path = %reloader.path().display(), "SIGHUP received, reloading settings");
+                        if let Err(e) = reloader.reload() {
+                            warn!(error = %e, "settings reload failed");
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    let backoff = BackoffStrategy {
+        jitter: JitterStrategy::None,
+        first_ms: 1_000,
+        max_ms: 1_000,
+        factor: 1.0,
+    };
+    let spec = CreateSpec {
+        slot: SIGHUP_RELOAD_SLOT.to_string(),
+        timeout_ms: 0,
+        restart: RestartStrategy::OnFailure,
+        backoff,
+        admission: AdmissionStrategy::Replace,
+        kind: TaskKind::None,
+        labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
+    };
+    (task, spec)
+}
@@ -0,0 +1,83 @@
+use solti_model::{TaskEvent, TaskEventKind, TaskId};
+
+/// Kinds of `events`, in order.
+pub fn event_kinds(events: &[TaskEvent]) -> Vec<TaskEventKind> {
+    events.iter().map(|e| e.kind).collect()
+}
+
+/// Events belonging to task `id`.
+pub fn events_for<'a>(events: &'a [TaskEvent], id: &TaskId) -> Vec<&'a TaskEvent> {
+    events
+        .iter()
+        .filter(|e| e.task.as_ref() == Some(id))
+        .collect()
+}
+
+/// Assert that `expected` kinds occur in `events` in this order.
+///
+/// Other events may appear in between, so assertions stay stable when the agent
+/// adds events of its own.
+#[track_caller]
+pub fn assert_event_kinds(events: &[TaskEvent], expected: &[TaskEventKind]) {
+    let mut remaining = events.iter().map(|e| e.kind);
+    for (i, kind) in expected.iter().enumerate() {
+        assert!(
+            remaining.any(|k| k == *kind),
+            "event {kind:?} (#{i} of {expected:?}) not found in order; got {:?}",
+            event_kinds(events)
+        );
+    }
+}
+
+/// Assert that no event of `kind` occurs in `events`.
+#[track_caller]
+pub fn assert_no_event(events: &[TaskEvent], kind: TaskEventKind) {
+    assert!(
+        events.iter().all(|e| e.kind != kind),
+        "unexpected event {kind:?}; got {:?}",
+        event_kinds(events)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(seq: u64, kind: TaskEventKind, task: &str) -> TaskEvent {
+        TaskEvent {
+            seq,
+            timestamp_ms: seq,
+            kind,
+            task: Some(TaskId::from(task)),
+            slot: None,
+            attempt: None,
+            reason: None,
+            timeout_ms: None,
+            delay_ms: None,
+        }
+    }
+
+    #[test]
+    fn kinds_are_matched_in_order() {
+        let events = [
+            event(1, TaskEventKind::TaskAdded, "a"),
+            event(2, TaskEventKind::TaskStarting, "a"),
+            event(3, TaskEventKind::TaskAdded, "b"),
+            event(4, TaskEventKind::TaskStopped, "a"),
+        ];
+        assert_event_kinds(
+            &events,
+            &[TaskEventKind::TaskAdded, TaskEventKind::TaskStopped],
+        );
+        assert_no_event(&events, TaskEventKind::TaskFailed);
+        assert_eq!(events_for(&events, &TaskId::from("b")).len(), 1);
+
+        let out_of_order = std::panic::catch_unwind(|| {
+            assert_event_kinds(
+                &events,
+                &[TaskEventKind::TaskStopped, TaskEventKind::TaskStarting],
+            )
+        });
+        assert!(out_of_order.is_err());
+    }
+}
@@ -0,0 +1,477 @@
+use std::{
+    collections::VecDeque,
+    sync::Mutex,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use async_trait::async_trait;
+use solti_api::{ApiError, ApiHandler};
+use solti_model::{
+    CreateSpec, EventQuery, GroupInfo, TaskEvent, TaskEventKind, TaskId, TaskInfo, TaskPage,
+    TaskQuery, TaskStatus,
+};
+use tokio::sync::watch;
+
+/// In-memory [`ApiHandler`] for tests.
+///
+/// Submissions are recorded and registered as pending tasks; nothing runs. Tests move
+/// tasks through their lifecycle with [`FakeHandler::set_status`] / [`FakeHandler::fail`],
+/// which also record the lifecycle events the agent would emit, so `wait_*`, group and
+/// event endpoints behave like against a real agent.
+pub struct FakeHandler {
+    inner: Mutex<Inner>,
+    revision: watch::Sender<u64>,
+}
+
+#[derive(Default)]
+struct Inner {
+    /// Tasks in registration order.
+    tasks: Vec<TaskInfo>,
+    submitted: Vec<CreateSpec>,
+    canceled: Vec<TaskId>,
+    events: Vec<TaskEvent>,
+    submit_errors: VecDeque<ApiError>,
+    next_id: u64,
+    next_seq: u64,
+    maintenance: bool,
+}
+
+impl Inner {
+    fn task_mut(&mut self, id: &TaskId) -> Option<&mut TaskInfo> {
+        self.tasks.iter_mut().find(|t| &t.id == id)
+    }
+
+    fn push_event(&mut self, kind: TaskEventKind, info: &TaskInfo, reason: Option<String>) {
+        self.next_seq += 1;
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        self.events.push(TaskEvent {
+            seq: self.next_seq,
+            timestamp_ms,
+            kind,
+            task: Some(info.id.clone()),
+            slot: Some(info.slot.clone()),
+            attempt: (info.attempt > 0).then_some(info.attempt),
+            reason,
+            timeout_ms: None,
+            delay_ms: None,
+        });
+    }
+
+    fn transition(&mut self, id: &TaskId, status: TaskStatus, error: Option<String>) -> bool {
+        let now = SystemTime::now();
+        let Some(task) = self.task_mut(id) else {
+            return false;
+        };
+        if status == TaskStatus::Running {
+            task.attempt += 1;
+            task.started_at = Some(now);
+            task.finished_at = None;
+            task.duration_ms = None;
+        }
+        if status.is_terminal() {
+            task.finished_at = Some(now);
+            task.duration_ms = task
+                .started_at
+                .and_then(|at| now.duration_since(at).ok())
+                .map(|d| d.as_millis() as u64);
+        }
+        task.status = status;
+        task.updated_at = now;
+        task.error = error.clone();
+        let info = task.clone();
+
+        let kind = match status {
+            TaskStatus::Running => Some(TaskEventKind::TaskStarting),
+            TaskStatus::Succeeded | TaskStatus::Canceled => Some(TaskEventKind::TaskStopped),
+            TaskStatus::Failed => Some(TaskEventKind::TaskFailed),
+            TaskStatus::Timeout => Some(TaskEventKind::TimeoutHit),
+            TaskStatus::Exhausted => Some(TaskEventKind::ActorExhausted),
+            TaskStatus::Pending | TaskStatus::Skipped => None,
+        };
+        if let Some(kind) = kind {
+            self.push_event(kind, &info, error);
+        }
+        true
+    }
+}
+
+impl FakeHandler {
+    pub fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner::default()),
+            revision: watch::Sender::new(0),
+        }
+    }
+
+    /// Register `info` as if it had been submitted earlier.
+    pub fn insert(&self, info: TaskInfo) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tasks.retain(|t| t.id != info.id);
+        inner.tasks.push(info);
+        drop(inner);
+        self.bump();
+    }
+
+    /// Move task `id` to `status`, recording the matching lifecycle event.
+    ///
+    /// Entering [`TaskStatus::Running`] starts a new attempt. Returns `false` if the task is unknown.
+    pub fn set_status(&self, id: &TaskId, status: TaskStatus) -> bool {
+        self.update(id, status, None)
+    }
+
+    /// Fail task `id` with `reason`. Returns `false` if the task is unknown.
+    pub fn fail(&self, id: &TaskId, reason: impl Into<String>) -> bool {
+        self.update(id, TaskStatus::Failed, Some(reason.into()))
+    }
+
+    /// Reject the next submission with `error`; queued errors are returned in order.
+    pub fn fail_next_submit(&self, error: ApiError) {
+        self.inner.lock().unwrap().submit_errors.push_back(error);
+    }
+
+    /// Accepted specs, in submission order.
+    pub fn submitted(&self) -> Vec<CreateSpec> {
+        self.inner.lock().unwrap().submitted.clone()
+    }
+
+    /// Tasks canceled through [`ApiHandler::cancel_task`] or [`ApiHandler::cancel_group`].
+    pub fn canceled(&self) -> Vec<TaskId> {
+        self.inner.lock().unwrap().canceled.clone()
+    }
+
+    /// All tasks, in registration order.
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        self.inner.lock().unwrap().tasks.clone()
+    }
+
+    /// All recorded lifecycle events, oldest first.
+    pub fn events(&self) -> Vec<TaskEvent> {
+        self.inner.lock().unwrap().events.clone()
+    }
+
+    pub(crate) fn update(&self, id: &TaskId, status: TaskStatus, error: Option<String>) -> bool {
+        let changed = self.inner.lock().unwrap().transition(id, status, error);
+        if changed {
+            self.bump();
+        }
+        changed
+    }
+
+    fn bump(&self) {
+        self.revision.send_modify(|r| *r += 1);
+    }
+
+    fn select(&self, filter: impl Fn(&TaskInfo) -> bool) -> Vec<TaskInfo> {
+        let inner = self.inner.lock().unwrap();
+        inner.tasks.iter().filter(|t| filter(t)).cloned().collect()
+    }
+
+    fn group_info(&self, group: &str) -> Option<GroupInfo> {
+        let members = self.select(|t| t.group.as_deref() == Some(group));
+        (!members.is_empty()).then(|| GroupInfo::from_tasks(group, &members))
+    }
+
+    /// Wait until `done` returns a value, re-checking on every change.
+    async fn wait_for<T>(
+        &self,
+        timeout: Duration,
+        what: String,
+        done: impl Fn() -> Result<Option<T>, ApiError>,
+    ) -> Result<T, ApiError> {
+        let mut changes = self.revision.subscribe();
+        let wait = async {
+            loop {
+                changes.borrow_and_update();
+                if let Some(value) = done()? {
+                    return Ok(value);
+                }
+                // The sender lives as long as `self`.
+                let _ = changes.changed().await;
+            }
+        };
+        tokio::time::timeout(timeout, wait)
+            .await
+            .map_err(|_| ApiError::Timeout(what))?
+    }
+}
+
+impl Default for FakeHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl ApiHandler for FakeHandler {
+    async fn submit_task(&self, spec: CreateSpec) -> Result<TaskId, ApiError> {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(error) = inner.submit_errors.pop_front() {
+            return Err(error);
+        }
+        if let Some(error) = ApiError::from_diagnostics(&solti_model::validate(&spec)) {
+            return Err(error);
+        }
+
+        inner.next_id += 1;
+        let id = TaskId::from(format!("{}-{}", spec.slot, inner.next_id));
+        let mut info = TaskInfo::pending(id.clone(), spec.slot.clone());
+        info.group = spec.group().map(str::to_string);
+        info.labels = spec.labels.clone();
+        info.kind = Some(spec.kind.kind().to_string());
+        inner.push_event(TaskEventKind::TaskAdded, &info, None);
+        inner.tasks.push(info);
+        inner.submitted.push(spec);
+        drop(inner);
+
+        self.bump();
+        Ok(id)
+    }
+
+    async fn get_task_status(&self, id: &TaskId) -> Result<Option<TaskInfo>, ApiError> {
+        Ok(self.select(|t| &t.id == id).pop())
+    }
+
+    async fn list_all_tasks(&self) -> Result<Vec<TaskInfo>, ApiError> {
+        Ok(self.tasks())
+    }
+
+    async fn list_tasks_by_slot(&self, slot: &str) -> Result<Vec<TaskInfo>, ApiError> {
+        Ok(self.select(|t| t.slot == slot))
+    }
+
+    async fn list_tasks_by_status(&self, status: TaskStatus) -> Result<Vec<TaskInfo>, ApiError> {
+        Ok(self.select(|t| t.status == status))
+    }
+
+    async fn query_tasks(&self, query: TaskQuery) -> Result<TaskPage<TaskInfo>, ApiError> {
+        let matching = self.select(|t| query.matches(t));
+        Ok(TaskPage {
+            total: matching.len(),
+            items: matching
+                .into_iter()
+                .skip(query.offset)
+                .take(query.limit)
+                .collect(),
+        })
+    }
+
+    async fn tasks_revision(&self) -> Result<Option<u64>, ApiError> {
+        Ok(Some(*self.revision.borrow()))
+    }
+
+    async fn cancel_task(&self, id: &TaskId) -> Result<(), ApiError> {
+        let mut inner = self.inner.lock().unwrap();
+        let status = inner
+            .task_mut(id)
+            .map(|t| t.status)
+            .ok_or_else(|| ApiError::TaskNotFound(id.to_string()))?;
+        inner.canceled.push(id.clone());
+        if status.is_active() {
+            inner.transition(id, TaskStatus::Canceled, None);
+        }
+        drop(inner);
+
+        self.bump();
+        Ok(())
+    }
+
+    async fn wait_task(&self, id: &TaskId, timeout: Duration) -> Result<TaskInfo, ApiError> {
+        self.wait_for(timeout, format!("task {id}"), || {
+            let info = self
+                .select(|t| &t.id == id)
+                .pop()
+                .ok_or_else(|| ApiError::TaskNotFound(id.to_string()))?;
+            Ok(info.status.is_terminal().then_some(info))
+        })
+        .await
+    }
+
+    async fn wait_task_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        timeout: Duration,
+    ) -> Result<TaskInfo, ApiError> {
+        self.wait_for(timeout, format!("task {id}"), || {
+            let info = self
+                .select(|t| &t.id == id)
+                .pop()
+                .ok_or_else(|| ApiError::TaskNotFound(id.to_string()))?;
+            Ok((info.status != from).then_some(info))
+        })
+        .await
+    }
+
+    async fn get_group_status(&self, group: &str) -> Result<Option<GroupInfo>, ApiError> {
+        Ok(self.group_info(group))
+    }
+
+    async fn cancel_group(&self, group: &str) -> Result<usize, ApiError> {
+        let members = self.select(|t| t.group.as_deref() == Some(group));
+        if members.is_empty() {
+            return Err(ApiError::GroupNotFound(group.to_string()));
+        }
+        let mut inner = self.inner.lock().unwrap();
+        let mut canceled = 0;
+        for task in members.iter().filter(|t| t.status.is_active()) {
+            inner.canceled.push(task.id.clone());
+            inner.transition(&task.id, TaskStatus::Canceled, None);
+            canceled += 1;
+        }
+        drop(inner);
+
+        self.bump();
+        Ok(canceled)
+    }
+
+    async fn wait_group(&self, group: &str, timeout: Duration) -> Result<GroupInfo, ApiError> {
+        self.wait_for(timeout, format!("group {group}"), || {
+            let info = self
+                .group_info(group)
+                .ok_or_else(|| ApiError::GroupNotFound(group.to_string()))?;
+            Ok(info.status.is_terminal().then_some(info))
+        })
+        .await
+    }
+
+    async fn get_maintenance(&self) -> Result<bool, ApiError> {
+        Ok(self.inner.lock().unwrap().maintenance)
+    }
+
+    async fn set_maintenance(&self, enabled: bool) -> Result<bool, ApiError> {
+        let mut inner = self.inner.lock().unwrap();
+        Ok(std::mem::replace(&mut inner.maintenance, enabled))
+    }
+
+    async fn list_events(&self, query: EventQuery) -> Result<Vec<TaskEvent>, ApiError> {
+        let inner = self.inner.lock().unwrap();
+        Ok(inner
+            .events
+            .iter()
+            .filter(|e| query.matches(e))
+            .take(query.limit)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{SpecBuilder, TaskInfoBuilder, assert_event_kinds};
+
+    #[tokio::test]
+    async fn submitted_tasks_follow_driven_lifecycle() {
+        let handler = Arc::new(FakeHandler::new());
+        let id = handler
+            .submit_task(SpecBuilder::new("backup").build())
+            .await
+            .unwrap();
+        assert_eq!(handler.submitted().len(), 1);
+
+        let waiter = {
+            let handler = Arc::clone(&handler);
+            let id = id.clone();
+            tokio::spawn(async move { handler.wait_task(&id, Duration::from_secs(5)).await })
+        };
+        assert!(handler.set_status(&id, TaskStatus::Running));
+        assert!(handler.fail(&id, "disk full"));
+
+        let info = waiter.await.unwrap().unwrap();
+        assert_eq!(info.status, TaskStatus::Failed);
+        assert_eq!(info.attempt, 1);
+        assert_eq!(info.error.as_deref(), Some("disk full"));
+        assert_event_kinds(
+            &handler.events(),
+            &[
+                TaskEventKind::TaskAdded,
+                TaskEventKind::TaskStarting,
+                TaskEventKind::TaskFailed,
+            ],
+        );
+        assert!(!handler.set_status(&TaskId::from("missing"), TaskStatus::Running));
+    }
+
+    #[tokio::test]
+    async fn queued_errors_and_validation_reject_submissions() {
+        let handler = FakeHandler::new();
+        handler.fail_next_submit(ApiError::QuotaExceeded("slot backup".into()));
+        let err = handler
+            .submit_task(SpecBuilder::new("backup").build())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ApiError::QuotaExceeded(_)));
+
+        let err = handler
+            .submit_task(SpecBuilder::new("").build())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), "invalid_request");
+        assert!(handler.tasks().is_empty());
+    }
+
+    #[tokio::test]
+    async fn groups_cancel_and_wait() {
+        let handler = FakeHandler::new();
+        handler.insert(
+            TaskInfoBuilder::new("done", "a")
+                .with_group("g")
+                .with_status(TaskStatus::Succeeded)
+                .build(),
+        );
+        let id = handler
+            .submit_task(SpecBuilder::new("b").with_group("g").build())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            handler.get_group_status("g").await.unwrap().unwrap().status,
+            TaskStatus::Pending
+        );
+        assert!(matches!(
+            handler.wait_group("g", Duration::from_millis(20)).await,
+            Err(ApiError::Timeout(_))
+        ));
+        assert_eq!(handler.cancel_group("g").await.unwrap(), 1);
+        assert_eq!(handler.canceled(), vec![id]);
+
+        let info = handler
+            .wait_group("g", Duration::from_secs(1))
+            .await
+            .unwrap();
+        assert_eq!(info.status, TaskStatus::Canceled);
+        assert!(matches!(
+            handler.cancel_group("missing").await,
+            Err(ApiError::GroupNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn queries_and_events_are_filtered() {
+        let handler = FakeHandler::new();
+        for slot in ["a", "a", "b"] {
+            handler
+                .submit_task(SpecBuilder::new(slot).with_label("team", slot).build())
+                .await
+                .unwrap();
+        }
+        let page = handler
+            .query_tasks(TaskQuery::new().with_label("team", "a").with_limit(1))
+            .await
+            .unwrap();
+        assert_eq!((page.total, page.items.len()), (2, 1));
+
+        let events = handler
+            .list_events(EventQuery::new().with_slot("b"))
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let revision = handler.tasks_revision().await.unwrap();
+        assert_eq!(revision, Some(3));
+    }
+}
@@ -0,0 +1,199 @@
+use std::time::{Duration, SystemTime};
+
+use solti_model::{
+    AdmissionStrategy, BackoffStrategy, CreateSpec, Flag, JitterStrategy, LABEL_GROUP,
+    RestartStrategy, RunnerLabels, TaskEnv, TaskId, TaskInfo, TaskKind, TaskStatus,
+};
+
+/// Builder of [`CreateSpec`] fixtures.
+///
+/// Defaults to a one-shot `true` subprocess with a 5s timeout, no restarts,
+/// a 100ms–1s exponential backoff and [`AdmissionStrategy::DropIfRunning`].
+#[derive(Debug, Clone)]
+pub struct SpecBuilder {
+    spec: CreateSpec,
+}
+
+impl SpecBuilder {
+    pub fn new(slot: impl Into<String>) -> Self {
+        Self {
+            spec: CreateSpec {
+                slot: slot.into(),
+                kind: TaskKind::Subprocess {
+                    command: "true".into(),
+                    args: Vec::new(),
+                    env: TaskEnv::default(),
+                    cwd: None,
+                    fail_on_non_zero: Flag::enabled(),
+                },
+                timeout_ms: 5_000,
+                restart: RestartStrategy::Never,
+                backoff: BackoffStrategy {
+                    jitter: JitterStrategy::None,
+                    first_ms: 100,
+                    max_ms: 1_000,
+                    factor: 2.0,
+                },
+                admission: AdmissionStrategy::DropIfRunning,
+                labels: RunnerLabels::new(),
+                window: None,
+                follow_up: None,
+                resources: None,
+                namespace: None,
+                catch_up: None,
+            },
+        }
+    }
+
+    /// Run `command` with `args` as a subprocess.
+    pub fn with_command<I, S>(self, command: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.with_kind(TaskKind::Subprocess {
+            command: command.into(),
+            args: args.into_iter().map(Into::into).collect(),
+            env: TaskEnv::default(),
+            cwd: None,
+            fail_on_non_zero: Flag::enabled(),
+        })
+    }
+
+    pub fn with_kind(mut self, kind: TaskKind) -> Self {
+        self.spec.kind = kind;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.spec.timeout_ms = timeout.as_millis() as u64;
+        self
+    }
+
+    pub fn with_restart(mut self, restart: RestartStrategy) -> Self {
+        self.spec.restart = restart;
+        self
+    }
+
+    pub fn with_backoff(mut self, backoff: BackoffStrategy) -> Self {
+        self.spec.backoff = backoff;
+        self
+    }
+
+    pub fn with_admission(mut self, admission: AdmissionStrategy) -> Self {
+        self.spec.admission = admission;
+        self
+    }
+
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.spec.labels.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.spec = self.spec.with_group(group);
+        self
+    }
+
+    pub fn build(self) -> CreateSpec {
+        self.spec
+    }
+}
+
+/// Builder of [`TaskInfo`] fixtures.
+///
+/// Starts from [`TaskInfo::pending`]; setting a status fills in the attempt and
+/// timestamps the way the agent reports them.
+#[derive(Debug, Clone)]
+pub struct TaskInfoBuilder {
+    info: TaskInfo,
+}
+
+impl TaskInfoBuilder {
+    pub fn new(id: impl Into<TaskId>, slot: impl Into<String>) -> Self {
+        Self {
+            info: TaskInfo::pending(id.into(), slot.into()),
+        }
+    }
+
+    pub fn with_status(mut self, status: TaskStatus) -> Self {
+        let now = SystemTime::now();
+        self.info.status = status;
+        self.info.updated_at = now;
+        if status != TaskStatus::Pending {
+            self.info.attempt = self.info.attempt.max(1);
+            self.info.started_at.get_or_insert(now);
+        }
+        if status.is_terminal() {
+            self.info.finished_at = Some(now);
+            self.info.duration_ms = Some(0);
+        }
+        self
+    }
+
+    pub fn with_attempt(mut self, attempt: u32) -> Self {
+        self.info.attempt = attempt;
+        self
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.info.error = Some(error.into());
+        self
+    }
+
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.info.labels.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        let group = group.into();
+        self.info.labels.insert(LABEL_GROUP, group.clone());
+        self.info.group = Some(group);
+        self
+    }
+
+    /// Pretend the task was created and last updated `age` ago.
+    pub fn with_age(mut self, age: Duration) -> Self {
+        let at = SystemTime::now() - age;
+        self.info.created_at = at;
+        self.info.updated_at = at;
+        self
+    }
+
+    pub fn build(self) -> TaskInfo {
+        self.info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spec_defaults_are_valid() {
+        let spec = SpecBuilder::new("backup")
+            .with_command("tar", ["czf", "/tmp/b.tgz", "/etc"])
+            .with_group("nightly")
+            .build();
+        assert!(solti_model::validate(&spec).iter().all(|d| !d.is_error()));
+        assert_eq!(spec.group(), Some("nightly"));
+        assert!(matches!(spec.kind, TaskKind::Subprocess { ref args, .. } if args.len() == 3));
+    }
+
+    #[test]
+    fn task_info_status_fills_lifecycle_fields() {
+        let info = TaskInfoBuilder::new("t1", "backup")
+            .with_status(TaskStatus::Failed)
+            .with_error("exit 1")
+            .with_group("nightly")
+            .build();
+        assert_eq!(info.attempt, 1);
+        assert!(info.started_at.is_some() && info.finished_at.is_some());
+        assert_eq!(info.group.as_deref(), Some("nightly"));
+
+        let info = TaskInfoBuilder::new("t2", "backup").build();
+        assert_eq!(info.status, TaskStatus::Pending);
+        assert_eq!(info.attempt, 0);
+    }
+}
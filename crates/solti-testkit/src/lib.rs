@@ -0,0 +1,31 @@
+//! Test helpers for code built on the solti API traits.
+//!
+//! - [`FakeHandler`] — in-memory [`solti_api::ApiHandler`] whose task states are driven by the test;
+//! - [`SpecBuilder`] / [`TaskInfoBuilder`] — concise fixtures with sensible defaults;
+//! - [`assert_event_kinds`] and friends — assertions over lifecycle event histories;
+//! - [`InMemoryHandler`] — standalone handler simulating task lifecycles on its own.
+//!
+//! ```rust,ignore
+//! let handler = Arc::new(FakeHandler::new());
+//! let app = HttpApi::new(handler.clone()).router();
+//!
+//! let id = client.submit(SpecBuilder::new("backup").build()).await?;
+//! handler.set_status(&id, TaskStatus::Running);
+//! handler.fail(&id, "disk full");
+//!
+//! assert_event_kinds(
+//!     &handler.events(),
+//!     &[TaskEventKind::TaskAdded, TaskEventKind::TaskStarting, TaskEventKind::TaskFailed],
+//! );
+//! ```
+mod events;
+pub use events::{assert_event_kinds, assert_no_event, event_kinds, events_for};
+
+mod fake;
+pub use fake::FakeHandler;
+
+mod fixtures;
+pub use fixtures::{SpecBuilder, TaskInfoBuilder};
+
+mod sim;
+pub use sim::{InMemoryHandler, Outcome};
@@ -0,0 +1,336 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use solti_api::{ApiError, ApiHandler};
+use solti_model::{
+    CreateSpec, EventQuery, GroupInfo, RestartStrategy, TaskEvent, TaskId, TaskInfo, TaskPage,
+    TaskQuery, TaskStatus,
+};
+
+use crate::FakeHandler;
+
+/// How a simulated attempt ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Attempt succeeds after the run time.
+    Succeed,
+    /// Attempt fails with the given reason after the run time.
+    Fail(String),
+    /// Attempt never finishes on its own and hits the spec timeout.
+    Hang,
+}
+
+/// Standalone [`ApiHandler`] simulating task lifecycles without a supervisor.
+///
+/// Every submitted task waits [`with_start_delay`](Self::with_start_delay), runs for
+/// [`with_run_time`](Self::with_run_time) and then ends with the [`Outcome`] configured for
+/// its slot. The spec timeout, restart strategy and first backoff delay are honored, so
+/// periodic and retried tasks cycle like on an agent. Lets UIs and control planes be
+/// developed against the HTTP/gRPC API before an agent exists:
+///
+/// ```rust,ignore
+/// let handler = InMemoryHandler::new()
+///     .with_run_time(Duration::from_secs(3))
+///     .with_slot_outcome("flaky", Outcome::Fail("exit code 1".into()));
+/// ApiServer::new(Arc::new(handler)).with_http_addr(addr).run().await?;
+/// ```
+///
+/// Submissions must happen inside a Tokio runtime; each task is driven by its own Tokio task.
+pub struct InMemoryHandler {
+    state: Arc<FakeHandler>,
+    profile: Arc<Profile>,
+}
+
+#[derive(Debug, Clone)]
+struct Profile {
+    start_delay: Duration,
+    run_time: Duration,
+    outcome: Outcome,
+    slot_outcomes: HashMap<String, Outcome>,
+}
+
+impl InMemoryHandler {
+    /// Tasks start after 100ms, run for 1s and succeed.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(FakeHandler::new()),
+            profile: Arc::new(Profile {
+                start_delay: Duration::from_millis(100),
+                run_time: Duration::from_secs(1),
+                outcome: Outcome::Succeed,
+                slot_outcomes: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Time tasks stay pending after submission.
+    pub fn with_start_delay(mut self, delay: Duration) -> Self {
+        Arc::make_mut(&mut self.profile).start_delay = delay;
+        self
+    }
+
+    /// Duration of each attempt.
+    pub fn with_run_time(mut self, run_time: Duration) -> Self {
+        Arc::make_mut(&mut self.profile).run_time = run_time;
+        self
+    }
+
+    /// Outcome of attempts in slots without their own outcome.
+    pub fn with_outcome(mut self, outcome: Outcome) -> Self {
+        Arc::make_mut(&mut self.profile).outcome = outcome;
+        self
+    }
+
+    /// Outcome of attempts in `slot`.
+    pub fn with_slot_outcome(mut self, slot: impl Into<String>, outcome: Outcome) -> Self {
+        Arc::make_mut(&mut self.profile)
+            .slot_outcomes
+            .insert(slot.into(), outcome);
+        self
+    }
+
+    /// Underlying task store, e.g. to seed tasks or inspect submissions.
+    pub fn state(&self) -> &FakeHandler {
+        &self.state
+    }
+}
+
+impl Default for InMemoryHandler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Profile {
+    fn outcome(&self, slot: &str) -> &Outcome {
+        self.slot_outcomes.get(slot).unwrap_or(&self.outcome)
+    }
+}
+
+/// Drive task `id` through its simulated attempts until it stops or is canceled.
+async fn simulate(state: Arc<FakeHandler>, profile: Arc<Profile>, id: TaskId, spec: CreateSpec) {
+    let canceled = |state: &FakeHandler| state.canceled().contains(&id);
+    let timeout = Duration::from_millis(spec.timeout_ms);
+
+    tokio::time::sleep(profile.start_delay).await;
+    loop {
+        if canceled(&state) || !state.set_status(&id, TaskStatus::Running) {
+            return;
+        }
+        let outcome = profile.outcome(&spec.slot);
+        let run_time = match outcome {
+            Outcome::Hang => timeout,
+            _ => profile.run_time.min(timeout),
+        };
+        tokio::time::sleep(run_time).await;
+        if canceled(&state) {
+            return;
+        }
+
+        let succeeded = match outcome {
+            _ if run_time >= timeout => {
+                state.update(
+                    &id,
+                    TaskStatus::Timeout,
+                    Some(format!("timed out after {}ms", spec.timeout_ms)),
+                );
+                false
+            }
+            Outcome::Succeed => state.set_status(&id, TaskStatus::Succeeded),
+            Outcome::Fail(reason) => {
+                state.fail(&id, reason.clone());
+                false
+            }
+            Outcome::Hang => unreachable!("hanging attempts always time out"),
+        };
+
+        let delay = match spec.restart {
+            RestartStrategy::Never => return,
+            RestartStrategy::OnFailure if succeeded => return,
+            RestartStrategy::Always {
+                interval_ms: Some(interval),
+            } if succeeded => interval,
+            _ => spec.backoff.first_ms,
+        };
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+    }
+}
+
+#[async_trait]
+impl ApiHandler for InMemoryHandler {
+    async fn submit_task(&self, spec: CreateSpec) -> Result<TaskId, ApiError> {
+        let id = self.state.submit_task(spec.clone()).await?;
+        tokio::spawn(simulate(
+            Arc::clone(&self.state),
+            Arc::clone(&self.profile),
+            id.clone(),
+            spec,
+        ));
+        Ok(id)
+    }
+
+    async fn get_task_status(&self, id: &TaskId) -> Result<Option<TaskInfo>, ApiError> {
+        self.state.get_task_status(id).await
+    }
+
+    async fn list_all_tasks(&self) -> Result<Vec<TaskInfo>, ApiError> {
+        self.state.list_all_tasks().await
+    }
+
+    async fn list_tasks_by_slot(&self, slot: &str) -> Result<Vec<TaskInfo>, ApiError> {
+        self.state.list_tasks_by_slot(slot).await
+    }
+
+    async fn list_tasks_by_status(&self, status: TaskStatus) -> Result<Vec<TaskInfo>, ApiError> {
+        self.state.list_tasks_by_status(status).await
+    }
+
+    async fn query_tasks(&self, query: TaskQuery) -> Result<TaskPage<TaskInfo>, ApiError> {
+        self.state.query_tasks(query).await
+    }
+
+    async fn tasks_revision(&self) -> Result<Option<u64>, ApiError> {
+        self.state.tasks_revision().await
+    }
+
+    async fn cancel_task(&self, id: &TaskId) -> Result<(), ApiError> {
+        self.state.cancel_task(id).await
+    }
+
+    async fn wait_task(&self, id: &TaskId, timeout: Duration) -> Result<TaskInfo, ApiError> {
+        self.state.wait_task(id, timeout).await
+    }
+
+    async fn wait_task_change(
+        &self,
+        id: &TaskId,
+        from: TaskStatus,
+        timeout: Duration,
+    ) -> Result<TaskInfo, ApiError> {
+        self.state.wait_task_change(id, from, timeout).await
+    }
+
+    async fn get_group_status(&self, group: &str) -> Result<Option<GroupInfo>, ApiError> {
+        self.state.get_group_status(group).await
+    }
+
+    async fn cancel_group(&self, group: &str) -> Result<usize, ApiError> {
+        self.state.cancel_group(group).await
+    }
+
+    async fn wait_group(&self, group: &str, timeout: Duration) -> Result<GroupInfo, ApiError> {
+        self.state.wait_group(group, timeout).await
+    }
+
+    async fn get_maintenance(&self) -> Result<bool, ApiError> {
+        self.state.get_maintenance().await
+    }
+
+    async fn set_maintenance(&self, enabled: bool) -> Result<bool, ApiError> {
+        self.state.set_maintenance(enabled).await
+    }
+
+    async fn list_events(&self, query: EventQuery) -> Result<Vec<TaskEvent>, ApiError> {
+        self.state.list_events(query).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use solti_model::{BackoffStrategy, JitterStrategy, TaskEventKind};
+
+    use super::*;
+    use crate::{SpecBuilder, assert_event_kinds, events_for};
+
+    fn handler() -> InMemoryHandler {
+        InMemoryHandler::new()
+            .with_start_delay(Duration::from_millis(5))
+            .with_run_time(Duration::from_millis(10))
+            .with_slot_outcome("flaky", Outcome::Fail("exit code 1".into()))
+            .with_slot_outcome("stuck", Outcome::Hang)
+    }
+
+    async fn run(handler: &InMemoryHandler, spec: CreateSpec) -> TaskInfo {
+        let id = handler.submit_task(spec).await.unwrap();
+        handler
+            .wait_task(&id, Duration::from_secs(5))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn outcomes_follow_slot_configuration() {
+        let handler = handler();
+
+        let ok = run(&handler, SpecBuilder::new("backup").build()).await;
+        assert_eq!(ok.status, TaskStatus::Succeeded);
+        assert_eq!(ok.attempt, 1);
+
+        let failed = run(&handler, SpecBuilder::new("flaky").build()).await;
+        assert_eq!(failed.status, TaskStatus::Failed);
+        assert_eq!(failed.error.as_deref(), Some("exit code 1"));
+
+        let stuck = SpecBuilder::new("stuck")
+            .with_timeout(Duration::from_millis(20))
+            .build();
+        assert_eq!(run(&handler, stuck).await.status, TaskStatus::Timeout);
+    }
+
+    #[tokio::test]
+    async fn status_changes_can_be_long_polled() {
+        let handler = handler();
+        let id = handler
+            .submit_task(SpecBuilder::new("backup").build())
+            .await
+            .unwrap();
+
+        let mut status = TaskStatus::Pending;
+        let mut seen = Vec::new();
+        while !status.is_terminal() {
+            let info = handler
+                .wait_task_change(&id, status, Duration::from_secs(5))
+                .await
+                .unwrap();
+            status = info.status;
+            seen.push(status);
+        }
+        assert_eq!(seen, [TaskStatus::Running, TaskStatus::Succeeded]);
+    }
+
+    #[tokio::test]
+    async fn failed_attempts_restart_until_canceled() {
+        let handler = handler();
+        let spec = SpecBuilder::new("flaky")
+            .with_restart(RestartStrategy::OnFailure)
+            .with_backoff(BackoffStrategy {
+                jitter: JitterStrategy::None,
+                first_ms: 5,
+                max_ms: 5,
+                factor: 1.0,
+            })
+            .build();
+        let id = handler.submit_task(spec).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        handler.cancel_task(&id).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let events = handler.state().events();
+        let starts = events_for(&events, &id)
+            .iter()
+            .filter(|e| e.kind == TaskEventKind::TaskStarting)
+            .count();
+        assert!(starts > 1, "{starts} attempts");
+        assert_event_kinds(
+            &events,
+            &[TaskEventKind::TaskFailed, TaskEventKind::TaskStarting],
+        );
+
+        let info = handler.get_task_status(&id).await.unwrap().unwrap();
+        assert!(info.status.is_terminal());
+        let attempts = info.attempt;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let info = handler.get_task_status(&id).await.unwrap().unwrap();
+        assert_eq!(info.attempt, attempts);
+    }
+}
@@ -0,0 +1,270 @@
+//! Dashboard state, independent of rendering and transport.
+use std::collections::{BTreeMap, VecDeque};
+
+use solti_model::{TaskEvent, TaskInfo, TaskStatus};
+
+/// Number of recent events kept for the event pane.
+const EVENT_HISTORY: usize = 200;
+
+/// Aggregated status of one slot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SlotSummary {
+    pub slot: String,
+    /// Pending and running tasks.
+    pub active: usize,
+    /// Failed and timed out tasks.
+    pub failed: usize,
+    pub total: usize,
+    /// Status of the most recently updated task.
+    pub last: TaskStatus,
+}
+
+#[derive(Default)]
+pub struct App {
+    tasks: Vec<TaskInfo>,
+    selected: usize,
+    events: VecDeque<TaskEvent>,
+    cursor: Option<u64>,
+    show_terminal: bool,
+    message: Option<String>,
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self {
+            show_terminal: true,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the task list, keeping the selection on the same task when it is still listed.
+    pub fn set_tasks(&mut self, mut tasks: Vec<TaskInfo>) {
+        let selected = self.selected_task().map(|t| t.id.clone());
+        tasks.sort_by(|a, b| {
+            b.status
+                .is_active()
+                .cmp(&a.status.is_active())
+                .then(b.updated_at.cmp(&a.updated_at))
+                .then(a.id.as_str().cmp(b.id.as_str()))
+        });
+        self.tasks = tasks;
+        self.selected = selected
+            .and_then(|id| self.visible().position(|t| t.id == id))
+            .unwrap_or(self.selected);
+        self.clamp_selection();
+    }
+
+    /// Append events fetched after the current cursor.
+    pub fn push_events(&mut self, events: Vec<TaskEvent>, next: Option<u64>) {
+        for event in events {
+            if self.events.len() == EVENT_HISTORY {
+                self.events.pop_front();
+            }
+            self.events.push_back(event);
+        }
+        if next.is_some() {
+            self.cursor = next;
+        }
+    }
+
+    /// Cursor for the next event poll.
+    pub fn cursor(&self) -> Option<u64> {
+        self.cursor
+    }
+
+    /// Tasks shown in the table.
+    pub fn visible(&self) -> impl Iterator<Item = &TaskInfo> {
+        self.tasks
+            .iter()
+            .filter(|t| self.show_terminal || t.status.is_active())
+    }
+
+    /// Events, newest first.
+    pub fn events(&self) -> impl Iterator<Item = &TaskEvent> {
+        self.events.iter().rev()
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_task(&self) -> Option<&TaskInfo> {
+        self.visible().nth(self.selected)
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = self.selected.saturating_add(1);
+        self.clamp_selection();
+    }
+
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Toggle whether finished tasks are listed.
+    pub fn toggle_terminal(&mut self) {
+        self.show_terminal = !self.show_terminal;
+        self.selected = 0;
+    }
+
+    pub fn shows_terminal(&self) -> bool {
+        self.show_terminal
+    }
+
+    /// Status line shown in the footer (last action result or error).
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn set_message(&mut self, message: impl Into<String>) {
+        self.message = Some(message.into());
+    }
+
+    pub fn clear_message(&mut self) {
+        self.message = None;
+    }
+
+    /// Per-slot summary of all tasks, ordered by slot name.
+    pub fn slots(&self) -> Vec<SlotSummary> {
+        let mut slots: BTreeMap<&str, (SlotSummary, std::time::SystemTime)> = BTreeMap::new();
+        for task in &self.tasks {
+            let (summary, updated) = slots.entry(task.slot.as_str()).or_insert_with(|| {
+                (
+                    SlotSummary {
+                        slot: task.slot.clone(),
+                        active: 0,
+                        failed: 0,
+                        total: 0,
+                        last: task.status,
+                    },
+                    task.updated_at,
+                )
+            });
+            summary.total += 1;
+            if task.status.is_active() {
+                summary.active += 1;
+            }
+            if matches!(task.status, TaskStatus::Failed | TaskStatus::Timeout) {
+                summary.failed += 1;
+            }
+            if task.updated_at > *updated {
+                *updated = task.updated_at;
+                summary.last = task.status;
+            }
+        }
+        slots.into_values().map(|(s, _)| s).collect()
+    }
+
+    pub fn counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for task in &self.tasks {
+            *counts.entry(task.status.as_str()).or_default() += 1;
+        }
+        counts
+    }
+
+    fn clamp_selection(&mut self) {
+        let len = self.visible().count();
+        self.selected = self.selected.min(len.saturating_sub(1));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use solti_model::{TaskEventKind, TaskId};
+
+    use super::*;
+
+    fn task(id: &str, slot: &str, status: TaskStatus, age_s: u64) -> TaskInfo {
+        let mut info = TaskInfo::pending(TaskId::from(id), slot.into());
+        info.status = status;
+        info.updated_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000 - age_s);
+        info
+    }
+
+    #[test]
+    fn active_tasks_come_first_and_selection_follows_task() {
+        let mut app = App::new();
+        app.set_tasks(vec![
+            task("a", "backup", TaskStatus::Succeeded, 1),
+            task("b", "sync", TaskStatus::Running, 5),
+            task("c", "sync", TaskStatus::Failed, 2),
+        ]);
+        let ids: Vec<_> = app.visible().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["b", "a", "c"]);
+
+        app.select_next();
+        assert_eq!(app.selected_task().unwrap().id.as_str(), "a");
+
+        app.set_tasks(vec![
+            task("a", "backup", TaskStatus::Succeeded, 1),
+            task("b", "sync", TaskStatus::Running, 5),
+            task("d", "sync", TaskStatus::Pending, 0),
+        ]);
+        assert_eq!(app.selected_task().unwrap().id.as_str(), "a");
+
+        app.toggle_terminal();
+        let ids: Vec<_> = app.visible().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["d", "b"]);
+        app.select_next();
+        app.select_next();
+        assert_eq!(app.selected(), 1);
+    }
+
+    #[test]
+    fn slots_are_summarized() {
+        let mut app = App::new();
+        app.set_tasks(vec![
+            task("a", "sync", TaskStatus::Running, 0),
+            task("b", "sync", TaskStatus::Failed, 10),
+            task("c", "backup", TaskStatus::Timeout, 3),
+        ]);
+        assert_eq!(
+            app.slots(),
+            vec![
+                SlotSummary {
+                    slot: "backup".into(),
+                    active: 0,
+                    failed: 1,
+                    total: 1,
+                    last: TaskStatus::Timeout,
+                },
+                SlotSummary {
+                    slot: "sync".into(),
+                    active: 1,
+                    failed: 1,
+                    total: 2,
+                    last: TaskStatus::Running,
+                },
+            ]
+        );
+        assert_eq!(app.counts().get("failed"), Some(&1));
+    }
+
+    #[test]
+    fn event_history_is_bounded() {
+        let mut app = App::new();
+        let events = (0..EVENT_HISTORY as u64 + 5)
+            .map(|seq| TaskEvent {
+                seq,
+                timestamp_ms: seq,
+                kind: TaskEventKind::TaskStarting,
+                task: None,
+                slot: None,
+                attempt: None,
+                reason: None,
+                timeout_ms: None,
+                delay_ms: None,
+            })
+            .collect();
+        app.push_events(events, Some(204));
+        assert_eq!(app.events().count(), EVENT_HISTORY);
+        assert_eq!(app.events().next().unwrap().seq, 204);
+        assert_eq!(app.cursor(), Some(204));
+
+        app.push_events(Vec::new(), None);
+        assert_eq!(app.cursor(), Some(204));
+    }
+}
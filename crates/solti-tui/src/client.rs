@@ -0,0 +1,81 @@
+//! Blocking client for the agent HTTP API.
+use std::time::Duration;
+
+use serde::Deserialize;
+use solti_model::{TaskEvent, TaskInfo};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("agent returned {status}: {body}")]
+    Status { status: u16, body: String },
+}
+
+#[derive(Deserialize)]
+struct ListTasksResponse {
+    tasks: Vec<TaskInfo>,
+}
+
+#[derive(Deserialize)]
+struct ListEventsResponse {
+    events: Vec<TaskEvent>,
+    next: Option<u64>,
+}
+
+/// Client for one agent, e.g. `http://127.0.0.1:8085`.
+pub struct Client {
+    base: String,
+    http: reqwest::blocking::Client,
+}
+
+impl Client {
+    pub fn new(endpoint: &str, timeout: Duration) -> Result<Self, ClientError> {
+        Ok(Self {
+            base: endpoint.trim_end_matches('/').to_string(),
+            http: reqwest::blocking::Client::builder()
+                .timeout(timeout)
+                .build()?,
+        })
+    }
+
+    pub fn endpoint(&self) -> &str {
+        &self.base
+    }
+
+    /// Up to 1000 tasks known to the agent.
+    pub fn list_tasks(&self) -> Result<Vec<TaskInfo>, ClientError> {
+        let url = format!("{}/api/v1/tasks?limit=1000", self.base);
+        let resp: ListTasksResponse = check(self.http.get(url).send()?)?.json()?;
+        Ok(resp.tasks)
+    }
+
+    /// Events after `since`, with the cursor for the next call.
+    pub fn events(&self, since: Option<u64>) -> Result<(Vec<TaskEvent>, Option<u64>), ClientError> {
+        let mut url = format!("{}/api/v1/events?limit=200", self.base);
+        if let Some(since) = since {
+            url.push_str(&format!("&since={since}"));
+        }
+        let resp: ListEventsResponse = check(self.http.get(url).send()?)?.json()?;
+        Ok((resp.events, resp.next))
+    }
+
+    pub fn cancel(&self, id: &str) -> Result<(), ClientError> {
+        let url = format!("{}/api/v1/tasks/{id}/cancel", self.base);
+        check(self.http.post(url).send()?)?;
+        Ok(())
+    }
+}
+
+fn check(resp: reqwest::blocking::Response) -> Result<reqwest::blocking::Response, ClientError> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+    Err(ClientError::Status {
+        status: status.as_u16(),
+        body: resp.text().unwrap_or_default(),
+    })
+}
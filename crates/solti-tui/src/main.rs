@@ -0,0 +1,115 @@
+//! Terminal dashboard for a single solti agent.
+//!
+//! Polls the agent HTTP API for tasks and lifecycle events and renders live task and
+//! per-slot tables; the selected task can be canceled from the dashboard.
+//!
+//! ```text
+//! solti-tui [ENDPOINT]
+//! ```
+//!
+//! `ENDPOINT` defaults to `$SOLTI_ENDPOINT` or `http://127.0.0.1:8085`; the refresh
+//! interval is taken from `$SOLTI_TUI_REFRESH_MS` (default 1000).
+mod app;
+mod client;
+mod ui;
+
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    DefaultTerminal,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+};
+
+use app::App;
+use client::{Client, ClientError};
+
+const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:8085";
+const DEFAULT_REFRESH_MS: u64 = 1_000;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("SOLTI_ENDPOINT").ok())
+        .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+    let refresh = std::env::var("SOLTI_TUI_REFRESH_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(Duration::from_millis(DEFAULT_REFRESH_MS));
+    let client = Client::new(&endpoint, REQUEST_TIMEOUT)?;
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &client, refresh);
+    ratatui::restore();
+    Ok(result?)
+}
+
+fn run(terminal: &mut DefaultTerminal, client: &Client, refresh: Duration) -> std::io::Result<()> {
+    let mut app = App::new();
+    poll(&mut app, client);
+    let mut last_poll = Instant::now();
+
+    loop {
+        terminal.draw(|frame| ui::draw(frame, &app, client.endpoint()))?;
+
+        let timeout = refresh.saturating_sub(last_poll.elapsed());
+        if event::poll(timeout)?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => app.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => app.select_prev(),
+                KeyCode::Char('a') => app.toggle_terminal(),
+                KeyCode::Char('c') => cancel_selected(&mut app, client),
+                KeyCode::Char('r') => {
+                    poll(&mut app, client);
+                    last_poll = Instant::now();
+                }
+                _ => {}
+            }
+        }
+        if last_poll.elapsed() >= refresh {
+            poll(&mut app, client);
+            last_poll = Instant::now();
+        }
+    }
+}
+
+fn poll(app: &mut App, client: &Client) {
+    let result = client.list_tasks().and_then(|tasks| {
+        app.set_tasks(tasks);
+        let (events, next) = client.events(app.cursor())?;
+        app.push_events(events, next);
+        Ok::<_, ClientError>(())
+    });
+    match result {
+        Ok(())
+            if app
+                .message()
+                .is_some_and(|m| m.starts_with("agent unreachable")) =>
+        {
+            app.clear_message()
+        }
+        Ok(()) => {}
+        Err(e) => app.set_message(format!("agent unreachable: {e}")),
+    }
+}
+
+fn cancel_selected(app: &mut App, client: &Client) {
+    let Some(task) = app.selected_task() else {
+        return;
+    };
+    if !task.status.is_active() {
+        let msg = format!("{} is already {}", task.id, task.status.as_str());
+        app.set_message(msg);
+        return;
+    }
+    let id = task.id.clone();
+    match client.cancel(id.as_str()) {
+        Ok(()) => app.set_message(format!("cancel requested for {id}")),
+        Err(e) => app.set_message(format!("cancel {id} failed: {e}")),
+    }
+}
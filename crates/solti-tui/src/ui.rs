@@ -0,0 +1,176 @@
+//! Rendering of the dashboard.
+use std::time::SystemTime;
+
+use ratatui::{
+    Frame,
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, List, ListItem, Paragraph, Row, Table, TableState},
+};
+use solti_model::{TaskEvent, TaskStatus};
+
+use crate::app::App;
+
+const KEYS: &str = "q quit  ↑/↓ select  c cancel  a toggle finished  r refresh";
+
+pub fn draw(frame: &mut Frame, app: &App, endpoint: &str) {
+    let [header, body, events, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(8),
+        Constraint::Length(10),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [tasks, slots] =
+        Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(body);
+
+    frame.render_widget(header_line(app, endpoint), header);
+    draw_tasks(frame, app, tasks);
+    draw_slots(frame, app, slots);
+    draw_events(frame, app, events);
+
+    let footer_text = match app.message() {
+        Some(msg) => Line::from(vec![
+            Span::styled(msg.to_string(), Style::new().fg(Color::Yellow)),
+            Span::raw("  "),
+            Span::styled(KEYS, Style::new().fg(Color::DarkGray)),
+        ]),
+        None => Line::styled(KEYS, Style::new().fg(Color::DarkGray)),
+    };
+    frame.render_widget(Paragraph::new(footer_text), footer);
+}
+
+fn header_line(app: &App, endpoint: &str) -> Paragraph<'static> {
+    let mut spans = vec![Span::styled(
+        format!("solti {endpoint}"),
+        Style::new().add_modifier(Modifier::BOLD),
+    )];
+    for (status, count) in app.counts() {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{status}: {count}"),
+            Style::new().fg(status_color_str(status)),
+        ));
+    }
+    Paragraph::new(Line::from(spans))
+}
+
+fn draw_tasks(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let rows: Vec<Row> = app
+        .visible()
+        .map(|t| {
+            Row::new(vec![
+                Cell::from(t.id.to_string()),
+                Cell::from(t.slot.clone()),
+                Cell::from(t.status.as_str()).style(Style::new().fg(status_color(t.status))),
+                Cell::from(t.attempt.to_string()),
+                Cell::from(ago(t.updated_at)),
+                Cell::from(t.error.clone().unwrap_or_default()),
+            ])
+        })
+        .collect();
+    let title = if app.shows_terminal() {
+        " tasks "
+    } else {
+        " tasks (active) "
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(28),
+            Constraint::Length(20),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(["ID", "SLOT", "STATUS", "ATTEMPT", "UPDATED", "ERROR"])
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+
+    let mut state = TableState::default().with_selected(Some(app.selected()));
+    frame.render_stateful_widget(table, area, &mut state);
+}
+
+fn draw_slots(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let rows: Vec<Row> = app
+        .slots()
+        .into_iter()
+        .map(|s| {
+            Row::new(vec![
+                Cell::from(s.slot),
+                Cell::from(s.last.as_str()).style(Style::new().fg(status_color(s.last))),
+                Cell::from(format!("{}/{}/{}", s.active, s.failed, s.total)),
+            ])
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Min(10),
+            Constraint::Length(10),
+            Constraint::Length(10),
+        ],
+    )
+    .header(
+        Row::new(["SLOT", "LAST", "ACT/FAIL/ALL"]).style(Style::new().add_modifier(Modifier::BOLD)),
+    )
+    .block(Block::default().borders(Borders::ALL).title(" slots "));
+    frame.render_widget(table, area);
+}
+
+fn draw_events(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    let items: Vec<ListItem> = app.events().map(|e| ListItem::new(event_line(e))).collect();
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(" events "));
+    frame.render_widget(list, area);
+}
+
+fn event_line(event: &TaskEvent) -> String {
+    let mut line = format!("#{:<6} {:?}", event.seq, event.kind);
+    if let Some(slot) = &event.slot {
+        line.push_str(&format!(" slot={slot}"));
+    }
+    if let Some(task) = &event.task {
+        line.push_str(&format!(" task={task}"));
+    }
+    if let Some(attempt) = event.attempt {
+        line.push_str(&format!(" attempt={attempt}"));
+    }
+    if let Some(reason) = &event.reason {
+        line.push_str(&format!(" reason={reason}"));
+    }
+    line
+}
+
+fn status_color(status: TaskStatus) -> Color {
+    status_color_str(status.as_str())
+}
+
+fn status_color_str(status: &str) -> Color {
+    match status {
+        "running" => Color::Green,
+        "pending" => Color::Cyan,
+        "succeeded" => Color::Gray,
+        "failed" | "timeout" => Color::Red,
+        _ => Color::DarkGray,
+    }
+}
+
+/// Compact age such as `42s`, `5m` or `3h`.
+fn ago(at: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(at)
+        .unwrap_or_default()
+        .as_secs();
+    match secs {
+        s if s < 60 => format!("{s}s"),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86_400 => format!("{}h", s / 3600),
+        s => format!("{}d", s / 86_400),
+    }
+}
@@ -37,9 +37,9 @@ async fn main() -> anyhow::Result<()> {
     register_subprocess_runner_with_backend(
         &mut router,
         "dev-runner",
-        SubprocessBackendConfig::new(),
+        SubprocessBackendConfig::new().with_trace_context(true),
     )?;
-    info!("registered dev-runner (no restrictions)");
+    info!("registered dev-runner (no restrictions, TRACEPARENT injected)");
 
     // 3b) Production runner - moderate restrictions
     let prod_backend = SubprocessBackendConfig::new()
@@ -118,6 +118,11 @@ async fn main() -> anyhow::Result<()> {
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     }
     .with_runner_tag("dev-runner");
 
@@ -141,6 +146,11 @@ async fn main() -> anyhow::Result<()> {
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     }
     .with_runner_tag("prod-runner");
 
@@ -164,6 +174,11 @@ async fn main() -> anyhow::Result<()> {
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     }
     .with_runner_tag("untrusted-runner");
 
@@ -190,6 +205,11 @@ async fn main() -> anyhow::Result<()> {
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     }
     .with_runner_tag("untrusted-runner");
 
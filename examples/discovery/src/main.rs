@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use axum::routing::get;
 use tracing::info;
 
 use solti_api::{HttpApi, SupervisorApiAdapter};
 use solti_core::{BuildContext, RunnerRouter, SupervisorApi, TaskPolicy};
-use solti_discover::{DiscoverConfig, DiscoveryTransport};
+use solti_discover::{DiscoverConfig, DiscoveryTransport, SyncBuilder};
 use solti_exec::subprocess::register_subprocess_runner;
 use solti_model::{
-    AdmissionStrategy, BackoffStrategy, CreateSpec, Flag, JitterStrategy, RestartStrategy,
-    RunnerLabels, TaskEnv, TaskKind,
+    AGENT_LABEL_REGION, AGENT_LABEL_ROLE, AdmissionStrategy, BackoffStrategy, CreateSpec, Flag,
+    JitterStrategy, RestartStrategy, RunnerLabels, TaskEnv, TaskKind,
+};
+use solti_observe::{
+    LoggerConfig, LoggerLevel, RateLimitedSubscriber, SpanSubscriber, current_level, init_logger,
+    level_signal, reload_level, timezone_sync,
 };
-use solti_observe::{LoggerConfig, LoggerLevel, Subscriber, init_logger, timezone_sync};
 use solti_prometheus::PrometheusMetrics;
 use taskvisor::{ControllerConfig, Subscribe, SupervisorConfig};
 
@@ -41,14 +45,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("registered default subprocess runner");
 
     // 4) Supervisor
-    let subscribers: Vec<Arc<dyn Subscribe>> = vec![Arc::new(Subscriber)];
-    let supervisor = SupervisorApi::new(
-        SupervisorConfig::default(),
-        ControllerConfig::default(),
-        subscribers,
-        router,
-    )
-    .await?;
+    let subscribers: Vec<Arc<dyn Subscribe>> = vec![
+        Arc::new(RateLimitedSubscriber::default()),
+        Arc::new(SpanSubscriber::new().with_runners(["default-runner"])),
+    ];
+    let supervisor = Arc::new(
+        SupervisorApi::new(
+            SupervisorConfig::default(),
+            ControllerConfig::default(),
+            subscribers,
+            router,
+        )
+        .await?,
+    );
     info!("supervisor ready");
 
     // 5) Internal tasks: timezone sync
@@ -57,6 +66,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     supervisor.submit_with_task(tz_task, &tz_policy).await?;
     info!("timezone sync task submitted");
 
+    // 5b) Internal tasks: SIGUSR1 toggles debug logging
+    let (sig_task, sig_spec) = level_signal(LoggerLevel::new("debug")?);
+    let sig_policy = TaskPolicy::from_spec(&sig_spec);
+    supervisor.submit_with_task(sig_task, &sig_policy).await?;
+    info!("log level signal task submitted (kill -USR1 to toggle debug)");
+
     // 6) Discovery — periodic sync with control plane
     let discover_config = DiscoverConfig {
         name: "demo-agent".to_string(),
@@ -68,6 +83,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ("role".into(), "worker".into()),
         ]),
         delay_ms: 10_000,
+        tls: None,
+        token: None,
+        labels: {
+            let mut labels = RunnerLabels::new();
+            labels
+                .insert(AGENT_LABEL_REGION, "us-east-1")
+                .insert(AGENT_LABEL_ROLE, "worker");
+            labels
+        },
+        taints: Vec::new(),
     };
     info!(
         "discovery: control_plane={}, agent={}, transport={:?}",
@@ -75,7 +100,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         discover_config.agent_endpoint,
         discover_config.transport,
     );
-    let (sync_task, sync_spec) = solti_discover::sync(discover_config);
+    let (sync_task, sync_spec, deregistration) = SyncBuilder::new(discover_config)
+        .with_maintenance(supervisor.maintenance())
+        .with_router(supervisor.router())
+        .with_reconciler(Arc::clone(&supervisor))
+        .with_task_summary(Arc::clone(&supervisor))
+        .build_with_deregistration();
     let sync_policy = TaskPolicy::from_spec(&sync_spec);
     supervisor.submit_with_task(sync_task, &sync_policy).await?;
     info!("discovery sync task submitted");
@@ -84,7 +114,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     submit_background_tasks(&supervisor).await?;
 
     // 8) HTTP API + metrics
-    let handler = Arc::new(SupervisorApiAdapter::new(Arc::new(supervisor)));
+    let handler = Arc::new(SupervisorApiAdapter::new(supervisor).with_log_level(
+        || current_level().map(|level| level.as_str().to_string()),
+        |filter| {
+            LoggerLevel::new(filter)
+                .and_then(|level| reload_level(&level))
+                .map_err(|e| e.to_string())
+        },
+    ));
     let http_api = HttpApi::new(handler);
     let app = http_api.router();
 
@@ -98,9 +135,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(AGENT_HTTP_ADDR).await?;
     info!("HTTP API:  http://{}/api/v1/tasks", AGENT_HTTP_ADDR);
     info!("Metrics:   http://{}/metrics", AGENT_HTTP_ADDR);
+    info!(
+        "Log level: http://{}/api/v1/admin/loglevel",
+        AGENT_HTTP_ADDR
+    );
     info!("press Ctrl+C to stop");
 
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    // 10) Tell the control plane we are going away
+    deregistration
+        .send_best_effort("shutdown", Duration::from_secs(2))
+        .await;
 
     Ok(())
 }
@@ -139,6 +189,11 @@ async fn submit_background_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std:
         backoff: backoff.clone(),
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     let id = api.submit(&heartbeat).await?;
     info!("[1/5] agent-heartbeat submitted: {}", id);
@@ -158,6 +213,11 @@ async fn submit_background_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std:
         backoff: backoff.clone(),
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     let id = api.submit(&sysmon).await?;
     info!("[2/5] sys-monitor submitted: {}", id);
@@ -177,6 +237,11 @@ async fn submit_background_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std:
         backoff: backoff.clone(),
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     let id = api.submit(&disk_check).await?;
     info!("[3/5] disk-check submitted: {}", id);
@@ -196,6 +261,11 @@ async fn submit_background_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std:
         backoff: backoff.clone(),
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     let id = api.submit(&oneshot).await?;
     info!("[4/5] oneshot-date submitted: {}", id);
@@ -220,6 +290,11 @@ async fn submit_background_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std:
         },
         admission: AdmissionStrategy::Replace,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
     let id = api.submit(&flaky).await?;
     info!("[5/5] flaky-job submitted: {}", id);
@@ -1,9 +1,9 @@
 use std::sync::Arc;
 
-use tonic::transport::Server;
+use tonic::{Status, metadata::MetadataMap, transport::Server};
 use tracing::info;
 
-use solti_api::{SoltiApiServer, SoltiApiService, SupervisorApiAdapter};
+use solti_api::{SoltiApiService, SupervisorApiAdapter};
 use solti_core::{RunnerRouter, SupervisorApi};
 use solti_exec::subprocess::register_subprocess_runner;
 use solti_model::{
@@ -51,7 +51,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // 6) Create API handler and gRPC service
     let handler = Arc::new(SupervisorApiAdapter::new(Arc::new(supervisor)));
-    let service = SoltiApiService::new(handler);
+    let mut service = SoltiApiService::new(handler);
+
+    // Require a bearer token when SOLTI_API_TOKEN is set
+    if let Ok(token) = std::env::var("SOLTI_API_TOKEN") {
+        service = service.with_interceptor(bearer_auth(token));
+        info!("bearer token authentication enabled");
+    }
 
     // 7) Start gRPC server
     let addr = "[::1]:50051".parse()?;
@@ -59,13 +65,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     info!("use grpcurl to interact with the API");
 
     Server::builder()
-        .add_service(SoltiApiServer::new(service))
+        .add_service(service.into_server())
         .serve(addr)
         .await?;
 
     Ok(())
 }
 
+/// Interceptor accepting only calls with `authorization: Bearer <token>`.
+#[allow(clippy::result_large_err)]
+fn bearer_auth(token: String) -> impl Fn(&MetadataMap) -> Result<(), Status> + Send + Sync {
+    let expected = format!("Bearer {token}");
+    move |metadata| match metadata.get("authorization").and_then(|v| v.to_str().ok()) {
+        Some(value) if value == expected => Ok(()),
+        _ => Err(Status::unauthenticated("missing or invalid token")),
+    }
+}
+
 /// Submit demo periodic tasks that run continuously
 async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error::Error>> {
     // Task 1: Print date every 10 seconds
@@ -88,6 +104,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     // Task 2: Print uptime every 30 seconds
@@ -110,6 +131,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     // Task 3: Echo message every 5 seconds
@@ -132,6 +158,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::Replace,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     let date_id = api.submit(&date_spec).await?;
@@ -1,17 +1,16 @@
 use std::sync::Arc;
 
-use axum::routing::get;
 use tracing::info;
 
-use solti_api::{HttpApi, SupervisorApiAdapter};
-use solti_core::{BuildContext, RunnerRouter, SupervisorApi};
+use solti_api::{ApiServer, SupervisorApiAdapter};
+use solti_core::{BuildContext, RunnerRouter, SupervisorApi, runner_health_check};
 use solti_exec::subprocess::register_subprocess_runner;
 use solti_model::{
     AdmissionStrategy, BackoffStrategy, CreateSpec, Flag, JitterStrategy, RestartStrategy,
     RunnerLabels, TaskEnv, TaskKind,
 };
 use solti_observe::{LoggerConfig, LoggerLevel, Subscriber, init_logger, timezone_sync};
-use solti_prometheus::PrometheusMetrics;
+use solti_prometheus::{ExpositionFormat, PrometheusMetrics};
 use taskvisor::{ControllerConfig, Subscribe, SupervisorConfig};
 
 #[tokio::main]
@@ -52,44 +51,39 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     supervisor.submit_with_task(tz_task, &tz_policy).await?;
     info!("timezone sync task submitted");
 
-    // 6) Submit demo periodic tasks
+    // 6) Submit runner health check task
+    let (health_task, health_spec) = runner_health_check(supervisor.router(), 30_000);
+    let health_policy = solti_core::TaskPolicy::from_spec(&health_spec);
+    supervisor
+        .submit_with_task(health_task, &health_policy)
+        .await?;
+    info!("runner health check task submitted");
+
+    // 7) Submit demo periodic tasks
     submit_demo_tasks(&supervisor).await?;
     info!("demo periodic tasks submitted");
 
-    // 7) Create API handler and HTTP service
+    // 8) Serve the HTTP API and /metrics until SIGINT / SIGTERM
     let handler = Arc::new(SupervisorApiAdapter::new(Arc::new(supervisor)));
-    let http_api = HttpApi::new(handler);
-    let app = http_api.router();
-
-    // 8) Add /metrics endpoint
-    let metrics_clone = metrics.clone();
-    let app = app.route(
-        "/metrics",
-        get(move || metrics_handler(metrics_clone.clone())),
-    );
-
-    // 9) Start HTTP server
-    let addr = "0.0.0.0:8080";
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    info!("starting HTTP server on http://{}", addr);
+    let addr = "0.0.0.0:8080".parse()?;
     info!("API: http://{}/api/v1/tasks", addr);
     info!("Metrics: http://{}/metrics", addr);
 
-    axum::serve(listener, app).await?;
+    ApiServer::new(handler)
+        .with_http_addr(addr)
+        .with_metrics(move |accept| render_metrics(&metrics, accept))
+        .run()
+        .await?;
 
     Ok(())
 }
 
-/// Prometheus metrics handler
-async fn metrics_handler(metrics: PrometheusMetrics) -> String {
-    use solti_prometheus::{Encoder, TextEncoder};
-
-    let families = metrics.gather();
-    let encoder = TextEncoder::new();
-    let mut buffer = vec![];
-
-    encoder.encode(&families, &mut buffer).unwrap();
-    String::from_utf8(buffer).unwrap()
+/// Prometheus metrics in the format requested by the scraper's `Accept` header.
+fn render_metrics(metrics: &PrometheusMetrics, accept: &str) -> Result<(String, Vec<u8>), String> {
+    let encoded = metrics
+        .encode(ExpositionFormat::from_accept(accept))
+        .map_err(|e| e.to_string())?;
+    Ok((encoded.content_type.to_string(), encoded.body))
 }
 
 /// Submit demo periodic tasks that run continuously
@@ -114,6 +108,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     // Task 2: Print uptime every 30 seconds
@@ -136,6 +135,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::DropIfRunning,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     // Task 3: Echo message every 5 seconds
@@ -158,6 +162,11 @@ async fn submit_demo_tasks(api: &SupervisorApi) -> Result<(), Box<dyn std::error
         },
         admission: AdmissionStrategy::Replace,
         labels: RunnerLabels::default(),
+        window: None,
+        follow_up: None,
+        resources: None,
+        namespace: None,
+        catch_up: None,
     };
 
     let date_id = api.submit(&date_spec).await?;
@@ -0,0 +1,25 @@
+use std::{sync::Arc, time::Duration};
+
+use solti_api::ApiServer;
+use solti_testkit::{InMemoryHandler, Outcome};
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+
+    // Tasks run for 3 seconds; slots `flaky` and `stuck` fail and hang.
+    let handler = InMemoryHandler::new()
+        .with_start_delay(Duration::from_millis(500))
+        .with_run_time(Duration::from_secs(3))
+        .with_slot_outcome("flaky", Outcome::Fail("exit code 1".into()))
+        .with_slot_outcome("stuck", Outcome::Hang);
+
+    let addr = std::env::var("SOLTI_MOCK_ADDR").unwrap_or_else(|_| "127.0.0.1:8085".into());
+    println!("mock agent API: http://{addr}/api/v1/tasks");
+
+    ApiServer::new(Arc::new(handler))
+        .with_http_addr(addr.parse()?)
+        .run()
+        .await?;
+    Ok(())
+}